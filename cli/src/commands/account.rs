@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use snarkos_node::sync::locators::TrustedCheckpoint;
 use snarkvm::console::{
     account::PrivateKey,
     prelude::{Environment, Uniform},
@@ -40,6 +41,18 @@ pub enum Account {
         #[clap(short = 'v', long)]
         vanity: Option<String>,
     },
+    /// Signs a block hash as a trusted checkpoint, for the '--checkpoints' file of a node
+    SignCheckpoint {
+        /// Specify the private key of the checkpoint signer
+        #[clap(long = "private-key")]
+        private_key: String,
+        /// Specify the block height
+        #[clap(long)]
+        height: u32,
+        /// Specify the block hash
+        #[clap(long)]
+        hash: String,
+    },
 }
 
 impl Account {
@@ -60,9 +73,19 @@ impl Account {
                     Self::new_seeded(seed)
                 }
             }
+            Self::SignCheckpoint { private_key, height, hash } => Self::sign_checkpoint(&private_key, height, &hash),
         }
     }
 
+    /// Signs the given block height and hash as a trusted checkpoint, returning the checkpoint as JSON.
+    fn sign_checkpoint(private_key: &str, height: u32, hash: &str) -> Result<String> {
+        let private_key = PrivateKey::<Network>::from_str(private_key.trim())?;
+        let hash = <Network as snarkvm::console::network::Network>::BlockHash::from_str(hash.trim())
+            .map_err(|e| anyhow!("Invalid block hash - {e}"))?;
+        let checkpoint = TrustedCheckpoint::sign(&private_key, height, hash, &mut rand::thread_rng())?;
+        Ok(serde_json::to_string_pretty(&checkpoint)?)
+    }
+
     /// Generates a new Aleo account with the given vanity string.
     fn new_vanity(vanity: &str) -> Result<String> {
         // A closure to generate a new Aleo account.
@@ -156,6 +179,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sign_checkpoint() {
+        let private_key = "APrivateKey1zkp2n22c19hNdGF8wuEoQcuiyuWbquY6up4CtG5DYKqPX2X".to_string();
+        let hash = "ab1fxetqjm0ppruay8vlg6gtt52d5fkeydmrk0talp04ymjm65acg9sh8d0r5".to_string();
+        let account = Account::SignCheckpoint { private_key, height: 10, hash };
+        let checkpoint = account.parse().unwrap();
+        assert!(checkpoint.contains("\"height\": 10"));
+        assert!(checkpoint.contains("ab1fxetqjm0ppruay8vlg6gtt52d5fkeydmrk0talp04ymjm65acg9sh8d0r5"));
+    }
+
     #[test]
     fn test_new_seeded() {
         let seed = Some(1231275789u64.to_string());
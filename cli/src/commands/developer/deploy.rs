@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{CurrentAleo, CurrentNetwork, Developer};
+use super::{CurrentAleo, CurrentNetwork, Developer, FeeConfidence};
 
 use snarkvm::{
     console::program::ProgramOwner,
@@ -47,8 +47,11 @@ pub struct Deploy {
     #[clap(short, long)]
     query: String,
     /// The priority fee in microcredits.
-    #[clap(long)]
-    priority_fee: u64,
+    #[clap(long, required_unless_present = "auto_fee", conflicts_with = "auto_fee")]
+    priority_fee: Option<u64>,
+    /// Pays the priority fee suggested by the query node, at the given confidence level (e.g. `--auto-fee=high`)
+    #[clap(long, value_enum, num_args = 0..=1, require_equals = true, default_missing_value = "medium")]
+    auto_fee: Option<FeeConfidence>,
     /// The record to spend the fee from.
     #[clap(short, long)]
     record: Option<String>,
@@ -98,6 +101,19 @@ impl Deploy {
             // Compute the minimum deployment cost.
             let (minimum_deployment_cost, (_, _)) = deployment_cost(&deployment)?;
 
+            // Determine the priority fee.
+            let priority_fee = match self.auto_fee {
+                Some(confidence) => {
+                    // Pay the priority fee suggested by the query node, for the size of the deployment.
+                    let priority_fee_per_byte = Developer::fetch_priority_fee_per_byte(&self.query, confidence)?;
+                    let priority_fee =
+                        Developer::priority_fee_for_size(priority_fee_per_byte, deployment.size_in_bytes()?);
+                    println!("💸 Paying a priority fee of {priority_fee} microcredits ({priority_fee_per_byte:.3} per byte)\n");
+                    priority_fee
+                }
+                None => self.priority_fee.unwrap_or(0),
+            };
+
             // Prepare the fees.
             let fee = match &self.record {
                 Some(record) => {
@@ -106,7 +122,7 @@ impl Deploy {
                         &private_key,
                         fee_record,
                         minimum_deployment_cost,
-                        self.priority_fee,
+                        priority_fee,
                         deployment_id,
                         rng,
                     )?;
@@ -116,7 +132,7 @@ impl Deploy {
                     let fee_authorization = vm.authorize_fee_public(
                         &private_key,
                         minimum_deployment_cost,
-                        self.priority_fee,
+                        priority_fee,
                         deployment_id,
                         rng,
                     )?;
@@ -169,10 +185,29 @@ mod tests {
             assert_eq!(deploy.program_id, "hello.aleo".try_into().unwrap());
             assert_eq!(deploy.private_key, "PRIVATE_KEY");
             assert_eq!(deploy.query, "QUERY");
-            assert_eq!(deploy.priority_fee, 77);
+            assert_eq!(deploy.priority_fee, Some(77));
+            assert_eq!(deploy.auto_fee, None);
             assert_eq!(deploy.record, Some("RECORD".to_string()));
         } else {
             panic!("Unexpected result of clap parsing!");
         }
     }
+
+    #[test]
+    fn clap_snarkos_deploy_auto_fee() {
+        let arg_vec =
+            vec!["snarkos", "developer", "deploy", "-p", "PRIVATE_KEY", "-q", "QUERY", "--auto-fee=low", "hello.aleo"];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::Deploy(deploy)) = cli.command {
+            assert_eq!(deploy.priority_fee, None);
+            assert_eq!(deploy.auto_fee, Some(FeeConfidence::Low));
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+
+        // Ensure either the priority fee or the automatic fee is required.
+        let arg_vec = vec!["snarkos", "developer", "deploy", "-p", "PRIVATE_KEY", "-q", "QUERY", "hello.aleo"];
+        assert!(CLI::try_parse_from(arg_vec).is_err());
+    }
 }
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{CurrentNetwork, Developer};
+use super::{CurrentNetwork, Developer, FeeConfidence};
 
 use snarkvm::prelude::{
     query::Query,
@@ -48,8 +48,11 @@ pub struct Execute {
     #[clap(short, long)]
     query: String,
     /// The priority fee in microcredits.
-    #[clap(long)]
+    #[clap(long, conflicts_with = "auto_fee")]
     priority_fee: Option<u64>,
+    /// Pays the priority fee suggested by the query node, at the given confidence level (e.g. `--auto-fee=high`)
+    #[clap(long, value_enum, num_args = 0..=1, require_equals = true, default_missing_value = "medium")]
+    auto_fee: Option<FeeConfidence>,
     /// The record to spend the fee from.
     #[clap(short, long)]
     record: Option<String>,
@@ -102,15 +105,31 @@ impl Execute {
             let priority_fee = self.priority_fee.unwrap_or(0);
 
             // Create a new transaction.
-            vm.execute(
-                &private_key,
-                (self.program_id, self.function),
-                self.inputs.iter(),
-                fee_record,
-                priority_fee,
-                Some(query),
-                rng,
-            )?
+            match self.auto_fee {
+                Some(confidence) => {
+                    // Pay the priority fee suggested by the query node, for the size of the execution.
+                    let priority_fee_per_byte = Developer::fetch_priority_fee_per_byte(&self.query, confidence)?;
+                    Developer::execute_with_priority_fee_per_byte(
+                        &vm,
+                        &private_key,
+                        (self.program_id, self.function),
+                        &self.inputs,
+                        fee_record,
+                        priority_fee_per_byte,
+                        &self.query,
+                        rng,
+                    )?
+                }
+                None => vm.execute(
+                    &private_key,
+                    (self.program_id, self.function),
+                    self.inputs.iter(),
+                    fee_record,
+                    priority_fee,
+                    Some(query),
+                    rng,
+                )?,
+            }
         };
 
         // Check if the public balance is sufficient.
@@ -128,7 +147,7 @@ impl Execute {
             // Calculate the base fee.
             // This fee is the minimum fee required to pay for the transaction,
             // excluding any finalize fees that the execution may incur.
-            let base_fee = storage_cost.saturating_add(self.priority_fee.unwrap_or(0));
+            let base_fee = storage_cost.saturating_add(*transaction.priority_fee_amount()?);
 
             // If the public balance is insufficient, return an error.
             if public_balance < base_fee {
@@ -208,6 +227,7 @@ mod tests {
             assert_eq!(execute.private_key, "PRIVATE_KEY");
             assert_eq!(execute.query, "QUERY");
             assert_eq!(execute.priority_fee, Some(77));
+            assert_eq!(execute.auto_fee, None);
             assert_eq!(execute.record, Some("RECORD".into()));
             assert_eq!(execute.program_id, "hello.aleo".try_into().unwrap());
             assert_eq!(execute.function, "hello".try_into().unwrap());
@@ -216,4 +236,64 @@ mod tests {
             panic!("Unexpected result of clap parsing!");
         }
     }
+
+    #[test]
+    fn clap_snarkos_execute_auto_fee() {
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "execute",
+            "-p",
+            "PRIVATE_KEY",
+            "-q",
+            "QUERY",
+            "--auto-fee",
+            "hello.aleo",
+            "hello",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::Execute(execute)) = cli.command {
+            assert_eq!(execute.priority_fee, None);
+            assert_eq!(execute.auto_fee, Some(FeeConfidence::Medium));
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "execute",
+            "-p",
+            "KEY",
+            "-q",
+            "QUERY",
+            "--auto-fee=high",
+            "hello.aleo",
+            "hello",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+        if let Command::Developer(Developer::Execute(execute)) = cli.command {
+            assert_eq!(execute.auto_fee, Some(FeeConfidence::High));
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+
+        // Ensure the priority fee and the automatic fee are mutually exclusive.
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "execute",
+            "-p",
+            "KEY",
+            "-q",
+            "QUERY",
+            "--priority-fee",
+            "1",
+            "--auto-fee",
+            "hello.aleo",
+            "hello",
+        ];
+        assert!(CLI::try_parse_from(arg_vec).is_err());
+    }
 }
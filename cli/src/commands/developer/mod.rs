@@ -31,8 +31,12 @@ use snarkvm::{
     package::Package,
     prelude::{
         block::Transaction,
+        execution_cost,
+        query::Query,
+        store::helpers::memory::ConsensusMemory,
         Address,
         Ciphertext,
+        CryptoRng,
         Identifier,
         Literal,
         Plaintext,
@@ -40,13 +44,15 @@ use snarkvm::{
         Program,
         ProgramID,
         Record,
+        Rng,
         ToBytes,
         Value,
         ViewKey,
+        VM,
     },
 };
 
-use anyhow::{bail, ensure, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use clap::Parser;
 use colored::Colorize;
 use std::{path::PathBuf, str::FromStr};
@@ -54,6 +60,30 @@ use std::{path::PathBuf, str::FromStr};
 type CurrentAleo = snarkvm::circuit::AleoV0;
 type CurrentNetwork = snarkvm::prelude::Testnet3;
 
+/// The confidence level of the priority fee suggested by the fee estimate of the node,
+/// i.e. the share of the recent and unconfirmed transactions that pay at most this priority fee.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum FeeConfidence {
+    /// The priority fee paid by 25% of the transactions.
+    Low,
+    /// The priority fee paid by 50% of the transactions.
+    #[default]
+    Medium,
+    /// The priority fee paid by 90% of the transactions.
+    High,
+}
+
+impl FeeConfidence {
+    /// Returns the name of the confidence level in the fee estimate.
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
 /// Commands to manage Aleo accounts.
 #[derive(Debug, Parser)]
 pub enum Developer {
@@ -121,7 +151,7 @@ impl Developer {
     /// Fetch the program from the given endpoint.
     fn fetch_program(program_id: &ProgramID<CurrentNetwork>, endpoint: &str) -> Result<Program<CurrentNetwork>> {
         // Send a request to the query node.
-        let response = ureq::get(&format!("{endpoint}/testnet3/program/{program_id}")).call();
+        let response = ureq::get(&format!("{endpoint}/v1/testnet3/program/{program_id}")).call();
 
         // Deserialize the program.
         match response {
@@ -143,7 +173,7 @@ impl Developer {
 
         // Send a request to the query node.
         let response =
-            ureq::get(&format!("{endpoint}/testnet3/program/{credits}/mapping/{account_mapping}/{address}")).call();
+            ureq::get(&format!("{endpoint}/v1/testnet3/program/{credits}/mapping/{account_mapping}/{address}")).call();
 
         // Deserialize the balance.
         let balance: Result<Option<Value<CurrentNetwork>>> = match response {
@@ -165,6 +195,77 @@ impl Developer {
         }
     }
 
+    /// Fetch the suggested priority fee per byte in microcredits, at the given confidence level, from the given endpoint.
+    fn fetch_priority_fee_per_byte(endpoint: &str, confidence: FeeConfidence) -> Result<f64> {
+        // Send a request to the query node.
+        let response = ureq::get(&format!("{endpoint}/v1/testnet3/fee/estimate")).call();
+
+        // Deserialize the fee estimate.
+        let estimate: serde_json::Value = match response {
+            Ok(response) => response.into_json()?,
+            Err(err) => match err {
+                ureq::Error::Status(_status, response) => {
+                    bail!(response.into_string().unwrap_or("Response too large!".to_owned()))
+                }
+                err => bail!(err),
+            },
+        };
+
+        // Return the priority fee per byte at the given confidence level.
+        estimate["priority_fee_per_byte"][confidence.as_str()]
+            .as_f64()
+            .ok_or_else(|| anyhow!("Failed to deserialize the fee estimate from {endpoint}"))
+    }
+
+    /// Returns the priority fee in microcredits for a deployment or execution of the given size,
+    /// at the given priority fee per byte (which the fee estimate derives from the same size).
+    fn priority_fee_for_size(priority_fee_per_byte: f64, size_in_bytes: u64) -> u64 {
+        (priority_fee_per_byte * size_in_bytes as f64).ceil() as u64
+    }
+
+    /// Executes the given function, and pays the priority fee for the size of the execution at the given rate.
+    /// Note: This splits the `VM::execute` flow, as the size of the execution is only known after it is proven.
+    #[allow(clippy::too_many_arguments)]
+    fn execute_with_priority_fee_per_byte<R: Rng + CryptoRng>(
+        vm: &VM<CurrentNetwork, ConsensusMemory<CurrentNetwork>>,
+        private_key: &PrivateKey<CurrentNetwork>,
+        (program_id, function_name): (ProgramID<CurrentNetwork>, Identifier<CurrentNetwork>),
+        inputs: &[Value<CurrentNetwork>],
+        fee_record: Option<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>,
+        priority_fee_per_byte: f64,
+        endpoint: &str,
+        rng: &mut R,
+    ) -> Result<Transaction<CurrentNetwork>> {
+        // Execute the function, without the fee.
+        let authorization = vm.authorize(private_key, program_id, function_name, inputs.iter(), rng)?;
+        let transaction = vm.execute_authorization(authorization, None, Some(Query::from(endpoint)), rng)?;
+        let execution =
+            transaction.execution().ok_or_else(|| anyhow!("The transaction does not contain an execution"))?.clone();
+        let execution_id = execution.to_execution_id()?;
+
+        // Compute the minimum execution cost, and the priority fee for the size of the execution.
+        let (minimum_execution_cost, (_, _)) = execution_cost(vm, &execution)?;
+        let priority_fee = Self::priority_fee_for_size(priority_fee_per_byte, execution.size_in_bytes()?);
+        println!("💸 Paying a priority fee of {priority_fee} microcredits ({priority_fee_per_byte:.3} per byte)\n");
+
+        // Prepare the fee.
+        let fee_authorization = match fee_record {
+            Some(fee_record) => vm.authorize_fee_private(
+                private_key,
+                fee_record,
+                minimum_execution_cost,
+                priority_fee,
+                execution_id,
+                rng,
+            )?,
+            None => vm.authorize_fee_public(private_key, minimum_execution_cost, priority_fee, execution_id, rng)?,
+        };
+        let fee = vm.execute_fee_authorization(fee_authorization, Some(Query::from(endpoint)), rng)?;
+
+        // Create a new transaction.
+        Transaction::from_execution(execution, Some(fee))
+    }
+
     /// Determine if the transaction should be broadcast or displayed to user.
     fn handle_transaction(
         broadcast: Option<String>,
@@ -12,14 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{CurrentNetwork, Developer};
+use super::{CurrentNetwork, Developer, FeeConfidence};
 
 use snarkvm::prelude::{
     query::Query,
     store::{helpers::memory::ConsensusMemory, ConsensusStore},
     Address,
+    Identifier,
     Locator,
     PrivateKey,
+    ProgramID,
     Value,
     VM,
 };
@@ -47,8 +49,11 @@ pub struct TransferPrivate {
     #[clap(short, long)]
     query: String,
     /// The priority fee in microcredits.
-    #[clap(long)]
-    priority_fee: u64,
+    #[clap(long, required_unless_present = "auto_fee", conflicts_with = "auto_fee")]
+    priority_fee: Option<u64>,
+    /// Pays the priority fee suggested by the query node, at the given confidence level (e.g. `--auto-fee=high`)
+    #[clap(long, value_enum, num_args = 0..=1, require_equals = true, default_missing_value = "medium")]
+    auto_fee: Option<FeeConfidence>,
     /// The record to spend the fee from.
     #[clap(long)]
     fee_record: String,
@@ -91,7 +96,7 @@ impl TransferPrivate {
 
             // Prepare the fee.
             let fee_record = Developer::parse_record(&private_key, &self.fee_record)?;
-            let priority_fee = self.priority_fee;
+            let priority_fee = self.priority_fee.unwrap_or(0);
 
             // Prepare the inputs for a transfer.
             let input_record = Developer::parse_record(&private_key, &self.input_record)?;
@@ -102,15 +107,31 @@ impl TransferPrivate {
             ];
 
             // Create a new transaction.
-            vm.execute(
-                &private_key,
-                ("credits.aleo", "transfer_private"),
-                inputs.iter(),
-                Some(fee_record),
-                priority_fee,
-                Some(query),
-                rng,
-            )?
+            match self.auto_fee {
+                Some(confidence) => {
+                    // Pay the priority fee suggested by the query node, for the size of the execution.
+                    let priority_fee_per_byte = Developer::fetch_priority_fee_per_byte(&self.query, confidence)?;
+                    Developer::execute_with_priority_fee_per_byte(
+                        &vm,
+                        &private_key,
+                        (ProgramID::from_str("credits.aleo")?, Identifier::from_str("transfer_private")?),
+                        &inputs,
+                        Some(fee_record),
+                        priority_fee_per_byte,
+                        &self.query,
+                        rng,
+                    )?
+                }
+                None => vm.execute(
+                    &private_key,
+                    ("credits.aleo", "transfer_private"),
+                    inputs.iter(),
+                    Some(fee_record),
+                    priority_fee,
+                    Some(query),
+                    rng,
+                )?,
+            }
         };
         let locator = Locator::<CurrentNetwork>::from_str("credits.aleo/transfer_private")?;
         println!("✅ Created private transfer of {} microcredits to {}\n", &self.amount, self.recipient);
@@ -21,6 +21,12 @@ pub use clean::*;
 mod developer;
 pub use developer::*;
 
+mod rollback;
+pub use rollback::*;
+
+mod snapshot;
+pub use snapshot::*;
+
 mod start;
 pub use start::*;
 
@@ -57,6 +63,10 @@ pub enum Command {
     Clean(Clean),
     #[clap(subcommand)]
     Developer(Developer),
+    #[clap(name = "rollback")]
+    Rollback(Rollback),
+    #[clap(subcommand)]
+    Snapshot(Snapshot),
     #[clap(name = "start")]
     Start(Box<Start>),
     #[clap(name = "update")]
@@ -70,6 +80,8 @@ impl Command {
             Self::Account(command) => command.parse(),
             Self::Clean(command) => command.parse(),
             Self::Developer(command) => command.parse(),
+            Self::Rollback(command) => command.parse(),
+            Self::Snapshot(command) => command.parse(),
             Self::Start(command) => command.parse(),
             Self::Update(command) => command.parse(),
         }
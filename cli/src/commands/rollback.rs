@@ -0,0 +1,298 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{
+    block::Block,
+    store::helpers::rocksdb::ConsensusDB,
+    FromBytes,
+    Ledger,
+    Network,
+    Testnet3,
+    ToBytes,
+};
+
+use anyhow::{bail, ensure, Result};
+use clap::Parser;
+use colored::Colorize;
+use std::{
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// The phrase the operator must enter to confirm the rollback.
+const CONFIRMATION_PHRASE: &str = "rollback";
+
+/// Rolls the ledger back to the given height, so that the node can follow a different branch after a fork
+/// (the node must not be running).
+#[derive(Debug, Parser)]
+pub struct Rollback {
+    /// Specify the height to roll the ledger back to (e.g. the common ancestor reported by `/v1/testnet3/sync/forks`)
+    #[clap(long = "to")]
+    pub to: u32,
+    /// Specify the network of the ledger
+    #[clap(default_value = "3", long = "network")]
+    pub network: u16,
+    /// Enables development mode, specify the unique ID of the local node to roll back
+    #[clap(long)]
+    pub dev: Option<u16>,
+    /// If the flag is set, the rollback proceeds without asking for confirmation
+    #[clap(long)]
+    pub yes: bool,
+    /// Replays the blocks in the given file into a new ledger (used internally by the rollback)
+    #[clap(long, hide = true)]
+    pub replay: Option<PathBuf>,
+}
+
+impl Rollback {
+    /// Rolls the ledger back to the given height.
+    pub fn parse(self) -> Result<String> {
+        match (self.network, &self.replay) {
+            (3, None) => self.rollback::<Testnet3>(),
+            (3, Some(blocks_path)) => self.replay::<Testnet3>(blocks_path),
+            _ => bail!("Invalid network ID specified"),
+        }
+    }
+
+    /// Rolls the ledger back to the given height, by replaying the blocks up to the height into a new ledger.
+    /// Note: The finalize state cannot be reverted in place, which is why the ledger is rebuilt from genesis.
+    fn rollback<N: Network>(&self) -> Result<String> {
+        // Construct the paths to the ledger, its backup, and the replayed blocks.
+        let ledger_path = aleo_std::aleo_ledger_dir(N::ID, self.dev);
+        ensure!(ledger_path.exists(), "No ledger was found in '{}'", ledger_path.display());
+        let backup_path = with_suffix(&ledger_path, ".rollback");
+        let blocks_path = with_suffix(&ledger_path, ".rollback.blocks");
+        ensure!(
+            !backup_path.exists(),
+            "A previous rollback did not complete (restore or remove '{}' first)",
+            backup_path.display()
+        );
+
+        // Load the ledger, and export the blocks up to the given height.
+        // Note: This fails if the ledger is in use by a running node.
+        let (latest_height, latest_hash, target_hash) = {
+            let ledger = load_ledger::<N>(self.dev)?;
+            let latest_height = ledger.latest_height();
+            ensure!(self.to < latest_height, "The ledger is at height {latest_height}, which is not above {}", self.to);
+            let target_hash = ledger.get_hash(self.to)?;
+
+            // Ask the operator to confirm the rollback.
+            println!(
+                "The ledger will be rolled back from block {latest_height} ('{}') to block {} ('{target_hash}'),\n\
+                 removing {} blocks. The remaining blocks are replayed from genesis, which may take a while.",
+                ledger.latest_hash(),
+                self.to,
+                latest_height - self.to,
+            );
+            if !self.yes && !confirm()? {
+                bail!("The rollback was not confirmed");
+            }
+
+            export_blocks(self.to, &blocks_path, |height| ledger.get_block(height))?;
+            (latest_height, ledger.latest_hash(), target_hash)
+        };
+
+        // Move the ledger aside, and replay the exported blocks into a new ledger.
+        // Note: The replay runs in a separate process, as the storage remains open for the lifetime of this process.
+        fs::rename(&ledger_path, &backup_path)?;
+        let replay = || -> Result<()> {
+            let mut command = Command::new(std::env::current_exe()?);
+            command.args(["rollback", "--to", &self.to.to_string(), "--network", &self.network.to_string()]);
+            if let Some(dev) = self.dev {
+                command.args(["--dev", &dev.to_string()]);
+            }
+            let output = command.arg("--replay").arg(&blocks_path).output()?;
+            if !output.status.success() {
+                // Report the last line of the output, which contains the error.
+                let output = String::from_utf8_lossy(&output.stdout);
+                bail!("{}", output.lines().rev().map(str::trim).find(|line| !line.is_empty()).unwrap_or_default());
+            }
+            Ok(())
+        };
+        let result = replay();
+        let _ = fs::remove_file(&blocks_path);
+        match result {
+            Ok(()) => fs::remove_dir_all(&backup_path)?,
+            Err(error) => {
+                // Restore the original ledger.
+                let _ = fs::remove_dir_all(&ledger_path);
+                fs::rename(&backup_path, &ledger_path)?;
+                bail!("Failed to roll back the ledger (the original ledger was restored) - {error}");
+            }
+        }
+
+        Ok(format!(
+            "✅ Rolled back the ledger from block {latest_height} ('{latest_hash}') to block {} ('{target_hash}') {}\n\
+             Restart the node with '--peers' set to the peers on the intended branch, to sync from them.",
+            self.to,
+            format!("(in \"{}\")", ledger_path.display()).dimmed()
+        ))
+    }
+
+    /// Replays the blocks in the given file into a new ledger, and ensures it reaches the given height.
+    fn replay<N: Network>(&self, blocks_path: &Path) -> Result<String> {
+        let ledger = load_ledger::<N>(self.dev)?;
+        ensure!(ledger.latest_height() == 0, "The ledger to replay the blocks into is not empty");
+        import_blocks(blocks_path, |block| ledger.advance_to_next_block(block))?;
+        ensure!(ledger.latest_height() == self.to, "The replayed ledger is not at height {}", self.to);
+        Ok(format!("✅ Replayed the ledger up to block {}", self.to))
+    }
+}
+
+/// Loads the ledger from storage.
+fn load_ledger<N: Network>(dev: Option<u16>) -> Result<Ledger<N, ConsensusDB<N>>> {
+    // Load the genesis block.
+    let genesis = Block::from_bytes_le(N::genesis_bytes())?;
+    // Load the ledger.
+    Ledger::<N, ConsensusDB<N>>::load(genesis, dev)
+}
+
+/// Returns the given path, with the given suffix appended to its file name.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Prompts the operator to confirm the rollback, and returns `true` if it was confirmed.
+fn confirm() -> Result<bool> {
+    print!("Type '{CONFIRMATION_PHRASE}' to confirm: ");
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().lock().read_line(&mut input)?;
+    Ok(input.trim() == CONFIRMATION_PHRASE)
+}
+
+/// Writes the blocks from height 1 up to the given height to the given file, each prefixed by its length.
+fn export_blocks<N: Network>(height: u32, path: &Path, get_block: impl Fn(u32) -> Result<Block<N>>) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for height in 1..=height {
+        let bytes = get_block(height)?.to_bytes_le()?;
+        writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+        writer.write_all(&bytes)?;
+    }
+    writer.into_inner().map_err(|error| error.into_error())?.sync_all()?;
+    Ok(())
+}
+
+/// Advances the ledger with the blocks in the given file, in order.
+fn import_blocks<N: Network>(
+    path: &Path,
+    mut advance_to_next_block: impl FnMut(&Block<N>) -> Result<()>,
+) -> Result<()> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut length = [0u8; 8];
+    loop {
+        match reader.read_exact(&mut length) {
+            Ok(()) => (),
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(error) => return Err(error.into()),
+        }
+        let mut bytes = vec![0u8; u64::from_le_bytes(length) as usize];
+        reader.read_exact(&mut bytes)?;
+        advance_to_next_block(&Block::from_bytes_le(&bytes)?)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{ledger::ledger_test_helpers::sample_genesis_block, utilities::TestRng};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_parse_rollback() {
+        let rollback = Rollback::try_parse_from(["snarkos", "--to", "5"].iter()).unwrap();
+        assert_eq!(rollback.to, 5);
+        assert_eq!(rollback.network, 3);
+        assert_eq!(rollback.dev, None);
+        assert!(!rollback.yes);
+        assert_eq!(rollback.replay, None);
+
+        let rollback =
+            Rollback::try_parse_from(["snarkos", "--to", "5", "--dev", "1", "--yes", "--replay", "blocks"].iter())
+                .unwrap();
+        assert_eq!(rollback.dev, Some(1));
+        assert!(rollback.yes);
+        assert_eq!(rollback.replay, Some(PathBuf::from("blocks")));
+
+        // Ensure the target height is required, and is a valid height.
+        assert!(Rollback::try_parse_from(["snarkos"].iter()).is_err());
+        assert!(Rollback::try_parse_from(["snarkos", "--to", "-1"].iter()).is_err());
+        assert!(Rollback::try_parse_from(["snarkos", "--to", "tip"].iter()).is_err());
+
+        // Ensure an unknown network is rejected.
+        let rollback = Rollback::try_parse_from(["snarkos", "--to", "5", "--network", "2"].iter()).unwrap();
+        assert!(rollback.parse().is_err());
+    }
+
+    #[test]
+    fn test_export_and_import_blocks() {
+        let rng = &mut TestRng::default();
+        let path = std::env::temp_dir().join(format!("snarkos-test-rollback-{}.blocks", std::process::id()));
+
+        // Export the blocks.
+        let blocks: Vec<Block<CurrentNetwork>> = (0..3).map(|_| sample_genesis_block(rng)).collect();
+        export_blocks(3, &path, |height| Ok(blocks[height as usize - 1].clone())).unwrap();
+
+        // Import the blocks, and ensure they are replayed in order.
+        let mut imported = Vec::new();
+        import_blocks(&path, |block: &Block<CurrentNetwork>| {
+            imported.push(block.clone());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(imported, blocks);
+
+        // Ensure a failure to advance the ledger is reported.
+        assert!(import_blocks(&path, |_: &Block<CurrentNetwork>| bail!("Invalid block")).is_err());
+
+        // Ensure a truncated file is rejected.
+        let bytes = fs::read(&path).unwrap();
+        fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+        assert!(import_blocks(&path, |_: &Block<CurrentNetwork>| Ok(())).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_into_temporary_ledger() {
+        // Use a development ID that is unlikely to collide with a local node.
+        let dev = Some(u16::MAX - (std::process::id() % 1000) as u16);
+        let ledger_path = aleo_std::aleo_ledger_dir(CurrentNetwork::ID, dev);
+        let blocks_path = std::env::temp_dir().join(format!("snarkos-test-replay-{}.blocks", std::process::id()));
+        let _ = fs::remove_dir_all(&ledger_path);
+
+        // Export the blocks above the genesis block, from a new ledger.
+        let ledger = load_ledger::<CurrentNetwork>(dev).unwrap();
+        export_blocks(ledger.latest_height(), &blocks_path, |height| ledger.get_block(height)).unwrap();
+        drop(ledger);
+
+        // Replay the exported blocks into the ledger.
+        let replay = |to| Rollback { to, network: 3, dev, yes: true, replay: Some(blocks_path.clone()) }.parse();
+        assert!(replay(0).is_ok());
+        // Ensure the replay fails if the ledger does not reach the target height.
+        assert!(replay(1).is_err());
+
+        // Ensure the ledger cannot be rolled back to (or above) its latest height.
+        assert!(Rollback { to: 0, network: 3, dev, yes: true, replay: None }.parse().is_err());
+        assert!(ledger_path.exists());
+
+        fs::remove_file(&blocks_path).unwrap();
+        fs::remove_dir_all(&ledger_path).unwrap();
+    }
+}
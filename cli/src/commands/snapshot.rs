@@ -0,0 +1,278 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use snarkvm::prelude::{block::Block, store::helpers::rocksdb::ConsensusDB, FromBytes, Ledger, Network, Testnet3};
+
+use anyhow::{bail, ensure, Result};
+use clap::Parser;
+use colored::Colorize;
+use core::str::FromStr;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+/// The version of the snapshot format.
+const SNAPSHOT_VERSION: u8 = 2;
+/// The file name of the snapshot manifest.
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+/// The directory name of the block files in the snapshot.
+const BLOCKS_DIR_NAME: &str = "blocks";
+/// The number of blocks per block file, which matches the bundles of the CDN.
+const BLOCKS_PER_FILE: u32 = 50;
+
+/// Commands to create and restore snapshots of the ledger.
+#[derive(Debug, Parser)]
+pub enum Snapshot {
+    /// Creates a checksummed snapshot of the blocks of the ledger up to a height (the node must not be running)
+    Create {
+        /// Specify the path to the directory to write the snapshot to
+        path: PathBuf,
+        /// Specify the network of the ledger
+        #[clap(default_value = "3", long = "network")]
+        network: u16,
+        /// Specify the height of the snapshot (defaults to the latest height)
+        #[clap(long = "height")]
+        height: Option<u32>,
+    },
+    /// Restores the ledger from a snapshot, after checking it against a trusted block hash
+    Restore {
+        /// Specify the path to the directory of the snapshot
+        path: PathBuf,
+        /// Specify the trusted block hash at the height of the snapshot
+        #[clap(long = "trusted-hash")]
+        trusted_hash: String,
+        /// Specify the network of the ledger
+        #[clap(default_value = "3", long = "network")]
+        network: u16,
+    },
+}
+
+impl Snapshot {
+    pub fn parse(self) -> Result<String> {
+        match self {
+            Self::Create { path, network, height } => match network {
+                3 => Self::create::<Testnet3>(&path, height),
+                _ => bail!("Invalid network ID specified"),
+            },
+            Self::Restore { path, trusted_hash, network } => match network {
+                3 => Self::restore::<Testnet3>(&path, &trusted_hash),
+                _ => bail!("Invalid network ID specified"),
+            },
+        }
+    }
+
+    /// Creates a snapshot of the ledger at the given path, by exporting the blocks up to the given height.
+    fn create<N: Network>(path: &Path, height: Option<u32>) -> Result<String> {
+        // Ensure the snapshot does not overwrite an existing directory.
+        ensure!(!path.exists(), "The snapshot path '{}' already exists", path.display());
+
+        // Construct the path to the ledger in storage.
+        let ledger_path = aleo_std::aleo_ledger_dir(N::ID, None);
+        ensure!(ledger_path.exists(), "No ledger was found in '{}'", ledger_path.display());
+
+        // Load the ledger.
+        // Note: This fails if the ledger is in use by a running node.
+        let ledger = load_ledger::<N>()?;
+        // Determine the height of the snapshot.
+        let latest_height = ledger.latest_height();
+        let height = height.unwrap_or(latest_height);
+        ensure!(height <= latest_height, "The ledger is at height {latest_height}, below the height {height}");
+        let block_hash = ledger.get_hash(height)?;
+
+        // Export the blocks after the genesis block into the snapshot, in files of `BLOCKS_PER_FILE` blocks.
+        let blocks_path = path.join(BLOCKS_DIR_NAME);
+        fs::create_dir_all(&blocks_path)?;
+        let mut files = vec![];
+        for start in (1..=height).step_by(BLOCKS_PER_FILE as usize) {
+            let end = start.saturating_add(BLOCKS_PER_FILE).min(height + 1);
+            let blocks = (start..end).map(|height| ledger.get_block(height)).collect::<Result<Vec<_>>>()?;
+            files.push(write_blocks_file(&blocks_path, start, end, &blocks)?);
+        }
+
+        // Write the manifest.
+        let manifest = SnapshotManifest {
+            version: SNAPSHOT_VERSION,
+            network: N::ID,
+            height,
+            block_hash: block_hash.to_string(),
+            files,
+        };
+        fs::write(path.join(MANIFEST_FILE_NAME), serde_json::to_string_pretty(&manifest)?)?;
+
+        Ok(format!(
+            "✅ Created a snapshot of the ledger at height {height} (block hash '{block_hash}') in '{}'",
+            path.display()
+        ))
+    }
+
+    /// Restores the ledger from the snapshot at the given path.
+    fn restore<N: Network>(path: &Path, trusted_hash: &str) -> Result<String> {
+        // Parse the trusted block hash.
+        let trusted_hash = match N::BlockHash::from_str(trusted_hash) {
+            Ok(trusted_hash) => trusted_hash,
+            Err(_) => bail!("Invalid trusted block hash '{trusted_hash}'"),
+        };
+
+        // Read the manifest.
+        let manifest: SnapshotManifest = serde_json::from_str(&fs::read_to_string(path.join(MANIFEST_FILE_NAME))?)?;
+        ensure!(manifest.version == SNAPSHOT_VERSION, "Unsupported snapshot version {}", manifest.version);
+        ensure!(manifest.network == N::ID, "The snapshot is for network {}, not {}", manifest.network, N::ID);
+        // Ensure the snapshot is at the trusted block hash.
+        ensure!(
+            manifest.block_hash == trusted_hash.to_string(),
+            "The snapshot block hash '{}' does not match the trusted block hash '{trusted_hash}'",
+            manifest.block_hash
+        );
+
+        // Construct the path to the ledger in storage.
+        let ledger_path = aleo_std::aleo_ledger_dir(N::ID, None);
+        ensure!(
+            !ledger_path.exists(),
+            "A ledger already exists in '{}' (run 'snarkos clean' to remove it first)",
+            ledger_path.display()
+        );
+
+        // Add the blocks of the snapshot to a new ledger.
+        // Note: Every block is checked against the ledger before it is added, and the last block must match
+        // the trusted block hash, so the restored ledger is the chain that ends at the trusted block hash.
+        let import = || -> Result<()> {
+            let ledger = load_ledger::<N>()?;
+            for file in &manifest.files {
+                for block in read_blocks_file::<N>(&path.join(BLOCKS_DIR_NAME), file)? {
+                    ensure!(
+                        block.height() == ledger.latest_height() + 1,
+                        "The snapshot skips the block {}",
+                        block.height()
+                    );
+                    ledger.check_next_block(&block)?;
+                    ledger.advance_to_next_block(&block)?;
+                }
+            }
+            ensure!(ledger.latest_height() == manifest.height, "The restored ledger is not at the snapshot height");
+            ensure!(ledger.latest_hash() == trusted_hash, "The restored ledger is not at the trusted hash");
+            Ok(())
+        };
+        if let Err(error) = import() {
+            let _ = fs::remove_dir_all(&ledger_path);
+            bail!("Failed to restore the ledger from the snapshot - {error}");
+        }
+
+        Ok(format!(
+            "✅ Restored the ledger at height {} (block hash '{trusted_hash}') {}",
+            manifest.height,
+            format!("(in \"{}\")", ledger_path.display()).dimmed()
+        ))
+    }
+}
+
+/// The manifest of a snapshot.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct SnapshotManifest {
+    /// The version of the snapshot format.
+    version: u8,
+    /// The network ID of the ledger.
+    network: u16,
+    /// The height of the snapshot.
+    height: u32,
+    /// The block hash at the height of the snapshot.
+    block_hash: String,
+    /// The block files, in the order of their blocks.
+    files: Vec<SnapshotFile>,
+}
+
+/// A block file in a snapshot.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct SnapshotFile {
+    /// The path of the file, relative to the blocks directory.
+    path: String,
+    /// The size of the file in bytes.
+    size: u64,
+    /// The SHA-256 checksum of the file.
+    checksum: String,
+}
+
+/// Loads the ledger from storage.
+fn load_ledger<N: Network>() -> Result<Ledger<N, ConsensusDB<N>>> {
+    // Load the genesis block.
+    let genesis = Block::from_bytes_le(N::genesis_bytes())?;
+    // Load the ledger.
+    Ledger::<N, ConsensusDB<N>>::load(genesis, None)
+}
+
+/// Writes the given blocks in the range of heights into a file in the given directory,
+/// and returns the checksummed file.
+///
+/// The file holds the `bincode` encoding of the vector of blocks, which is the format of the CDN bundles.
+fn write_blocks_file<N: Network>(directory: &Path, start: u32, end: u32, blocks: &[Block<N>]) -> Result<SnapshotFile> {
+    let path = format!("{start}.{end}.blocks");
+    let bytes = bincode::serialize(blocks)?;
+    fs::write(directory.join(&path), &bytes)?;
+    Ok(SnapshotFile { path, size: bytes.len() as u64, checksum: format!("{:x}", Sha256::digest(&bytes)) })
+}
+
+/// Reads the blocks of the given file in the given directory, ensuring the file matches its checksum.
+fn read_blocks_file<N: Network>(directory: &Path, file: &SnapshotFile) -> Result<Vec<Block<N>>> {
+    // Ensure the file path stays within the blocks directory.
+    let relative_path = Path::new(&file.path);
+    ensure!(
+        relative_path.components().all(|component| matches!(component, Component::Normal(_))),
+        "Invalid file path '{}' in the snapshot",
+        file.path
+    );
+    let bytes = fs::read(directory.join(relative_path))?;
+    ensure!(
+        bytes.len() as u64 == file.size && format!("{:x}", Sha256::digest(&bytes)) == file.checksum,
+        "The checksum of '{}' does not match",
+        file.path
+    );
+    Ok(bincode::deserialize(&bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_write_and_read_blocks_file() {
+        let directory = std::env::temp_dir().join(format!("snarkos-test-snapshot-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+
+        // Write the blocks, and ensure the file is checksummed.
+        let block = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let blocks = vec![block.clone(), block];
+        let file = write_blocks_file(&directory, 1, 3, &blocks).unwrap();
+        assert_eq!(file.path, "1.3.blocks");
+        assert_eq!(file.size, fs::metadata(directory.join(&file.path)).unwrap().len());
+
+        // Read the blocks.
+        assert_eq!(read_blocks_file::<CurrentNetwork>(&directory, &file).unwrap(), blocks);
+
+        // Ensure a corrupted file is rejected.
+        let mut bytes = fs::read(directory.join(&file.path)).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        fs::write(directory.join(&file.path), bytes).unwrap();
+        assert!(read_blocks_file::<CurrentNetwork>(&directory, &file).is_err());
+
+        // Ensure a file outside of the blocks directory is rejected.
+        let escaping_file = SnapshotFile { path: "../escape".to_string(), ..file };
+        assert!(read_blocks_file::<CurrentNetwork>(&directory, &escaping_file).is_err());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -12,9 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::helpers::{LogFormat, LogRotation};
+
 use snarkos_account::Account;
 use snarkos_display::Display;
-use snarkos_node::{bft::MEMORY_POOL_PORT, router::messages::NodeType, Node};
+use snarkos_node::{
+    bft::MEMORY_POOL_PORT,
+    rest::{RateLimits, RestConfig},
+    router::messages::{ListenerAddress, NodeType},
+    sync::locators::{TrustedCheckpoint, TrustedCheckpoints},
+    tcp::{BandwidthLimits, Transport},
+    Node,
+};
 use snarkvm::{
     console::{
         account::{Address, PrivateKey},
@@ -31,13 +40,17 @@ use snarkvm::{
     utilities::to_bytes_le,
 };
 
-use anyhow::{bail, ensure, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use clap::Parser;
 use colored::Colorize;
-use core::str::FromStr;
+use core::{str::FromStr, time::Duration};
 use rand::SeedableRng;
 use rand_chacha::ChaChaRng;
-use std::{net::SocketAddr, path::PathBuf};
+use serde::Deserialize;
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
 use tokio::runtime::{self, Runtime};
 
 /// The recommended minimum number of 'open files' limit for a validator.
@@ -77,9 +90,24 @@ pub struct Start {
     /// Specify the IP address and port for the node server
     #[clap(default_value = "0.0.0.0:4133", long = "node")]
     pub node: SocketAddr,
+    /// Specify the transport for the node server ('tcp' or 'quic')
+    #[clap(default_value = "tcp", long = "node-transport")]
+    pub node_transport: Transport,
+    /// Specify the IP address and port of the SOCKS5 proxy for the outbound node connections (e.g. Tor)
+    #[clap(long = "socks5-proxy")]
+    pub socks5_proxy: Option<SocketAddr>,
+    /// Specify the address advertised for inbound node connections ('port', 'none', or a 'host:port' such as an onion address)
+    #[clap(default_value = "port", long = "listener-address")]
+    pub listener_address: ListenerAddress,
     /// Specify the IP address and port for the BFT
     #[clap(long = "bft")]
     pub bft: Option<SocketAddr>,
+    /// Specify the transport for the BFT ('tcp' or 'quic')
+    #[clap(default_value = "tcp", long = "bft-transport")]
+    pub bft_transport: Transport,
+    /// Specify the bandwidth limits of the node and BFT servers, in bytes per second (e.g. 'upload=10000000,peer-download=1000000,sync-share=30')
+    #[clap(long = "bandwidth-limits")]
+    pub bandwidth_limits: Option<BandwidthLimits>,
     /// Specify the IP address and port of the peer(s) to connect to
     #[clap(default_value = "", long = "peers")]
     pub peers: String,
@@ -87,12 +115,31 @@ pub struct Start {
     #[clap(default_value = "", long = "validators")]
     pub validators: String,
 
+    /// Specify the path to a JSON file of signed checkpoints, which the synced headers and blocks must match (client only)
+    #[clap(long = "checkpoints")]
+    pub checkpoints: Option<PathBuf>,
+    /// Specify the address(es) trusted to sign the checkpoints
+    #[clap(default_value = "", long = "checkpoint-signers")]
+    pub checkpoint_signers: String,
+    /// Specify the URL(s) of the REST servers to sync blocks from, alongside the peers (client only)
+    #[clap(default_value = "", long = "sync-endpoints")]
+    pub sync_endpoints: String,
+
     /// Specify the IP address and port for the REST server
     #[clap(default_value = "0.0.0.0:3033", long = "rest")]
     pub rest: SocketAddr,
     /// If the flag is set, the node will not initialize the REST server
     #[clap(long)]
     pub norest: bool,
+    /// If the flag is set, the REST server indexes the ledger by address, to serve the account endpoints (client and validator only)
+    #[clap(long = "address-index")]
+    pub address_index: bool,
+    /// If the flag is set, the REST server archives the mapping values of each new block, to serve the past mapping values (client and validator only)
+    #[clap(long)]
+    pub archive: bool,
+    /// If the flag is set, the REST server limits the rate of the requests per route group, with the default quotas or the given quotas in requests per second (e.g. 'blocks=10,state=0', where 0 disables the limit)
+    #[clap(long = "rest-rate-limits", num_args = 0..=1, default_missing_value = "", value_name = "LIMITS")]
+    pub rest_rate_limits: Option<RateLimits>,
 
     /// If the flag is set, the node will not render the display
     #[clap(long)]
@@ -103,6 +150,31 @@ pub struct Start {
     /// Specify the path to the file where logs will be stored
     #[clap(default_value_os_t = std::env::temp_dir().join("snarkos.log"), long = "logfile")]
     pub logfile: PathBuf,
+    /// Specify the per-module log filter directives (e.g. 'snarkos_node_bft=trace,snarkos_node_router=warn')
+    #[clap(long = "log-filter")]
+    pub log_filter: Option<String>,
+    /// Specify the format of the logs [options: text, json]
+    #[clap(value_enum, default_value_t = LogFormat::Text, long = "log-format")]
+    pub log_format: LogFormat,
+    /// Specify the maximum size of the logfile in MiB, before it is rotated (0 disables rotation by size)
+    #[clap(default_value = "0", long = "log-max-size")]
+    pub log_max_size: u64,
+    /// Specify the maximum age of the logfile in hours, before it is rotated (0 disables rotation by age)
+    #[clap(default_value = "0", long = "log-max-age")]
+    pub log_max_age: u64,
+    /// Specify the maximum number of rotated logfiles to keep
+    #[clap(default_value = "10", long = "log-max-files")]
+    pub log_max_files: usize,
+    /// Specify the endpoint of an OpenTelemetry collector, to export the BFT traces over OTLP (e.g. 'http://127.0.0.1:4317')
+    #[clap(long = "otlp")]
+    pub otlp: Option<String>,
+    /// Specify the path to a JSON file of reloadable configurations, which is re-read on SIGHUP
+    #[clap(long = "config")]
+    pub config: Option<PathBuf>,
+    /// If the flag is set, the node will export its metrics for Prometheus (on port 9000)
+    #[cfg(feature = "metrics")]
+    #[clap(long)]
+    pub metrics: bool,
 
     /// Enables the node to prefetch initial blocks from a CDN
     #[clap(default_value = "https://s3.us-west-1.amazonaws.com/testnet3.blocks/phase3", long = "cdn")]
@@ -122,17 +194,42 @@ pub struct Start {
 impl Start {
     /// Starts the snarkOS node.
     pub fn parse(self) -> Result<String> {
-        // Initialize the logger.
-        let log_receiver = crate::helpers::initialize_logger(self.verbosity, self.nodisplay, self.logfile.clone());
         // Initialize the runtime.
-        Self::runtime().block_on(async move {
+        let runtime = Self::runtime();
+        // Initialize the logger.
+        // Note: The logger is initialized within the runtime, as the OTLP exporter spawns a background task.
+        let log_receiver = {
+            let _guard = runtime.enter();
+            crate::helpers::initialize_logger(
+                self.verbosity,
+                self.log_filter.as_deref(),
+                self.log_format,
+                self.nodisplay,
+                self.logfile.clone(),
+                self.parse_log_rotation(),
+                self.otlp.as_deref(),
+            )?
+        };
+        runtime.block_on(async move {
             // Clone the configurations.
             let mut cli = self.clone();
+            // Initialize the metrics exporter.
+            #[cfg(feature = "metrics")]
+            if cli.metrics {
+                snarkos_node_metrics::initialize();
+            }
             // Parse the network.
             match cli.network {
                 3 => {
                     // Parse the node from the configurations.
                     let node = cli.parse_node::<Testnet3>().await.expect("Failed to parse the node");
+                    // Reload the configurations on SIGHUP.
+                    #[cfg(target_family = "unix")]
+                    cli.handle_reload_signal(node.clone());
+                    // Attach the state of the node to the structured logs.
+                    if cli.log_format == LogFormat::Json {
+                        Self::handle_log_context(node.clone());
+                    }
                     // If the display is enabled, render the display.
                     if !cli.nodisplay {
                         // Initialize the display.
@@ -153,37 +250,48 @@ impl Start {
 impl Start {
     /// Returns the initial peer(s) to connect to, from the given configurations.
     fn parse_trusted_peers(&self) -> Result<Vec<SocketAddr>> {
-        match self.peers.is_empty() {
-            true => Ok(vec![]),
-            false => Ok(self
-                .peers
-                .split(',')
-                .flat_map(|ip| match ip.parse::<SocketAddr>() {
-                    Ok(ip) => Some(ip),
-                    Err(e) => {
-                        eprintln!("The IP supplied to --peers ('{ip}') is malformed: {e}");
-                        None
-                    }
-                })
-                .collect()),
-        }
+        Ok(parse_socket_addrs(&self.peers, "--peers"))
     }
 
     /// Returns the initial validator(s) to connect to, from the given configurations.
     fn parse_trusted_validators(&self) -> Result<Vec<SocketAddr>> {
-        match self.validators.is_empty() {
-            true => Ok(vec![]),
-            false => Ok(self
-                .validators
-                .split(',')
-                .flat_map(|ip| match ip.parse::<SocketAddr>() {
-                    Ok(ip) => Some(ip),
-                    Err(e) => {
-                        eprintln!("The IP supplied to --validators ('{ip}') is malformed: {e}");
-                        None
-                    }
-                })
-                .collect()),
+        Ok(parse_socket_addrs(&self.validators, "--validators"))
+    }
+
+    /// Returns the URLs of the REST servers to sync blocks from, from the given configurations.
+    fn parse_sync_endpoints(&self) -> Vec<String> {
+        self.sync_endpoints.split(',').map(str::trim).filter(|url| !url.is_empty()).map(String::from).collect()
+    }
+
+    /// Returns the trusted checkpoints, from the given configurations.
+    fn parse_checkpoints<N: Network>(&self) -> Result<TrustedCheckpoints<N>> {
+        // If no checkpoints file was provided, there are no trusted checkpoints.
+        let Some(path) = &self.checkpoints else {
+            return Ok(Default::default());
+        };
+        // Ensure the node is a client, as only the client sync enforces the checkpoints.
+        ensure!(self.parse_node_type().is_client(), "The '--checkpoints' argument is only supported for client nodes");
+        // Parse the checkpoints.
+        let checkpoints: Vec<TrustedCheckpoint<N>> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        // Parse the trusted signers.
+        let signers = self
+            .checkpoint_signers
+            .split(',')
+            .map(str::trim)
+            .filter(|signer| !signer.is_empty())
+            .map(Address::from_str)
+            .collect::<Result<Vec<_>>>()?;
+        // Ensure the checkpoints are signed by the trusted signers.
+        TrustedCheckpoints::new(checkpoints, &signers)
+            .map_err(|error| anyhow!("Invalid checkpoints in '{}' - {error}", path.display()))
+    }
+
+    /// Returns the rotation policy of the logfile, from the given configurations.
+    fn parse_log_rotation(&self) -> LogRotation {
+        LogRotation {
+            max_size: self.log_max_size.saturating_mul(1024 * 1024),
+            max_age: (self.log_max_age > 0).then(|| Duration::from_secs(self.log_max_age.saturating_mul(3600))),
+            max_files: self.log_max_files,
         }
     }
 
@@ -381,6 +489,10 @@ impl Start {
 
         // Parse the CDN.
         let cdn = self.parse_cdn();
+        // Parse the trusted checkpoints.
+        let checkpoints = self.parse_checkpoints::<N>()?;
+        // Parse the REST servers to sync blocks from.
+        let sync_endpoints = self.parse_sync_endpoints();
 
         // Parse the genesis block.
         let genesis = self.parse_genesis::<N>()?;
@@ -388,12 +500,20 @@ impl Start {
         let account = self.parse_private_key::<N>()?;
         // Parse the node type.
         let node_type = self.parse_node_type();
+        // Parse the bandwidth limits.
+        let bandwidth_limits = self.bandwidth_limits.unwrap_or_default();
 
         // Parse the REST IP.
         let rest_ip = match self.norest {
             true => None,
             false => Some(self.rest),
         };
+        // Initialize the configuration of the REST server.
+        let rest_config = RestConfig {
+            address_index: self.address_index,
+            archive: self.archive,
+            rate_limits: self.rest_rate_limits.clone(),
+        };
 
         // If the display is not enabled, render the welcome message.
         if self.nodisplay {
@@ -431,12 +551,51 @@ impl Start {
         // Initialize the node.
         let bft_ip = if self.dev.is_some() { self.bft } else { None };
         match node_type {
-            NodeType::Validator => Node::new_validator(self.node, rest_ip, bft_ip, account, &trusted_peers, &trusted_validators, genesis, cdn, self.dev).await,
-            NodeType::Prover => Node::new_prover(self.node, account, &trusted_peers, genesis, self.dev).await,
-            NodeType::Client => Node::new_client(self.node, rest_ip, account, &trusted_peers, genesis, cdn, self.dev).await,
+            NodeType::Validator => Node::new_validator(self.node, self.node_transport, bandwidth_limits, self.socks5_proxy, self.listener_address.clone(), rest_ip, rest_config, bft_ip, self.bft_transport, account, &trusted_peers, &trusted_validators, genesis, cdn, self.dev).await,
+            NodeType::Prover => Node::new_prover(self.node, self.node_transport, bandwidth_limits, self.socks5_proxy, self.listener_address.clone(), account, &trusted_peers, genesis, self.dev).await,
+            NodeType::Client => Node::new_client(self.node, self.node_transport, bandwidth_limits, self.socks5_proxy, self.listener_address.clone(), rest_ip, rest_config, account, &trusted_peers, genesis, cdn, checkpoints, &sync_endpoints, self.dev).await,
         }
     }
 
+    /// Reloads the configurations from the `--config` file, whenever the process receives SIGHUP.
+    #[cfg(target_family = "unix")]
+    fn handle_reload_signal<N: Network>(&self, node: Node<N>) {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        // If no configuration file was provided, there is nothing to reload.
+        let Some(path) = self.config.clone() else {
+            return;
+        };
+
+        tokio::spawn(async move {
+            let mut sighup = match signal(SignalKind::hangup()) {
+                Ok(sighup) => sighup,
+                Err(error) => {
+                    eprintln!("Failed to listen for SIGHUP: {error}");
+                    return;
+                }
+            };
+            while sighup.recv().await.is_some() {
+                match ReloadableConfig::load(&path).and_then(|config| config.apply(&node)) {
+                    Ok(()) => println!("🔄 Reloaded the configurations from '{}'", path.display()),
+                    Err(error) => eprintln!("Failed to reload the configurations from '{}': {error}", path.display()),
+                }
+            }
+        });
+    }
+
+    /// Periodically updates the node context, which is attached to the structured logs.
+    fn handle_log_context<N: Network>(node: Node<N>) {
+        tokio::spawn(async move {
+            let node_type = node.node_type().to_string();
+            let address = node.address().to_string();
+            loop {
+                crate::helpers::set_log_context(&node_type, &address, node.latest_height(), node.current_round());
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        });
+    }
+
     /// Returns a runtime for the node.
     fn runtime() -> Runtime {
         // Retrieve the number of cores.
@@ -474,6 +633,72 @@ impl Start {
     }
 }
 
+/// The configurations that may be reloaded at runtime, from the `--config` file.
+///
+/// ```json
+/// { "verbosity": 2, "log_filter": "snarkos_node_bft=trace", "peers": "1.2.3.4:4133,5.6.7.8:4133", "validators": "1.2.3.4:5000" }
+/// ```
+///
+/// The OTLP export of the traces is enabled with `"otlp": "http://127.0.0.1:4317"`, and disabled with `"otlp": ""`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ReloadableConfig {
+    /// The verbosity of the node.
+    verbosity: Option<u8>,
+    /// The per-module log filter directives.
+    log_filter: Option<String>,
+    /// The endpoint of the OpenTelemetry collector, or an empty string to disable the OTLP export.
+    otlp: Option<String>,
+    /// The comma-separated IP addresses and ports of the trusted peers.
+    peers: Option<String>,
+    /// The comma-separated IP addresses and ports of the trusted validators.
+    validators: Option<String>,
+}
+
+impl ReloadableConfig {
+    /// Loads the configurations from the given JSON file.
+    fn load(path: &Path) -> Result<Self> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Applies the configurations to the logger and the given node.
+    fn apply<N: Network>(&self, node: &Node<N>) -> Result<()> {
+        if let Some(verbosity) = self.verbosity {
+            crate::helpers::set_log_verbosity(verbosity)?;
+        }
+        if let Some(log_filter) = &self.log_filter {
+            crate::helpers::set_log_directives(log_filter)?;
+        }
+        if let Some(otlp) = &self.otlp {
+            crate::helpers::set_otlp_endpoint(Some(otlp))?;
+        }
+        if let Some(peers) = &self.peers {
+            node.set_trusted_peers(&parse_socket_addrs(peers, "peers"));
+        }
+        if let Some(validators) = &self.validators {
+            node.set_trusted_validators(&parse_socket_addrs(validators, "validators"));
+        }
+        Ok(())
+    }
+}
+
+/// Parses the given comma-separated list of IP addresses and ports, skipping (and reporting) malformed entries.
+fn parse_socket_addrs(ips: &str, source: &str) -> Vec<SocketAddr> {
+    match ips.is_empty() {
+        true => vec![],
+        false => ips
+            .split(',')
+            .flat_map(|ip| match ip.parse::<SocketAddr>() {
+                Ok(ip) => Some(ip),
+                Err(e) => {
+                    eprintln!("The IP supplied to {source} ('{ip}') is malformed: {e}");
+                    None
+                }
+            })
+            .collect(),
+    }
+}
+
 /// Loads or computes the genesis block.
 fn load_or_compute_genesis<N: Network>(
     genesis_private_key: PrivateKey<N>,
@@ -582,6 +807,87 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_reloadable_config() {
+        let path = std::env::temp_dir().join("snarkos-test-reloadable-config.json");
+
+        std::fs::write(&path, r#"{ "verbosity": 2, "peers": "1.2.3.4:5,6.7.8.9:0", "otlp": "" }"#).unwrap();
+        let config = ReloadableConfig::load(&path).unwrap();
+        assert_eq!(config.verbosity, Some(2));
+        assert_eq!(config.otlp.as_deref(), Some(""));
+        assert_eq!(parse_socket_addrs(config.peers.as_deref().unwrap(), "peers"), vec![
+            SocketAddr::from_str("1.2.3.4:5").unwrap(),
+            SocketAddr::from_str("6.7.8.9:0").unwrap()
+        ]);
+        assert!(config.validators.is_none());
+
+        // Unknown configurations are rejected, as they are not reloadable.
+        std::fs::write(&path, r#"{ "node": "0.0.0.0:4133" }"#).unwrap();
+        assert!(ReloadableConfig::load(&path).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_sync_endpoints() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert!(config.parse_sync_endpoints().is_empty());
+
+        let config =
+            Start::try_parse_from(["snarkos", "--sync-endpoints", "http://1.2.3.4:3033, http://5.6.7.8:3033"].iter())
+                .unwrap();
+        assert_eq!(config.parse_sync_endpoints(), vec!["http://1.2.3.4:3033", "http://5.6.7.8:3033"]);
+    }
+
+    #[test]
+    fn test_parse_rest_rate_limits() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert_eq!(config.rest_rate_limits, None);
+
+        let config = Start::try_parse_from(["snarkos", "--rest-rate-limits"].iter()).unwrap();
+        assert_eq!(config.rest_rate_limits, Some(RateLimits::default()));
+
+        let config = Start::try_parse_from(["snarkos", "--rest-rate-limits", "blocks=10,state=0"].iter()).unwrap();
+        assert_eq!(config.rest_rate_limits, Some(RateLimits::from_str("blocks=10,state=0").unwrap()));
+
+        assert!(Start::try_parse_from(["snarkos", "--rest-rate-limits", "mappings=10"].iter()).is_err());
+    }
+
+    #[test]
+    fn test_parse_checkpoints() {
+        let path = std::env::temp_dir().join("snarkos-test-checkpoints.json");
+        let rng = &mut rand::thread_rng();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let signer = Address::try_from(&private_key).unwrap().to_string();
+        let checkpoint = TrustedCheckpoint::sign(&private_key, 10, Default::default(), rng).unwrap();
+        std::fs::write(&path, serde_json::to_string(&vec![checkpoint]).unwrap()).unwrap();
+        let path_str = path.to_str().unwrap();
+
+        // Without a checkpoints file, there are no trusted checkpoints.
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert!(config.parse_checkpoints::<CurrentNetwork>().unwrap().is_empty());
+
+        // The checkpoints must be signed by a trusted signer.
+        let config = Start::try_parse_from(["snarkos", "--checkpoints", path_str].iter()).unwrap();
+        assert!(config.parse_checkpoints::<CurrentNetwork>().is_err());
+        let config = Start::try_parse_from(
+            ["snarkos", "--checkpoints", path_str, "--checkpoint-signers", signer.as_str()].iter(),
+        )
+        .unwrap();
+        assert_eq!(config.parse_checkpoints::<CurrentNetwork>().unwrap().latest_height(), Some(10));
+
+        // The checkpoints are only supported for client nodes.
+        for node_type in ["--validator", "--prover"] {
+            let config = Start::try_parse_from(
+                ["snarkos", node_type, "--checkpoints", path_str, "--checkpoint-signers", signer.as_str()].iter(),
+            )
+            .unwrap();
+            assert!(config.parse_checkpoints::<CurrentNetwork>().is_err());
+        }
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn test_parse_cdn() {
         // Validator (Prod)
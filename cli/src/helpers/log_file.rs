@@ -0,0 +1,189 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use parking_lot::Mutex;
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The rotation policy of the log file.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogRotation {
+    /// The maximum size of the log file in bytes, before it is rotated (`0` disables the size limit).
+    pub max_size: u64,
+    /// The maximum age of the log file, before it is rotated.
+    pub max_age: Option<Duration>,
+    /// The maximum number of rotated log files to keep.
+    pub max_files: usize,
+}
+
+/// A log file, which is rotated by size and age.
+pub struct LogFile {
+    /// The path of the active log file.
+    path: PathBuf,
+    /// The rotation policy.
+    rotation: LogRotation,
+    /// The state of the active log file.
+    state: Mutex<LogFileState>,
+}
+
+/// The state of the active log file.
+struct LogFileState {
+    /// The file handle.
+    file: File,
+    /// The number of bytes in the file.
+    size: u64,
+    /// The time the file was created.
+    created_at: SystemTime,
+}
+
+impl LogFile {
+    /// Opens the log file at the given path, creating it (and its directories) if it does not exist.
+    pub fn open<P: AsRef<Path>>(path: P, rotation: LogRotation) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        // Create the directories tree for the log file if it doesn't exist.
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let state = Self::open_state(&path)?;
+        Ok(Self { path, rotation, state: Mutex::new(state) })
+    }
+
+    /// Opens the active log file for appending.
+    fn open_state(path: &Path) -> io::Result<LogFileState> {
+        let file = File::options().append(true).create(true).open(path)?;
+        let metadata = file.metadata()?;
+        // Note: Not every filesystem records the creation time, in which case the file is aged from now.
+        let created_at = metadata.created().unwrap_or_else(|_| SystemTime::now());
+        Ok(LogFileState { file, size: metadata.len(), created_at })
+    }
+
+    /// Returns `true` if the active log file must be rotated, before writing the given number of bytes.
+    fn should_rotate(&self, state: &LogFileState, num_bytes: usize) -> bool {
+        // Check if the size limit would be exceeded (an empty file is always written to).
+        let exceeds_size =
+            self.rotation.max_size > 0 && state.size > 0 && state.size + num_bytes as u64 > self.rotation.max_size;
+        // Check if the age limit has been reached.
+        let exceeds_age = match self.rotation.max_age {
+            Some(max_age) => state.created_at.elapsed().map_or(false, |age| age >= max_age),
+            None => false,
+        };
+        exceeds_size || exceeds_age
+    }
+
+    /// Renames the active log file to `{path}.{timestamp}`, opens a new active log file,
+    /// and removes the oldest rotated log files beyond the limit.
+    fn rotate(&self, state: &mut LogFileState) -> io::Result<()> {
+        state.file.flush()?;
+        // Note: The timestamp is in milliseconds, so the rotated files sort chronologically.
+        let mut timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+        // Ensure a rotated log file is never overwritten, if several rotations occur within a millisecond.
+        while self.rotated_path(timestamp).exists() {
+            timestamp += 1;
+        }
+        fs::rename(&self.path, self.rotated_path(timestamp))?;
+        *state = Self::open_state(&self.path)?;
+        self.prune()
+    }
+
+    /// Returns the path of the rotated log file with the given timestamp.
+    fn rotated_path(&self, timestamp: u128) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{timestamp:020}"));
+        PathBuf::from(path)
+    }
+
+    /// Returns the paths of the rotated log files, from oldest to newest.
+    fn rotated_paths(&self) -> io::Result<Vec<PathBuf>> {
+        let (Some(parent), Some(file_name)) = (self.path.parent(), self.path.file_name()) else {
+            return Ok(vec![]);
+        };
+        let prefix = format!("{}.", file_name.to_string_lossy());
+        let directory = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+
+        let mut paths = vec![];
+        for entry in fs::read_dir(directory)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            // Only consider the files with a timestamp suffix.
+            if let Some(suffix) = name.strip_prefix(&prefix) {
+                if !suffix.is_empty() && suffix.bytes().all(|byte| byte.is_ascii_digit()) {
+                    paths.push(entry.path());
+                }
+            }
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Removes the oldest rotated log files, until at most `max_files` remain.
+    fn prune(&self) -> io::Result<()> {
+        let paths = self.rotated_paths()?;
+        let num_to_remove = paths.len().saturating_sub(self.rotation.max_files);
+        for path in paths.into_iter().take(num_to_remove) {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+impl io::Write for &LogFile {
+    /// Writes the given buffer into the log file, rotating the file first if necessary.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.state.lock();
+        if self.should_rotate(&state, buf.len()) {
+            // Note: If the rotation fails, the logs continue to be appended to the active log file.
+            if let Err(error) = self.rotate(&mut state) {
+                eprintln!("Failed to rotate the log file '{}': {error}", self.path.display());
+            }
+        }
+        let num_bytes = state.file.write(buf)?;
+        state.size += num_bytes as u64;
+        Ok(num_bytes)
+    }
+
+    /// Flushes the log file.
+    fn flush(&mut self) -> io::Result<()> {
+        self.state.lock().file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate_by_size() {
+        let directory = std::env::temp_dir().join(format!("snarkos-test-log-file-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+
+        let rotation = LogRotation { max_size: 10, max_age: None, max_files: 2 };
+        let log_file = LogFile::open(directory.join("snarkos.log"), rotation).unwrap();
+
+        // Write 4 lines of 8 bytes, which must each land in a separate file.
+        for _ in 0..4 {
+            (&log_file).write_all(b"0123456\n").unwrap();
+        }
+
+        // Ensure the active log file only contains the last line.
+        assert_eq!(fs::read(directory.join("snarkos.log")).unwrap(), b"0123456\n");
+        // Ensure only the 2 most recent rotated log files are kept.
+        assert_eq!(log_file.rotated_paths().unwrap().len(), 2);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
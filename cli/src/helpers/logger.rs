@@ -12,17 +12,80 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::helpers::LogWriter;
+use crate::helpers::{LogFile, LogRotation, LogWriter};
 
+use anyhow::{bail, Result};
 use crossterm::tty::IsTty;
-use std::{fs::File, io, path::Path};
+use once_cell::sync::{Lazy, OnceCell};
+use opentelemetry::{
+    trace::{TraceResult, TracerProvider as _},
+    Context,
+    KeyValue,
+};
+use opentelemetry_otlp::{SpanExporterBuilder, WithExportConfig};
+use opentelemetry_sdk::{
+    export::trace::SpanData,
+    runtime::Tokio,
+    trace::{BatchSpanProcessor, Span, SpanProcessor, Tracer, TracerProvider},
+    Resource,
+};
+use parking_lot::{Mutex, RwLock};
+use serde_json::{Map, Value};
+use std::{
+    fmt,
+    io,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 use tokio::sync::mpsc;
+use tracing::{
+    field::{Field, Visit},
+    Event,
+    Level,
+    Subscriber,
+};
 use tracing_subscriber::{
-    layer::{Layer, SubscriberExt},
+    filter::{filter_fn, Directive, FilterExt, Targets},
+    fmt::{
+        format::Writer,
+        time::{FormatTime, SystemTime},
+        FmtContext,
+        FormatEvent,
+        FormatFields,
+        MakeWriter,
+    },
+    layer::{Filter, Layer, SubscriberExt},
+    registry::LookupSpan,
+    reload,
     util::SubscriberInitExt,
     EnvFilter,
 };
 
+/// The handler to reload the log filters.
+static LOG_FILTER_RELOADER: OnceCell<LogFilterReloader> = OnceCell::new();
+
+/// The processor exporting the spans to the OTLP endpoint, if the export is enabled.
+static OTLP_PROCESSOR: Lazy<RwLock<Option<OtlpProcessor>>> = Lazy::new(Default::default);
+
+/// A flag indicating whether the spans are exported over OTLP, which is checked before tracing each span.
+static OTLP_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The node context, which is attached to every log in the JSON format.
+static LOG_CONTEXT: Lazy<RwLock<LogContext>> = Lazy::new(Default::default);
+
+/// The format of the logs.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+}
+
 /// Initializes the logger.
 ///
 /// ```ignore
@@ -34,65 +97,47 @@ use tracing_subscriber::{
 /// 5 => info, debug, trace, snarkos_node_router=trace
 /// 6 => info, debug, trace, snarkos_node_tcp=trace
 /// ```
-pub fn initialize_logger<P: AsRef<Path>>(verbosity: u8, nodisplay: bool, logfile: P) -> mpsc::Receiver<Vec<u8>> {
-    match verbosity {
-        0 => std::env::set_var("RUST_LOG", "info"),
-        1 => std::env::set_var("RUST_LOG", "debug"),
-        2.. => std::env::set_var("RUST_LOG", "trace"),
-    };
+///
+/// The given `directives` (e.g. `snarkos_node_bft=trace,snarkos_node_router=warn`) are applied on top of the verbosity.
+///
+/// If an `otlp` endpoint is given, the spans of the BFT and consensus are exported to it.
+/// The export may also be enabled or disabled at runtime, with `set_otlp_endpoint`.
+/// Note: The OTLP exporter must be initialized within a Tokio runtime.
+pub fn initialize_logger<P: AsRef<Path>>(
+    verbosity: u8,
+    directives: Option<&str>,
+    format: LogFormat,
+    nodisplay: bool,
+    logfile: P,
+    rotation: LogRotation,
+    otlp: Option<&str>,
+) -> Result<mpsc::Receiver<Vec<u8>>> {
+    // Initialize the log filter.
+    let log_filter = LogFilter { verbosity, directives: directives.unwrap_or_default().trim().to_string() };
 
     // Filter out undesirable logs. (unfortunately EnvFilter cannot be cloned)
-    let [filter, filter2] = std::array::from_fn(|_| {
-        let filter = EnvFilter::from_default_env()
-            .add_directive("mio=off".parse().unwrap())
-            .add_directive("tokio_util=off".parse().unwrap())
-            .add_directive("hyper=off".parse().unwrap())
-            .add_directive("reqwest=off".parse().unwrap())
-            .add_directive("want=off".parse().unwrap())
-            .add_directive("warp=off".parse().unwrap());
-
-        let filter = if verbosity >= 2 {
-            filter.add_directive("snarkos_node_sync=trace".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_sync=debug".parse().unwrap())
-        };
-
-        let filter = if verbosity >= 3 {
-            filter
-                .add_directive("snarkos_node_bft=trace".parse().unwrap())
-                .add_directive("snarkos_node_bft::gateway=debug".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_bft=debug".parse().unwrap())
-        };
-
-        let filter = if verbosity >= 4 {
-            filter.add_directive("snarkos_node_bft::gateway=trace".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_bft::gateway=debug".parse().unwrap())
-        };
-
-        let filter = if verbosity >= 5 {
-            filter.add_directive("snarkos_node_router=trace".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_router=debug".parse().unwrap())
-        };
-
-        if verbosity >= 6 {
-            filter.add_directive("snarkos_node_tcp=trace".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_tcp=off".parse().unwrap())
-        }
+    // Note: The filters are reloadable, in order to update the log filter at runtime.
+    let (filter, filter_handle) = reload::Layer::new(log_filter.to_env_filter()?);
+    let (filter2, filter2_handle) = reload::Layer::new(log_filter.to_env_filter()?);
+
+    // Set the handler to reload the filters.
+    let _ = LOG_FILTER_RELOADER.set(LogFilterReloader {
+        current: Mutex::new(log_filter),
+        reload: Box::new(move |log_filter| {
+            filter_handle.reload(log_filter.to_env_filter()?)?;
+            filter2_handle.reload(log_filter.to_env_filter()?)?;
+            Ok(())
+        }),
     });
+    // Allow the log filter and the OTLP endpoint to be updated from the REST server.
+    snarkos_node_rest::set_log_filter_handler(set_log_directives);
+    snarkos_node_rest::set_otlp_handler(set_otlp_endpoint);
 
-    // Create the directories tree for a logfile if it doesn't exist.
-    let logfile_dir = logfile.as_ref().parent().expect("Root directory passed as a logfile");
-    if !logfile_dir.exists() {
-        std::fs::create_dir_all(logfile_dir)
-            .expect("Failed to create a directories: '{logfile_dir}', please check if user has permissions");
-    }
-    // Create a file to write logs to.
-    let logfile =
-        File::options().append(true).create(true).open(logfile).expect("Failed to open the file for writing logs");
+    // Open the file to write logs to, which is rotated according to the given policy.
+    let logfile = match LogFile::open(logfile.as_ref(), rotation) {
+        Ok(logfile) => Arc::new(logfile),
+        Err(error) => bail!("Failed to open the file '{}' for writing logs: {error}", logfile.as_ref().display()),
+    };
 
     // Initialize the log channel.
     let (log_sender, log_receiver) = mpsc::channel(1024);
@@ -102,28 +147,332 @@ pub fn initialize_logger<P: AsRef<Path>>(verbosity: u8, nodisplay: bool, logfile
         true => None,
         false => Some(log_sender),
     };
+    let ansi = log_sender.is_none() && io::stdout().is_tty();
+
+    // Initialize the OTLP export, if an endpoint was provided.
+    if let Some(endpoint) = otlp {
+        set_otlp_endpoint(Some(endpoint))?;
+    }
 
     // Initialize tracing.
     let _ = tracing_subscriber::registry()
         .with(
             // Add layer using LogWriter for stdout / terminal
-            tracing_subscriber::fmt::Layer::default()
-                .with_ansi(log_sender.is_none() && io::stdout().is_tty())
-                .with_writer(move || LogWriter::new(&log_sender))
-                .with_target(verbosity > 2)
-                .with_filter(filter),
+            format_layer(format, ansi, verbosity > 2, move || LogWriter::new(&log_sender)).with_filter(filter),
         )
         .with(
             // Add layer redirecting logs to the file
-            tracing_subscriber::fmt::Layer::default()
-                .with_ansi(false)
-                .with_writer(logfile)
-                .with_target(verbosity > 2)
-                .with_filter(filter2),
+            format_layer(format, false, verbosity > 2, logfile).with_filter(filter2),
+        )
+        .with(
+            // Add layer exporting the spans over OTLP, which is skipped while the export is disabled
+            tracing_opentelemetry::layer().with_tracer(otlp_tracer()).with_filter(otlp_filter()),
         )
         .try_init();
 
-    log_receiver
+    Ok(log_receiver)
+}
+
+/// Returns a formatting layer for the given log format.
+fn format_layer<S, W>(format: LogFormat, ansi: bool, target: bool, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a> + 'static,
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::Layer::default().with_ansi(ansi).with_writer(writer).with_target(target);
+    match format {
+        LogFormat::Text => layer.boxed(),
+        LogFormat::Json => layer.event_format(JsonFormat).boxed(),
+    }
+}
+
+/// Returns a tracer, which exports the spans in batches to the current OTLP endpoint, if any.
+fn otlp_tracer() -> Tracer {
+    let resource = Resource::new(vec![KeyValue::new("service.name", "snarkos")]);
+    let provider = TracerProvider::builder()
+        .with_span_processor(OtlpSpanProcessor)
+        .with_config(opentelemetry_sdk::trace::config().with_resource(resource))
+        .build();
+    let tracer = provider.tracer("snarkos");
+    // Note: The tracer only holds a weak reference to its provider, which is kept alive as the global provider.
+    let _ = opentelemetry::global::set_tracer_provider(provider);
+    tracer
+}
+
+/// Returns the filter of the spans exported over OTLP, which covers the lifecycle of a batch.
+/// Note: The spans are only traced while the export is enabled.
+fn otlp_filter<S>() -> impl Filter<S> {
+    let targets =
+        Targets::new().with_target("snarkos_node_bft", Level::INFO).with_target("snarkos_node_consensus", Level::INFO);
+    FilterExt::<S>::and(targets, filter_fn(|_| OTLP_ENABLED.load(Ordering::Relaxed)))
+}
+
+/// Enables the export of the spans to the given OTLP endpoint, or disables the export if no endpoint is given,
+/// and returns the resulting OTLP endpoint.
+pub fn set_otlp_endpoint(endpoint: Option<&str>) -> Result<Option<String>> {
+    // Initialize the processor for the new endpoint, if any.
+    let processor = match endpoint.map(str::trim).filter(|endpoint| !endpoint.is_empty()) {
+        Some(endpoint) => Some(OtlpProcessor::new(endpoint)?),
+        None => None,
+    };
+    let endpoint = processor.as_ref().map(|processor| processor.endpoint.clone());
+
+    // Swap the processor, and update the flag while the lock is held, so concurrent updates are applied in order.
+    let mut current = OTLP_PROCESSOR.write();
+    let previous = std::mem::replace(&mut *current, processor);
+    OTLP_ENABLED.store(current.is_some(), Ordering::Relaxed);
+    drop(current);
+
+    // Flush and shut down the previous processor.
+    // Note: The shutdown blocks until the pending spans are exported, so it is done on a separate thread.
+    if let Some(mut previous) = previous {
+        std::thread::spawn(move || {
+            if let Err(error) = previous.processor.shutdown() {
+                eprintln!("Failed to shut down the OTLP exporter for '{}': {error}", previous.endpoint);
+            }
+        });
+    }
+    Ok(endpoint)
+}
+
+/// Updates the verbosity of the logger at runtime.
+pub fn set_log_verbosity(verbosity: u8) -> Result<()> {
+    update_log_filter(|log_filter| log_filter.verbosity = verbosity).map(|_| ())
+}
+
+/// Updates the filter directives of the logger at runtime, and returns the resulting log filter.
+pub fn set_log_directives(directives: &str) -> Result<String> {
+    update_log_filter(|log_filter| log_filter.directives = directives.trim().to_string())
+}
+
+/// Updates the log filter with the given function, and returns the resulting log filter.
+fn update_log_filter(update: impl FnOnce(&mut LogFilter)) -> Result<String> {
+    let Some(reloader) = LOG_FILTER_RELOADER.get() else {
+        bail!("The logger is not initialized");
+    };
+    // Note: The lock is held until the reload completes, to ensure concurrent updates are applied in order.
+    let mut current = reloader.current.lock();
+    let mut log_filter = current.clone();
+    update(&mut log_filter);
+    // Ensure the log filter is valid, before reloading it.
+    let env_filter = log_filter.to_env_filter()?;
+    (reloader.reload)(&log_filter)?;
+    *current = log_filter;
+    Ok(env_filter.to_string())
+}
+
+/// Updates the node context, which is attached to every log in the JSON format.
+pub fn set_log_context(node_type: &str, address: &str, height: u32, round: Option<u64>) {
+    *LOG_CONTEXT.write() = LogContext {
+        node_type: Some(node_type.to_string()),
+        address: Some(address.to_string()),
+        height: Some(height),
+        round,
+    };
+}
+
+/// The processor exporting the spans in batches to an OTLP endpoint.
+struct OtlpProcessor {
+    /// The OTLP endpoint.
+    endpoint: String,
+    /// The processor of the spans.
+    processor: BatchSpanProcessor<Tokio>,
+}
+
+impl OtlpProcessor {
+    /// Initializes a new processor, which exports the spans in batches to the given OTLP endpoint.
+    fn new(endpoint: &str) -> Result<Self> {
+        let exporter = opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint);
+        match SpanExporterBuilder::from(exporter).build_span_exporter() {
+            Ok(exporter) => Ok(Self {
+                endpoint: endpoint.to_string(),
+                processor: BatchSpanProcessor::builder(exporter, Tokio).build(),
+            }),
+            Err(error) => bail!("Failed to initialize the OTLP exporter for '{endpoint}': {error}"),
+        }
+    }
+}
+
+/// The span processor of the OTLP tracer, which forwards the spans to the current OTLP processor, if any.
+#[derive(Debug)]
+struct OtlpSpanProcessor;
+
+impl SpanProcessor for OtlpSpanProcessor {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        if let Some(otlp) = OTLP_PROCESSOR.read().as_ref() {
+            otlp.processor.on_start(span, cx);
+        }
+    }
+
+    fn on_end(&self, span: SpanData) {
+        if let Some(otlp) = OTLP_PROCESSOR.read().as_ref() {
+            otlp.processor.on_end(span);
+        }
+    }
+
+    fn force_flush(&self) -> TraceResult<()> {
+        match OTLP_PROCESSOR.read().as_ref() {
+            Some(otlp) => otlp.processor.force_flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn shutdown(&mut self) -> TraceResult<()> {
+        match OTLP_PROCESSOR.write().as_mut() {
+            Some(otlp) => otlp.processor.shutdown(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The handler to reload the log filters.
+struct LogFilterReloader {
+    /// The current log filter.
+    current: Mutex<LogFilter>,
+    /// The function to reload the filters of every layer.
+    reload: Box<dyn Fn(&LogFilter) -> Result<()> + Send + Sync>,
+}
+
+/// The log filter, composed of a verbosity and additional filter directives.
+#[derive(Clone, Debug, Default)]
+struct LogFilter {
+    /// The verbosity of the logger.
+    verbosity: u8,
+    /// The comma-separated filter directives, which take precedence over the verbosity.
+    directives: String,
+}
+
+impl LogFilter {
+    /// Returns the environment filter for the log filter.
+    fn to_env_filter(&self) -> Result<EnvFilter> {
+        let mut filter = verbosity_filter(self.verbosity);
+        for directive in self.directives.split(',').map(str::trim).filter(|directive| !directive.is_empty()) {
+            match directive.parse::<Directive>() {
+                Ok(directive) => filter = filter.add_directive(directive),
+                Err(error) => bail!("Invalid log filter directive '{directive}': {error}"),
+            }
+        }
+        Ok(filter)
+    }
+}
+
+/// The node context, which is attached to every log in the JSON format.
+#[derive(Clone, Debug, Default)]
+struct LogContext {
+    /// The node type.
+    node_type: Option<String>,
+    /// The address of the node.
+    address: Option<String>,
+    /// The latest block height of the node.
+    height: Option<u32>,
+    /// The current BFT round of the node, if the node is a validator.
+    round: Option<u64>,
+}
+
+/// A formatter, which writes each log as a single-line JSON object.
+struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(&self, _ctx: &FmtContext<'_, S, N>, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        let mut timestamp = String::new();
+        SystemTime.format_time(&mut Writer::new(&mut timestamp))?;
+
+        let metadata = event.metadata();
+        let mut log = Map::new();
+        log.insert("timestamp".to_string(), timestamp.into());
+        log.insert("level".to_string(), metadata.level().as_str().into());
+        log.insert("target".to_string(), metadata.target().into());
+
+        // Attach the node context.
+        let context = LOG_CONTEXT.read().clone();
+        log.insert("node_type".to_string(), context.node_type.into());
+        log.insert("address".to_string(), context.address.into());
+        log.insert("height".to_string(), context.height.into());
+        log.insert("round".to_string(), context.round.into());
+
+        // Attach the fields of the event (including the message).
+        event.record(&mut JsonVisitor(&mut log));
+
+        writeln!(writer, "{}", Value::Object(log))
+    }
+}
+
+/// A visitor, which records the fields of an event into a JSON object.
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{value:?}").into());
+    }
+}
+
+/// Returns the log filter for the given verbosity.
+fn verbosity_filter(verbosity: u8) -> EnvFilter {
+    let filter = match verbosity {
+        0 => EnvFilter::new("info"),
+        1 => EnvFilter::new("debug"),
+        2.. => EnvFilter::new("trace"),
+    };
+
+    let filter = filter
+        .add_directive("mio=off".parse().unwrap())
+        .add_directive("tokio_util=off".parse().unwrap())
+        .add_directive("hyper=off".parse().unwrap())
+        .add_directive("reqwest=off".parse().unwrap())
+        .add_directive("want=off".parse().unwrap())
+        .add_directive("warp=off".parse().unwrap());
+
+    let filter = if verbosity >= 2 {
+        filter.add_directive("snarkos_node_sync=trace".parse().unwrap())
+    } else {
+        filter.add_directive("snarkos_node_sync=debug".parse().unwrap())
+    };
+
+    let filter = if verbosity >= 3 {
+        filter
+            .add_directive("snarkos_node_bft=trace".parse().unwrap())
+            .add_directive("snarkos_node_bft::gateway=debug".parse().unwrap())
+    } else {
+        filter.add_directive("snarkos_node_bft=debug".parse().unwrap())
+    };
+
+    let filter = if verbosity >= 4 {
+        filter.add_directive("snarkos_node_bft::gateway=trace".parse().unwrap())
+    } else {
+        filter.add_directive("snarkos_node_bft::gateway=debug".parse().unwrap())
+    };
+
+    let filter = if verbosity >= 5 {
+        filter.add_directive("snarkos_node_router=trace".parse().unwrap())
+    } else {
+        filter.add_directive("snarkos_node_router=debug".parse().unwrap())
+    };
+
+    if verbosity >= 6 {
+        filter.add_directive("snarkos_node_tcp=trace".parse().unwrap())
+    } else {
+        filter.add_directive("snarkos_node_tcp=off".parse().unwrap())
+    }
 }
 
 /// Returns the welcome message as a string.
@@ -151,3 +500,39 @@ pub fn welcome_message() -> String {
     output += &"👋 Welcome to Aleo! We thank you for running a node and supporting privacy.\n".bold();
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_filter() {
+        // Ensure the directives take precedence over the verbosity.
+        let log_filter = LogFilter { verbosity: 0, directives: "snarkos_node_tcp=trace, snarkos_node_bft=warn".into() };
+        let env_filter = log_filter.to_env_filter().unwrap().to_string();
+        assert!(env_filter.contains("snarkos_node_tcp=trace"));
+        assert!(!env_filter.contains("snarkos_node_tcp=off"));
+        assert!(env_filter.contains("snarkos_node_bft=warn"));
+
+        // Ensure malformed directives are rejected.
+        let log_filter = LogFilter { verbosity: 0, directives: "snarkos_node_bft=loud".into() };
+        assert!(log_filter.to_env_filter().is_err());
+    }
+
+    #[test]
+    fn test_set_otlp_endpoint() {
+        // Note: The OTLP exporter must be initialized within a Tokio runtime.
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let _guard = runtime.enter();
+
+        // Ensure the export is enabled at runtime.
+        let endpoint = "http://127.0.0.1:4317";
+        assert_eq!(set_otlp_endpoint(Some(endpoint)).unwrap().as_deref(), Some(endpoint));
+        assert!(OTLP_ENABLED.load(Ordering::Relaxed));
+
+        // Ensure the export is disabled at runtime.
+        assert_eq!(set_otlp_endpoint(Some(" ")).unwrap(), None);
+        assert!(!OTLP_ENABLED.load(Ordering::Relaxed));
+        assert!(OTLP_PROCESSOR.read().is_none());
+    }
+}
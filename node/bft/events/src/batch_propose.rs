@@ -18,12 +18,20 @@ use super::*;
 pub struct BatchPropose<N: Network> {
     pub round: u64,
     pub batch_header: Data<BatchHeader<N>>,
+    /// The trace context of the proposer, if the proposal is traced.
+    pub trace_parent: Option<TraceParent>,
 }
 
 impl<N: Network> BatchPropose<N> {
     /// Initializes a new batch propose event.
     pub fn new(round: u64, batch_header: Data<BatchHeader<N>>) -> Self {
-        Self { round, batch_header }
+        Self { round, batch_header, trace_parent: None }
+    }
+
+    /// Attaches the trace context of the proposer to the batch propose event.
+    pub fn with_trace_parent(mut self, trace_parent: Option<TraceParent>) -> Self {
+        self.trace_parent = trace_parent;
+        self
     }
 }
 
@@ -46,6 +54,7 @@ impl<N: Network> ToBytes for BatchPropose<N> {
     fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
         self.round.write_le(&mut writer)?;
         self.batch_header.write_le(&mut writer)?;
+        TraceParent::write_optional(&self.trace_parent, &mut writer)?;
         Ok(())
     }
 }
@@ -54,14 +63,19 @@ impl<N: Network> FromBytes for BatchPropose<N> {
     fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
         let round = u64::read_le(&mut reader)?;
         let batch_header = Data::read_le(&mut reader)?;
+        let trace_parent = TraceParent::read_optional(&mut reader)?;
 
-        Ok(Self { round, batch_header })
+        Ok(Self { round, batch_header, trace_parent })
     }
 }
 
 #[cfg(test)]
 pub mod prop_tests {
-    use crate::{certificate_response::prop_tests::any_batch_header, BatchPropose};
+    use crate::{
+        certificate_response::prop_tests::any_batch_header,
+        helpers::prop_tests::any_trace_parent,
+        BatchPropose,
+    };
     use snarkvm::{
         console::prelude::{FromBytes, ToBytes},
         ledger::committee::prop_tests::CommitteeContext,
@@ -76,8 +90,10 @@ pub mod prop_tests {
 
     pub fn any_batch_propose() -> BoxedStrategy<BatchPropose<CurrentNetwork>> {
         any::<CommitteeContext>()
-            .prop_flat_map(|committee| (any::<u64>(), any_batch_header(&committee)))
-            .prop_map(|(round, batch_header)| BatchPropose::new(round, Data::Object(batch_header)))
+            .prop_flat_map(|committee| (any::<u64>(), any_batch_header(&committee), any_trace_parent()))
+            .prop_map(|(round, batch_header, trace_parent)| {
+                BatchPropose::new(round, Data::Object(batch_header)).with_trace_parent(trace_parent)
+            })
             .boxed()
     }
 
@@ -89,6 +105,7 @@ pub mod prop_tests {
         let deserialized: BatchPropose<CurrentNetwork> = BatchPropose::read_le(buf.into_inner().reader()).unwrap();
         // because of the Data enum, we cannot compare the structs directly even though it derives PartialEq
         assert_eq!(original.round, deserialized.round);
+        assert_eq!(original.trace_parent, deserialized.trace_parent);
         assert_eq!(
             original.batch_header.deserialize_blocking().unwrap(),
             deserialized.batch_header.deserialize_blocking().unwrap()
@@ -18,12 +18,20 @@ use super::*;
 pub struct BatchSignature<N: Network> {
     pub batch_id: Field<N>,
     pub signature: Signature<N>,
+    /// The trace context of the signer, if the signing is traced.
+    pub trace_parent: Option<TraceParent>,
 }
 
 impl<N: Network> BatchSignature<N> {
     /// Initializes a new batch signature event.
     pub fn new(batch_id: Field<N>, signature: Signature<N>) -> Self {
-        Self { batch_id, signature }
+        Self { batch_id, signature, trace_parent: None }
+    }
+
+    /// Attaches the trace context of the signer to the batch signature event.
+    pub fn with_trace_parent(mut self, trace_parent: Option<TraceParent>) -> Self {
+        self.trace_parent = trace_parent;
+        self
     }
 }
 
@@ -39,6 +47,7 @@ impl<N: Network> ToBytes for BatchSignature<N> {
     fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
         self.batch_id.write_le(&mut writer)?;
         self.signature.write_le(&mut writer)?;
+        TraceParent::write_optional(&self.trace_parent, &mut writer)?;
         Ok(())
     }
 }
@@ -47,8 +56,9 @@ impl<N: Network> FromBytes for BatchSignature<N> {
     fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
         let batch_id = Field::read_le(&mut reader)?;
         let signature = Signature::read_le(&mut reader)?;
+        let trace_parent = TraceParent::read_optional(&mut reader)?;
 
-        Ok(Self { batch_id, signature })
+        Ok(Self { batch_id, signature, trace_parent })
     }
 }
 
@@ -57,6 +67,7 @@ pub mod prop_tests {
     use crate::{
         certificate_request::prop_tests::any_field,
         challenge_response::prop_tests::any_signature,
+        helpers::prop_tests::any_trace_parent,
         BatchSignature,
     };
     use snarkvm::console::prelude::{FromBytes, ToBytes};
@@ -68,8 +79,10 @@ pub mod prop_tests {
     type CurrentNetwork = snarkvm::prelude::Testnet3;
 
     pub fn any_batch_signature() -> BoxedStrategy<BatchSignature<CurrentNetwork>> {
-        (any_field(), any_signature())
-            .prop_map(|(certificate_id, signature)| BatchSignature::new(certificate_id, signature))
+        (any_field(), any_signature(), any_trace_parent())
+            .prop_map(|(certificate_id, signature, trace_parent)| {
+                BatchSignature::new(certificate_id, signature).with_trace_parent(trace_parent)
+            })
             .boxed()
     }
 
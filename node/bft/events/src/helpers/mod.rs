@@ -14,3 +14,6 @@
 
 mod codec;
 pub use codec::*;
+
+mod trace_parent;
+pub use trace_parent::*;
@@ -0,0 +1,159 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::console::prelude::{error, FromBytes, ToBytes};
+
+use std::{
+    fmt,
+    io::{self, Read, Write},
+    str::FromStr,
+};
+
+/// The W3C trace context of a span (i.e. the `traceparent`), which is sent along with the events of a batch,
+/// in order to attach the spans of every validator to the trace of the proposer.
+///
+/// The trace context is appended to the end of the event, so validators that do not read it simply ignore it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TraceParent {
+    /// The ID of the trace.
+    pub trace_id: [u8; 16],
+    /// The ID of the parent span.
+    pub span_id: [u8; 8],
+    /// The trace flags (e.g. `0x01` if the trace is sampled).
+    pub flags: u8,
+}
+
+impl TraceParent {
+    /// The version of the W3C trace context.
+    const VERSION: u8 = 0;
+
+    /// Initializes a new trace parent.
+    pub const fn new(trace_id: [u8; 16], span_id: [u8; 8], flags: u8) -> Self {
+        Self { trace_id, span_id, flags }
+    }
+
+    /// Writes the given trace parent, if any, at the end of an event.
+    pub fn write_optional<W: Write>(trace_parent: &Option<Self>, writer: W) -> io::Result<()> {
+        match trace_parent {
+            Some(trace_parent) => trace_parent.write_le(writer),
+            None => Ok(()),
+        }
+    }
+
+    /// Reads the trace parent at the end of an event, if any.
+    ///
+    /// Note: Events from validators that do not send the trace context end before it.
+    pub fn read_optional<R: Read>(mut reader: R) -> io::Result<Option<Self>> {
+        // Read the version, which is absent if there is no trace context.
+        let mut version = [0u8; 1];
+        if reader.read(&mut version)? == 0 {
+            return Ok(None);
+        }
+        if version[0] != Self::VERSION {
+            return Err(error(format!("Unknown trace context version {}", version[0])));
+        }
+        let mut trace_id = [0u8; 16];
+        reader.read_exact(&mut trace_id)?;
+        let mut span_id = [0u8; 8];
+        reader.read_exact(&mut span_id)?;
+        let flags = u8::read_le(&mut reader)?;
+        Ok(Some(Self { trace_id, span_id, flags }))
+    }
+}
+
+impl ToBytes for TraceParent {
+    fn write_le<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        Self::VERSION.write_le(&mut writer)?;
+        writer.write_all(&self.trace_id)?;
+        writer.write_all(&self.span_id)?;
+        self.flags.write_le(&mut writer)
+    }
+}
+
+impl FromBytes for TraceParent {
+    fn read_le<R: Read>(reader: R) -> io::Result<Self> {
+        Self::read_optional(reader)?.ok_or_else(|| error("Missing trace context"))
+    }
+}
+
+impl fmt::Display for TraceParent {
+    /// Formats the trace parent as a W3C `traceparent` header (e.g. `00-{trace_id}-{span_id}-01`).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02x}-", Self::VERSION)?;
+        self.trace_id.iter().try_for_each(|byte| write!(f, "{byte:02x}"))?;
+        write!(f, "-")?;
+        self.span_id.iter().try_for_each(|byte| write!(f, "{byte:02x}"))?;
+        write!(f, "-{:02x}", self.flags)
+    }
+}
+
+impl FromStr for TraceParent {
+    type Err = anyhow::Error;
+
+    /// Parses a W3C `traceparent` header.
+    fn from_str(traceparent: &str) -> anyhow::Result<Self> {
+        /// Decodes the given hex string into bytes.
+        fn decode<const SIZE: usize>(hex: &str) -> anyhow::Result<[u8; SIZE]> {
+            anyhow::ensure!(hex.len() == 2 * SIZE, "Expected {} hex characters, found '{hex}'", 2 * SIZE);
+            let mut bytes = [0u8; SIZE];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16)?;
+            }
+            Ok(bytes)
+        }
+
+        let parts = traceparent.trim().split('-').collect::<Vec<_>>();
+        let [version, trace_id, span_id, flags] = parts[..] else {
+            anyhow::bail!("Invalid traceparent '{traceparent}'");
+        };
+        anyhow::ensure!(decode::<1>(version)? == [Self::VERSION], "Unknown traceparent version '{version}'");
+        let [flags] = decode::<1>(flags)?;
+        Ok(Self { trace_id: decode(trace_id)?, span_id: decode(span_id)?, flags })
+    }
+}
+
+#[cfg(test)]
+pub mod prop_tests {
+    use super::*;
+
+    use bytes::{Buf, BufMut, BytesMut};
+    use proptest::prelude::{any, BoxedStrategy, Strategy};
+    use test_strategy::proptest;
+
+    pub fn any_trace_parent() -> BoxedStrategy<Option<TraceParent>> {
+        proptest::option::of(
+            any::<([u8; 16], [u8; 8], u8)>()
+                .prop_map(|(trace_id, span_id, flags)| TraceParent::new(trace_id, span_id, flags)),
+        )
+        .boxed()
+    }
+
+    #[proptest]
+    fn serialize_deserialize(#[strategy(any_trace_parent())] original: Option<TraceParent>) {
+        let mut buf = BytesMut::default().writer();
+        TraceParent::write_optional(&original, &mut buf).unwrap();
+
+        let deserialized = TraceParent::read_optional(buf.into_inner().reader()).unwrap();
+        assert_eq!(original, deserialized);
+    }
+
+    #[proptest]
+    fn display_from_str(#[strategy(any_trace_parent())] original: Option<TraceParent>) {
+        if let Some(original) = original {
+            let traceparent = original.to_string();
+            assert_eq!(traceparent.len(), 55);
+            assert_eq!(original, traceparent.parse::<TraceParent>().unwrap());
+        }
+    }
+}
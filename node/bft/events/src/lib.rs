@@ -50,6 +50,9 @@ pub use helpers::*;
 mod primary_ping;
 pub use primary_ping::PrimaryPing;
 
+mod primary_pong;
+pub use primary_pong::PrimaryPong;
+
 mod transmission_request;
 pub use transmission_request::TransmissionRequest;
 
@@ -100,6 +103,7 @@ pub enum Event<N: Network> {
     ChallengeResponse(ChallengeResponse<N>),
     Disconnect(Disconnect),
     PrimaryPing(PrimaryPing<N>),
+    PrimaryPong(PrimaryPong),
     TransmissionRequest(TransmissionRequest<N>),
     TransmissionResponse(TransmissionResponse<N>),
     ValidatorsRequest(ValidatorsRequest),
@@ -114,8 +118,10 @@ impl<N: Network> From<DisconnectReason> for Event<N> {
 }
 
 impl<N: Network> Event<N> {
+    /// The minimum version of the event protocol that this node remains compatible with.
+    pub const MIN_VERSION: u32 = 3;
     /// The version of the event protocol; it can be incremented in order to force users to update.
-    pub const VERSION: u32 = 3;
+    pub const VERSION: u32 = 4;
 
     /// Returns the event name.
     #[inline]
@@ -132,6 +138,7 @@ impl<N: Network> Event<N> {
             Self::ChallengeResponse(event) => event.name(),
             Self::Disconnect(event) => event.name(),
             Self::PrimaryPing(event) => event.name(),
+            Self::PrimaryPong(event) => event.name(),
             Self::TransmissionRequest(event) => event.name(),
             Self::TransmissionResponse(event) => event.name(),
             Self::ValidatorsRequest(event) => event.name(),
@@ -160,6 +167,7 @@ impl<N: Network> Event<N> {
             Self::ValidatorsRequest(..) => 13,
             Self::ValidatorsResponse(..) => 14,
             Self::WorkerPing(..) => 15,
+            Self::PrimaryPong(..) => 16,
         }
     }
 }
@@ -180,6 +188,7 @@ impl<N: Network> ToBytes for Event<N> {
             Self::ChallengeResponse(event) => event.write_le(writer),
             Self::Disconnect(event) => event.write_le(writer),
             Self::PrimaryPing(event) => event.write_le(writer),
+            Self::PrimaryPong(event) => event.write_le(writer),
             Self::TransmissionRequest(event) => event.write_le(writer),
             Self::TransmissionResponse(event) => event.write_le(writer),
             Self::ValidatorsRequest(event) => event.write_le(writer),
@@ -212,7 +221,8 @@ impl<N: Network> FromBytes for Event<N> {
             13 => Self::ValidatorsRequest(ValidatorsRequest::read_le(reader)?),
             14 => Self::ValidatorsResponse(ValidatorsResponse::read_le(reader)?),
             15 => Self::WorkerPing(WorkerPing::read_le(reader)?),
-            16.. => return Err(error("Unknown event ID {id}")),
+            16 => Self::PrimaryPong(PrimaryPong::read_le(reader)?),
+            17.. => return Err(error("Unknown event ID {id}")),
         };
 
         Ok(event)
@@ -254,6 +264,7 @@ pub mod prop_tests {
         Disconnect,
         DisconnectReason,
         Event,
+        PrimaryPong,
     };
     use snarkvm::{
         console::{network::Network, types::Field},
@@ -312,6 +323,7 @@ pub mod prop_tests {
                 any::<Selector>()
             )
                 .prop_map(|(reasons, selector)| Event::Disconnect(Disconnect::from(selector.select(reasons)))),
+            Just(Event::PrimaryPong(PrimaryPong)),
             any_transmission_request().prop_map(Event::TransmissionRequest),
             any_transmission_response().prop_map(Event::TransmissionResponse),
             any_worker_ping().prop_map(Event::WorkerPing)
@@ -12,18 +12,40 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use axum::{routing::get, Router};
+use axum::{extract::State, routing::get, Router};
+use axum_extra::response::ErasedJson;
+use clap::Parser;
 use std::net::SocketAddr;
 use tower_http::services::{ServeDir, ServeFile};
 
 const SERVER_URL: &str = "127.0.0.1:6060";
 
-async fn start_server() {
+/// A monitor that renders the live DAG of a validator.
+#[derive(Parser, Debug, Clone)]
+struct Args {
+    /// The REST endpoint of the validator to monitor.
+    #[arg(long, default_value = "http://127.0.0.1:3033")]
+    rest: String,
+    /// The JWT token of the validator, which is printed by the validator at startup.
+    #[arg(long)]
+    jwt: String,
+}
+
+/// Returns the monitor configuration, which is used by 'assets/index.html' to connect to the validator.
+async fn get_config(State(args): State<Args>) -> ErasedJson {
+    ErasedJson::pretty(serde_json::json!({ "rest": args.rest, "jwt": args.jwt }))
+}
+
+async fn start_server(args: Args) {
     // Serve the 'assets/' directory.
     let serve_dir = ServeDir::new("assets").fallback(ServeFile::new("assets/index.html"));
 
     // Initialize the routes.
-    let router = Router::new().route("/", get(|| async { "Hello, World!" })).fallback_service(serve_dir);
+    let router = Router::new()
+        .route("/", get(|| async { "Hello, World!" }))
+        .route("/config", get(get_config))
+        .with_state(args)
+        .fallback_service(serve_dir);
 
     // Run the server.
     println!("Starting server at '{SERVER_URL}'...");
@@ -35,7 +57,8 @@ async fn start_server() {
 
 #[tokio::main]
 async fn main() {
-    tokio::spawn(async move { start_server().await });
+    let args = Args::parse();
+    tokio::spawn(async move { start_server(args).await });
     open::that(format!("http://{SERVER_URL}/assets/index.html")).expect("Failed to open website");
     // Note: Do not move this.
     std::future::pending::<()>().await;
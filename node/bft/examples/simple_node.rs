@@ -24,6 +24,7 @@ use snarkos_node_bft::{
     MEMORY_POOL_PORT,
 };
 use snarkos_node_bft_ledger_service::MockLedgerService;
+use snarkos_node_tcp::Transport;
 use snarkvm::{
     ledger::{
         committee::{Committee, MIN_VALIDATOR_STAKE},
@@ -128,7 +129,16 @@ pub async fn start_bft(
     // Initialize the consensus receiver handler.
     consensus_handler(consensus_receiver);
     // Initialize the BFT instance.
-    let mut bft = BFT::<CurrentNetwork>::new(account, storage, ledger, ip, &trusted_validators, dev)?;
+    let mut bft = BFT::<CurrentNetwork>::new(
+        account,
+        storage,
+        ledger,
+        ip,
+        Transport::Tcp,
+        Default::default(),
+        &trusted_validators,
+        dev,
+    )?;
     // Run the BFT instance.
     bft.run(Some(consensus_sender), sender.clone(), receiver).await?;
     // Retrieve the BFT's primary.
@@ -161,7 +171,16 @@ pub async fn start_primary(
     // Initialize the trusted validators.
     let trusted_validators = trusted_validators(node_id, num_nodes, peers);
     // Initialize the primary instance.
-    let mut primary = Primary::<CurrentNetwork>::new(account, storage, ledger, ip, &trusted_validators, dev)?;
+    let mut primary = Primary::<CurrentNetwork>::new(
+        account,
+        storage,
+        ledger,
+        ip,
+        Transport::Tcp,
+        Default::default(),
+        &trusted_validators,
+        dev,
+    )?;
     // Run the primary instance.
     primary.run(None, sender.clone(), receiver).await?;
     // Handle OS signals.
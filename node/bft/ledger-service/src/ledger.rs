@@ -22,24 +22,40 @@ use snarkvm::{
         store::ConsensusStorage,
         Ledger,
     },
-    prelude::{bail, Field, Network, Result},
+    prelude::{anyhow, bail, Field, Network, Result},
 };
 
 use indexmap::IndexMap;
+use parking_lot::RwLock;
 use snarkvm::prelude::narwhal::BatchCertificate;
 use std::{fmt, ops::Range, sync::Arc};
 
+/// An observer of the blocks that the core ledger service adds to the ledger.
+pub trait LedgerObserver<N: Network>: Send + Sync {
+    /// Invoked before the given block is added to the ledger.
+    fn before_block(&self, block: &Block<N>);
+
+    /// Invoked after the given block is added to the ledger.
+    fn after_block(&self, block: &Block<N>);
+}
+
 /// A core ledger service.
 pub struct CoreLedgerService<N: Network, C: ConsensusStorage<N>> {
     ledger: Ledger<N, C>,
     coinbase_verifying_key: Arc<CoinbaseVerifyingKey<N>>,
+    observers: RwLock<Vec<Arc<dyn LedgerObserver<N>>>>,
 }
 
 impl<N: Network, C: ConsensusStorage<N>> CoreLedgerService<N, C> {
     /// Initializes a new core ledger service.
     pub fn new(ledger: Ledger<N, C>) -> Self {
         let coinbase_verifying_key = Arc::new(ledger.coinbase_puzzle().coinbase_verifying_key().clone());
-        Self { ledger, coinbase_verifying_key }
+        Self { ledger, coinbase_verifying_key, observers: Default::default() }
+    }
+
+    /// Adds an observer of the blocks that are added to the ledger.
+    pub fn add_observer(&self, observer: Arc<dyn LedgerObserver<N>>) {
+        self.observers.write().push(observer);
     }
 }
 
@@ -212,6 +228,40 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for CoreLedgerService<
         spawn_blocking!(ledger.check_transaction_basic(&transaction, None))
     }
 
+    /// Checks the proofs of the transactions in the given block, independent of the state of the ledger.
+    ///
+    /// Note: The executions of programs that are not deployed yet (e.g. deployed in a block that is not added yet)
+    /// are skipped, as their proofs are checked along with the block.
+    fn check_block_proofs(&self, block: &Block<N>) -> Result<()> {
+        let process = self.ledger.vm().process();
+        block.transactions().iter().try_for_each(|transaction| {
+            let check = || -> Result<()> {
+                let process = process.read();
+                match &**transaction {
+                    Transaction::Deploy(_, _, deployment, fee) => {
+                        process.verify_fee(fee, deployment.to_deployment_id()?)
+                    }
+                    Transaction::Execute(_, execution, fee) => {
+                        if execution.transitions().all(|transition| process.contains_program(transition.program_id())) {
+                            process.verify_execution(execution)?;
+                        }
+                        match fee {
+                            Some(fee) => process.verify_fee(fee, execution.to_execution_id()?),
+                            None => Ok(()),
+                        }
+                    }
+                    Transaction::Fee(_, fee) => match transaction.to_rejected_id()? {
+                        Some(rejected_id) => process.verify_fee(fee, rejected_id),
+                        None => Ok(()),
+                    },
+                }
+            };
+            check().map_err(|error| {
+                anyhow!("Invalid proof in transaction '{}' in block {} - {error}", transaction.id(), block.height())
+            })
+        })
+    }
+
     /// Checks the given block is valid next block.
     fn check_next_block(&self, block: &Block<N>) -> Result<()> {
         self.ledger.check_next_block(block)
@@ -230,7 +280,11 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for CoreLedgerService<
     /// Adds the given block as the next block in the ledger.
     #[cfg(feature = "ledger-write")]
     fn advance_to_next_block(&self, block: &Block<N>) -> Result<()> {
+        // Note: The observers are invoked in this thread, so that they observe the ledger before and after the block.
+        let observers = self.observers.read().clone();
+        observers.iter().for_each(|observer| observer.before_block(block));
         self.ledger.advance_to_next_block(block)?;
+        observers.iter().for_each(|observer| observer.after_block(block));
         tracing::info!("\n\nAdvanced to block {} at round {} - {}\n", block.height(), block.round(), block.hash());
         Ok(())
     }
@@ -163,6 +163,11 @@ impl<N: Network> LedgerService<N> for MockLedgerService<N> {
         Ok(())
     }
 
+    /// Checks the proofs of the transactions in the given block, independent of the state of the ledger.
+    fn check_block_proofs(&self, _block: &Block<N>) -> Result<()> {
+        Ok(())
+    }
+
     /// Checks the given block is valid next block.
     fn check_next_block(&self, _block: &Block<N>) -> Result<()> {
         Ok(())
@@ -92,6 +92,9 @@ pub trait LedgerService<N: Network>: Debug + Send + Sync {
         transaction: Data<Transaction<N>>,
     ) -> Result<()>;
 
+    /// Checks the proofs of the transactions in the given block, independent of the state of the ledger.
+    fn check_block_proofs(&self, block: &Block<N>) -> Result<()>;
+
     /// Checks the given block is valid next block.
     fn check_next_block(&self, block: &Block<N>) -> Result<()>;
 
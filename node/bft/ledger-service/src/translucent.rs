@@ -147,6 +147,11 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for TranslucentLedgerS
         Ok(())
     }
 
+    /// Always succeeds.
+    fn check_block_proofs(&self, _block: &Block<N>) -> Result<()> {
+        Ok(())
+    }
+
     /// Always succeeds.
     fn check_next_block(&self, _block: &Block<N>) -> Result<()> {
         Ok(())
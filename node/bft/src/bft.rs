@@ -14,21 +14,27 @@
 
 use crate::{
     helpers::{
+        attach_span_to_batch,
         fmt_id,
         init_bft_channels,
         now,
         BFTReceiver,
+        CertificateView,
         ConsensusSender,
+        DagEvent,
         PrimaryReceiver,
         PrimarySender,
+        RoundView,
         Storage,
         DAG,
+        MAX_DAG_EVENTS_IN_FLIGHT,
     },
     Primary,
     MAX_LEADER_CERTIFICATE_DELAY_IN_SECS,
 };
 use snarkos_account::Account;
 use snarkos_node_bft_ledger_service::LedgerService;
+use snarkos_node_tcp::{BandwidthLimits, Transport};
 use snarkvm::{
     console::account::Address,
     ledger::{
@@ -53,9 +59,10 @@ use std::{
     },
 };
 use tokio::{
-    sync::{oneshot, Mutex as TMutex, OnceCell},
+    sync::{broadcast, oneshot, Mutex as TMutex, OnceCell},
     task::JoinHandle,
 };
+use tracing::{field::Empty, instrument, Span};
 
 #[derive(Clone)]
 pub struct BFT<N: Network> {
@@ -69,6 +76,8 @@ pub struct BFT<N: Network> {
     leader_certificate_timer: Arc<AtomicI64>,
     /// The consensus sender.
     consensus_sender: Arc<OnceCell<ConsensusSender<N>>>,
+    /// The sender for DAG events.
+    dag_events: broadcast::Sender<DagEvent<N>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The BFT lock.
@@ -82,15 +91,18 @@ impl<N: Network> BFT<N> {
         storage: Storage<N>,
         ledger: Arc<dyn LedgerService<N>>,
         ip: Option<SocketAddr>,
+        transport: Transport,
+        bandwidth_limits: BandwidthLimits,
         trusted_validators: &[SocketAddr],
         dev: Option<u16>,
     ) -> Result<Self> {
         Ok(Self {
-            primary: Primary::new(account, storage, ledger, ip, trusted_validators, dev)?,
+            primary: Primary::new(account, storage, ledger, ip, transport, bandwidth_limits, trusted_validators, dev)?,
             dag: Default::default(),
             leader_certificate: Default::default(),
             leader_certificate_timer: Default::default(),
             consensus_sender: Default::default(),
+            dag_events: broadcast::channel(MAX_DAG_EVENTS_IN_FLIGHT).0,
             handles: Default::default(),
             lock: Default::default(),
         })
@@ -142,6 +154,54 @@ impl<N: Network> BFT<N> {
     pub const fn leader_certificate(&self) -> &Arc<RwLock<Option<BatchCertificate<N>>>> {
         &self.leader_certificate
     }
+
+    /// Returns the last committed round in the DAG.
+    pub fn last_committed_round(&self) -> u64 {
+        self.dag.read().last_committed_round()
+    }
+
+    /// Returns a subscription to the DAG events (inserted certificates and committed subdags).
+    pub fn subscribe_dag_events(&self) -> broadcast::Receiver<DagEvent<N>> {
+        self.dag_events.subscribe()
+    }
+}
+
+impl<N: Network> BFT<N> {
+    /// Returns a view of the DAG for the rounds in the given range `[start_round, end_round)`.
+    ///
+    /// Note: Only rounds above the GC round are available in storage.
+    pub fn dag_rounds(&self, start_round: u64, end_round: u64) -> Vec<RoundView<N>> {
+        // Clamp the start round to the GC round, as older rounds are no longer in storage.
+        let start_round = start_round.max(self.storage().gc_round());
+        // Acquire the read lock on the DAG.
+        let dag = self.dag.read();
+
+        (start_round..end_round)
+            .filter(|round| self.storage().contains_certificates_for_round(*round))
+            .map(|round| {
+                // Determine the leader of the round, if the round is even.
+                let leader = match round % 2 == 0 && round >= 2 {
+                    true => self
+                        .ledger()
+                        .get_previous_committee_for_round(round)
+                        .and_then(|committee| committee.get_leader(round))
+                        .ok(),
+                    false => None,
+                };
+                // Retrieve the certificates for the round.
+                let certificates = self
+                    .storage()
+                    .get_certificates_for_round(round)
+                    .iter()
+                    .map(|certificate| {
+                        // Note: The DAG retains the committed certificate IDs up to the GC round, as does storage.
+                        CertificateView::new(certificate, dag.is_recently_committed(round, certificate.id()))
+                    })
+                    .collect();
+                RoundView { round, leader, certificates }
+            })
+            .collect()
+    }
 }
 
 impl<N: Network> BFT<N> {
@@ -411,6 +471,9 @@ impl<N: Network> BFT<N> {
 
         // Retrieve the certificate round.
         let certificate_round = certificate.round();
+        // Notify any subscribers of the certificate.
+        // Note: This returns an error if there are no subscribers, which is safe to ignore.
+        let _ = self.dag_events.send(DagEvent::Certificate(CertificateView::new(&certificate, false)));
         // Insert the certificate into the DAG.
         self.dag.write().insert(certificate);
 
@@ -466,12 +529,21 @@ impl<N: Network> BFT<N> {
     }
 
     /// Commits the leader certificate, and all previous leader certificates since the last committed round.
+    #[instrument(
+        name = "commit_leader_certificate",
+        skip_all,
+        fields(round = leader_certificate.round(), author = %leader_certificate.author(), batch_id = Empty)
+    )]
     async fn commit_leader_certificate<const ALLOW_LEDGER_ACCESS: bool, const IS_SYNCING: bool>(
         &self,
         leader_certificate: BatchCertificate<N>,
     ) -> Result<()> {
+        // Attach the span to the trace of the leader batch.
+        attach_span_to_batch(&Span::current(), leader_certificate.batch_id());
         // Retrieve the leader certificate round.
         let leader_round = leader_certificate.round();
+        // Retrieve the leader certificate author.
+        let leader_author = leader_certificate.author();
         // Compute the commit subdag.
         let commit_subdag = match self.order_dag_with_dfs::<ALLOW_LEDGER_ACCESS>(leader_certificate) {
             Ok(subdag) => subdag,
@@ -553,6 +625,13 @@ impl<N: Network> BFT<N> {
             for certificate in commit_subdag.values().flatten() {
                 dag_write.commit(certificate, self.storage().max_gc_rounds());
             }
+            // Notify any subscribers of the commit.
+            // Note: This returns an error if there are no subscribers, which is safe to ignore.
+            let _ = self.dag_events.send(DagEvent::Commit {
+                anchor_round,
+                leader: leader_author,
+                certificate_ids: commit_subdag.values().flatten().map(|certificate| certificate.id()).collect(),
+            });
         }
         Ok(())
     }
@@ -744,7 +823,11 @@ impl<N: Network> BFT<N> {
     /// Shuts down the BFT.
     pub async fn shut_down(&self) {
         info!("Shutting down the BFT...");
+        // Drain the primary, so the in-flight batch proposal is not lost.
+        // Note: This must happen before acquiring the lock, as certifying the batch requires the lock.
+        self.primary.drain().await;
         // Acquire the lock.
+        // Note: This ensures any in-flight commit (and the corresponding ledger write) completes first.
         let _lock = self.lock.lock().await;
         // Shut down the primary.
         self.primary.shut_down().await;
@@ -756,11 +839,12 @@ impl<N: Network> BFT<N> {
 #[cfg(test)]
 mod tests {
     use crate::{
-        helpers::{now, Storage},
+        helpers::{now, CertificateView, DagEvent, Storage},
         BFT,
     };
     use snarkos_account::Account;
     use snarkos_node_bft_ledger_service::MockLedgerService;
+    use snarkos_node_tcp::Transport;
     use snarkvm::{
         ledger::narwhal::batch_certificate::test_helpers::{
             sample_batch_certificate,
@@ -784,7 +868,7 @@ mod tests {
         let storage = Storage::new(ledger.clone(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage, ledger, None, Transport::Tcp, Default::default(), &[], None)?;
         assert!(bft.is_timer_expired()); // 0 + 5 < now()
 
         // Ensure this call succeeds on an odd round.
@@ -822,7 +906,7 @@ mod tests {
         let storage = Storage::new(ledger.clone(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage, ledger, None, Transport::Tcp, Default::default(), &[], None)?;
         assert!(bft.is_timer_expired()); // 0 + 5 < now()
 
         // Store is at round 1, and we are checking for round 2.
@@ -844,7 +928,7 @@ mod tests {
         assert_eq!(storage.current_round(), 2);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage, ledger, None, Transport::Tcp, Default::default(), &[], None)?;
         assert!(bft.is_timer_expired()); // 0 + 5 < now()
 
         // Ensure this call fails on an even round.
@@ -864,7 +948,7 @@ mod tests {
         let storage = Storage::new(ledger.clone(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage, ledger, None, Transport::Tcp, Default::default(), &[], None)?;
 
         let result = bft.is_even_round_ready_for_next_round(IndexSet::new(), committee.clone(), 2);
         assert!(!result);
@@ -890,7 +974,7 @@ mod tests {
         let storage = Storage::new(ledger.clone(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage, ledger, None, Transport::Tcp, Default::default(), &[], None)?;
 
         // Ensure this call fails on an odd round.
         let result = bft.update_leader_certificate_to_even_round(1);
@@ -909,7 +993,7 @@ mod tests {
         let storage = Storage::new(ledger.clone(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage, ledger, None, Transport::Tcp, Default::default(), &[], None)?;
 
         // Ensure this call succeeds on an even round.
         let result = bft.update_leader_certificate_to_even_round(6);
@@ -960,7 +1044,7 @@ mod tests {
 
         // Initialize the BFT.
         let account = Account::new(rng)?;
-        let bft = BFT::new(account, storage.clone(), ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage.clone(), ledger, None, Transport::Tcp, Default::default(), &[], None)?;
 
         // Set the leader certificate.
         *bft.leader_certificate.write() = Some(leader_certificate);
@@ -973,6 +1057,105 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[tracing_test::traced_test]
+    fn test_dag_rounds() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Sample the certificates.
+        let (certificate, previous_certificates) = snarkvm::ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate_with_previous_certificates(
+            3,
+            rng,
+        );
+
+        // Initialize the committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee_for_round_and_members(
+            2,
+            previous_certificates.iter().map(|certificate| certificate.author()).collect(),
+            rng,
+        );
+
+        // Initialize the ledger.
+        let ledger = Arc::new(MockLedgerService::new(committee.clone()));
+
+        // Initialize the storage.
+        let storage = Storage::new(ledger.clone(), 10);
+        for previous_certificate in previous_certificates.iter() {
+            storage.testing_only_insert_certificate_testing_only(previous_certificate.clone());
+        }
+        storage.testing_only_insert_certificate_testing_only(certificate.clone());
+
+        // Initialize the BFT.
+        let account = Account::new(rng)?;
+        let bft = BFT::new(account, storage, ledger, None, Transport::Tcp, Default::default(), &[], None)?;
+
+        // Commit one of the certificates.
+        bft.dag.write().commit(&previous_certificates[0], 10);
+
+        // Ensure only the rounds with certificates are returned, with the leader of the even rounds.
+        let rounds = bft.dag_rounds(0, 10);
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds[0].round, 2);
+        assert_eq!(rounds[0].leader, Some(committee.get_leader(2)?));
+        assert_eq!(rounds[0].certificates.len(), previous_certificates.len());
+        assert_eq!(rounds[1].round, 3);
+        assert_eq!(rounds[1].leader, None);
+        assert_eq!(rounds[1].certificates.len(), 1);
+        assert_eq!(rounds[1].certificates[0].previous_certificate_ids.len(), previous_certificates.len());
+
+        // Ensure the commit status of each certificate is reported.
+        for view in &rounds[0].certificates {
+            assert_eq!(view.is_committed, view.id == previous_certificates[0].id());
+        }
+        assert!(!rounds[1].certificates[0].is_committed);
+
+        // Ensure the range is exclusive of the end round.
+        let rounds = bft.dag_rounds(3, 3);
+        assert!(rounds.is_empty());
+        let rounds = bft.dag_rounds(3, 4);
+        assert_eq!(rounds.len(), 1);
+        assert_eq!(rounds[0].certificates[0].id, certificate.id());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_dag_events() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee_for_round(1, rng);
+        let account = Account::new(rng)?;
+        let ledger = Arc::new(MockLedgerService::new(committee));
+
+        // Sample the previous certificates.
+        let (_, previous_certificates) = snarkvm::ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate_with_previous_certificates(
+            3,
+            rng,
+        );
+
+        // Initialize the BFT, and subscribe to the DAG events.
+        let storage = Storage::new(ledger.clone(), 1);
+        let bft = BFT::new(account, storage, ledger, None, Transport::Tcp, Default::default(), &[], None)?;
+        let mut events = bft.subscribe_dag_events();
+
+        // Insert the previous certificates into the BFT.
+        for certificate in previous_certificates.clone() {
+            assert!(bft.update_dag::<false>(certificate).await.is_ok());
+        }
+
+        // Ensure each inserted certificate is notified, in order.
+        for certificate in &previous_certificates {
+            match events.try_recv()? {
+                DagEvent::Certificate(view) => {
+                    assert_eq!(view, CertificateView::new(certificate, false));
+                }
+                event => panic!("Unexpected DAG event: {event:?}"),
+            }
+        }
+        assert!(events.try_recv().is_err());
+        Ok(())
+    }
+
     #[tokio::test]
     #[tracing_test::traced_test]
     async fn test_order_dag_with_dfs() -> Result<()> {
@@ -999,7 +1182,16 @@ mod tests {
             // Initialize the storage.
             let storage = Storage::new(ledger.clone(), 1);
             // Initialize the BFT.
-            let bft = BFT::new(account.clone(), storage, ledger.clone(), None, &[], None)?;
+            let bft = BFT::new(
+                account.clone(),
+                storage,
+                ledger.clone(),
+                None,
+                Transport::Tcp,
+                Default::default(),
+                &[],
+                None,
+            )?;
 
             // Insert a mock DAG in the BFT.
             *bft.dag.write() = crate::helpers::dag::test_helpers::mock_dag_with_modified_last_committed_round(3);
@@ -1029,7 +1221,7 @@ mod tests {
             // Initialize the storage.
             let storage = Storage::new(ledger.clone(), 1);
             // Initialize the BFT.
-            let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+            let bft = BFT::new(account, storage, ledger, None, Transport::Tcp, Default::default(), &[], None)?;
 
             // Insert a mock DAG in the BFT.
             *bft.dag.write() = crate::helpers::dag::test_helpers::mock_dag_with_modified_last_committed_round(2);
@@ -1087,7 +1279,7 @@ mod tests {
         // Initialize the storage.
         let storage = Storage::new(ledger.clone(), 1);
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage, ledger, None, Transport::Tcp, Default::default(), &[], None)?;
 
         // The expected error message.
         let error_msg = format!(
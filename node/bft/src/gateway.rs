@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::{
-    events::{EventCodec, PrimaryPing},
+    events::{EventCodec, PrimaryPing, PrimaryPong},
     helpers::{assign_to_worker, Cache, PrimarySender, Resolver, SyncSender, WorkerSender},
     spawn_blocking,
     CONTEXT,
@@ -43,11 +43,16 @@ use snarkos_node_bft_events::{
 use snarkos_node_bft_ledger_service::LedgerService;
 use snarkos_node_sync::communication_service::CommunicationService;
 use snarkos_node_tcp::{
-    protocols::{Disconnect, Handshake, OnConnect, Reading, Writing},
+    challenge_message,
+    protocols::{Disconnect, Handshake, OnConnect, Priority, Reading, Writing},
+    BandwidthLimits,
+    Budget,
     Config,
     Connection,
     ConnectionSide,
+    ConnectionStream,
     Tcp,
+    Transport as TcpTransport,
     P2P,
 };
 use snarkvm::{
@@ -63,8 +68,7 @@ use parking_lot::{Mutex, RwLock};
 use rand::seq::{IteratorRandom, SliceRandom};
 use std::{collections::HashSet, future::Future, io, net::SocketAddr, sync::Arc, time::Duration};
 use tokio::{
-    net::TcpStream,
-    sync::{oneshot, OnceCell},
+    sync::{oneshot, OnceCell, Semaphore},
     task::{self, JoinHandle},
 };
 use tokio_stream::StreamExt;
@@ -84,6 +88,8 @@ const RESTRICTED_INTERVAL: i64 = (MAX_CONNECTION_ATTEMPTS as u64 * MAX_BATCH_DEL
 const MIN_CONNECTED_VALIDATORS: usize = 175;
 /// The maximum number of validators to send in a validators response event.
 const MAX_VALIDATORS_TO_SEND: usize = 200;
+/// The maximum number of block responses that are processed concurrently.
+const MAX_CONCURRENT_BLOCK_RESPONSES: usize = 8;
 
 /// Part of the Gateway API that deals with networking.
 /// This is a separate trait to allow for easier testing/mocking.
@@ -106,7 +112,7 @@ pub struct Gateway<N: Network> {
     /// The resolver.
     resolver: Arc<Resolver<N>>,
     /// The set of trusted validators.
-    trusted_validators: IndexSet<SocketAddr>,
+    trusted_validators: Arc<RwLock<IndexSet<SocketAddr>>>,
     /// The map of connected peer IPs to their peer handlers.
     connected_peers: Arc<RwLock<IndexSet<SocketAddr>>>,
     /// The set of handshaking peers. While `Tcp` already recognizes the connecting IP addresses
@@ -120,6 +126,8 @@ pub struct Gateway<N: Network> {
     worker_senders: Arc<OnceCell<IndexMap<u8, WorkerSender<N>>>>,
     /// The sync sender.
     sync_sender: Arc<OnceCell<SyncSender<N>>>,
+    /// The permits to process block responses, which bound the number of block responses processed concurrently.
+    block_response_permits: Arc<Semaphore>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
@@ -130,6 +138,8 @@ impl<N: Network> Gateway<N> {
         account: Account<N>,
         ledger: Arc<dyn LedgerService<N>>,
         ip: Option<SocketAddr>,
+        transport: TcpTransport,
+        bandwidth_limits: BandwidthLimits,
         trusted_validators: &[SocketAddr],
         dev: Option<u16>,
     ) -> Result<Self> {
@@ -140,7 +150,10 @@ impl<N: Network> Gateway<N> {
             (Some(ip), _) => ip,
         };
         // Initialize the TCP stack.
-        let tcp = Tcp::new(Config::new(ip, Committee::<N>::MAX_COMMITTEE_SIZE));
+        let config = Config::new(ip, Committee::<N>::MAX_COMMITTEE_SIZE)
+            .with_transport(transport)
+            .with_bandwidth_limits(bandwidth_limits);
+        let tcp = Tcp::new(config);
         // Return the gateway.
         Ok(Self {
             account,
@@ -148,12 +161,13 @@ impl<N: Network> Gateway<N> {
             tcp,
             cache: Default::default(),
             resolver: Default::default(),
-            trusted_validators: trusted_validators.iter().copied().collect(),
+            trusted_validators: Arc::new(RwLock::new(trusted_validators.iter().copied().collect())),
             connected_peers: Default::default(),
             connecting_peers: Default::default(),
             primary_sender: Default::default(),
             worker_senders: Default::default(),
             sync_sender: Default::default(),
+            block_response_permits: Arc::new(Semaphore::new(MAX_CONCURRENT_BLOCK_RESPONSES)),
             handles: Default::default(),
         })
     }
@@ -267,11 +281,26 @@ impl<N: Network> Gateway<N> {
         &self.resolver
     }
 
+    /// Returns the smoothed round-trip time to the given peer IP, if it has been measured.
+    pub fn latency(&self, peer_ip: SocketAddr) -> Option<Duration> {
+        self.resolver.get_ambiguous(peer_ip).and_then(|peer_addr| self.tcp.known_peers().get(peer_addr)?.rtt())
+    }
+
     /// Returns the primary sender.
     pub fn primary_sender(&self) -> &PrimarySender<N> {
         self.primary_sender.get().expect("Primary sender not set in gateway")
     }
 
+    /// Returns the set of trusted validators.
+    pub fn trusted_validators(&self) -> IndexSet<SocketAddr> {
+        self.trusted_validators.read().clone()
+    }
+
+    /// Replaces the set of trusted validators with the given validators.
+    pub fn set_trusted_validators(&self, validators: &[SocketAddr]) {
+        *self.trusted_validators.write() = validators.iter().copied().collect();
+    }
+
     /// Returns the number of workers.
     pub fn num_workers(&self) -> u8 {
         u8::try_from(self.worker_senders.get().expect("Missing worker senders in gateway").len())
@@ -306,7 +335,7 @@ impl<N: Network> Gateway<N> {
     /// Returns `true` if the given peer IP is an authorized validator.
     pub fn is_authorized_validator_ip(&self, ip: SocketAddr) -> bool {
         // If the peer IP is in the trusted validators, return early.
-        if self.trusted_validators.contains(&ip) {
+        if self.trusted_validators.read().contains(&ip) {
             return true;
         }
         // Retrieve the Aleo address of the peer IP.
@@ -466,6 +495,10 @@ impl<N: Network> Gateway<N> {
             warn!("Unable to resolve the listener IP address '{peer_ip}'");
             return None;
         };
+        // If the event type is a primary ping, start measuring the round-trip time.
+        if matches!(event, Event::PrimaryPing(_)) {
+            self.tcp.known_peers().register_ping(peer_addr);
+        }
         // Retrieve the event name.
         let name = event.name();
         // Send the event to the peer.
@@ -581,12 +614,32 @@ impl<N: Network> Gateway<N> {
                 if let Some(sync_sender) = self.sync_sender.get() {
                     // Retrieve the block response.
                     let BlockResponse { request, blocks } = block_response;
-                    // Perform the deferred non-blocking deserialization of the blocks.
-                    let blocks = blocks.deserialize().await.map_err(|error| anyhow!("[BlockResponse] {error}"))?;
-                    // Ensure the block response is well-formed.
-                    blocks.ensure_response_is_well_formed(peer_ip, request.start_height, request.end_height)?;
-                    // Send the blocks to the sync module.
-                    return sync_sender.advance_with_sync_blocks(peer_ip, blocks.0).await;
+                    // Process the block response in a separate task, so the subsequent block responses
+                    // from this peer are deserialized in parallel.
+                    // Note: The number of concurrent tasks is bounded, so the reads from the peer are paused
+                    // (by the backpressure of the message queue) while the block responses are processed.
+                    let permit = self.block_response_permits.clone().acquire_owned().await?;
+                    let self_ = self.clone();
+                    let sync_sender = sync_sender.clone();
+                    tokio::spawn(async move {
+                        let _permit = permit;
+                        let result = async {
+                            // Perform the deferred non-blocking deserialization of the blocks.
+                            let blocks =
+                                blocks.deserialize().await.map_err(|error| anyhow!("[BlockResponse] {error}"))?;
+                            // Ensure the block response is well-formed.
+                            blocks.ensure_response_is_well_formed(peer_ip, request.start_height, request.end_height)?;
+                            // Send the blocks to the sync module.
+                            sync_sender.advance_with_sync_blocks(peer_ip, blocks.0).await
+                        }
+                        .await;
+                        // Disconnect if the peer violated the protocol.
+                        if let Err(error) = result {
+                            warn!("{CONTEXT} Disconnecting from '{peer_ip}' - {error}");
+                            Transport::send(&self_, peer_ip, DisconnectReason::ProtocolViolation.into()).await;
+                            self_.disconnect(peer_ip);
+                        }
+                    });
                 }
                 Ok(())
             }
@@ -617,10 +670,15 @@ impl<N: Network> Gateway<N> {
                 let PrimaryPing { version, block_locators, primary_certificate, batch_certificates } = ping;
 
                 // Ensure the event version is not outdated.
-                if version < Event::<N>::VERSION {
+                if version < Event::<N>::MIN_VERSION {
                     bail!("Dropping '{peer_ip}' on event version {version} (outdated)");
                 }
 
+                // Respond with a `PrimaryPong`, so that the peer can measure the round-trip time.
+                if version >= PrimaryPong::MIN_VERSION {
+                    let _ = self.send_inner(peer_ip, Event::PrimaryPong(PrimaryPong));
+                }
+
                 // If a sync sender was provided, update the peer locators.
                 if let Some(sync_sender) = self.sync_sender.get() {
                     // Check the block locators are valid, and update the validators in the sync module.
@@ -637,6 +695,17 @@ impl<N: Network> Gateway<N> {
                     .await;
                 Ok(())
             }
+            Event::PrimaryPong(..) => {
+                // Measure the round-trip time to the peer.
+                if let Some(rtt) = self.tcp.known_peers().register_pong(peer_addr) {
+                    trace!("{CONTEXT} Measured a round-trip time of {}ms to '{peer_ip}'", rtt.as_millis());
+                    // If a sync sender was provided, update the peer latency in the sync module.
+                    if let Some(sync_sender) = self.sync_sender.get() {
+                        let _ = sync_sender.tx_block_sync_update_peer_latency.send((peer_ip, rtt)).await;
+                    }
+                }
+                Ok(())
+            }
             Event::TransmissionRequest(request) => {
                 // TODO (howardwu): Add rate limiting checks on this event, on a per-peer basis.
                 // Determine the worker ID.
@@ -838,7 +907,8 @@ impl<N: Network> Gateway<N> {
     /// This function attempts to connect to any disconnected trusted validators.
     fn handle_trusted_validators(&self) {
         // Ensure that the trusted nodes are connected.
-        for validator_ip in &self.trusted_validators {
+        let trusted_validators = self.trusted_validators.read().clone();
+        for validator_ip in &trusted_validators {
             // If the trusted_validator is not connected, attempt to connect to it.
             if !self.is_local_ip(*validator_ip)
                 && !self.is_connecting_ip(*validator_ip)
@@ -978,6 +1048,14 @@ impl<N: Network> Reading for Gateway<N> {
         Default::default()
     }
 
+    /// Returns the bandwidth budget of the event; block and transmission responses are sync traffic.
+    fn budget(&self, event: &Self::Message) -> Budget {
+        match event {
+            Event::BlockResponse(..) | Event::TransmissionResponse(..) => Budget::Sync,
+            _ => Budget::Consensus,
+        }
+    }
+
     /// Processes a message received from the network.
     async fn process_message(&self, peer_addr: SocketAddr, message: Self::Message) -> io::Result<()> {
         // Process the message. Disconnect if the peer violated the protocol.
@@ -1010,6 +1088,20 @@ impl<N: Network> Writing for Gateway<N> {
     fn codec(&self, _peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
         Default::default()
     }
+
+    /// Returns the priority of the event; the consensus-critical events are sent ahead of the queued ones,
+    /// and the bulk traffic is sent on a separate stream if supported, so that it does not delay the others.
+    fn priority(&self, event: &Self::Message) -> Priority {
+        match event {
+            Event::BatchPropose(..)
+            | Event::BatchSignature(..)
+            | Event::BatchCertified(..)
+            | Event::CertificateRequest(..)
+            | Event::CertificateResponse(..) => Priority::High,
+            Event::BlockResponse(..) | Event::TransmissionResponse(..) => Priority::Low,
+            _ => Priority::Normal,
+        }
+    }
 }
 
 #[async_trait]
@@ -1036,6 +1128,7 @@ impl<N: Network> Handshake for Gateway<N> {
         // Perform the handshake.
         let peer_addr = connection.addr();
         let peer_side = connection.side();
+        let channel_binding = connection.channel_binding();
         let stream = self.borrow_stream(&mut connection);
 
         // If this is an inbound connection, we log it, but don't know the listening address yet.
@@ -1050,9 +1143,9 @@ impl<N: Network> Handshake for Gateway<N> {
 
         // Perform the handshake; we pass on a mutable reference to peer_ip in case the process is broken at any point in time.
         let handshake_result = if peer_side == ConnectionSide::Responder {
-            self.handshake_inner_initiator(peer_addr, peer_ip, stream).await
+            self.handshake_inner_initiator(peer_addr, peer_ip, stream, channel_binding).await
         } else {
-            self.handshake_inner_responder(peer_addr, &mut peer_ip, stream).await
+            self.handshake_inner_responder(peer_addr, &mut peer_ip, stream, channel_binding).await
         };
 
         // Remove the address from the collection of connecting peers (if the handshake got to the point where it's known).
@@ -1102,7 +1195,7 @@ macro_rules! expect_event {
 
 /// Send the given message to the peer.
 async fn send_event<N: Network>(
-    framed: &mut Framed<&mut TcpStream, EventCodec<N>>,
+    framed: &mut Framed<&mut ConnectionStream, EventCodec<N>>,
     peer_addr: SocketAddr,
     event: Event<N>,
 ) -> io::Result<()> {
@@ -1116,8 +1209,9 @@ impl<N: Network> Gateway<N> {
         &'a self,
         peer_addr: SocketAddr,
         peer_ip: Option<SocketAddr>,
-        stream: &'a mut TcpStream,
-    ) -> io::Result<(SocketAddr, Framed<&mut TcpStream, EventCodec<N>>)> {
+        stream: &'a mut ConnectionStream,
+        channel_binding: Option<[u8; 32]>,
+    ) -> io::Result<(SocketAddr, Framed<&mut ConnectionStream, EventCodec<N>>)> {
         // This value is immediately guaranteed to be present, so it can be unwrapped.
         let peer_ip = peer_ip.unwrap();
 
@@ -1143,8 +1237,9 @@ impl<N: Network> Gateway<N> {
         let peer_request = expect_event!(Event::ChallengeRequest, framed, peer_addr);
 
         // Verify the challenge response. If a disconnect reason was returned, send the disconnect message and abort.
+        let expected_message = challenge_message(our_nonce, channel_binding);
         if let Some(reason) =
-            self.verify_challenge_response(peer_addr, peer_request.address, peer_response, our_nonce).await
+            self.verify_challenge_response(peer_addr, peer_request.address, peer_response, expected_message).await
         {
             send_event(&mut framed, peer_addr, reason.into()).await?;
             return Err(error(format!("Dropped '{peer_addr}' for reason: {reason:?}")));
@@ -1157,8 +1252,9 @@ impl<N: Network> Gateway<N> {
 
         /* Step 3: Send the challenge response. */
 
-        // Sign the counterparty nonce.
-        let Ok(our_signature) = self.account.sign_bytes(&peer_request.nonce.to_le_bytes(), rng) else {
+        // Sign the counterparty nonce, bound to the session of the connection (if any).
+        let message = challenge_message(peer_request.nonce, channel_binding);
+        let Ok(our_signature) = self.account.sign_bytes(&message, rng) else {
             return Err(error(format!("Failed to sign the challenge request nonce from '{peer_addr}'")));
         };
         // Send the challenge response.
@@ -1176,8 +1272,9 @@ impl<N: Network> Gateway<N> {
         &'a self,
         peer_addr: SocketAddr,
         peer_ip: &mut Option<SocketAddr>,
-        stream: &'a mut TcpStream,
-    ) -> io::Result<(SocketAddr, Framed<&mut TcpStream, EventCodec<N>>)> {
+        stream: &'a mut ConnectionStream,
+        channel_binding: Option<[u8; 32]>,
+    ) -> io::Result<(SocketAddr, Framed<&mut ConnectionStream, EventCodec<N>>)> {
         // Construct the stream.
         let mut framed = Framed::new(stream, EventCodec::<N>::handshake());
 
@@ -1210,8 +1307,9 @@ impl<N: Network> Gateway<N> {
         // Initialize an RNG.
         let rng = &mut rand::rngs::OsRng;
 
-        // Sign the counterparty nonce.
-        let Ok(our_signature) = self.account.sign_bytes(&peer_request.nonce.to_le_bytes(), rng) else {
+        // Sign the counterparty nonce, bound to the session of the connection (if any).
+        let message = challenge_message(peer_request.nonce, channel_binding);
+        let Ok(our_signature) = self.account.sign_bytes(&message, rng) else {
             return Err(error(format!("Failed to sign the challenge request nonce from '{peer_addr}'")));
         };
         // Send the challenge response.
@@ -1229,8 +1327,9 @@ impl<N: Network> Gateway<N> {
         // Listen for the challenge response message.
         let peer_response = expect_event!(Event::ChallengeResponse, framed, peer_addr);
         // Verify the challenge response. If a disconnect reason was returned, send the disconnect message and abort.
+        let expected_message = challenge_message(our_nonce, channel_binding);
         if let Some(reason) =
-            self.verify_challenge_response(peer_addr, peer_request.address, peer_response, our_nonce).await
+            self.verify_challenge_response(peer_addr, peer_request.address, peer_response, expected_message).await
         {
             send_event(&mut framed, peer_addr, reason.into()).await?;
             return Err(error(format!("Dropped '{peer_addr}' for reason: {reason:?}")));
@@ -1246,7 +1345,7 @@ impl<N: Network> Gateway<N> {
         // Retrieve the components of the challenge request.
         let &ChallengeRequest { version, listener_port: _, address, nonce: _ } = event;
         // Ensure the event protocol version is not outdated.
-        if version < Event::<N>::VERSION {
+        if version < Event::<N>::MIN_VERSION {
             warn!("{CONTEXT} Gateway is dropping '{peer_addr}' on version {version} (outdated)");
             return Some(DisconnectReason::OutdatedClientVersion);
         }
@@ -1269,7 +1368,7 @@ impl<N: Network> Gateway<N> {
         peer_addr: SocketAddr,
         peer_address: Address<N>,
         response: ChallengeResponse<N>,
-        expected_nonce: u64,
+        expected_message: Vec<u8>,
     ) -> Option<DisconnectReason> {
         // Retrieve the components of the challenge response.
         let ChallengeResponse { signature } = response;
@@ -1279,7 +1378,7 @@ impl<N: Network> Gateway<N> {
             return Some(DisconnectReason::InvalidChallengeResponse);
         };
         // Verify the signature.
-        if !signature.verify_bytes(&peer_address, &expected_nonce.to_le_bytes()) {
+        if !signature.verify_bytes(&peer_address, &expected_message) {
             warn!("{CONTEXT} Gateway handshake with '{peer_addr}' failed (invalid signature)");
             return Some(DisconnectReason::InvalidChallengeResponse);
         }
@@ -1362,6 +1461,8 @@ mod prop_tests {
                         Account::try_from(private_key).unwrap(),
                         storage.ledger().clone(),
                         address.ip(),
+                        TcpTransport::Tcp,
+                        Default::default(),
                         &[],
                         address.port(),
                     )
@@ -1408,7 +1509,16 @@ mod prop_tests {
         let (storage, _, private_key, dev) = input;
         let account = Account::try_from(private_key).unwrap();
 
-        let gateway = Gateway::new(account.clone(), storage.ledger().clone(), dev.ip(), &[], dev.port()).unwrap();
+        let gateway = Gateway::new(
+            account.clone(),
+            storage.ledger().clone(),
+            dev.ip(),
+            TcpTransport::Tcp,
+            Default::default(),
+            &[],
+            dev.port(),
+        )
+        .unwrap();
         let tcp_config = gateway.tcp().config();
         assert_eq!(tcp_config.listener_ip, Some(IpAddr::V4(Ipv4Addr::LOCALHOST)));
         assert_eq!(tcp_config.desired_listening_port, Some(MEMORY_POOL_PORT + dev.port().unwrap()));
@@ -1423,7 +1533,16 @@ mod prop_tests {
         let (storage, _, private_key, dev) = input;
         let account = Account::try_from(private_key).unwrap();
 
-        let gateway = Gateway::new(account.clone(), storage.ledger().clone(), dev.ip(), &[], dev.port()).unwrap();
+        let gateway = Gateway::new(
+            account.clone(),
+            storage.ledger().clone(),
+            dev.ip(),
+            TcpTransport::Tcp,
+            Default::default(),
+            &[],
+            dev.port(),
+        )
+        .unwrap();
         let tcp_config = gateway.tcp().config();
         if let Some(socket_addr) = dev.ip() {
             assert_eq!(tcp_config.listener_ip, Some(socket_addr.ip()));
@@ -1448,7 +1567,16 @@ mod prop_tests {
         let worker_storage = storage.clone();
         let account = Account::try_from(private_key).unwrap();
 
-        let gateway = Gateway::new(account, storage.ledger().clone(), dev.ip(), &[], dev.port()).unwrap();
+        let gateway = Gateway::new(
+            account,
+            storage.ledger().clone(),
+            dev.ip(),
+            TcpTransport::Tcp,
+            Default::default(),
+            &[],
+            dev.port(),
+        )
+        .unwrap();
 
         let (primary_sender, _) = init_primary_channels();
 
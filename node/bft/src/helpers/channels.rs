@@ -32,7 +32,7 @@ use snarkvm::{
 };
 
 use indexmap::IndexMap;
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 use tokio::sync::{mpsc, oneshot};
 
 const MAX_CHANNEL_SIZE: usize = 8192;
@@ -230,11 +230,12 @@ pub fn init_worker_channels<N: Network>() -> (WorkerSender<N>, WorkerReceiver<N>
     (sender, receiver)
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct SyncSender<N: Network> {
     pub tx_block_sync_advance_with_sync_blocks: mpsc::Sender<(SocketAddr, Vec<Block<N>>, oneshot::Sender<Result<()>>)>,
     pub tx_block_sync_remove_peer: mpsc::Sender<SocketAddr>,
     pub tx_block_sync_update_peer_locators: mpsc::Sender<(SocketAddr, BlockLocators<N>, oneshot::Sender<Result<()>>)>,
+    pub tx_block_sync_update_peer_latency: mpsc::Sender<(SocketAddr, Duration)>,
     pub tx_certificate_request: mpsc::Sender<(SocketAddr, CertificateRequest<N>)>,
     pub tx_certificate_response: mpsc::Sender<(SocketAddr, CertificateResponse<N>)>,
 }
@@ -267,6 +268,7 @@ pub struct SyncReceiver<N: Network> {
         mpsc::Receiver<(SocketAddr, Vec<Block<N>>, oneshot::Sender<Result<()>>)>,
     pub rx_block_sync_remove_peer: mpsc::Receiver<SocketAddr>,
     pub rx_block_sync_update_peer_locators: mpsc::Receiver<(SocketAddr, BlockLocators<N>, oneshot::Sender<Result<()>>)>,
+    pub rx_block_sync_update_peer_latency: mpsc::Receiver<(SocketAddr, Duration)>,
     pub rx_certificate_request: mpsc::Receiver<(SocketAddr, CertificateRequest<N>)>,
     pub rx_certificate_response: mpsc::Receiver<(SocketAddr, CertificateResponse<N>)>,
 }
@@ -277,6 +279,7 @@ pub fn init_sync_channels<N: Network>() -> (SyncSender<N>, SyncReceiver<N>) {
         mpsc::channel(MAX_CHANNEL_SIZE);
     let (tx_block_sync_remove_peer, rx_block_sync_remove_peer) = mpsc::channel(MAX_CHANNEL_SIZE);
     let (tx_block_sync_update_peer_locators, rx_block_sync_update_peer_locators) = mpsc::channel(MAX_CHANNEL_SIZE);
+    let (tx_block_sync_update_peer_latency, rx_block_sync_update_peer_latency) = mpsc::channel(MAX_CHANNEL_SIZE);
     let (tx_certificate_request, rx_certificate_request) = mpsc::channel(MAX_CHANNEL_SIZE);
     let (tx_certificate_response, rx_certificate_response) = mpsc::channel(MAX_CHANNEL_SIZE);
 
@@ -284,6 +287,7 @@ pub fn init_sync_channels<N: Network>() -> (SyncSender<N>, SyncReceiver<N>) {
         tx_block_sync_advance_with_sync_blocks,
         tx_block_sync_remove_peer,
         tx_block_sync_update_peer_locators,
+        tx_block_sync_update_peer_latency,
         tx_certificate_request,
         tx_certificate_response,
     };
@@ -291,6 +295,7 @@ pub fn init_sync_channels<N: Network>() -> (SyncSender<N>, SyncReceiver<N>) {
         rx_block_sync_advance_with_sync_blocks,
         rx_block_sync_remove_peer,
         rx_block_sync_update_peer_locators,
+        rx_block_sync_update_peer_latency,
         rx_certificate_request,
         rx_certificate_response,
     };
@@ -0,0 +1,88 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    console::types::{Address, Field},
+    ledger::narwhal::BatchCertificate,
+    prelude::Network,
+};
+
+use serde::Serialize;
+
+/// The maximum number of DAG events that are buffered for each subscriber.
+pub const MAX_DAG_EVENTS_IN_FLIGHT: usize = 1 << 10;
+
+/// A read-only view of a batch certificate in the DAG.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(bound = "")]
+pub struct CertificateView<N: Network> {
+    /// The certificate ID.
+    pub id: Field<N>,
+    /// The batch ID.
+    pub batch_id: Field<N>,
+    /// The round of the certificate.
+    pub round: u64,
+    /// The author of the certificate.
+    pub author: Address<N>,
+    /// The certificate IDs from the previous round (i.e. the parent edges).
+    pub previous_certificate_ids: Vec<Field<N>>,
+    /// The number of transmissions in the certificate.
+    pub num_transmissions: usize,
+    /// Whether the certificate has been committed.
+    pub is_committed: bool,
+}
+
+impl<N: Network> CertificateView<N> {
+    /// Initializes a new certificate view, with the given commit status.
+    pub fn new(certificate: &BatchCertificate<N>, is_committed: bool) -> Self {
+        Self {
+            id: certificate.id(),
+            batch_id: certificate.batch_id(),
+            round: certificate.round(),
+            author: certificate.author(),
+            previous_certificate_ids: certificate.previous_certificate_ids().iter().copied().collect(),
+            num_transmissions: certificate.transmission_ids().len(),
+            is_committed,
+        }
+    }
+}
+
+/// A read-only view of a single round in the DAG.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(bound = "")]
+pub struct RoundView<N: Network> {
+    /// The round number.
+    pub round: u64,
+    /// The leader of the round, if the round is even.
+    pub leader: Option<Address<N>>,
+    /// The certificates in the round.
+    pub certificates: Vec<CertificateView<N>>,
+}
+
+/// An event emitted by the BFT as the DAG evolves.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(bound = "", tag = "type", rename_all = "snake_case")]
+pub enum DagEvent<N: Network> {
+    /// A certificate was inserted into the DAG.
+    Certificate(CertificateView<N>),
+    /// A subdag was committed.
+    Commit {
+        /// The round of the anchor (leader) certificate.
+        anchor_round: u64,
+        /// The author of the anchor (leader) certificate.
+        leader: Address<N>,
+        /// The IDs of the certificates that were committed.
+        certificate_ids: Vec<Field<N>>,
+    },
+}
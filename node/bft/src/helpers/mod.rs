@@ -21,6 +21,9 @@ pub use channels::*;
 pub mod dag;
 pub use dag::*;
 
+pub mod dag_view;
+pub use dag_view::*;
+
 pub mod partition;
 pub use partition::*;
 
@@ -39,6 +42,9 @@ pub use resolver::*;
 pub mod storage;
 pub use storage::*;
 
+pub mod telemetry;
+pub use telemetry::*;
+
 pub mod timestamp;
 pub use timestamp::*;
 
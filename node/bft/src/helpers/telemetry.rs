@@ -0,0 +1,116 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::events::TraceParent;
+use snarkvm::{console::types::Field, prelude::Network};
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use opentelemetry::{
+    trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState},
+    Context,
+};
+use parking_lot::Mutex;
+use tracing::{field::display, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// The maximum number of batches, whose trace context is kept to attach the spans of the batch to it.
+const MAX_TRACED_BATCHES: usize = 1 << 10;
+
+/// The trace contexts of the recent batches, by batch ID.
+///
+/// The trace of a batch is rooted at the `propose_batch` span of the proposer, whose trace context is sent
+/// to the signers in the batch proposal.
+static BATCH_TRACES: Lazy<Mutex<IndexMap<String, TraceParent>>> = Lazy::new(Default::default);
+
+/// Returns the trace context of the given span, if the span is exported.
+pub fn trace_parent_of(span: &Span) -> Option<TraceParent> {
+    let context = span.context();
+    let span_context = context.span().span_context().clone();
+    match span_context.is_valid() {
+        true => Some(TraceParent::new(
+            span_context.trace_id().to_bytes(),
+            span_context.span_id().to_bytes(),
+            span_context.trace_flags().to_u8(),
+        )),
+        false => None,
+    }
+}
+
+/// Attaches the span to the given (remote) trace context.
+pub fn set_trace_parent(span: &Span, trace_parent: TraceParent) {
+    let span_context = SpanContext::new(
+        TraceId::from_bytes(trace_parent.trace_id),
+        SpanId::from_bytes(trace_parent.span_id),
+        TraceFlags::new(trace_parent.flags),
+        true,
+        TraceState::default(),
+    );
+    span.set_parent(Context::new().with_remote_span_context(span_context));
+}
+
+/// Records the trace context of the given batch, i.e. of the `propose_batch` span of its proposer.
+pub fn record_batch_trace<N: Network>(batch_id: Field<N>, trace_parent: TraceParent) {
+    let mut batch_traces = BATCH_TRACES.lock();
+    batch_traces.insert(batch_id.to_string(), trace_parent);
+    // Evict the oldest batches.
+    if batch_traces.len() > MAX_TRACED_BATCHES {
+        let num_evicted = batch_traces.len() - MAX_TRACED_BATCHES;
+        batch_traces.drain(..num_evicted);
+    }
+}
+
+/// Returns the trace context of the given batch, if it is known.
+pub fn batch_trace<N: Network>(batch_id: Field<N>) -> Option<TraceParent> {
+    BATCH_TRACES.lock().get(&batch_id.to_string()).copied()
+}
+
+/// Records the given batch ID in the span, and attaches the span to the trace of the batch, if it is known.
+///
+/// The span must declare a `batch_id` field (e.g. `batch_id = tracing::field::Empty`).
+pub fn attach_span_to_batch<N: Network>(span: &Span, batch_id: Field<N>) {
+    span.record("batch_id", display(batch_id));
+    if let Some(trace_parent) = batch_trace(batch_id) {
+        set_trace_parent(span, trace_parent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{TestRng, Uniform};
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_batch_trace() {
+        let rng = &mut TestRng::default();
+
+        // Ensure an untraced span has no trace context.
+        assert_eq!(trace_parent_of(&Span::none()), None);
+
+        // Record the trace contexts of more batches than are kept.
+        let batch_ids = (0..=MAX_TRACED_BATCHES).map(|_| Field::<CurrentNetwork>::rand(rng)).collect::<Vec<_>>();
+        for (i, batch_id) in batch_ids.iter().enumerate() {
+            let trace_parent = TraceParent::new([1; 16], (i as u64).to_le_bytes(), 1);
+            record_batch_trace(*batch_id, trace_parent);
+            assert_eq!(batch_trace(*batch_id), Some(trace_parent));
+        }
+        // Ensure the oldest batch is evicted.
+        assert_eq!(batch_trace(batch_ids[0]), None);
+        assert_eq!(batch_trace(batch_ids[1]), Some(TraceParent::new([1; 16], 1u64.to_le_bytes(), 1)));
+        // Ensure an unknown batch has no trace context.
+        assert_eq!(batch_trace(Field::<CurrentNetwork>::rand(rng)), None);
+    }
+}
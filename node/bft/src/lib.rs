@@ -59,6 +59,8 @@ pub const MAX_TRANSMISSIONS_PER_BATCH: usize = 250; // transmissions
 pub const MAX_TRANSMISSIONS_PER_WORKER_PING: usize = MAX_TRANSMISSIONS_PER_BATCH / 10; // transmissions
 /// The maximum number of workers that can be spawned.
 pub const MAX_WORKERS: u8 = 1; // workers
+/// The maximum number of milliseconds to wait for an in-flight batch proposal to be certified on shutdown.
+pub const MAX_SHUTDOWN_DRAIN_IN_MS: u64 = 2 * MAX_BATCH_DELAY_IN_MS; // ms
 
 /// The frequency at which each primary broadcasts a ping to every other node.
 pub const PRIMARY_PING_IN_MS: u64 = 2 * MAX_BATCH_DELAY_IN_MS; // ms
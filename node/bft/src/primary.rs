@@ -17,10 +17,14 @@ use crate::{
     helpers::{
         assign_to_worker,
         assign_to_workers,
+        attach_span_to_batch,
         fmt_id,
         init_sync_channels,
         init_worker_channels,
         now,
+        record_batch_trace,
+        set_trace_parent,
+        trace_parent_of,
         BFTSender,
         PrimaryReceiver,
         PrimarySender,
@@ -33,6 +37,7 @@ use crate::{
     Transport,
     Worker,
     MAX_BATCH_DELAY_IN_MS,
+    MAX_SHUTDOWN_DRAIN_IN_MS,
     MAX_TRANSMISSIONS_PER_BATCH,
     MAX_WORKERS,
     PRIMARY_PING_IN_MS,
@@ -41,6 +46,7 @@ use crate::{
 use snarkos_account::Account;
 use snarkos_node_bft_events::PrimaryPing;
 use snarkos_node_bft_ledger_service::LedgerService;
+use snarkos_node_tcp::{BandwidthLimits, Transport as TcpTransport};
 use snarkvm::{
     console::{
         account::Signature,
@@ -63,13 +69,22 @@ use std::{
     collections::{HashMap, HashSet},
     future::Future,
     net::SocketAddr,
-    sync::Arc,
-    time::Duration,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 use tokio::{
     sync::{Mutex as TMutex, OnceCell},
     task::JoinHandle,
 };
+use tracing::{
+    field::{display, Empty},
+    instrument,
+    Instrument,
+    Span,
+};
 
 /// A helper type for an optional proposed batch.
 pub type ProposedBatch<N> = RwLock<Option<Proposal<N>>>;
@@ -92,6 +107,8 @@ pub struct Primary<N: Network> {
     proposed_batch: Arc<ProposedBatch<N>>,
     /// The recently-signed batch proposals (a map from the address to the round, batch ID, and signature).
     signed_proposals: Arc<RwLock<HashMap<Address<N>, (u64, Field<N>, Signature<N>)>>>,
+    /// The flag indicating whether the primary is paused from proposing new batches.
+    is_proposing_paused: Arc<AtomicBool>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The lock for propose_batch.
@@ -105,11 +122,13 @@ impl<N: Network> Primary<N> {
         storage: Storage<N>,
         ledger: Arc<dyn LedgerService<N>>,
         ip: Option<SocketAddr>,
+        transport: TcpTransport,
+        bandwidth_limits: BandwidthLimits,
         trusted_validators: &[SocketAddr],
         dev: Option<u16>,
     ) -> Result<Self> {
         // Initialize the gateway.
-        let gateway = Gateway::new(account, ledger.clone(), ip, trusted_validators, dev)?;
+        let gateway = Gateway::new(account, ledger.clone(), ip, transport, bandwidth_limits, trusted_validators, dev)?;
         // Initialize the sync module.
         let sync = Sync::new(gateway.clone(), storage.clone(), ledger.clone());
         // Initialize the primary instance.
@@ -122,6 +141,7 @@ impl<N: Network> Primary<N> {
             bft_sender: Default::default(),
             proposed_batch: Default::default(),
             signed_proposals: Default::default(),
+            is_proposing_paused: Default::default(),
             handles: Default::default(),
             propose_lock: Default::default(),
         })
@@ -201,6 +221,11 @@ impl<N: Network> Primary<N> {
         &self.ledger
     }
 
+    /// Returns the sync module.
+    pub const fn sync(&self) -> &Sync<N> {
+        &self.sync
+    }
+
     /// Returns the number of workers.
     pub fn num_workers(&self) -> u8 {
         u8::try_from(self.workers.len()).expect("Too many workers")
@@ -215,6 +240,26 @@ impl<N: Network> Primary<N> {
     pub fn proposed_batch(&self) -> &Arc<ProposedBatch<N>> {
         &self.proposed_batch
     }
+
+    /// Returns `true` if the primary is paused from proposing new batches.
+    pub fn is_proposing_paused(&self) -> bool {
+        self.is_proposing_paused.load(Ordering::SeqCst)
+    }
+
+    /// Pauses the primary from proposing new batches.
+    /// Note: The primary continues to sign batches from other validators, and to certify its in-flight proposal.
+    pub fn pause_proposing(&self) {
+        if !self.is_proposing_paused.swap(true, Ordering::SeqCst) {
+            info!("Primary has paused proposing new batches");
+        }
+    }
+
+    /// Resumes the primary to propose new batches.
+    pub fn resume_proposing(&self) {
+        if self.is_proposing_paused.swap(false, Ordering::SeqCst) {
+            info!("Primary has resumed proposing new batches");
+        }
+    }
 }
 
 impl<N: Network> Primary<N> {
@@ -329,6 +374,12 @@ impl<N: Network> Primary<N> {
             bail!("Primary is safely skipping {}", format!("(round {round} was already certified)").dimmed());
         }
 
+        // If the primary is paused, do not propose a new batch.
+        if self.is_proposing_paused() {
+            debug!("Primary is safely skipping a batch proposal {}", "(proposing is paused)".dimmed());
+            return Ok(());
+        }
+
         // Check if the primary is connected to enough validators to reach quorum threshold.
         {
             // Retrieve the committee to check against.
@@ -442,29 +493,45 @@ impl<N: Network> Primary<N> {
         /* Proceeding to sign & propose the batch. */
         info!("Proposing a batch with {} transmissions for round {round}...", transmissions.len());
 
-        // Retrieve the private key.
-        let private_key = *self.gateway.account().private_key();
-        // Prepare the transmission IDs.
-        let transmission_ids = transmissions.keys().copied().collect();
-        // Prepare the certificate IDs.
-        let certificate_ids = previous_certificates.into_iter().map(|c| c.id()).collect();
-        // Sign the batch header.
-        let batch_header = spawn_blocking!(BatchHeader::new(
-            &private_key,
-            round,
-            now(),
-            transmission_ids,
-            certificate_ids,
-            &mut rand::thread_rng()
-        ))?;
-        // Construct the proposal.
-        let proposal =
-            Proposal::new(self.ledger.get_previous_committee_for_round(round)?, batch_header.clone(), transmissions)?;
-        // Broadcast the batch to all validators for signing.
-        self.gateway.broadcast(Event::BatchPropose(batch_header.into()));
-        // Set the proposed batch.
-        *self.proposed_batch.write() = Some(proposal);
-        Ok(())
+        // Initialize the span for the proposal, which is the root of the trace of the batch.
+        let span = info_span!("propose_batch", round, author = %self.gateway.account().address(), batch_id = Empty);
+        async move {
+            // Retrieve the private key.
+            let private_key = *self.gateway.account().private_key();
+            // Prepare the transmission IDs.
+            let transmission_ids = transmissions.keys().copied().collect();
+            // Prepare the certificate IDs.
+            let certificate_ids = previous_certificates.into_iter().map(|c| c.id()).collect();
+            // Sign the batch header.
+            let batch_header = spawn_blocking!(BatchHeader::new(
+                &private_key,
+                round,
+                now(),
+                transmission_ids,
+                certificate_ids,
+                &mut rand::thread_rng()
+            ))?;
+            // Record the batch ID in the span, and make the span the root of the trace of the batch.
+            Span::current().record("batch_id", display(batch_header.batch_id()));
+            let trace_parent = trace_parent_of(&Span::current());
+            if let Some(trace_parent) = trace_parent {
+                record_batch_trace(batch_header.batch_id(), trace_parent);
+            }
+            // Construct the proposal.
+            let proposal = Proposal::new(
+                self.ledger.get_previous_committee_for_round(round)?,
+                batch_header.clone(),
+                transmissions,
+            )?;
+            // Broadcast the batch to all validators for signing, along with the trace context of the proposal.
+            let batch_propose = BatchPropose::from(batch_header).with_trace_parent(trace_parent);
+            self.gateway.broadcast(Event::BatchPropose(batch_propose));
+            // Set the proposed batch.
+            *self.proposed_batch.write() = Some(proposal);
+            Ok::<_, anyhow::Error>(())
+        }
+        .instrument(span)
+        .await
     }
 
     /// Processes a batch propose from a peer.
@@ -476,11 +543,23 @@ impl<N: Network> Primary<N> {
     ///
     /// If our primary is ahead of the peer, we will not sign the batch.
     /// If our primary is behind the peer, but within GC range, we will sync up to the peer's round, and then sign the batch.
+    #[instrument(
+        name = "sign_batch",
+        skip_all,
+        fields(peer = %peer_ip, round = batch_propose.round, author = Empty, batch_id = Empty)
+    )]
     async fn process_batch_propose_from_peer(&self, peer_ip: SocketAddr, batch_propose: BatchPropose<N>) -> Result<()> {
-        let BatchPropose { round: batch_round, batch_header } = batch_propose;
+        let BatchPropose { round: batch_round, batch_header, trace_parent } = batch_propose;
 
         // Deserialize the batch header.
         let batch_header = spawn_blocking!(batch_header.deserialize_blocking())?;
+        // Attach the span to the trace of the proposer, which is the trace of the batch.
+        Span::current().record("author", display(batch_header.author()));
+        Span::current().record("batch_id", display(batch_header.batch_id()));
+        if let Some(trace_parent) = trace_parent {
+            set_trace_parent(&Span::current(), trace_parent);
+            record_batch_trace(batch_header.batch_id(), trace_parent);
+        }
         // Ensure the round matches in the batch header.
         if batch_round != batch_header.round() {
             // Proceed to disconnect the validator.
@@ -528,9 +607,11 @@ impl<N: Network> Primary<N> {
             // Instead, rebroadcast the cached signature to the peer.
             if signed_round == batch_header.round() && signed_batch_id == batch_header.batch_id() {
                 let gateway = self.gateway.clone();
+                let trace_parent = trace_parent_of(&Span::current());
                 tokio::spawn(async move {
                     debug!("Resending a signature for a batch in round {batch_round} from '{peer_ip}'");
-                    let event = Event::BatchSignature(BatchSignature::new(batch_header.batch_id(), signature));
+                    let batch_signature = BatchSignature::new(batch_header.batch_id(), signature);
+                    let event = Event::BatchSignature(batch_signature.with_trace_parent(trace_parent));
                     // Resend the batch signature to the peer.
                     if gateway.send(peer_ip, event).await.is_none() {
                         warn!("Failed to resend a signature for a batch in round {batch_round} to '{peer_ip}'");
@@ -586,10 +667,11 @@ impl<N: Network> Primary<N> {
             }
         };
 
-        // Broadcast the signature back to the validator.
+        // Broadcast the signature back to the validator, along with the trace context of the signing.
         let self_ = self.clone();
+        let trace_parent = trace_parent_of(&Span::current());
         tokio::spawn(async move {
-            let event = Event::BatchSignature(BatchSignature::new(batch_id, signature));
+            let event = Event::BatchSignature(BatchSignature::new(batch_id, signature).with_trace_parent(trace_parent));
             // Send the batch signature to the peer.
             if self_.gateway.send(peer_ip, event).await.is_some() {
                 debug!("Signed a batch for round {batch_round} from '{peer_ip}'");
@@ -606,16 +688,30 @@ impl<N: Network> Primary<N> {
     /// 3. Store the signature.
     /// 4. Certify the batch if enough signatures have been received.
     /// 5. Broadcast the batch certificate to all validators.
+    #[instrument(
+        name = "process_batch_signature",
+        skip_all,
+        fields(peer = %peer_ip, round = Empty, author = %self.gateway.account().address(), batch_id = Empty)
+    )]
     async fn process_batch_signature_from_peer(
         &self,
         peer_ip: SocketAddr,
         batch_signature: BatchSignature<N>,
     ) -> Result<()> {
+        // Attach the span to the span of the signer, or else to the trace of the batch.
+        match batch_signature.trace_parent {
+            Some(trace_parent) => {
+                Span::current().record("batch_id", display(batch_signature.batch_id));
+                set_trace_parent(&Span::current(), trace_parent);
+            }
+            None => attach_span_to_batch(&Span::current(), batch_signature.batch_id),
+        }
+
         // Ensure the proposed batch has not expired, and clear the proposed batch if it has expired.
         self.check_proposed_batch_for_expiration().await?;
 
         // Retrieve the signature and timestamp.
-        let BatchSignature { batch_id, signature } = batch_signature;
+        let BatchSignature { batch_id, signature, .. } = batch_signature;
 
         // Retrieve the signer.
         let signer = spawn_blocking!(Ok(signature.to_address()))?;
@@ -648,6 +744,8 @@ impl<N: Network> Primary<N> {
                             ),
                         }
                     }
+                    // Record the round of the batch in the span.
+                    Span::current().record("round", proposal.round());
                     // Retrieve the previous committee for the round.
                     let previous_committee = self.ledger.get_previous_committee_for_round(proposal.round())?;
                     // Retrieve the address of the validator.
@@ -1183,6 +1281,11 @@ impl<N: Network> Primary<N> {
     }
 
     /// Stores the certified batch and broadcasts it to all validators, returning the certificate.
+    #[instrument(
+        name = "store_and_broadcast_certificate",
+        skip_all,
+        fields(round = proposal.round(), author = %proposal.batch_header().author(), batch_id = %proposal.batch_id())
+    )]
     async fn store_and_broadcast_certificate(&self, proposal: &Proposal<N>, committee: &Committee<N>) -> Result<()> {
         // Create the batch certificate and transmissions.
         let (certificate, transmissions) = proposal.to_certificate(committee)?;
@@ -1435,6 +1538,23 @@ impl<N: Network> Primary<N> {
         self.handles.lock().push(tokio::spawn(future));
     }
 
+    /// Pauses proposing new batches, and waits for the in-flight batch proposal (if any) to be certified,
+    /// for up to `MAX_SHUTDOWN_DRAIN_IN_MS` milliseconds. Returns `true` if there is no in-flight batch proposal.
+    pub async fn drain(&self) -> bool {
+        // Stop proposing new batches.
+        self.pause_proposing();
+        // Wait for the in-flight batch proposal to be certified.
+        let deadline = Instant::now() + Duration::from_millis(MAX_SHUTDOWN_DRAIN_IN_MS);
+        while self.proposed_batch.read().is_some() {
+            if Instant::now() >= deadline {
+                warn!("Primary is abandoning the in-flight batch proposal {}", "(drain timed out)".dimmed());
+                return false;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        true
+    }
+
     /// Shuts down the primary.
     pub async fn shut_down(&self) {
         info!("Shutting down the primary...");
@@ -1487,7 +1607,7 @@ mod tests {
         let storage = Storage::new(ledger.clone(), 10);
 
         // Initialize the primary.
-        let mut primary = Primary::new(account, storage, ledger, None, &[], None).unwrap();
+        let mut primary = Primary::new(account, storage, ledger, None, TcpTransport::Tcp, Default::default(), &[], None).unwrap();
 
         // Construct a worker instance.
         primary.workers = Arc::from([Worker::new(
@@ -1826,7 +1946,8 @@ mod tests {
             primary
                 .process_batch_propose_from_peer(peer_ip, BatchPropose {
                     round: round + 1,
-                    batch_header: Data::Object(proposal.batch_header().clone())
+                    batch_header: Data::Object(proposal.batch_header().clone()),
+                    trace_parent: None,
                 })
                 .await
                 .is_err()
@@ -1868,7 +1989,8 @@ mod tests {
             primary
                 .process_batch_propose_from_peer(peer_ip, BatchPropose {
                     round: round + 1,
-                    batch_header: Data::Object(proposal.batch_header().clone())
+                    batch_header: Data::Object(proposal.batch_header().clone()),
+                    trace_parent: None,
                 })
                 .await
                 .is_err()
@@ -105,6 +105,7 @@ impl<N: Network> Sync<N> {
             mut rx_block_sync_advance_with_sync_blocks,
             mut rx_block_sync_remove_peer,
             mut rx_block_sync_update_peer_locators,
+            mut rx_block_sync_update_peer_latency,
             mut rx_certificate_request,
             mut rx_certificate_response,
         } = sync_receiver;
@@ -154,6 +155,14 @@ impl<N: Network> Sync<N> {
             }
         });
 
+        // Process the block sync request to update the peer latency.
+        let self_ = self.clone();
+        self.spawn(async move {
+            while let Some((peer_ip, rtt)) = rx_block_sync_update_peer_latency.recv().await {
+                self_.block_sync.update_peer_latency(peer_ip, rtt);
+            }
+        });
+
         // Process the certificate request.
         let self_ = self.clone();
         self.spawn(async move {
@@ -319,6 +328,11 @@ impl<N: Network> Sync<N> {
     pub fn get_block_locators(&self) -> Result<BlockLocators<N>> {
         self.block_sync.get_block_locators()
     }
+
+    /// Returns the block sync module.
+    pub const fn block_sync(&self) -> &BlockSync<N> {
+        &self.block_sync
+    }
 }
 
 // Methods to assist with fetching batch certificates from peers.
@@ -493,6 +493,7 @@ mod tests {
                 transaction_id: N::TransactionID,
                 transaction: Data<Transaction<N>>,
             ) -> Result<()>;
+            fn check_block_proofs(&self, block: &Block<N>) -> Result<()>;
             fn check_next_block(&self, block: &Block<N>) -> Result<()>;
             fn prepare_advance_to_next_quorum_block(
                 &self,
@@ -25,6 +25,7 @@ use snarkos_node_bft::{
     MAX_BATCH_DELAY_IN_MS,
     MAX_GC_ROUNDS,
 };
+use snarkos_node_tcp::Transport;
 use snarkvm::{
     console::algorithms::BHP256,
     ledger::{
@@ -154,11 +155,30 @@ impl TestNetwork {
             let storage = Storage::new(ledger.clone(), MAX_GC_ROUNDS);
 
             let (primary, bft) = if config.bft {
-                let bft = BFT::<CurrentNetwork>::new(account, storage, ledger, None, &[], Some(id as u16)).unwrap();
+                let bft = BFT::<CurrentNetwork>::new(
+                    account,
+                    storage,
+                    ledger,
+                    None,
+                    Transport::Tcp,
+                    Default::default(),
+                    &[],
+                    Some(id as u16),
+                )
+                .unwrap();
                 (bft.primary().clone(), Some(bft))
             } else {
-                let primary =
-                    Primary::<CurrentNetwork>::new(account, storage, ledger, None, &[], Some(id as u16)).unwrap();
+                let primary = Primary::<CurrentNetwork>::new(
+                    account,
+                    storage,
+                    ledger,
+                    None,
+                    Transport::Tcp,
+                    Default::default(),
+                    &[],
+                    Some(id as u16),
+                )
+                .unwrap();
                 (primary, None)
             };
 
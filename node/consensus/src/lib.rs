@@ -20,6 +20,7 @@ extern crate tracing;
 use snarkos_account::Account;
 use snarkos_node_bft::{
     helpers::{
+        attach_span_to_batch,
         fmt_id,
         init_consensus_channels,
         ConsensusReceiver,
@@ -33,6 +34,7 @@ use snarkos_node_bft::{
     MAX_TRANSMISSIONS_PER_BATCH,
 };
 use snarkos_node_bft_ledger_service::LedgerService;
+use snarkos_node_tcp::{BandwidthLimits, Transport};
 use snarkvm::{
     ledger::{
         block::Transaction,
@@ -44,7 +46,7 @@ use snarkvm::{
 
 use anyhow::Result;
 use colored::Colorize;
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 use lru::LruCache;
 use parking_lot::Mutex;
 use std::{future::Future, net::SocketAddr, num::NonZeroUsize, sync::Arc};
@@ -52,6 +54,7 @@ use tokio::{
     sync::{oneshot, OnceCell},
     task::JoinHandle,
 };
+use tracing::{field::Empty, instrument, Span};
 
 #[derive(Clone)]
 pub struct Consensus<N: Network> {
@@ -69,6 +72,8 @@ pub struct Consensus<N: Network> {
     seen_solutions: Arc<Mutex<LruCache<PuzzleCommitment<N>, ()>>>,
     /// The recently-seen unconfirmed transactions.
     seen_transactions: Arc<Mutex<LruCache<N::TransactionID, ()>>>,
+    /// The serial numbers spent by the recently-checked unconfirmed transactions.
+    spent_serial_numbers: Arc<Mutex<LruCache<N::TransactionID, Vec<Field<N>>>>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
@@ -79,13 +84,15 @@ impl<N: Network> Consensus<N> {
         account: Account<N>,
         ledger: Arc<dyn LedgerService<N>>,
         ip: Option<SocketAddr>,
+        transport: Transport,
+        bandwidth_limits: BandwidthLimits,
         trusted_validators: &[SocketAddr],
         dev: Option<u16>,
     ) -> Result<Self> {
         // Initialize the Narwhal storage.
         let storage = NarwhalStorage::new(ledger.clone(), MAX_GC_ROUNDS);
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger.clone(), ip, trusted_validators, dev)?;
+        let bft = BFT::new(account, storage, ledger.clone(), ip, transport, bandwidth_limits, trusted_validators, dev)?;
         // Return the consensus.
         Ok(Self {
             ledger,
@@ -95,6 +102,7 @@ impl<N: Network> Consensus<N> {
             transactions_queue: Default::default(),
             seen_solutions: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
             seen_transactions: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
+            spent_serial_numbers: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
             handles: Default::default(),
         })
     }
@@ -172,6 +180,51 @@ impl<N: Network> Consensus<N> {
     pub fn unconfirmed_transactions(&self) -> impl '_ + Iterator<Item = (N::TransactionID, Data<Transaction<N>>)> {
         self.bft.unconfirmed_transactions()
     }
+
+    /// Returns `true` if the transaction is in the queue or the memory pool of this node.
+    pub fn contains_unconfirmed_transaction(&self, transaction_id: &N::TransactionID) -> bool {
+        self.transactions_queue.lock().contains_key(transaction_id)
+            || self.bft.primary().workers().iter().any(|worker| worker.contains_transmission(transaction_id))
+    }
+
+    /// Returns `true` if the solution is in the queue or the memory pool of this node.
+    pub fn contains_unconfirmed_solution(&self, solution_id: &PuzzleCommitment<N>) -> bool {
+        self.solutions_queue.lock().contains_key(solution_id)
+            || self.bft.primary().workers().iter().any(|worker| worker.contains_transmission(*solution_id))
+    }
+
+    /// Returns the ID and the spent serial number of an unconfirmed transaction (other than the given one)
+    /// that spends one of the given serial numbers, if the queue or the memory pool of this node contains one.
+    /// Note: The serial numbers of the unconfirmed transactions are cached, so each transaction is deserialized once.
+    pub fn find_unconfirmed_spend(
+        &self,
+        transaction_id: &N::TransactionID,
+        serial_numbers: &IndexSet<Field<N>>,
+    ) -> Result<Option<(N::TransactionID, Field<N>)>> {
+        // Check the transactions in the queue.
+        for (id, transaction) in self.transactions_queue.lock().iter().filter(|(id, _)| *id != transaction_id) {
+            if let Some(serial_number) = transaction.serial_numbers().find(|sn| serial_numbers.contains(*sn)) {
+                return Ok(Some((*id, *serial_number)));
+            }
+        }
+        // Check the transactions in the memory pool.
+        for (id, transaction) in self.unconfirmed_transactions().filter(|(id, _)| id != transaction_id) {
+            // Retrieve the serial numbers spent by the transaction, deserializing it if they are not cached.
+            let cached = self.spent_serial_numbers.lock().get(&id).cloned();
+            let spent = match cached {
+                Some(spent) => spent,
+                None => {
+                    let spent = transaction.deserialize_blocking()?.serial_numbers().copied().collect::<Vec<_>>();
+                    self.spent_serial_numbers.lock().put(id, spent.clone());
+                    spent
+                }
+            };
+            if let Some(serial_number) = spent.into_iter().find(|sn| serial_numbers.contains(sn)) {
+                return Ok(Some((id, serial_number)));
+            }
+        }
+        Ok(None)
+    }
 }
 
 impl<N: Network> Consensus<N> {
@@ -321,13 +374,27 @@ impl<N: Network> Consensus<N> {
     }
 
     /// Attempts to advance to the next block.
+    #[instrument(
+        name = "advance_to_next_block",
+        skip_all,
+        fields(
+            round = subdag.anchor_round(),
+            author = %subdag.leader_certificate().author(),
+            batch_id = Empty,
+            height = Empty
+        )
+    )]
     fn try_advance_to_next_block(
         &self,
         subdag: Subdag<N>,
         transmissions: IndexMap<TransmissionID<N>, Transmission<N>>,
     ) -> Result<()> {
+        // Attach the span to the trace of the leader batch.
+        attach_span_to_batch(&Span::current(), subdag.leader_certificate().batch_id());
         // Create the candidate next block.
         let next_block = self.ledger.prepare_advance_to_next_quorum_block(subdag, transmissions)?;
+        // Record the height of the block in the span.
+        Span::current().record("height", next_block.height());
         // Check that the block is well-formed.
         self.ledger.check_next_block(&next_block)?;
         // Advance to the next block.
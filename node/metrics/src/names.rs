@@ -12,7 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub const GAUGE_NAMES: [&str; 4] = [blocks::HEIGHT, peers::CONNECTED, peers::CANDIDATE, peers::RESTRICTED];
+pub const GAUGE_NAMES: [&str; 7] = [
+    blocks::HEIGHT,
+    peers::CONNECTED,
+    peers::CANDIDATE,
+    peers::RESTRICTED,
+    sync::FORKS,
+    sync::DIVERGING_PEERS,
+    tcp::QUEUED_MESSAGES,
+];
+
+pub const COUNTER_NAMES: [&str; 4] =
+    [rest::CACHE_HITS, rest::CACHE_MISSES, rest::THROTTLED_REQUESTS, tcp::DROPPED_MESSAGES];
 
 pub mod blocks {
     pub const HEIGHT: &str = "snarkos_blocks_height_total";
@@ -23,3 +34,19 @@ pub mod peers {
     pub const CANDIDATE: &str = "snarkos_peers_candidate_total";
     pub const RESTRICTED: &str = "snarkos_peers_restricted_total";
 }
+
+pub mod rest {
+    pub const CACHE_HITS: &str = "snarkos_rest_cache_hits_total";
+    pub const CACHE_MISSES: &str = "snarkos_rest_cache_misses_total";
+    pub const THROTTLED_REQUESTS: &str = "snarkos_rest_throttled_requests_total";
+}
+
+pub mod sync {
+    pub const FORKS: &str = "snarkos_sync_forks_total";
+    pub const DIVERGING_PEERS: &str = "snarkos_sync_diverging_peers_total";
+}
+
+pub mod tcp {
+    pub const QUEUED_MESSAGES: &str = "snarkos_tcp_queued_messages_total";
+    pub const DROPPED_MESSAGES: &str = "snarkos_tcp_dropped_messages_total";
+}
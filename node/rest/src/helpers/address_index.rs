@@ -0,0 +1,408 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::schema;
+use snarkvm::prelude::{
+    block::{Block, Input, Transaction, Transition},
+    store::ConsensusStorage,
+    Address,
+    Identifier,
+    Ledger,
+    Literal,
+    Network,
+    Plaintext,
+    ProgramID,
+    ToBytes,
+    Value,
+};
+
+use anyhow::{anyhow, Result};
+use indexmap::IndexSet;
+use parking_lot::RwLock;
+use rocksdb::{DBCompressionType, Direction, IteratorMode, Options, WriteBatch, DB};
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    path::Path,
+    str::FromStr,
+    sync::Arc,
+};
+
+/// The maximum number of blocks that are indexed per call to [`AddressIndex::catch_up`].
+pub const MAX_BLOCKS_PER_CATCH_UP: u32 = 1_000;
+
+/// The key of the height and hash of the latest indexed block.
+const INDEXED_BLOCK_KEY: &[u8] = b"indexed_block";
+/// The key prefix of the public transitions.
+const TRANSITIONS_PREFIX: u8 = 0;
+/// The key prefix of the deployments.
+const DEPLOYMENTS_PREFIX: u8 = 1;
+/// The key prefix of the public fees.
+const FEES_PREFIX: u8 = 2;
+
+/// A public transition of `credits.aleo` that involves an address (e.g. as the sender or recipient).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(bound = "")]
+#[schemars(rename = "AddressTransition")]
+pub struct AddressTransition<N: Network> {
+    /// The height of the block.
+    pub height: u32,
+    /// The ID of the transaction.
+    #[schemars(with = "schema::TransactionId")]
+    pub transaction_id: N::TransactionID,
+    /// The ID of the transition.
+    #[schemars(with = "schema::TransitionId")]
+    pub transition_id: N::TransitionID,
+    /// The name of the function.
+    #[schemars(with = "schema::Identifier")]
+    pub function_name: Identifier<N>,
+}
+
+/// A program deployed by an address.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(bound = "")]
+#[schemars(rename = "AddressDeployment")]
+pub struct AddressDeployment<N: Network> {
+    /// The height of the block.
+    pub height: u32,
+    /// The ID of the deployment transaction.
+    #[schemars(with = "schema::TransactionId")]
+    pub transaction_id: N::TransactionID,
+    /// The ID of the program.
+    #[schemars(with = "schema::ProgramId")]
+    pub program_id: ProgramID<N>,
+}
+
+/// A public fee paid by an address.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(bound = "")]
+#[schemars(rename = "AddressFee")]
+pub struct AddressFee<N: Network> {
+    /// The height of the block.
+    pub height: u32,
+    /// The ID of the transaction.
+    #[schemars(with = "schema::TransactionId")]
+    pub transaction_id: N::TransactionID,
+    /// The fee amount, in microcredits.
+    pub amount: u64,
+}
+
+/// The position of an entry in the address index, which is the cursor of the entry in the pages of the index.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct IndexPosition {
+    /// The height of the block.
+    pub height: u32,
+    /// The position of the entry among the entries of the block.
+    pub index: u32,
+}
+
+impl IndexPosition {
+    /// Returns the position after this one.
+    fn next(&self) -> Self {
+        match self.index.checked_add(1) {
+            Some(index) => Self { height: self.height, index },
+            None => Self { height: self.height.saturating_add(1), index: 0 },
+        }
+    }
+}
+
+impl Display for IndexPosition {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.height, self.index)
+    }
+}
+
+impl FromStr for IndexPosition {
+    type Err = anyhow::Error;
+
+    fn from_str(position: &str) -> Result<Self> {
+        let (height, index) = position.split_once('.').ok_or_else(|| anyhow!("Invalid position '{position}'"))?;
+        Ok(Self { height: height.parse()?, index: index.parse()? })
+    }
+}
+
+/// A secondary index of the ledger by address, of the public transitions of `credits.aleo`,
+/// the deployments by owner, and the public fees by payer.
+///
+/// The index is persisted in its own database, and is built incrementally from the blocks of the ledger
+/// with [`AddressIndex::catch_up`], which also backfills the index from genesis for an existing ledger.
+/// The entries are keyed by the prefix of their kind, the address, and their position in the ledger.
+#[derive(Clone)]
+pub struct AddressIndex<N: Network> {
+    /// The ID of `credits.aleo`.
+    credits_id: ProgramID<N>,
+    /// The database of the index.
+    database: Arc<DB>,
+    /// The height and hash of the latest indexed block, if any.
+    indexed_block: Arc<RwLock<Option<(u32, N::BlockHash)>>>,
+}
+
+impl<N: Network> AddressIndex<N> {
+    /// Opens the address index in the given directory, creating it if it does not exist.
+    pub fn open(path: &Path) -> Result<Self> {
+        std::fs::create_dir_all(path)?;
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.set_compression_type(DBCompressionType::Lz4);
+        let database = DB::open(&options, path)?;
+        let indexed_block = match database.get(INDEXED_BLOCK_KEY)? {
+            Some(bytes) => Some(bincode::deserialize(&bytes)?),
+            None => None,
+        };
+        Ok(Self {
+            credits_id: ProgramID::from_str("credits.aleo")?,
+            database: Arc::new(database),
+            indexed_block: Arc::new(RwLock::new(indexed_block)),
+        })
+    }
+
+    /// Returns the height of the latest indexed block, if any.
+    pub fn indexed_height(&self) -> Option<u32> {
+        self.indexed_block.read().map(|(height, _)| height)
+    }
+
+    /// Returns the public transitions of the given address, after the given position, in the order of the ledger.
+    pub fn transitions(
+        &self,
+        address: &Address<N>,
+        after: Option<IndexPosition>,
+    ) -> Result<impl '_ + Iterator<Item = Result<(IndexPosition, AddressTransition<N>)>>> {
+        self.entries(TRANSITIONS_PREFIX, address, after)
+    }
+
+    /// Returns the deployments of the given address, after the given position, in the order of the ledger.
+    pub fn deployments(
+        &self,
+        address: &Address<N>,
+        after: Option<IndexPosition>,
+    ) -> Result<impl '_ + Iterator<Item = Result<(IndexPosition, AddressDeployment<N>)>>> {
+        self.entries(DEPLOYMENTS_PREFIX, address, after)
+    }
+
+    /// Returns the public fees paid by the given address, after the given position, in the order of the ledger.
+    pub fn fees(
+        &self,
+        address: &Address<N>,
+        after: Option<IndexPosition>,
+    ) -> Result<impl '_ + Iterator<Item = Result<(IndexPosition, AddressFee<N>)>>> {
+        self.entries(FEES_PREFIX, address, after)
+    }
+
+    /// Indexes the blocks of the ledger after the latest indexed block, up to [`MAX_BLOCKS_PER_CATCH_UP`] blocks.
+    /// If the latest indexed block is no longer in the ledger (e.g. after a rollback), the index is rebuilt.
+    /// Returns `true` if the index caught up with the ledger.
+    /// Note: This function deserializes the blocks, and must be called in a blocking task.
+    pub fn catch_up<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>) -> Result<bool> {
+        let indexed_block = *self.indexed_block.read();
+        if let Some((height, hash)) = indexed_block {
+            if height > ledger.latest_height() || ledger.get_hash(height)? != hash {
+                warn!("The address index diverged from the ledger at block {height}, rebuilding the index");
+                self.clear()?;
+            }
+        }
+
+        let start_height = self.indexed_height().map_or(0, |height| height + 1);
+        let end_height = ledger.latest_height().min(start_height.saturating_add(MAX_BLOCKS_PER_CATCH_UP - 1));
+        for height in start_height..=end_height {
+            self.index_block(&ledger.get_block(height)?)?;
+        }
+        Ok(self.indexed_height() == Some(ledger.latest_height()))
+    }
+
+    /// Indexes the given block, which must be the block after the latest indexed block.
+    /// The entries of the block and its height are written atomically.
+    pub fn index_block(&self, block: &Block<N>) -> Result<()> {
+        let mut indexed_block = self.indexed_block.write();
+        let is_next = match *indexed_block {
+            Some((height, hash)) => block.height() == height + 1 && block.previous_hash() == hash,
+            None => block.height() == 0,
+        };
+        if !is_next {
+            return Ok(());
+        }
+
+        let height = block.height();
+        let mut batch = WriteBatch::default();
+        let mut position = IndexPosition { height, index: 0 };
+        let mut put = |prefix: u8, address: &Address<N>, entry: Vec<u8>| -> Result<()> {
+            batch.put(entry_key(prefix, address, Some(position))?, entry);
+            position = position.next();
+            Ok(())
+        };
+
+        for confirmed in block.transactions().iter() {
+            // Note: A rejected transaction is confirmed as its fee transaction.
+            let transaction = confirmed.transaction();
+            let transaction_id = transaction.id();
+
+            // Index the public transitions of `credits.aleo`, by the addresses in their public inputs and finalize inputs.
+            for transition in transaction.transitions().filter(|transition| *transition.program_id() == self.credits_id)
+            {
+                let entry = AddressTransition {
+                    height,
+                    transaction_id,
+                    transition_id: *transition.id(),
+                    function_name: *transition.function_name(),
+                };
+                for address in public_addresses(transition) {
+                    put(TRANSITIONS_PREFIX, &address, bincode::serialize(&entry)?)?;
+                }
+            }
+            // Index the deployment by its owner.
+            if let Transaction::Deploy(_, owner, deployment, _) = transaction {
+                let entry = AddressDeployment { height, transaction_id, program_id: *deployment.program_id() };
+                put(DEPLOYMENTS_PREFIX, owner.address(), bincode::serialize(&entry)?)?;
+            }
+            // Index the public fee by its payer, which is the caller in the finalize inputs.
+            if let Some(fee_transition) = transaction.fee_transition() {
+                if fee_transition.function_name().to_string() == "fee_public" {
+                    if let (Some(payer), Ok(amount)) =
+                        (public_addresses(&fee_transition).into_iter().next(), transaction.fee_amount())
+                    {
+                        let entry = AddressFee { height, transaction_id, amount: *amount };
+                        put(FEES_PREFIX, &payer, bincode::serialize(&entry)?)?;
+                    }
+                }
+            }
+        }
+        batch.put(INDEXED_BLOCK_KEY, bincode::serialize(&(height, block.hash()))?);
+        self.database.write(batch)?;
+        *indexed_block = Some((height, block.hash()));
+        Ok(())
+    }
+
+    /// Returns the entries with the given prefix for the given address, after the given position.
+    fn entries<T: DeserializeOwned>(
+        &self,
+        prefix: u8,
+        address: &Address<N>,
+        after: Option<IndexPosition>,
+    ) -> Result<impl '_ + Iterator<Item = Result<(IndexPosition, T)>>> {
+        let address_prefix = entry_key(prefix, address, None)?;
+        let start = after.map_or(IndexPosition { height: 0, index: 0 }, |position| position.next());
+        let start = entry_key(prefix, address, Some(start))?;
+        let iterator = self.database.iterator(IteratorMode::From(&start, Direction::Forward));
+        Ok(iterator
+            .take_while(move |item| item.as_ref().map_or(true, |(key, _)| key.starts_with(&address_prefix)))
+            .map(|item| {
+                let (key, value) = item?;
+                // The position is the suffix of the key.
+                let suffix = &key[key.len() - 8..];
+                let height = u32::from_be_bytes(suffix[..4].try_into()?);
+                let index = u32::from_be_bytes(suffix[4..].try_into()?);
+                Ok((IndexPosition { height, index }, bincode::deserialize(&value)?))
+            }))
+    }
+
+    /// Removes every entry of the index.
+    fn clear(&self) -> Result<()> {
+        let mut indexed_block = self.indexed_block.write();
+        let mut batch = WriteBatch::default();
+        for item in self.database.iterator(IteratorMode::Start) {
+            batch.delete(item?.0);
+        }
+        self.database.write(batch)?;
+        *indexed_block = None;
+        Ok(())
+    }
+}
+
+/// Returns the key of the entry with the given prefix, address, and position,
+/// or the common prefix of the keys of the address if no position is given.
+/// Note: The position is big-endian, so that the keys are in the order of the ledger.
+fn entry_key<N: Network>(prefix: u8, address: &Address<N>, position: Option<IndexPosition>) -> Result<Vec<u8>> {
+    let mut key = vec![prefix];
+    address.write_le(&mut key)?;
+    if let Some(IndexPosition { height, index }) = position {
+        key.extend_from_slice(&height.to_be_bytes());
+        key.extend_from_slice(&index.to_be_bytes());
+    }
+    Ok(key)
+}
+
+/// Returns the addresses in the public inputs and finalize inputs of the given transition, in order of appearance.
+fn public_addresses<N: Network>(transition: &Transition<N>) -> IndexSet<Address<N>> {
+    let finalize_inputs = transition.finalize().into_iter().flatten().filter_map(|value| match value {
+        Value::Plaintext(plaintext) => Some(plaintext),
+        _ => None,
+    });
+    let public_inputs = transition.inputs().iter().filter_map(|input| match input {
+        Input::Public(_, Some(plaintext)) => Some(plaintext),
+        _ => None,
+    });
+    finalize_inputs
+        .chain(public_inputs)
+        .filter_map(|plaintext| match plaintext {
+            Plaintext::Literal(Literal::Address(address), _) => Some(*address),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::ledger_test_helpers::sample_genesis_block,
+        prelude::store::helpers::memory::ConsensusMemory,
+        utilities::TestRng,
+    };
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_index_block() {
+        let rng = &mut TestRng::default();
+        let genesis = sample_genesis_block(rng);
+        let directory = std::env::temp_dir().join(format!("snarkos-address-index-{}", std::process::id()));
+        let index = AddressIndex::<CurrentNetwork>::open(&directory).unwrap();
+        assert_eq!(index.indexed_height(), None);
+
+        // Ensure the genesis block is indexed, by the addresses in its public transitions.
+        index.index_block(&genesis).unwrap();
+        assert_eq!(index.indexed_height(), Some(0));
+        let transition = genesis.transitions().next().unwrap();
+        let address = *public_addresses(transition).first().unwrap();
+        let transitions = |after| index.transitions(&address, after).unwrap().collect::<Result<Vec<_>>>().unwrap();
+        let entries = transitions(None);
+        assert!(entries.iter().any(|(_, entry)| entry.transition_id == *transition.id()));
+
+        // Ensure the entries resume after the given position.
+        let (position, _) = entries[0];
+        assert_eq!(transitions(Some(position)), entries[1..]);
+        assert_eq!(position.to_string().parse::<IndexPosition>().unwrap(), position);
+
+        // Ensure a block is only indexed once.
+        index.index_block(&genesis).unwrap();
+        assert_eq!(transitions(None), entries);
+
+        // Ensure the index is persisted.
+        drop(transitions);
+        drop(index);
+        let index = AddressIndex::<CurrentNetwork>::open(&directory).unwrap();
+        assert_eq!(index.indexed_height(), Some(0));
+        assert_eq!(index.transitions(&address, None).unwrap().collect::<Result<Vec<_>>>().unwrap(), entries);
+
+        // Ensure the index is rebuilt if it diverged from the ledger.
+        let ledger = Ledger::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::load(genesis.clone(), None).unwrap();
+        *index.indexed_block.write() = Some((0, genesis.previous_hash()));
+        assert!(index.catch_up(&ledger).unwrap());
+        assert_eq!(*index.indexed_block.read(), Some((0, genesis.hash())));
+        assert_eq!(index.transitions(&address, None).unwrap().collect::<Result<Vec<_>>>().unwrap(), entries);
+
+        drop(index);
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -70,6 +70,13 @@ impl Claims {
     }
 }
 
+/// Returns the claims of the given json web token, if it was issued by this node and has not expired.
+pub fn verify_jwt(token: &str) -> Option<Claims> {
+    let decoded =
+        decode::<Claims>(token, &DecodingKey::from_secret(jwt_secret()), &Validation::new(Algorithm::HS256)).ok()?;
+    (!decoded.claims.is_expired()).then_some(decoded.claims)
+}
+
 pub async fn auth_middleware<B>(request: Request<B>, next: Next<B>) -> Result<Response, Response>
 where
     B: Send,
@@ -0,0 +1,151 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{block::Block, store::ConsensusStorage, Ledger, Network, ToBytes};
+
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+/// The maximum number of blocks per export.
+pub const MAX_EXPORT_BLOCKS: u32 = 100_000;
+/// The header of the number of blocks in an export, with which the clients detect an export that was cut off.
+pub const BLOCK_COUNT_HEADER: &str = "x-block-count";
+/// The number of blocks that are read from the ledger at once.
+const EXPORT_BATCH_SIZE: u32 = 10;
+/// The number of batches that are read ahead of the client.
+const EXPORT_BUFFER_SIZE: usize = 4;
+
+/// The encoding of the exported blocks.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Each block in its canonical byte encoding, prefixed by its length as a little-endian `u64`.
+    #[default]
+    Bytes,
+    /// Each block as a line of JSON.
+    Ndjson,
+    /// The `bincode` encoding of the vector of blocks, which is the format of the CDN bundles.
+    /// Note: The range is not aligned to the 50-block bundles of the CDN, so it holds exactly the requested blocks.
+    Cdn,
+}
+
+impl ExportFormat {
+    /// Returns the content type of the exported blocks.
+    pub const fn content_type(&self) -> &'static str {
+        match self {
+            Self::Bytes | Self::Cdn => "application/octet-stream",
+            Self::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    /// Returns the bytes that precede the given number of blocks.
+    pub fn encode_prefix(&self, num_blocks: u32) -> Vec<u8> {
+        match self {
+            Self::Bytes | Self::Ndjson => Vec::new(),
+            // Note: `bincode` prefixes a vector with its length, as a little-endian `u64`.
+            Self::Cdn => (num_blocks as u64).to_le_bytes().to_vec(),
+        }
+    }
+
+    /// Returns the encoding of the given block.
+    pub fn encode_block<N: Network>(&self, block: &Block<N>) -> Result<Vec<u8>> {
+        match self {
+            Self::Bytes => {
+                let bytes = block.to_bytes_le()?;
+                Ok([(bytes.len() as u64).to_le_bytes().to_vec(), bytes].concat())
+            }
+            Self::Ndjson => {
+                let mut bytes = serde_json::to_vec(block)?;
+                bytes.push(b'\n');
+                Ok(bytes)
+            }
+            Self::Cdn => Ok(bincode::serialize(block)?),
+        }
+    }
+}
+
+/// Returns the stream of the encoded blocks in the given range.
+///
+/// The blocks are read from the ledger in batches, as the client consumes them: once [`EXPORT_BUFFER_SIZE`]
+/// batches are waiting to be sent, the reads pause until the client catches up.
+/// If a block fails to be read, the stream ends with the error, which aborts the response without terminating
+/// its chunked body. As the `bytes` and `ndjson` encodings have no trailer, the clients must also check that
+/// they received the number of blocks in the [`BLOCK_COUNT_HEADER`] header.
+pub fn export_blocks<N: Network, C: ConsensusStorage<N>>(
+    ledger: Ledger<N, C>,
+    heights: Range<u32>,
+    format: ExportFormat,
+) -> ReceiverStream<Result<Vec<u8>>> {
+    let (sender, receiver) = mpsc::channel(EXPORT_BUFFER_SIZE);
+    tokio::spawn(async move {
+        let prefix = format.encode_prefix(heights.len() as u32);
+        if !prefix.is_empty() && sender.send(Ok(prefix)).await.is_err() {
+            return;
+        }
+        for start in heights.clone().step_by(EXPORT_BATCH_SIZE as usize) {
+            let end = start.saturating_add(EXPORT_BATCH_SIZE).min(heights.end);
+            let ledger = ledger.clone();
+            let batch = tokio::task::spawn_blocking(move || -> Result<Vec<u8>> {
+                let mut bytes = Vec::new();
+                for height in start..end {
+                    bytes.extend(format.encode_block(&ledger.get_block(height)?)?);
+                }
+                Ok(bytes)
+            })
+            .await
+            .unwrap_or_else(|error| Err(error.into()));
+            let is_error = batch.is_err();
+            // Stop if the client disconnected, or if the batch failed.
+            if sender.send(batch).await.is_err() || is_error {
+                break;
+            }
+        }
+    });
+    ReceiverStream::new(receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{ledger::ledger_test_helpers::sample_genesis_block, prelude::FromBytes, utilities::TestRng};
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_export_formats() {
+        let rng = &mut TestRng::default();
+        let block: Block<CurrentNetwork> = sample_genesis_block(rng);
+
+        // Ensure the blocks are prefixed by their length.
+        let bytes = ExportFormat::Bytes.encode_block(&block).unwrap();
+        assert_eq!(u64::from_le_bytes(bytes[..8].try_into().unwrap()) as usize, bytes.len() - 8);
+        assert_eq!(Block::read_le(&bytes[8..]).unwrap(), block);
+
+        // Ensure the blocks are delimited by a newline.
+        let bytes = ExportFormat::Ndjson.encode_block(&block).unwrap();
+        assert_eq!(bytes.iter().filter(|byte| **byte == b'\n').count(), 1);
+        assert_eq!(serde_json::from_slice::<Block<CurrentNetwork>>(&bytes).unwrap(), block);
+
+        // Ensure the blocks form a CDN bundle.
+        let format = ExportFormat::Cdn;
+        let bundle =
+            [format.encode_prefix(2), format.encode_block(&block).unwrap(), format.encode_block(&block).unwrap()];
+        let blocks: Vec<Block<CurrentNetwork>> = bincode::deserialize(&bundle.concat()).unwrap();
+        assert_eq!(blocks, vec![block.clone(), block]);
+    }
+}
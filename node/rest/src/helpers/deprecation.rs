@@ -0,0 +1,48 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::API_VERSION;
+
+use axum::{
+    body::Body,
+    http::{header::LINK, HeaderValue, Request},
+    middleware::{self, Next},
+    routing::MethodRouter,
+};
+
+/// The date since which the deprecated routes are deprecated, as a UNIX timestamp (RFC 9745).
+const DEPRECATION: &str = "@1798761600"; // 2027-01-01
+/// The date after which the deprecated routes are removed, as an HTTP date (RFC 8594).
+const SUNSET: &str = "Thu, 01 Jul 2027 00:00:00 GMT";
+
+/// Marks the given handler as deprecated, by adding the `Deprecation`, `Sunset`, and `Link` headers to its responses.
+/// The `Link` header points to the given successor path or, by default, to the request path under the API version.
+pub(crate) fn deprecated<S: 'static + Clone + Send + Sync>(
+    handler: MethodRouter<S>,
+    successor: Option<String>,
+) -> MethodRouter<S> {
+    handler.layer(middleware::from_fn(move |request: Request<Body>, next: Next<Body>| {
+        let successor = successor.clone().unwrap_or_else(|| format!("/{API_VERSION}{}", request.uri().path()));
+        async move {
+            let mut response = next.run(request).await;
+            let headers = response.headers_mut();
+            headers.insert("deprecation", HeaderValue::from_static(DEPRECATION));
+            headers.insert("sunset", HeaderValue::from_static(SUNSET));
+            if let Ok(link) = HeaderValue::from_str(&format!("<{successor}>; rel=\"successor-version\"")) {
+                headers.insert(LINK, link);
+            }
+            response
+        }
+    }))
+}
@@ -0,0 +1,316 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{auth_middleware, ErrorBody};
+
+use axum::{
+    handler::Handler,
+    http::Method,
+    middleware,
+    routing::{get, post, MethodRouter},
+};
+use indexmap::IndexMap;
+use schemars::{
+    gen::{SchemaGenerator, SchemaSettings},
+    schema::{ObjectValidation, Schema},
+    JsonSchema,
+};
+use serde_json::{json, Value};
+
+/// The version of the REST API.
+pub const API_VERSION: &str = "v1";
+
+/// Returns the schema of a type, from the schema generator of the OpenAPI document.
+pub(crate) type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+/// An endpoint of the REST API.
+/// Both the router and the OpenAPI document are built from the endpoints, so they are always in sync.
+pub(crate) struct Endpoint<S> {
+    /// The HTTP method.
+    pub(crate) method: Method,
+    /// The path, relative to the prefix of the API version and network (e.g. `/block/:height_or_hash`).
+    pub(crate) path: &'static str,
+    /// The ID of the operation in the OpenAPI document, which is unique among the endpoints.
+    pub(crate) operation_id: &'static str,
+    /// The summary of the endpoint.
+    pub(crate) summary: &'static str,
+    /// The schemas of the query objects, whose fields are the query parameters.
+    pub(crate) query: Vec<SchemaFn>,
+    /// The media type and the schema of the request body, if any.
+    pub(crate) request_body: Option<(&'static str, SchemaFn)>,
+    /// The media type and the schema of the response body.
+    pub(crate) response_body: (&'static str, SchemaFn),
+    /// If `true`, the endpoint requires a JSON web token.
+    pub(crate) is_protected: bool,
+    /// If `true`, the successful responses of the endpoint never change, and are cached.
+    pub(crate) is_cached: bool,
+    /// The handler.
+    pub(crate) handler: MethodRouter<S>,
+}
+
+impl<S: 'static + Clone + Send + Sync> Endpoint<S> {
+    /// Initializes a `GET` endpoint.
+    pub(crate) fn get<H: Handler<T, S>, T: 'static>(
+        path: &'static str,
+        operation_id: &'static str,
+        summary: &'static str,
+        handler: H,
+    ) -> Self {
+        Self::new(Method::GET, path, operation_id, summary, get(handler))
+    }
+
+    /// Initializes a `POST` endpoint.
+    pub(crate) fn post<H: Handler<T, S>, T: 'static>(
+        path: &'static str,
+        operation_id: &'static str,
+        summary: &'static str,
+        handler: H,
+    ) -> Self {
+        Self::new(Method::POST, path, operation_id, summary, post(handler))
+    }
+
+    /// Initializes an endpoint.
+    fn new(
+        method: Method,
+        path: &'static str,
+        operation_id: &'static str,
+        summary: &'static str,
+        handler: MethodRouter<S>,
+    ) -> Self {
+        Self {
+            method,
+            path,
+            operation_id,
+            summary,
+            query: Vec::new(),
+            request_body: None,
+            response_body: ("application/json", SchemaGenerator::subschema_for::<Value>),
+            is_protected: false,
+            is_cached: false,
+            handler,
+        }
+    }
+
+    /// Adds the query object of the given type, whose fields are the query parameters.
+    pub(crate) fn with_query<Q: JsonSchema>(mut self) -> Self {
+        self.query.push(Q::json_schema);
+        self
+    }
+
+    /// Sets the media type and the type of the request body.
+    pub(crate) fn with_request_body<T: JsonSchema>(self, media_type: &'static str) -> Self {
+        Self { request_body: Some((media_type, SchemaGenerator::subschema_for::<T>)), ..self }
+    }
+
+    /// Sets the type of the JSON response body.
+    pub(crate) fn with_response<T: JsonSchema>(self) -> Self {
+        self.with_response_body::<T>("application/json")
+    }
+
+    /// Sets the media type and the type of the response body.
+    pub(crate) fn with_response_body<T: JsonSchema>(self, media_type: &'static str) -> Self {
+        Self { response_body: (media_type, SchemaGenerator::subschema_for::<T>), ..self }
+    }
+
+    /// Marks the successful responses of the endpoint as immutable, so that they are cached.
+    pub(crate) fn cached(self) -> Self {
+        Self { is_cached: true, ..self }
+    }
+
+    /// Protects the endpoint with a JSON web token.
+    pub(crate) fn protected(self) -> Self {
+        let handler = self.handler.route_layer(middleware::from_fn(auth_middleware));
+        Self { is_protected: true, handler, ..self }
+    }
+}
+
+/// Returns the name of the network in the paths of the REST API (e.g. `testnet3` for `Aleo Testnet3`).
+pub fn network_path(network_name: &str) -> String {
+    network_name.split_whitespace().last().unwrap_or(network_name).to_lowercase()
+}
+
+/// Returns the OpenAPI 3 document of the given endpoints, which are served under the given prefix.
+/// The schemas of the query parameters, and of the request and response bodies, are generated from their types.
+pub(crate) fn openapi_document<S>(prefix: &str, endpoints: &[Endpoint<S>]) -> Value {
+    // Note: The generator collects the schemas that are referenced by the operations into the components.
+    let mut generator = SchemaSettings::openapi3().into_generator();
+    let error = generator.subschema_for::<ErrorBody<'static>>();
+
+    let mut paths = IndexMap::<String, IndexMap<String, Value>>::new();
+    for endpoint in endpoints {
+        // Convert the path parameters from `:name` to `{name}`.
+        let mut parameters = Vec::new();
+        let path = endpoint
+            .path
+            .split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(name) => {
+                    parameters
+                        .push(json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } }));
+                    format!("{{{name}}}")
+                }
+                None => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        // Add the fields of the query objects as the query parameters.
+        for query in &endpoint.query {
+            parameters.extend(query_parameters(query(&mut generator)));
+        }
+
+        let (media_type, response_schema) = endpoint.response_body;
+        let mut operation = json!({
+            "operationId": endpoint.operation_id,
+            "summary": endpoint.summary,
+            "parameters": parameters,
+            "responses": {
+                "200": {
+                    "description": "Success",
+                    "content": { media_type: { "schema": response_schema(&mut generator) } },
+                },
+                "default": {
+                    "description": "Error",
+                    "content": { "application/json": { "schema": error } },
+                },
+            },
+        });
+        if let Some((media_type, request_schema)) = endpoint.request_body {
+            operation["requestBody"] =
+                json!({ "required": true, "content": { media_type: { "schema": request_schema(&mut generator) } } });
+        }
+        if endpoint.is_protected {
+            operation["security"] = json!([{ "bearerAuth": [] }]);
+        }
+        paths.entry(path).or_default().insert(endpoint.method.as_str().to_lowercase(), operation);
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "snarkOS REST API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "servers": [{ "url": prefix }],
+        "paths": paths,
+        "components": {
+            "schemas": generator.definitions(),
+            "securitySchemes": {
+                "bearerAuth": { "type": "http", "scheme": "bearer", "bearerFormat": "JWT" },
+            },
+        },
+    })
+}
+
+/// Returns the query parameters for the fields of the given schema of a query object.
+fn query_parameters(schema: Schema) -> Vec<Value> {
+    let Some(object) = schema.into_object().object else {
+        return Vec::new();
+    };
+    let ObjectValidation { properties, required, .. } = *object;
+    properties
+        .into_iter()
+        .map(|(name, schema)| {
+            let required = required.contains(&name);
+            // Move the description of the field to the parameter.
+            let mut schema = schema.into_object();
+            let description = schema.metadata.as_mut().and_then(|metadata| metadata.description.take());
+            let mut parameter = json!({ "name": name, "in": "query", "required": required, "schema": schema });
+            if let Some(description) = description {
+                parameter["description"] = json!(description);
+            }
+            parameter
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The query object of the test endpoint.
+    #[allow(dead_code)]
+    #[derive(JsonSchema)]
+    struct TestQuery {
+        /// The starting height.
+        start: u32,
+        /// The ending height, if specified.
+        end: Option<u32>,
+    }
+
+    /// The response of the test endpoint.
+    #[allow(dead_code)]
+    #[derive(JsonSchema)]
+    struct TestResponse {
+        /// The block height.
+        height: u32,
+    }
+
+    async fn get_block<T>() {}
+
+    async fn set_log_filter() {}
+
+    #[test]
+    fn test_network_path() {
+        assert_eq!(network_path("Aleo Testnet3"), "testnet3");
+        assert_eq!(network_path("testnet3"), "testnet3");
+    }
+
+    #[test]
+    fn test_openapi_document() {
+        let endpoints = vec![
+            Endpoint::<()>::get("/block/:height_or_hash", "get_block", "Returns the block", get_block::<String>)
+                .with_query::<TestQuery>()
+                .with_response::<TestResponse>(),
+            Endpoint::<()>::post("/node/logFilter", "set_log_filter", "Updates the log filter", set_log_filter)
+                .with_request_body::<String>("text/plain")
+                .with_response::<String>()
+                .protected(),
+        ];
+        let document = openapi_document("/v1/testnet3", &endpoints);
+        assert_eq!(document["openapi"], "3.0.3");
+        assert_eq!(document["servers"][0]["url"], "/v1/testnet3");
+
+        // Ensure the path parameters are converted, and the operations have their IDs.
+        let operation = &document["paths"]["/block/{height_or_hash}"]["get"];
+        assert_eq!(operation["operationId"], "get_block");
+        assert_eq!(operation["parameters"][0]["name"], "height_or_hash");
+        assert_eq!(operation["parameters"][0]["in"], "path");
+        assert!(operation["security"].is_null());
+
+        // Ensure the query parameters are the fields of the query object, with their descriptions.
+        let parameters = &operation["parameters"];
+        assert_eq!((&parameters[1]["name"], &parameters[1]["in"]), (&json!("start"), &json!("query")));
+        assert_eq!(parameters[1]["required"], true);
+        assert_eq!(parameters[1]["description"], "The starting height.");
+        assert_eq!(parameters[1]["schema"]["type"], "integer");
+        assert_eq!((&parameters[2]["name"], &parameters[2]["required"]), (&json!("end"), &json!(false)));
+
+        // Ensure the response schema is generated from the response type, and referenced from the components.
+        let response = &operation["responses"]["200"]["content"]["application/json"]["schema"];
+        assert_eq!(response["$ref"], "#/components/schemas/TestResponse");
+        let schema = &document["components"]["schemas"]["TestResponse"];
+        assert_eq!(schema["properties"]["height"]["type"], "integer");
+        assert_eq!(schema["properties"]["height"]["description"], "The block height.");
+        // Ensure the errors are described by the schema of the error body.
+        let error = &operation["responses"]["default"]["content"]["application/json"]["schema"];
+        assert_eq!(error["$ref"], "#/components/schemas/ErrorBody");
+        assert_eq!(document["components"]["schemas"]["ErrorBody"]["required"], json!(["code", "message"]));
+
+        // Ensure the protected endpoints require a JSON web token.
+        let operation = &document["paths"]["/node/logFilter"]["post"];
+        assert_eq!(operation["operationId"], "set_log_filter");
+        assert_eq!(operation["requestBody"]["content"]["text/plain"]["schema"]["type"], "string");
+        assert_eq!(operation["security"][0]["bearerAuth"], json!([]));
+    }
+}
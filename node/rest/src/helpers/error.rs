@@ -13,21 +13,149 @@
 // limitations under the License.
 
 use axum::{
+    extract::rejection::{JsonRejection, PathRejection, QueryRejection},
     http::StatusCode,
     response::{IntoResponse, Response},
+    Json,
 };
+use schemars::JsonSchema;
+use serde::Serialize;
 
 /// An enum of error handlers for the REST API server.
-pub struct RestError(pub String);
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RestError {
+    /// The request is malformed, e.g. an invalid path parameter or range (400).
+    BadRequest(String),
+    /// The requested resource does not exist (404).
+    NotFound(String),
+    /// The request conflicts with the state of the node, e.g. a known transaction (409).
+    Conflict(String),
+    /// The requested state was pruned, or was never retained, by the node, e.g. a past state on a non-archival node (410).
+    Pruned(String),
+    /// The request is well-formed, but its content is invalid (422).
+    UnprocessableEntity(String),
+    /// The client sent too many requests (429).
+    TooManyRequests(String),
+    /// The route is not available on this node, or the node cannot serve it at the moment (503).
+    ServiceUnavailable(String),
+    /// The node failed to process the request (500).
+    InternalServerError(String),
+}
+
+/// The JSON body of an error response.
+#[derive(Serialize, JsonSchema)]
+pub(crate) struct ErrorBody<'a> {
+    /// The machine-readable error code.
+    code: &'static str,
+    /// The human-readable error message.
+    message: &'a str,
+}
+
+impl RestError {
+    /// Returns the error for a route that is not available for the type of this node.
+    pub fn unavailable_for_node_type() -> Self {
+        Self::ServiceUnavailable("Route isn't available for this node type".to_string())
+    }
+
+    /// Returns the HTTP status code of the error.
+    pub const fn status(&self) -> StatusCode {
+        match self {
+            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::Pruned(_) => StatusCode::GONE,
+            Self::UnprocessableEntity(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            Self::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            Self::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Returns the machine-readable error code, which is stable across releases.
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::BadRequest(_) => "bad_request",
+            Self::NotFound(_) => "not_found",
+            Self::Conflict(_) => "conflict",
+            Self::Pruned(_) => "pruned",
+            Self::UnprocessableEntity(_) => "unprocessable_entity",
+            Self::TooManyRequests(_) => "too_many_requests",
+            Self::ServiceUnavailable(_) => "service_unavailable",
+            Self::InternalServerError(_) => "internal_error",
+        }
+    }
+
+    /// Returns the human-readable error message.
+    pub fn message(&self) -> &str {
+        match self {
+            Self::BadRequest(message)
+            | Self::NotFound(message)
+            | Self::Conflict(message)
+            | Self::Pruned(message)
+            | Self::UnprocessableEntity(message)
+            | Self::TooManyRequests(message)
+            | Self::ServiceUnavailable(message)
+            | Self::InternalServerError(message) => message,
+        }
+    }
+}
 
 impl IntoResponse for RestError {
     fn into_response(self) -> Response {
-        (StatusCode::INTERNAL_SERVER_ERROR, format!("Something went wrong: {}", self.0)).into_response()
+        (self.status(), Json(ErrorBody { code: self.code(), message: self.message() })).into_response()
     }
 }
 
 impl From<anyhow::Error> for RestError {
     fn from(err: anyhow::Error) -> Self {
-        Self(err.to_string())
+        Self::InternalServerError(err.to_string())
+    }
+}
+
+impl From<PathRejection> for RestError {
+    fn from(rejection: PathRejection) -> Self {
+        Self::BadRequest(rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for RestError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::BadRequest(rejection.body_text())
+    }
+}
+
+impl From<JsonRejection> for RestError {
+    fn from(rejection: JsonRejection) -> Self {
+        match rejection {
+            // The body is valid JSON, but it is not a valid object of the expected type.
+            JsonRejection::JsonDataError(error) => Self::UnprocessableEntity(error.body_text()),
+            rejection => Self::BadRequest(rejection.body_text()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_response() {
+        let error = RestError::NotFound("Block 10 does not exist".to_string());
+        assert_eq!(error.status(), StatusCode::NOT_FOUND);
+        assert_eq!(error.code(), "not_found");
+
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()["content-type"], "application/json");
+
+        // Ensure unexpected errors are internal server errors.
+        let error = RestError::from(anyhow::anyhow!("Storage failure"));
+        assert_eq!(error.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(error.message(), "Storage failure");
+        assert_eq!(RestError::unavailable_for_node_type().status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        // Ensure the past states that were not retained are reported as pruned.
+        let error = RestError::Pruned("The state at block 10 was pruned".to_string());
+        assert_eq!((error.status(), error.code()), (StatusCode::GONE, "pruned"));
     }
 }
@@ -0,0 +1,32 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::RestError;
+
+use axum::extract::{FromRequest, FromRequestParts};
+
+/// Extracts the path parameters of the request, rejecting malformed parameters with a [`RestError`].
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Path), rejection(RestError))]
+pub struct Path<T>(pub T);
+
+/// Extracts the query parameters of the request, rejecting malformed parameters with a [`RestError`].
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(RestError))]
+pub struct Query<T>(pub T);
+
+/// Extracts the JSON body of the request, rejecting malformed bodies with a [`RestError`].
+#[derive(FromRequest)]
+#[from_request(via(axum::Json), rejection(RestError))]
+pub struct Json<T>(pub T);
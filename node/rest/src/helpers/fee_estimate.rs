@@ -0,0 +1,141 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node_consensus::Consensus;
+use snarkvm::prelude::{block::Transaction, store::ConsensusStorage, Ledger, Network};
+
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// The number of recent blocks that the fee estimate is derived from.
+pub const FEE_ESTIMATE_NUM_BLOCKS: u32 = 10;
+
+/// The suggested priority fees per byte, in microcredits, at each confidence level.
+/// Note: The size is that of the deployment or execution, which is known before the fee is created.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, JsonSchema)]
+pub struct PriorityFees {
+    /// The priority fee paid by 25% of the sampled transactions.
+    pub low: f64,
+    /// The priority fee paid by 50% of the sampled transactions.
+    pub medium: f64,
+    /// The priority fee paid by 90% of the sampled transactions.
+    pub high: f64,
+}
+
+/// The fee estimate, derived from the transactions in the recent blocks and in the memory pool.
+#[derive(Clone, Debug, PartialEq, Serialize, JsonSchema)]
+pub struct FeeEstimate {
+    /// The latest block height.
+    pub latest_height: u32,
+    /// The number of transactions sampled from the recent blocks.
+    pub num_block_samples: usize,
+    /// The number of transactions sampled from the memory pool.
+    pub num_mempool_samples: usize,
+    /// The suggested priority fees per byte.
+    pub priority_fee_per_byte: PriorityFees,
+}
+
+impl FeeEstimate {
+    /// Estimates the priority fees from the transactions in the recent blocks of the ledger,
+    /// and in the memory pool (if any).
+    /// Note: This function deserializes the recent blocks, and must be called in a blocking task.
+    pub fn new<N: Network, C: ConsensusStorage<N>>(
+        ledger: &Ledger<N, C>,
+        consensus: Option<&Consensus<N>>,
+    ) -> Result<Self> {
+        let latest_height = ledger.latest_height();
+
+        // Sample the accepted transactions in the recent blocks.
+        let mut block_samples = Vec::new();
+        for height in latest_height.saturating_sub(FEE_ESTIMATE_NUM_BLOCKS - 1)..=latest_height {
+            let block = ledger.get_block(height)?;
+            for confirmed in block.transactions().iter().filter(|confirmed| confirmed.is_accepted()) {
+                block_samples.extend(priority_fee_per_byte(confirmed.transaction()));
+            }
+        }
+        // Sample the unconfirmed transactions in the memory pool.
+        let mut mempool_samples = Vec::new();
+        if let Some(consensus) = consensus {
+            for (_, transaction) in consensus.unconfirmed_transactions() {
+                if let Ok(transaction) = transaction.deserialize_blocking() {
+                    mempool_samples.extend(priority_fee_per_byte(&transaction));
+                }
+            }
+        }
+
+        Ok(Self::from_samples(latest_height, block_samples, mempool_samples))
+    }
+
+    /// Returns the fee estimate for the given samples of the priority fee per byte.
+    /// The transactions in the memory pool compete with each other for the next blocks,
+    /// which is why they are sampled alongside the transactions in the recent blocks.
+    pub fn from_samples(latest_height: u32, block_samples: Vec<f64>, mempool_samples: Vec<f64>) -> Self {
+        let (num_block_samples, num_mempool_samples) = (block_samples.len(), mempool_samples.len());
+
+        let mut samples = block_samples;
+        samples.extend(mempool_samples);
+        samples.sort_unstable_by(f64::total_cmp);
+
+        let priority_fee_per_byte = PriorityFees {
+            low: percentile(&samples, 25),
+            medium: percentile(&samples, 50),
+            high: percentile(&samples, 90),
+        };
+        Self { latest_height, num_block_samples, num_mempool_samples, priority_fee_per_byte }
+    }
+}
+
+/// Returns the priority fee per byte of the deployment or execution of the given transaction, in microcredits.
+/// Note: Fee transactions are skipped, as they do not pay a priority fee of their own.
+fn priority_fee_per_byte<N: Network>(transaction: &Transaction<N>) -> Option<f64> {
+    let size = match transaction {
+        Transaction::Deploy(_, _, deployment, _) => deployment.size_in_bytes().ok()?,
+        Transaction::Execute(_, execution, _) => execution.size_in_bytes().ok()?,
+        Transaction::Fee(..) => return None,
+    };
+    let priority_fee = *transaction.priority_fee_amount().ok()?;
+    Some(priority_fee as f64 / size.max(1) as f64)
+}
+
+/// Returns the given percentile of the sorted samples (using the nearest rank), or zero if there are no samples.
+fn percentile(sorted_samples: &[f64], percentile: usize) -> f64 {
+    match sorted_samples.len() {
+        0 => 0.0,
+        num_samples => {
+            let rank = ((percentile * num_samples + 99) / 100).max(1);
+            sorted_samples[rank - 1]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_estimate_from_samples() {
+        // Ensure the estimate is zero without any samples.
+        let estimate = FeeEstimate::from_samples(5, vec![], vec![]);
+        assert_eq!(estimate.priority_fee_per_byte, PriorityFees::default());
+
+        // Ensure the estimate is derived from the samples of the blocks and the memory pool.
+        let block_samples = (1..=6).map(|fee| fee as f64).collect();
+        let mempool_samples = (7..=10).map(|fee| fee as f64).rev().collect();
+        let estimate = FeeEstimate::from_samples(5, block_samples, mempool_samples);
+        assert_eq!(estimate.num_block_samples, 6);
+        assert_eq!(estimate.num_mempool_samples, 4);
+        assert_eq!(estimate.priority_fee_per_byte, PriorityFees { low: 3.0, medium: 5.0, high: 9.0 });
+    }
+}
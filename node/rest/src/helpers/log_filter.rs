@@ -0,0 +1,57 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, Result};
+use once_cell::sync::OnceCell;
+
+/// The function to update the log filter directives, and return the resulting log filter.
+type LogFilterHandler = Box<dyn Fn(&str) -> Result<String> + Send + Sync>;
+
+/// The function to enable the OTLP export to the given endpoint (or disable it), and return the resulting endpoint.
+type OtlpHandler = Box<dyn Fn(Option<&str>) -> Result<Option<String>> + Send + Sync>;
+
+/// The handler to update the log filter, which is registered by the logger of the process.
+static LOG_FILTER_HANDLER: OnceCell<LogFilterHandler> = OnceCell::new();
+
+/// The handler to update the OTLP endpoint, which is registered by the logger of the process.
+static OTLP_HANDLER: OnceCell<OtlpHandler> = OnceCell::new();
+
+/// Registers the handler to update the log filter directives at runtime.
+/// Note: Only the first registered handler is used.
+pub fn set_log_filter_handler(handler: impl Fn(&str) -> Result<String> + Send + Sync + 'static) {
+    let _ = LOG_FILTER_HANDLER.set(Box::new(handler));
+}
+
+/// Updates the log filter directives, and returns the resulting log filter.
+pub(crate) fn update_log_filter(directives: &str) -> Result<String> {
+    match LOG_FILTER_HANDLER.get() {
+        Some(handler) => handler(directives),
+        None => bail!("The log filter is not reloadable in this process"),
+    }
+}
+
+/// Registers the handler to enable or disable the OTLP export of the spans at runtime.
+/// Note: Only the first registered handler is used.
+pub fn set_otlp_handler(handler: impl Fn(Option<&str>) -> Result<Option<String>> + Send + Sync + 'static) {
+    let _ = OTLP_HANDLER.set(Box::new(handler));
+}
+
+/// Enables the OTLP export to the given endpoint, or disables it if no endpoint is given,
+/// and returns the resulting endpoint.
+pub(crate) fn update_otlp_endpoint(endpoint: Option<&str>) -> Result<Option<String>> {
+    match OTLP_HANDLER.get() {
+        Some(handler) => handler(endpoint),
+        None => bail!("The OTLP export is not configurable in this process"),
+    }
+}
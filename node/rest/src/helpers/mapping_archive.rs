@@ -0,0 +1,636 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::RestError;
+use snarkos_node_bft_ledger_service::LedgerObserver;
+use snarkvm::{
+    prelude::{
+        block::{Block, Input, Output, Transaction},
+        store::ConsensusStorage,
+        Field,
+        Identifier,
+        Ledger,
+        Literal,
+        Network,
+        Plaintext,
+        ProgramID,
+        ToBits,
+        ToBytes,
+        Value,
+    },
+    synthesizer::program::FinalizeOperation,
+};
+
+use anyhow::{bail, Result};
+use indexmap::{IndexMap, IndexSet};
+use parking_lot::{RwLock, RwLockUpgradableReadGuard};
+use rocksdb::{DBCompressionType, Direction, IteratorMode, Options, WriteBatch, DB};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    path::Path,
+    str::FromStr,
+    sync::{
+        mpsc::{sync_channel, Receiver, SyncSender},
+        Arc,
+        Weak,
+    },
+};
+
+/// The maximum number of blocks whose deltas are queued to be written to the archive.
+/// Note: If the writer falls behind by this many blocks, adding a block to the ledger waits for the writer.
+const MAX_QUEUED_BLOCKS: usize = 64;
+
+/// The key of the archived heights.
+const CHECKPOINT_KEY: &[u8] = b"checkpoint";
+/// The key prefix of the deltas.
+const DELTAS_PREFIX: u8 = 0;
+
+/// The keys that a block writes in each mapping, where `None` is every key of the mapping.
+type WrittenKeys<N> = IndexMap<(ProgramID<N>, Identifier<N>), Option<IndexSet<Plaintext<N>>>>;
+
+/// The values of the keys of the mappings, by the serialized key, where `None` is a key that is not in the mapping.
+type Values<N> = IndexMap<(ProgramID<N>, Identifier<N>), IndexMap<Vec<u8>, Option<Value<N>>>>;
+
+/// The heights that the archive serves.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+struct Checkpoint<N: Network> {
+    /// The height from which the archive recorded the delta of every block.
+    start_height: u32,
+    /// The height of the latest archived block.
+    height: u32,
+    /// The hash of the latest archived block.
+    hash: N::BlockHash,
+}
+
+/// The previous values of the keys that a block changed.
+struct Delta<N: Network> {
+    /// The height of the block.
+    height: u32,
+    /// The hash of the block.
+    hash: N::BlockHash,
+    /// The previous values of the changed keys.
+    changes: Values<N>,
+}
+
+/// The state of the archive.
+struct ArchiveState<N: Network> {
+    /// The heights that the archive serves, including the blocks whose deltas are not written yet.
+    checkpoint: Checkpoint<N>,
+    /// The deltas that are queued to be written, in the order of the blocks.
+    queued: VecDeque<Delta<N>>,
+    /// The height of the block that is being added to the ledger, the keys that it writes,
+    /// and their values before the block.
+    pending: Option<(u32, WrittenKeys<N>, Values<N>)>,
+}
+
+/// An archive of the history of the mapping values, which serves the mapping values at past heights.
+///
+/// The finalize store only retains the latest mapping values. As each block is added to the ledger,
+/// the archive reads the values of the keys that the block writes, before and after the block, and queues
+/// the previous value of each key that changed. A background writer persists the deltas, keyed by the mapping,
+/// the key, and the height. The value of a key at a past height is the previous value of its first change
+/// after that height, or its latest value if it did not change since.
+///
+/// The written keys are derived from the finalize operations of the transactions, which commit to the keys
+/// by their IDs. The IDs are matched against the plaintexts in the transitions of each transaction, and the
+/// whole mapping is read for an operation whose key is not found (e.g. a key that is computed in the finalize
+/// scope, or a removed key). The ratifications write the staking mappings, and the accounts of the provers.
+///
+/// The archive serves every height from the height of the ledger when it was first opened. If the ledger
+/// advanced without the archive (e.g. with the CDN sync at startup, or after a rollback), the history
+/// is discarded, and the archive restarts from the height of the ledger.
+#[derive(Clone)]
+pub struct MappingArchive<N: Network, C: ConsensusStorage<N>> {
+    /// The ID of `credits.aleo`.
+    credits_id: ProgramID<N>,
+    /// The ledger.
+    ledger: Ledger<N, C>,
+    /// The database of the archive.
+    database: Arc<DB>,
+    /// The state of the archive.
+    state: Arc<RwLock<ArchiveState<N>>>,
+    /// The sender that notifies the writer of a queued delta.
+    writer: SyncSender<()>,
+}
+
+impl<N: Network, C: ConsensusStorage<N>> MappingArchive<N, C> {
+    /// Opens the archive in the given directory, creating it if it does not exist, and starts its writer.
+    /// If the archive diverged from the ledger, it restarts from the height of the ledger.
+    pub fn open(path: &Path, ledger: Ledger<N, C>) -> Result<Self> {
+        std::fs::create_dir_all(path)?;
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.set_compression_type(DBCompressionType::Lz4);
+        let database = DB::open(&options, path)?;
+        let checkpoint: Option<Checkpoint<N>> = match database.get(CHECKPOINT_KEY)? {
+            Some(bytes) => Some(bincode::deserialize(&bytes)?),
+            None => None,
+        };
+
+        let (height, hash) = (ledger.latest_height(), ledger.latest_hash());
+        let (writer, receiver) = sync_channel(MAX_QUEUED_BLOCKS);
+        let archive = Self {
+            credits_id: ProgramID::from_str("credits.aleo")?,
+            ledger,
+            database: Arc::new(database),
+            state: Arc::new(RwLock::new(ArchiveState {
+                checkpoint: checkpoint.unwrap_or(Checkpoint { start_height: height, height, hash }),
+                queued: Default::default(),
+                pending: None,
+            })),
+            writer,
+        };
+        match checkpoint {
+            Some(checkpoint) if checkpoint.height == height && checkpoint.hash == hash => (),
+            Some(checkpoint) => {
+                let diverged_height = checkpoint.height;
+                warn!(
+                    "The mapping archive diverged from the ledger at block {diverged_height}, restarting the archive"
+                );
+                reset(&archive.database, &mut archive.state.write(), height, hash)?;
+            }
+            None => reset(&archive.database, &mut archive.state.write(), height, hash)?,
+        }
+        start_writer(Arc::downgrade(&archive.database), Arc::downgrade(&archive.state), receiver);
+        Ok(archive)
+    }
+
+    /// Returns the height from which the archive serves the mapping values.
+    pub fn start_height(&self) -> u32 {
+        self.state.read().checkpoint.start_height
+    }
+
+    /// Returns the value of the given key in the mapping, at the given height.
+    /// Returns a `Pruned` error if the archive does not serve the given height.
+    pub fn get_value(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        key: &Plaintext<N>,
+        height: u32,
+    ) -> Result<Option<Value<N>>, RestError> {
+        // Note: The lock prevents a block from being recorded while the value is read.
+        let state = self.state.read();
+        let Checkpoint { start_height, height: archived_height, .. } = state.checkpoint;
+        if height < start_height {
+            return Err(RestError::Pruned(format!("The state before block {start_height} was not archived")));
+        }
+        if height > archived_height {
+            return Err(RestError::Pruned(format!("The state at block {height} was not archived yet")));
+        }
+
+        // Return the previous value of the first change of the key after the given height, if any.
+        // Note: The written deltas precede the queued deltas.
+        let key_bytes = key.to_bytes_le()?;
+        let prefix = delta_key(&program_id, &mapping_name, &key_bytes, None)?;
+        let start = delta_key(&program_id, &mapping_name, &key_bytes, Some(height.saturating_add(1)))?;
+        if let Some(item) = self.database.iterator(IteratorMode::From(&start, Direction::Forward)).next() {
+            let (delta_key, previous_value) = item.map_err(anyhow::Error::from)?;
+            if delta_key.starts_with(&prefix) {
+                return Ok(bincode::deserialize(&previous_value).map_err(anyhow::Error::from)?);
+            }
+        }
+        for delta in state.queued.iter().filter(|delta| delta.height > height) {
+            if let Some(previous_value) = delta.changes.get(&(program_id, mapping_name)).and_then(|c| c.get(&key_bytes))
+            {
+                return Ok(previous_value.clone());
+            }
+        }
+        // Otherwise, return the value as of the latest archived block. If the next block is being added
+        // to the ledger, the keys that it writes are read from before the block.
+        if let Some((_, written, before)) = &state.pending {
+            let values = before.get(&(program_id, mapping_name));
+            match written.get(&(program_id, mapping_name)) {
+                // Note: If the whole mapping was read, a key that is not in the mapping was not in it before the block.
+                Some(None) => return Ok(values.and_then(|values| values.get(&key_bytes)).cloned().flatten()),
+                Some(Some(_)) => {
+                    if let Some(value) = values.and_then(|values| values.get(&key_bytes)) {
+                        return Ok(value.clone());
+                    }
+                }
+                None => (),
+            }
+        }
+        Ok(self.ledger.vm().finalize_store().get_value_confirmed(program_id, mapping_name, key)?)
+    }
+
+    /// Returns the keys that the given block writes in each mapping.
+    fn written_keys(&self, block: &Block<N>) -> Result<WrittenKeys<N>> {
+        let mut written = WrittenKeys::new();
+        // Note: The ratifications do not have finalize operations. The block reward replaces the staking
+        // mappings of `credits.aleo`, and the puzzle reward updates the accounts of the provers.
+        for mapping_name in ["committee", "bonded"] {
+            written.insert((self.credits_id, Identifier::from_str(mapping_name)?), None);
+        }
+        let account = (self.credits_id, Identifier::from_str("account")?);
+        for solution in block.coinbase().into_iter().flat_map(|coinbase| coinbase.partial_solutions()) {
+            insert_key(&mut written, account, Some(Plaintext::from(Literal::Address(solution.address()))));
+        }
+
+        let finalize_store = self.ledger.vm().finalize_store();
+        for confirmed in block.transactions().iter() {
+            let transaction = confirmed.transaction();
+            // Resolve the mapping IDs of the programs of the transitions.
+            let mut mappings = IndexMap::new();
+            for program_id in
+                transaction.transitions().map(|transition| *transition.program_id()).collect::<IndexSet<_>>()
+            {
+                for mapping_name in finalize_store.get_mapping_names_confirmed(&program_id)?.into_iter().flatten() {
+                    mappings.insert(to_mapping_id(&program_id, &mapping_name)?, (program_id, mapping_name));
+                }
+            }
+            let plaintexts = transaction_plaintexts(transaction);
+
+            for operation in confirmed.finalize_operations().iter() {
+                let (mapping_id, key_id) = match operation {
+                    // Note: A new mapping is empty.
+                    FinalizeOperation::InitializeMapping(..) => continue,
+                    FinalizeOperation::InsertKeyValue(mapping_id, key_id, _)
+                    | FinalizeOperation::UpdateKeyValue(mapping_id, _, key_id, _) => (mapping_id, Some(key_id)),
+                    FinalizeOperation::RemoveKeyValue(mapping_id, _)
+                    | FinalizeOperation::ReplaceMapping(mapping_id)
+                    | FinalizeOperation::RemoveMapping(mapping_id) => (mapping_id, None),
+                };
+                let Some(&(program_id, mapping_name)) = mappings.get(mapping_id) else {
+                    // If the mapping is not resolved, every mapping of the transaction may be written.
+                    mappings.values().for_each(|mapping| insert_key(&mut written, *mapping, None));
+                    continue;
+                };
+                // Find the key whose ID is the one of the operation, if any.
+                let mut key = None;
+                if let Some(key_id) = key_id {
+                    for plaintext in &plaintexts {
+                        if to_key_id(&program_id, &mapping_name, plaintext)? == *key_id {
+                            key = Some(plaintext.clone());
+                            break;
+                        }
+                    }
+                }
+                insert_key(&mut written, (program_id, mapping_name), key);
+            }
+        }
+        Ok(written)
+    }
+
+    /// Reads the current values of the given keys.
+    fn read_values(&self, written: &WrittenKeys<N>) -> Result<Values<N>> {
+        let finalize_store = self.ledger.vm().finalize_store();
+        let mut values = Values::new();
+        for (&(program_id, mapping_name), keys) in written {
+            let mut entries = IndexMap::new();
+            match keys {
+                Some(keys) => {
+                    for key in keys {
+                        let value = finalize_store.get_value_confirmed(program_id, mapping_name, key)?;
+                        entries.insert(key.to_bytes_le()?, value);
+                    }
+                }
+                None => {
+                    for (key, value) in finalize_store.get_mapping_confirmed(program_id, mapping_name)? {
+                        entries.insert(key.to_bytes_le()?, Some(value));
+                    }
+                }
+            }
+            values.insert((program_id, mapping_name), entries);
+        }
+        Ok(values)
+    }
+
+    /// Reads the values of the keys that the given block wrote, after the block is added to the ledger,
+    /// and queues the previous values of the keys that changed.
+    fn record(&self, block: &Block<N>) -> Result<()> {
+        let after = match &self.state.read().pending {
+            Some((height, written, _)) if *height == block.height() => self.read_values(written)?,
+            _ => bail!("The mappings before the block were not read"),
+        };
+        let Some((height, _, before)) = self.state.write().pending.take() else {
+            bail!("The mappings before the block were not read")
+        };
+        self.queue(Delta { height, hash: block.hash(), changes: changes(&before, &after) });
+        Ok(())
+    }
+
+    /// Queues the given delta to be written, and advances the heights that the archive serves to its block.
+    fn queue(&self, delta: Delta<N>) {
+        {
+            let mut state = self.state.write();
+            state.checkpoint =
+                Checkpoint { start_height: state.checkpoint.start_height, height: delta.height, hash: delta.hash };
+            state.queued.push_back(delta);
+        }
+        // Note: This waits if the writer is behind by `MAX_QUEUED_BLOCKS` blocks.
+        if self.writer.send(()).is_err() {
+            error!("The writer of the mapping archive stopped");
+        }
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> LedgerObserver<N> for MappingArchive<N, C> {
+    /// Reads the values of the keys that the given block writes, before the block is added to the ledger.
+    fn before_block(&self, block: &Block<N>) {
+        let values = self.written_keys(block).and_then(|written| Ok((self.read_values(&written)?, written)));
+        let mut state = self.state.write();
+        let Checkpoint { height, hash, .. } = state.checkpoint;
+        // If the ledger advanced without the archive, restart the archive from the previous block.
+        if block.height() != height + 1 || block.previous_hash() != hash {
+            warn!("The mapping archive is not at the block before block {}, restarting the archive", block.height());
+            if let Err(error) =
+                reset(&self.database, &mut state, block.height().saturating_sub(1), block.previous_hash())
+            {
+                error!("Failed to restart the mapping archive - {error}");
+            }
+        }
+        state.pending = match values {
+            Ok((before, written)) => Some((block.height(), written, before)),
+            Err(error) => {
+                warn!("Failed to read the mappings before block {} - {error}", block.height());
+                None
+            }
+        };
+    }
+
+    /// Queues the changes of the keys that the given block wrote, after the block is added to the ledger.
+    fn after_block(&self, block: &Block<N>) {
+        // If the changes of the block were not recorded, restart the archive from the block.
+        if let Err(error) = self.record(block) {
+            warn!("Failed to archive the mappings of block {}, restarting the archive - {error}", block.height());
+            if let Err(error) = reset(&self.database, &mut self.state.write(), block.height(), block.hash()) {
+                error!("Failed to restart the mapping archive - {error}");
+            }
+        }
+    }
+}
+
+/// Starts the writer of the archive, which writes each queued delta and the new checkpoint atomically.
+/// Note: The writer holds weak references, so that the database is closed once the archive is dropped.
+fn start_writer<N: Network>(database: Weak<DB>, state: Weak<RwLock<ArchiveState<N>>>, receiver: Receiver<()>) {
+    std::thread::spawn(move || {
+        while receiver.recv().is_ok() {
+            let (Some(database), Some(state)) = (database.upgrade(), state.upgrade()) else { break };
+            if let Err(error) = write_queued(&database, &state) {
+                // If the delta was not written, restart the archive from the latest recorded block.
+                warn!("Failed to write the mapping archive, restarting the archive - {error}");
+                let mut state = state.write();
+                let Checkpoint { height, hash, .. } = state.checkpoint;
+                if let Err(error) = reset(&database, &mut state, height, hash) {
+                    error!("Failed to restart the mapping archive - {error}");
+                }
+            }
+        }
+    });
+}
+
+/// Writes the first queued delta, if any, and the new checkpoint atomically, and removes it from the queue.
+fn write_queued<N: Network>(database: &DB, state: &RwLock<ArchiveState<N>>) -> Result<()> {
+    // Note: The upgradable lock serves the values while the delta is written, and prevents a reset meanwhile.
+    let state = state.upgradable_read();
+    let Some(delta) = state.queued.front() else { return Ok(()) };
+    let mut batch = WriteBatch::default();
+    for ((program_id, mapping_name), entries) in &delta.changes {
+        for (key, previous_value) in entries {
+            batch.put(
+                delta_key(program_id, mapping_name, key, Some(delta.height))?,
+                bincode::serialize(previous_value)?,
+            );
+        }
+    }
+    let checkpoint = Checkpoint { start_height: state.checkpoint.start_height, height: delta.height, hash: delta.hash };
+    batch.put(CHECKPOINT_KEY, bincode::serialize(&checkpoint)?);
+    database.write(batch)?;
+    RwLockUpgradableReadGuard::upgrade(state).queued.pop_front();
+    Ok(())
+}
+
+/// Removes every delta of the archive, and restarts the archive from the given block.
+fn reset<N: Network>(database: &DB, state: &mut ArchiveState<N>, height: u32, hash: N::BlockHash) -> Result<()> {
+    let checkpoint = Checkpoint { start_height: height, height, hash };
+    let mut batch = WriteBatch::default();
+    for item in database.iterator(IteratorMode::Start) {
+        batch.delete(item?.0);
+    }
+    batch.put(CHECKPOINT_KEY, bincode::serialize(&checkpoint)?);
+    database.write(batch)?;
+    *state = ArchiveState { checkpoint, queued: Default::default(), pending: None };
+    Ok(())
+}
+
+/// Inserts the given key into the written keys of the mapping, or marks every key of the mapping as written
+/// if no key is given.
+fn insert_key<N: Network>(
+    written: &mut WrittenKeys<N>,
+    mapping: (ProgramID<N>, Identifier<N>),
+    key: Option<Plaintext<N>>,
+) {
+    match (written.entry(mapping).or_insert_with(|| Some(IndexSet::new())), key) {
+        (Some(keys), Some(key)) => {
+            keys.insert(key);
+        }
+        (keys, None) => *keys = None,
+        (None, Some(_)) => (),
+    }
+}
+
+/// Returns the previous values of the keys whose values changed from before to after a block.
+fn changes<N: Network>(before: &Values<N>, after: &Values<N>) -> Values<N> {
+    let mut changes = Values::new();
+    let empty = IndexMap::new();
+    for mapping in before.keys().chain(after.keys()).collect::<IndexSet<_>>() {
+        let (before, after) = (before.get(mapping).unwrap_or(&empty), after.get(mapping).unwrap_or(&empty));
+        // Note: A key that was not read is not in the mapping, as only the whole mapping omits keys.
+        for key in before.keys().chain(after.keys()).collect::<IndexSet<_>>() {
+            let previous_value = before.get(key).cloned().flatten();
+            if previous_value != after.get(key).cloned().flatten() {
+                changes.entry(*mapping).or_default().insert(key.clone(), previous_value);
+            }
+        }
+    }
+    changes
+}
+
+/// Returns the plaintexts in the inputs, outputs, and finalize inputs of the transitions of the given transaction,
+/// which are the candidates for the keys that its finalize operations write.
+fn transaction_plaintexts<N: Network>(transaction: &Transaction<N>) -> IndexSet<Plaintext<N>> {
+    let mut plaintexts = IndexSet::new();
+    for transition in transaction.transitions() {
+        plaintexts.extend(transition.finalize().into_iter().flatten().filter_map(|value| match value {
+            Value::Plaintext(plaintext) => Some(plaintext.clone()),
+            _ => None,
+        }));
+        plaintexts.extend(transition.inputs().iter().filter_map(|input| match input {
+            Input::Public(_, Some(plaintext)) => Some(plaintext.clone()),
+            _ => None,
+        }));
+        plaintexts.extend(transition.outputs().iter().filter_map(|output| match output {
+            Output::Public(_, Some(plaintext)) => Some(plaintext.clone()),
+            _ => None,
+        }));
+    }
+    plaintexts
+}
+
+/// Returns the ID of the given mapping, as it is committed to by the finalize operations.
+fn to_mapping_id<N: Network>(program_id: &ProgramID<N>, mapping_name: &Identifier<N>) -> Result<Field<N>> {
+    // Note: The preimages mirror the ones of the finalize store, with a separator bit between the parts.
+    let preimage = [program_id.to_bits_le(), vec![false], mapping_name.to_bits_le()].concat();
+    N::hash_bhp1024(&preimage)
+}
+
+/// Returns the ID of the given key in the mapping, as it is committed to by the finalize operations.
+fn to_key_id<N: Network>(
+    program_id: &ProgramID<N>,
+    mapping_name: &Identifier<N>,
+    key: &Plaintext<N>,
+) -> Result<Field<N>> {
+    let preimage =
+        [program_id.to_bits_le(), vec![false], mapping_name.to_bits_le(), vec![false], key.to_bits_le()].concat();
+    N::hash_bhp1024(&preimage)
+}
+
+/// Returns the key of the delta of the given key in the mapping at the given height,
+/// or the common prefix of the keys of the deltas of the given key if no height is given.
+/// Note: The height is big-endian, so that the deltas of each key are in the order of the ledger.
+fn delta_key<N: Network>(
+    program_id: &ProgramID<N>,
+    mapping_name: &Identifier<N>,
+    key: &[u8],
+    height: Option<u32>,
+) -> Result<Vec<u8>> {
+    let mut mapping_key = Vec::new();
+    program_id.write_le(&mut mapping_key)?;
+    mapping_name.write_le(&mut mapping_key)?;
+    mapping_key.extend_from_slice(key);
+    // Note: The length prefix ensures that the prefix of a key is not the prefix of a longer key.
+    let mut delta_key = vec![DELTAS_PREFIX];
+    delta_key.extend_from_slice(&u32::try_from(mapping_key.len())?.to_be_bytes());
+    delta_key.extend_from_slice(&mapping_key);
+    if let Some(height) = height {
+        delta_key.extend_from_slice(&height.to_be_bytes());
+    }
+    Ok(delta_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::{ledger_test_helpers::sample_genesis_block, store::helpers::memory::ConsensusMemory},
+        utilities::TestRng,
+    };
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_mapping_archive() {
+        let rng = &mut TestRng::default();
+        let genesis = sample_genesis_block(rng);
+        let ledger = Ledger::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::load(genesis.clone(), None).unwrap();
+        let directory = std::env::temp_dir().join(format!("snarkos-mapping-archive-{}", std::process::id()));
+        let archive = MappingArchive::open(&directory, ledger.clone()).unwrap();
+        assert_eq!(archive.start_height(), 0);
+
+        let mapping = (ProgramID::from_str("credits.aleo").unwrap(), Identifier::from_str("account").unwrap());
+        let (key_a, key_b) = (Plaintext::from_str("1u8").unwrap(), Plaintext::from_str("2u8").unwrap());
+        let value = |value: &str| Value::<CurrentNetwork>::from_str(value).unwrap();
+        let values = |entries: &[(&Plaintext<_>, Option<&str>)]| {
+            let entries = entries.iter().map(|(key, entry)| (key.to_bytes_le().unwrap(), entry.map(value)));
+            Values::from([(mapping, entries.collect())])
+        };
+        let get_value = |key: &Plaintext<_>, height| archive.get_value(mapping.0, mapping.1, key, height);
+
+        // Queue the changes of the blocks at heights 1, 2, and 3, without notifying the writer.
+        // Note: The keys are not in the ledger, which is the state after block 3.
+        let blocks = [
+            values(&[(&key_a, None), (&key_b, None)]),
+            values(&[(&key_a, Some("10u64")), (&key_b, None)]),
+            values(&[(&key_a, Some("20u64")), (&key_b, Some("30u64"))]),
+            values(&[(&key_a, None), (&key_b, None)]),
+        ];
+        for height in 1..=3 {
+            let changes = changes(&blocks[height - 1], &blocks[height]);
+            let mut state = archive.state.write();
+            state.checkpoint.height = height as u32;
+            state.queued.push_back(Delta { height: height as u32, hash: genesis.hash(), changes });
+        }
+        // Ensure the values are served at each archived height, before and after the deltas are written.
+        for written in [false, true] {
+            if written {
+                (0..3).for_each(|_| archive.writer.send(()).unwrap());
+                while !archive.state.read().queued.is_empty() {
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+            }
+            assert_eq!(get_value(&key_a, 0).unwrap(), None);
+            assert_eq!(get_value(&key_a, 1).unwrap(), Some(value("10u64")));
+            assert_eq!(get_value(&key_b, 1).unwrap(), None);
+            assert_eq!(get_value(&key_a, 2).unwrap(), Some(value("20u64")));
+            assert_eq!(get_value(&key_b, 2).unwrap(), Some(value("30u64")));
+            assert_eq!(get_value(&key_a, 3).unwrap(), None);
+            assert_eq!(get_value(&key_b, 3).unwrap(), None);
+        }
+        // Ensure only the changed keys are written.
+        assert_eq!(archive.database.iterator(IteratorMode::Start).count(), 1 + 5);
+
+        // Ensure the values of the keys that the next block writes are served from before the block.
+        let written = WrittenKeys::from([(mapping, Some(IndexSet::from([key_b.clone()])))]);
+        archive.state.write().pending = Some((4, written, values(&[(&key_b, Some("40u64"))])));
+        assert_eq!(get_value(&key_a, 3).unwrap(), None);
+        assert_eq!(get_value(&key_b, 3).unwrap(), Some(value("40u64")));
+        // Ensure a key that is not in a mapping that was read whole was not in the mapping before the block.
+        let written = WrittenKeys::from([(mapping, None)]);
+        archive.state.write().pending = Some((4, written, values(&[])));
+        assert_eq!(get_value(&key_b, 3).unwrap(), None);
+        archive.state.write().pending = None;
+
+        // Ensure the heights after the latest archived block are not served.
+        assert!(matches!(get_value(&key_a, 4), Err(RestError::Pruned(_))));
+
+        // Ensure the archive restarts from the ledger if it diverged from the ledger.
+        drop(get_value);
+        drop(archive);
+        let archive = MappingArchive::open(&directory, ledger).unwrap();
+        assert_eq!(archive.start_height(), 0);
+        assert_eq!(archive.database.iterator(IteratorMode::Start).count(), 1);
+        assert!(matches!(archive.get_value(mapping.0, mapping.1, &key_a, 1), Err(RestError::Pruned(_))));
+
+        drop(archive);
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_written_keys() {
+        let mut written = WrittenKeys::<CurrentNetwork>::new();
+        let mapping = (ProgramID::from_str("credits.aleo").unwrap(), Identifier::from_str("account").unwrap());
+        let key = Plaintext::from_str("1u8").unwrap();
+
+        // Ensure the keys are collected, until the whole mapping is written.
+        insert_key(&mut written, mapping, Some(key.clone()));
+        assert_eq!(written[&mapping], Some(IndexSet::from([key.clone()])));
+        insert_key(&mut written, mapping, None);
+        assert_eq!(written[&mapping], None);
+        insert_key(&mut written, mapping, Some(key));
+        assert_eq!(written[&mapping], None);
+
+        // Ensure the IDs of different keys and mappings differ.
+        let bonded = Identifier::from_str("bonded").unwrap();
+        let (key_a, key_b) = (Plaintext::from_str("1u8").unwrap(), Plaintext::from_str("2u8").unwrap());
+        assert_ne!(to_mapping_id(&mapping.0, &mapping.1).unwrap(), to_mapping_id(&mapping.0, &bonded).unwrap());
+        assert_ne!(
+            to_key_id(&mapping.0, &mapping.1, &key_a).unwrap(),
+            to_key_id(&mapping.0, &mapping.1, &key_b).unwrap()
+        );
+        assert_ne!(to_key_id(&mapping.0, &mapping.1, &key_a).unwrap(), to_key_id(&mapping.0, &bonded, &key_a).unwrap());
+    }
+}
@@ -12,8 +12,52 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod address_index;
+pub use address_index::*;
+
 mod auth;
 pub use auth::*;
 
+mod block_export;
+pub use block_export::*;
+
+mod deprecation;
+pub(crate) use deprecation::*;
+
+mod endpoint;
+pub use endpoint::*;
+
 mod error;
 pub use error::*;
+
+mod extractors;
+pub(crate) use extractors::*;
+
+mod fee_estimate;
+pub use fee_estimate::*;
+
+mod log_filter;
+pub use log_filter::*;
+
+mod mapping_archive;
+pub use mapping_archive::*;
+
+mod pagination;
+pub use pagination::*;
+
+mod programs;
+pub use programs::*;
+
+mod puzzle;
+pub use puzzle::*;
+
+mod rate_limit;
+pub use rate_limit::*;
+
+mod response_cache;
+pub use response_cache::*;
+
+pub(crate) mod schema;
+
+mod validation;
+pub use validation::*;
@@ -0,0 +1,125 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::RestError;
+
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// The default number of items per page.
+pub const DEFAULT_PAGE_LIMIT: usize = 50;
+/// The maximum number of items per page.
+pub const MAX_PAGE_LIMIT: usize = 100;
+
+/// The query object of the paginated endpoints.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+pub struct PageQuery {
+    /// The cursor returned with the previous page, if any.
+    pub cursor: Option<String>,
+    /// The maximum number of items in the page.
+    pub limit: Option<usize>,
+}
+
+impl PageQuery {
+    /// Returns the maximum number of items in the page.
+    pub fn limit(&self) -> Result<usize, RestError> {
+        match self.limit.unwrap_or(DEFAULT_PAGE_LIMIT) {
+            0 => Err(RestError::BadRequest("The page limit must be positive".to_string())),
+            limit if limit > MAX_PAGE_LIMIT => {
+                Err(RestError::BadRequest(format!("Cannot request more than {MAX_PAGE_LIMIT} items per page")))
+            }
+            limit => Ok(limit),
+        }
+    }
+
+    /// Returns the cursor, which is the key of the last item of the previous page, if any.
+    pub fn parse_cursor<T: FromStr>(&self) -> Result<Option<T>, RestError> {
+        match &self.cursor {
+            Some(cursor) => match cursor.parse() {
+                Ok(cursor) => Ok(Some(cursor)),
+                Err(_) => Err(RestError::BadRequest(format!("Invalid cursor '{cursor}'"))),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+/// A page of items, and the cursor of the next page, if there are more items.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct Page<T: Serialize> {
+    /// The items.
+    pub items: Vec<T>,
+    /// The cursor of the next page, if there are more items.
+    pub next_cursor: Option<String>,
+}
+
+impl<T: Serialize> Page<T> {
+    /// Returns the page of the given items, which are paired with their cursors.
+    /// The items must resume after the cursor of the query,
+    /// so that the next page resumes after the last item of this page.
+    /// Note: The items are read lazily, up to the first item after the page.
+    pub fn from_cursors<C: ToString>(
+        items: impl Iterator<Item = Result<(C, T)>>,
+        query: &PageQuery,
+    ) -> Result<Self, RestError> {
+        let limit = query.limit()?;
+        let (mut page, mut last_cursor) = (Vec::with_capacity(limit), None);
+        for item in items {
+            let (cursor, item) = item?;
+            // If there is an item after the page, resume the next page after the last item of this page.
+            if page.len() == limit {
+                return Ok(Self { items: page, next_cursor: last_cursor });
+            }
+            last_cursor = Some(cursor.to_string());
+            page.push(item);
+        }
+        Ok(Self { items: page, next_cursor: None })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(cursor: Option<&str>, limit: Option<usize>) -> PageQuery {
+        PageQuery { cursor: cursor.map(str::to_string), limit }
+    }
+
+    #[test]
+    fn test_page_from_cursors() {
+        // Resume after the cursor, as the sources of the items do.
+        let items = |cursor: Option<&str>| {
+            let start = cursor.map_or(0, |cursor| cursor.parse::<u32>().unwrap() + 1);
+            (start..5).map(|item| Ok((item, item * 10)))
+        };
+        let page = |cursor, limit| Page::from_cursors(items(cursor), &query(cursor, limit)).unwrap();
+
+        // Ensure the pages are consecutive, and the last page has no cursor.
+        assert_eq!(page(None, Some(2)), Page { items: vec![0, 10], next_cursor: Some("1".to_string()) });
+        assert_eq!(page(Some("1"), Some(2)), Page { items: vec![20, 30], next_cursor: Some("3".to_string()) });
+        assert_eq!(page(Some("3"), Some(2)), Page { items: vec![40], next_cursor: None });
+        assert_eq!(page(Some("2"), Some(2)), Page { items: vec![30, 40], next_cursor: None });
+
+        // Ensure invalid cursors and limits are rejected.
+        assert!(query(Some("abc"), None).parse_cursor::<u32>().is_err());
+        assert!(Page::from_cursors(items(None), &query(None, Some(0))).is_err());
+        assert!(Page::from_cursors(items(None), &query(None, Some(MAX_PAGE_LIMIT + 1))).is_err());
+
+        // Ensure the errors of the items are returned.
+        let items = [Ok((0, 0)), Err(anyhow::anyhow!("failed"))].into_iter();
+        assert!(Page::from_cursors(items, &query(None, None)).is_err());
+    }
+}
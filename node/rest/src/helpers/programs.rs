@@ -0,0 +1,163 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{schema, RestError};
+use snarkvm::prelude::{store::ConsensusStorage, Ledger, Network, ProgramID};
+
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A program that was deployed to the ledger.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(bound = "")]
+#[schemars(rename = "DeployedProgram")]
+pub struct DeployedProgram<N: Network> {
+    /// The program ID.
+    #[schemars(with = "schema::ProgramId")]
+    pub program_id: ProgramID<N>,
+    /// The ID of the deployment transaction.
+    #[schemars(with = "schema::TransactionId")]
+    pub transaction_id: N::TransactionID,
+    /// The height of the block that contains the deployment.
+    pub height: u32,
+    /// The programs that the program imports.
+    #[schemars(with = "Vec<schema::ProgramId>")]
+    pub imports: Vec<ProgramID<N>>,
+}
+
+impl<N: Network> DeployedProgram<N> {
+    /// Returns the deployed programs that match the given filter, after the given program,
+    /// in the (stable) order of the program IDs in the deployment store.
+    /// Each program is paired with its ID, which is the cursor to resume after it.
+    /// Note: The programs are loaded lazily, as the iterator advances. This function reads from storage,
+    /// and must be called in a blocking task.
+    pub fn list<'a, C: ConsensusStorage<N>>(
+        ledger: &'a Ledger<N, C>,
+        filter: &'a ProgramFilter<N>,
+        after: Option<ProgramID<N>>,
+    ) -> Result<impl 'a + Iterator<Item = Result<(ProgramID<N>, Self)>>, RestError> {
+        let mut program_ids =
+            ledger.vm().transaction_store().deployment_store().program_ids().map(|program_id| program_id.into_owned());
+        // Skip the programs up to the given program, without loading them.
+        // Note: A cursor that is not a deployed program is rejected, instead of returning an empty page.
+        if let Some(after) = after {
+            if !program_ids.any(|program_id| program_id == after) {
+                return Err(RestError::BadRequest(format!("Cursor '{after}' is not a deployed program")));
+            }
+        }
+        Ok(program_ids
+            // Skip the programs that do not match the name prefix, before loading them.
+            .filter(move |program_id| filter.matches_prefix(program_id))
+            .filter_map(move |program_id| Self::load(ledger, filter, program_id).transpose()))
+    }
+
+    /// Loads the given deployed program, if it matches the imports of the given filter.
+    fn load<C: ConsensusStorage<N>>(
+        ledger: &Ledger<N, C>,
+        filter: &ProgramFilter<N>,
+        program_id: ProgramID<N>,
+    ) -> Result<Option<(ProgramID<N>, Self)>> {
+        let imports = ledger.get_program(program_id)?.imports().keys().copied().collect::<Vec<_>>();
+        if !filter.matches_imports(&imports) {
+            return Ok(None);
+        }
+        // Retrieve the deployment transaction, and the height of its block.
+        let Some(transaction_id) = ledger.find_transaction_id_from_program_id(&program_id)? else {
+            return Ok(None);
+        };
+        let Some(block_hash) = ledger.find_block_hash(&transaction_id)? else {
+            return Ok(None);
+        };
+        let height = ledger.get_height(&block_hash)?;
+        Ok(Some((program_id, Self { program_id, transaction_id, height, imports })))
+    }
+}
+
+/// The filter of the deployed programs.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(bound = "")]
+#[schemars(rename = "ProgramFilter")]
+pub struct ProgramFilter<N: Network> {
+    /// If specified, only the programs that import this program are returned.
+    #[schemars(with = "Option<schema::ProgramId>")]
+    pub import: Option<ProgramID<N>>,
+    /// If specified, only the programs whose ID starts with this prefix are returned.
+    pub prefix: Option<String>,
+}
+
+impl<N: Network> ProgramFilter<N> {
+    /// Returns `true` if the given program ID starts with the prefix, if any.
+    pub fn matches_prefix(&self, program_id: &ProgramID<N>) -> bool {
+        self.prefix.as_ref().map_or(true, |prefix| program_id.to_string().starts_with(prefix.as_str()))
+    }
+
+    /// Returns `true` if the given imports contain the import, if any.
+    pub fn matches_imports(&self, imports: &[ProgramID<N>]) -> bool {
+        self.import.as_ref().map_or(true, |import| imports.contains(import))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        prelude::{
+            store::{helpers::memory::ConsensusMemory, ConsensusStore},
+            PrivateKey,
+            VM,
+        },
+        utilities::TestRng,
+    };
+
+    use std::str::FromStr;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+    type CurrentLedger = Ledger<CurrentNetwork, ConsensusMemory<CurrentNetwork>>;
+
+    #[test]
+    fn test_program_filter() {
+        let program_id = |id: &str| ProgramID::<CurrentNetwork>::from_str(id).unwrap();
+        let imports = [program_id("credits.aleo")];
+
+        // Ensure an empty filter matches every program.
+        let filter = ProgramFilter { import: None, prefix: None };
+        assert!(filter.matches_prefix(&program_id("token.aleo")));
+        assert!(filter.matches_imports(&[]));
+
+        // Ensure the programs are filtered by the prefix of their ID, and by their imports.
+        let filter = ProgramFilter { import: Some(program_id("credits.aleo")), prefix: Some("tok".to_string()) };
+        assert!(filter.matches_prefix(&program_id("token.aleo")));
+        assert!(!filter.matches_prefix(&program_id("swap.aleo")));
+        assert!(filter.matches_imports(&imports));
+        assert!(!filter.matches_imports(&[program_id("token.aleo")]));
+    }
+
+    #[test]
+    fn test_list_with_unknown_cursor() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let vm =
+            VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None).unwrap()).unwrap();
+        let ledger = CurrentLedger::load(vm.genesis_beacon(&private_key, rng).unwrap(), None).unwrap();
+        let filter = ProgramFilter { import: None, prefix: None };
+
+        // Ensure the programs are listed from the start without a cursor.
+        assert_eq!(DeployedProgram::list(&ledger, &filter, None).unwrap().count(), 0);
+        // Ensure a cursor that is not a deployed program is rejected, instead of returning an empty page.
+        let cursor = ProgramID::from_str("token.aleo").unwrap();
+        let error = DeployedProgram::list(&ledger, &filter, Some(cursor)).err().unwrap();
+        assert_eq!(error, RestError::BadRequest("Cursor 'token.aleo' is not a deployed program".to_string()));
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{schema, RestError};
+use snarkvm::prelude::{
+    coinbase::{EpochChallenge, ProverSolution},
+    store::ConsensusStorage,
+    Ledger,
+    Network,
+};
+
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// The latest state of the coinbase puzzle, from which the provers compute their solutions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(bound = "")]
+#[schemars(rename = "PuzzleState")]
+pub struct PuzzleState<N: Network> {
+    /// The latest block height.
+    pub latest_height: u32,
+    /// The latest block hash.
+    #[schemars(with = "schema::BlockHash")]
+    pub latest_hash: N::BlockHash,
+    /// The epoch number of the latest epoch challenge.
+    pub epoch_number: u32,
+    /// The block hash that the latest epoch challenge is derived from.
+    #[schemars(with = "schema::BlockHash")]
+    pub epoch_block_hash: N::BlockHash,
+    /// The latest coinbase target.
+    pub coinbase_target: u64,
+    /// The latest proof target.
+    pub proof_target: u64,
+}
+
+impl<N: Network> PuzzleState<N> {
+    /// Returns the latest state of the coinbase puzzle in the given ledger.
+    pub fn latest<C: ConsensusStorage<N>>(ledger: &Ledger<N, C>) -> Result<Self> {
+        let epoch_challenge = ledger.latest_epoch_challenge()?;
+        let header = ledger.latest_header();
+        Ok(Self {
+            latest_height: header.height(),
+            latest_hash: ledger.latest_hash(),
+            epoch_number: epoch_challenge.epoch_number(),
+            epoch_block_hash: epoch_challenge.epoch_block_hash(),
+            coinbase_target: header.coinbase_target(),
+            proof_target: header.proof_target(),
+        })
+    }
+}
+
+/// Ensures the given solution does not exist in the ledger, reaches the given proof target,
+/// and is valid for the given epoch challenge.
+/// Note: This function verifies the solution, and must be called in a blocking task.
+pub fn check_solution<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    epoch_challenge: &EpochChallenge<N>,
+    proof_target: u64,
+    solution: &ProverSolution<N>,
+) -> Result<(), RestError> {
+    let solution_id = solution.commitment();
+    // Ensure the solution does not exist in the ledger.
+    if ledger.contains_puzzle_commitment(&solution_id)? {
+        return Err(RestError::Conflict(format!("Solution '{solution_id}' exists in the ledger")));
+    }
+    // Ensure the solution reaches the proof target.
+    let target = solution.to_target()?;
+    if target < proof_target {
+        return Err(RestError::UnprocessableEntity(format!(
+            "Solution '{solution_id}' does not reach the proof target ({target} < {proof_target})"
+        )));
+    }
+    // Ensure the solution is valid for the epoch challenge.
+    match solution.verify(ledger.coinbase_puzzle().coinbase_verifying_key(), epoch_challenge, proof_target) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(RestError::UnprocessableEntity(format!("Solution '{solution_id}' is invalid"))),
+        Err(error) => Err(RestError::UnprocessableEntity(format!("Solution '{solution_id}' is invalid - {error}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        prelude::{
+            store::{helpers::memory::ConsensusMemory, ConsensusStore},
+            Address,
+            PrivateKey,
+            VM,
+        },
+        utilities::TestRng,
+    };
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+    type CurrentLedger = Ledger<CurrentNetwork, ConsensusMemory<CurrentNetwork>>;
+
+    /// Returns a ledger, and the private key of its genesis block.
+    fn sample_ledger(rng: &mut TestRng) -> (CurrentLedger, PrivateKey<CurrentNetwork>) {
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let vm =
+            VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None).unwrap()).unwrap();
+        let genesis = vm.genesis_beacon(&private_key, rng).unwrap();
+        (CurrentLedger::load(genesis, None).unwrap(), private_key)
+    }
+
+    /// Returns the first solution for the latest epoch challenge that reaches (or does not reach) the proof target.
+    fn sample_solution(
+        ledger: &CurrentLedger,
+        address: Address<CurrentNetwork>,
+        proof_target: u64,
+        reaches_target: bool,
+    ) -> ProverSolution<CurrentNetwork> {
+        let epoch_challenge = ledger.latest_epoch_challenge().unwrap();
+        (0..10_000u64)
+            .map(|nonce| ledger.coinbase_puzzle().prove(&epoch_challenge, address, nonce, None).unwrap())
+            .find(|solution| (solution.to_target().unwrap() >= proof_target) == reaches_target)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_puzzle_state() {
+        let rng = &mut TestRng::default();
+        let (ledger, _) = sample_ledger(rng);
+        let genesis = ledger.get_block(0).unwrap();
+
+        let state = PuzzleState::latest(&ledger).unwrap();
+        let expected = serde_json::json!({
+            "latest_height": 0,
+            "latest_hash": genesis.hash().to_string(),
+            "epoch_number": 0,
+            "epoch_block_hash": ledger.latest_epoch_challenge().unwrap().epoch_block_hash().to_string(),
+            "coinbase_target": genesis.header().coinbase_target(),
+            "proof_target": genesis.header().proof_target(),
+        });
+        assert_eq!(serde_json::to_value(&state).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_check_solution() {
+        let rng = &mut TestRng::default();
+        let (ledger, private_key) = sample_ledger(rng);
+        let address = Address::try_from(&private_key).unwrap();
+        let epoch_challenge = ledger.latest_epoch_challenge().unwrap();
+        let proof_target = ledger.latest_header().proof_target();
+        let check = |solution: &ProverSolution<_>| check_solution(&ledger, &epoch_challenge, proof_target, solution);
+
+        // Ensure a solution that reaches the proof target is valid.
+        let solution = sample_solution(&ledger, address, proof_target, true);
+        assert!(check(&solution).is_ok());
+
+        // Ensure a solution that does not reach the proof target is rejected.
+        let below_target = sample_solution(&ledger, address, proof_target, false);
+        let result = check(&below_target);
+        assert!(matches!(result, Err(RestError::UnprocessableEntity(message)) if message.contains("does not reach")));
+
+        // Ensure a solution with the proof of another solution is rejected.
+        let invalid = ProverSolution::new(*solution.partial_solution(), *below_target.proof());
+        let result = check_solution(&ledger, &epoch_challenge, 0, &invalid);
+        assert!(matches!(result, Err(RestError::UnprocessableEntity(message)) if message.contains("is invalid")));
+
+        // Ensure a solution that exists in the ledger is rejected.
+        let block =
+            ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![solution], vec![], rng).unwrap();
+        ledger.advance_to_next_block(&block).unwrap();
+        // Note: The epoch challenge is unchanged, so the same solution is found again.
+        let known = sample_solution(&ledger, address, proof_target, true);
+        assert!(matches!(check(&known), Err(RestError::Conflict(_))));
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{verify_jwt, RestError};
+
+use anyhow::{bail, Result};
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{
+        header::{AUTHORIZATION, RETRY_AFTER},
+        HeaderValue,
+        Request,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    fmt,
+    net::{IpAddr, SocketAddr},
+    num::NonZeroUsize,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The maximum number of rate limit buckets, after which the least recently used buckets are evicted.
+const MAX_BUCKETS: usize = 100_000;
+
+/// A group of routes that share a rate limit quota.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RouteGroup {
+    /// The routes of the blocks and transactions, e.g. `/blocks` and `/transaction/{id}`.
+    Blocks,
+    /// The routes of the finalize state and the programs, e.g. `/program/{id}/mapping/{name}/{key}`.
+    State,
+    /// The routes that broadcast or validate transactions and solutions.
+    Broadcast,
+    /// The other routes.
+    Default,
+}
+
+impl RouteGroup {
+    /// The route groups.
+    pub const ALL: [Self; 4] = [Self::Blocks, Self::State, Self::Broadcast, Self::Default];
+
+    /// Returns the group of the given endpoint path (e.g. `/block/:height_or_hash`).
+    pub fn of(path: &str) -> Self {
+        if path.ends_with("/broadcast") || path.ends_with("/validate") {
+            return Self::Broadcast;
+        }
+        match path.trim_start_matches('/').split('/').next().unwrap_or_default() {
+            "block" | "blocks" | "transaction" | "find" | "height" => Self::Blocks,
+            "address" | "committee" | "program" | "programs" | "statePath" | "stateRoot" => Self::State,
+            _ => Self::Default,
+        }
+    }
+
+    /// Returns the default quota of the group, in requests per second.
+    pub const fn default_quota(&self) -> u32 {
+        match self {
+            Self::Blocks => 20,
+            Self::State => 50,
+            Self::Broadcast => 10,
+            Self::Default => 100,
+        }
+    }
+}
+
+impl fmt::Display for RouteGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Blocks => write!(f, "blocks"),
+            Self::State => write!(f, "state"),
+            Self::Broadcast => write!(f, "broadcast"),
+            Self::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// The rate limit quotas of the route groups, in requests per second, where `0` disables the rate limit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateLimits {
+    /// The map of the route group to its quota.
+    quotas: HashMap<RouteGroup, u32>,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self { quotas: RouteGroup::ALL.into_iter().map(|group| (group, group.default_quota())).collect() }
+    }
+}
+
+impl RateLimits {
+    /// Returns the quota of the given route group, in requests per second.
+    pub fn quota(&self, group: RouteGroup) -> u32 {
+        self.quotas.get(&group).copied().unwrap_or_else(|| group.default_quota())
+    }
+}
+
+impl FromStr for RateLimits {
+    type Err = anyhow::Error;
+
+    /// Parses the quotas of the route groups (e.g. `blocks=10,state=0`), which override the default quotas.
+    fn from_str(limits: &str) -> Result<Self> {
+        let mut rate_limits = Self::default();
+        for limit in limits.split(',').map(str::trim).filter(|limit| !limit.is_empty()) {
+            let Some((name, quota)) = limit.split_once('=') else {
+                bail!("Invalid rate limit '{limit}' (expected e.g. 'blocks=10')")
+            };
+            let Some(group) = RouteGroup::ALL.into_iter().find(|group| group.to_string() == name.trim()) else {
+                bail!("Unknown route group '{name}' (expected one of 'blocks', 'state', 'broadcast', 'default')")
+            };
+            rate_limits.quotas.insert(group, quota.trim().parse()?);
+        }
+        Ok(rate_limits)
+    }
+}
+
+/// The client that a rate limit applies to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    /// A client without a valid token, identified by its IP address.
+    Ip(IpAddr),
+    /// A client with a valid token (issued by this node).
+    Token(String),
+}
+
+/// A token bucket, which refills at the quota of its route group.
+#[derive(Copy, Clone, Debug)]
+struct Bucket {
+    /// The number of available requests.
+    tokens: f64,
+    /// The time at which the tokens were last updated.
+    updated_at: Instant,
+}
+
+/// A rate limiter of the requests of each client, per route group.
+#[derive(Clone)]
+pub struct RateLimiter {
+    /// The quotas of the route groups.
+    limits: RateLimits,
+    /// The token bucket of each client, per route group.
+    buckets: Arc<Mutex<LruCache<(RateLimitKey, RouteGroup), Bucket>>>,
+}
+
+impl RateLimiter {
+    /// Initializes a new rate limiter with the given quotas.
+    pub fn new(limits: RateLimits) -> Self {
+        let capacity = NonZeroUsize::new(MAX_BUCKETS).expect("Invalid rate limit bucket capacity");
+        Self { limits, buckets: Arc::new(Mutex::new(LruCache::new(capacity))) }
+    }
+
+    /// Consumes a request of the given client to the given route group, at the given time.
+    /// Returns the time after which the client may retry, if the client exceeded the quota.
+    pub fn check(&self, key: RateLimitKey, group: RouteGroup, now: Instant) -> Result<(), Duration> {
+        let quota = self.limits.quota(group);
+        if quota == 0 {
+            return Ok(());
+        }
+        let capacity = f64::from(quota);
+        // Note: If there are too many buckets, the least recently used bucket is evicted.
+        let mut buckets = self.buckets.lock();
+        let bucket = buckets.get_or_insert_mut((key, group), || Bucket { tokens: capacity, updated_at: now });
+        // Refill the bucket, up to one second of requests.
+        let elapsed = now.saturating_duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * capacity).min(capacity);
+        bucket.updated_at = now;
+        // Consume a request, if one is available.
+        match bucket.tokens >= 1.0 {
+            true => {
+                bucket.tokens -= 1.0;
+                Ok(())
+            }
+            false => Err(Duration::from_secs_f64((1.0 - bucket.tokens) / capacity)),
+        }
+    }
+}
+
+/// Rejects the requests of the clients that exceeded the quota of the route group.
+/// Note: This middleware is only applied if the rate limits are enabled.
+pub async fn rate_limit_middleware<B>(
+    State((rate_limiter, group)): State<(RateLimiter, RouteGroup)>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response
+where
+    B: Send,
+{
+    // Identify the client by its token, if it is valid, and by its IP address otherwise.
+    let token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .filter(|token| verify_jwt(token).is_some());
+    let key = match token {
+        Some(token) => RateLimitKey::Token(token.to_string()),
+        None => RateLimitKey::Ip(addr.ip()),
+    };
+
+    match rate_limiter.check(key, group, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            #[cfg(feature = "metrics")]
+            metrics::increment_counter!(metrics::rest::THROTTLED_REQUESTS);
+            trace!("Throttled a request to '{}' from '{addr}'", request.uri());
+
+            let message = format!("Exceeded the rate limit of the '{group}' routes");
+            let mut response = RestError::TooManyRequests(message).into_response();
+            let retry_after = retry_after.as_secs().saturating_add(1);
+            response.headers_mut().insert(RETRY_AFTER, HeaderValue::from(retry_after));
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_groups() {
+        assert_eq!(RouteGroup::of("/blocks"), RouteGroup::Blocks);
+        assert_eq!(RouteGroup::of("/transaction/:id"), RouteGroup::Blocks);
+        assert_eq!(RouteGroup::of("/transaction/broadcast"), RouteGroup::Broadcast);
+        assert_eq!(RouteGroup::of("/solution/broadcast"), RouteGroup::Broadcast);
+        assert_eq!(RouteGroup::of("/program/:id/mapping/:name/:key"), RouteGroup::State);
+        assert_eq!(RouteGroup::of("/peers/count"), RouteGroup::Default);
+
+        // Ensure the quotas override the default quotas.
+        let limits = RateLimits::from_str("blocks=5, state=0").unwrap();
+        assert_eq!(limits.quota(RouteGroup::Blocks), 5);
+        assert_eq!(limits.quota(RouteGroup::State), 0);
+        assert_eq!(limits.quota(RouteGroup::Default), RouteGroup::Default.default_quota());
+        assert_eq!(RateLimits::from_str("").unwrap(), RateLimits::default());
+        assert!(RateLimits::from_str("blocks").is_err());
+        assert!(RateLimits::from_str("mappings=5").is_err());
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let rate_limiter = RateLimiter::new(RateLimits::from_str("blocks=2,state=0").unwrap());
+        let ip = RateLimitKey::Ip(IpAddr::from([127, 0, 0, 1]));
+        let now = Instant::now();
+
+        // Ensure a client is throttled once it exceeds the quota.
+        assert!(rate_limiter.check(ip.clone(), RouteGroup::Blocks, now).is_ok());
+        assert!(rate_limiter.check(ip.clone(), RouteGroup::Blocks, now).is_ok());
+        let retry_after = rate_limiter.check(ip.clone(), RouteGroup::Blocks, now).unwrap_err();
+        assert_eq!(retry_after, Duration::from_millis(500));
+
+        // Ensure the other clients, and the other route groups, are not throttled.
+        let token = RateLimitKey::Token("token".to_string());
+        assert!(rate_limiter.check(token, RouteGroup::Blocks, now).is_ok());
+        assert!(rate_limiter.check(ip.clone(), RouteGroup::Default, now).is_ok());
+        for _ in 0..10 {
+            assert!(rate_limiter.check(ip.clone(), RouteGroup::State, now).is_ok());
+        }
+
+        // Ensure the quota is restored over time.
+        assert!(rate_limiter.check(ip.clone(), RouteGroup::Blocks, now + Duration::from_millis(500)).is_ok());
+
+        // Ensure the least recently used buckets are evicted once there are too many buckets.
+        for i in 0..MAX_BUCKETS {
+            let key = RateLimitKey::Token(i.to_string());
+            assert!(rate_limiter.check(key, RouteGroup::Default, now).is_ok());
+        }
+        assert_eq!(rate_limiter.buckets.lock().len(), MAX_BUCKETS);
+        assert!(!rate_limiter.buckets.lock().contains(&(ip, RouteGroup::Blocks)));
+        assert!(rate_limiter.buckets.lock().contains(&(RateLimitKey::Token(0.to_string()), RouteGroup::Default)));
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::RestError;
+
+use axum::{
+    body::{boxed, Bytes, Full},
+    extract::State,
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH},
+        HeaderValue,
+        Request,
+        StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    num::NonZeroUsize,
+    sync::Arc,
+};
+
+/// The maximum number of cached responses.
+const RESPONSE_CACHE_CAPACITY: usize = 1_024;
+/// The maximum size of a cached response body.
+const MAX_CACHED_BODY_SIZE: usize = 4 * 1024 * 1024; // 4 MiB
+/// The `Cache-Control` header of the immutable responses.
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// A cached response.
+#[derive(Clone, Debug)]
+struct CachedResponse {
+    /// The content type of the response.
+    content_type: Option<HeaderValue>,
+    /// The entity tag of the response, which is derived from the body.
+    etag: HeaderValue,
+    /// The body of the response.
+    body: Bytes,
+}
+
+impl CachedResponse {
+    /// Initializes a new cached response, for the given content type and body.
+    fn new(content_type: Option<HeaderValue>, body: Bytes) -> Self {
+        let mut hasher = DefaultHasher::new();
+        body.hash(&mut hasher);
+        let etag = HeaderValue::from_str(&format!("\"{:016x}\"", hasher.finish())).expect("Invalid entity tag");
+        Self { content_type, etag, body }
+    }
+
+    /// Returns the response, or `304 Not Modified` if the client holds the response with the given entity tag.
+    fn to_response(&self, if_none_match: Option<&HeaderValue>) -> Response {
+        let mut response = match if_none_match == Some(&self.etag) {
+            true => StatusCode::NOT_MODIFIED.into_response(),
+            false => {
+                let mut response = Response::new(boxed(Full::new(self.body.clone())));
+                if let Some(content_type) = &self.content_type {
+                    response.headers_mut().insert(CONTENT_TYPE, content_type.clone());
+                }
+                response
+            }
+        };
+        response.headers_mut().insert(ETAG, self.etag.clone());
+        response.headers_mut().insert(CACHE_CONTROL, HeaderValue::from_static(IMMUTABLE_CACHE_CONTROL));
+        response
+    }
+}
+
+/// An in-memory cache of the responses of the endpoints whose responses never change once they succeed,
+/// such as the blocks and transactions by height, hash, or ID.
+#[derive(Clone)]
+pub struct ResponseCache {
+    /// The map of the request URI to the response.
+    responses: Arc<Mutex<LruCache<String, CachedResponse>>>,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        let capacity = NonZeroUsize::new(RESPONSE_CACHE_CAPACITY).expect("Invalid response cache capacity");
+        Self { responses: Arc::new(Mutex::new(LruCache::new(capacity))) }
+    }
+}
+
+/// Serves the cached response of the request, or caches the response if it succeeds.
+pub async fn cache_middleware<B>(State(cache): State<ResponseCache>, request: Request<B>, next: Next<B>) -> Response
+where
+    B: Send,
+{
+    // Note: The key includes the query, e.g. the range of `/blocks`.
+    let key = request.uri().to_string();
+    let if_none_match = request.headers().get(IF_NONE_MATCH).cloned();
+
+    // Retrieve the cached response, if any.
+    let cached = cache.responses.lock().get(&key).cloned();
+    let cached = match cached {
+        Some(cached) => {
+            #[cfg(feature = "metrics")]
+            metrics::increment_counter!(metrics::rest::CACHE_HITS);
+            cached
+        }
+        None => {
+            #[cfg(feature = "metrics")]
+            metrics::increment_counter!(metrics::rest::CACHE_MISSES);
+            let response = next.run(request).await;
+            // Note: Only the successful responses are cached, as e.g. a block that does not exist may be added later.
+            if response.status() != StatusCode::OK {
+                return response;
+            }
+            let (parts, body) = response.into_parts();
+            let body = match hyper::body::to_bytes(body).await {
+                Ok(body) => body,
+                Err(error) => return RestError::InternalServerError(error.to_string()).into_response(),
+            };
+            let cached = CachedResponse::new(parts.headers.get(CONTENT_TYPE).cloned(), body);
+            if cached.body.len() <= MAX_CACHED_BODY_SIZE {
+                cache.responses.lock().put(key, cached.clone());
+            }
+            cached
+        }
+    };
+    cached.to_response(if_none_match.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_response() {
+        let content_type = HeaderValue::from_static("application/json");
+        let cached = CachedResponse::new(Some(content_type.clone()), Bytes::from_static(b"{\"height\":1}"));
+        assert_eq!(cached.etag, CachedResponse::new(None, Bytes::from_static(b"{\"height\":1}")).etag);
+        assert_ne!(cached.etag, CachedResponse::new(None, Bytes::from_static(b"{\"height\":2}")).etag);
+
+        // Ensure the response is immutable, and carries its entity tag.
+        let response = cached.to_response(None);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], content_type);
+        assert_eq!(response.headers()[ETAG], cached.etag);
+        assert_eq!(response.headers()[CACHE_CONTROL], IMMUTABLE_CACHE_CONTROL);
+
+        // Ensure the client that holds the response is told that it is not modified.
+        assert_eq!(cached.to_response(Some(&cached.etag)).status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(cached.to_response(Some(&HeaderValue::from_static("\"0\""))).status(), StatusCode::OK);
+    }
+}
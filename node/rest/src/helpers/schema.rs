@@ -0,0 +1,117 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The schemas of the types in the bodies of the REST API that are defined outside of this crate
+//! (e.g. the snarkVM types), and therefore cannot derive [`JsonSchema`].
+//! Each schema is named after the type, and describes the JSON type that the type is serialized as.
+
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, Metadata, Schema, SchemaObject},
+    JsonSchema,
+};
+
+/// Returns the format of a schema, if any.
+macro_rules! schema_format {
+    () => {
+        None
+    };
+    ($format:literal) => {
+        Some($format.to_string())
+    };
+}
+
+/// Declares a type with the given JSON type (and format), for each type that is described in the OpenAPI document.
+macro_rules! schemas {
+    ($($(#[doc = $doc:literal])+ $name:ident: $instance_type:ident $(($format:literal))?;)*) => {
+        $(
+            $(#[doc = $doc])+
+            pub(crate) struct $name;
+
+            impl JsonSchema for $name {
+                fn schema_name() -> String {
+                    stringify!($name).to_string()
+                }
+
+                fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                    let description = [$($doc.trim()),+].join(" ");
+                    SchemaObject {
+                        instance_type: Some(InstanceType::$instance_type.into()),
+                        format: schema_format!($($format)?),
+                        metadata: Some(Box::new(Metadata { description: Some(description), ..Default::default() })),
+                        ..Default::default()
+                    }
+                    .into()
+                }
+            }
+        )*
+    };
+}
+
+schemas! {
+    /// An Aleo address (e.g. `aleo1...`).
+    Address: String;
+    /// A block hash (e.g. `ab1...`).
+    BlockHash: String;
+    /// A transaction ID (e.g. `at1...`).
+    TransactionId: String;
+    /// A transition ID (e.g. `au1...`).
+    TransitionId: String;
+    /// A program ID (e.g. `credits.aleo`).
+    ProgramId: String;
+    /// An identifier, such as the name of a function or a mapping.
+    Identifier: String;
+    /// A field element, such as a state root or a commitment.
+    Field: String;
+    /// The state path of a commitment.
+    StatePath: String;
+    /// The source code of a program.
+    Program: String;
+    /// A plaintext value, such as the key of a mapping (e.g. `aleo1...` or `100u64`).
+    Plaintext: String;
+    /// A value, such as the value of a mapping (e.g. `100u64`).
+    Value: String;
+    /// The ID of a prover solution, which is its puzzle commitment.
+    SolutionId: String;
+    /// A block.
+    Block: Object;
+    /// The confirmed transactions of a block.
+    Transactions: Object;
+    /// A transaction.
+    Transaction: Object;
+    /// A prover solution.
+    ProverSolution: Object;
+    /// The committee of validators, with their stakes.
+    Committee: Object;
+    /// The block locators of the ledger, which are the hashes of its recent and checkpoint blocks.
+    BlockLocators: Object;
+    /// The progress of the block sync, with the ledger height, the target height, and the sync rate.
+    SyncProgress: Object;
+    /// A fork between the ledger and the branch of some peers.
+    Fork: Object;
+    /// The socket address, the node type, and the round-trip time (in milliseconds, if any) of a connected peer.
+    PeerMetrics: Array;
+    /// The current and last committed rounds of the DAG, the leader, and the certificates of the rounds.
+    Dag: Object;
+    /// An event of the DAG, which is sent as a server-sent event.
+    DagEvent: Object;
+    /// The unconfirmed transmissions in the memory pool, by ID.
+    Transmissions: Object;
+    /// The unconfirmed prover solutions in the memory pool, by ID.
+    Solutions: Object;
+    /// The unconfirmed transactions in the memory pool, by ID.
+    UnconfirmedTransactions: Object;
+    /// A stream of blocks, in the encoding of the export format.
+    BlockStream: String("binary");
+}
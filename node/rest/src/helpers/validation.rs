@@ -0,0 +1,330 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{schema, RestError};
+use snarkos_node_consensus::Consensus;
+use snarkvm::prelude::{
+    block::Transaction,
+    deployment_cost,
+    execution_cost,
+    store::ConsensusStorage,
+    Field,
+    Ledger,
+    Network,
+};
+
+use anyhow::{bail, ensure, Result};
+use indexmap::IndexSet;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// The memory pool, against which the transactions are checked.
+pub trait MemoryPool<N: Network> {
+    /// Returns `true` if the memory pool contains the given transaction.
+    fn contains_transaction(&self, transaction_id: &N::TransactionID) -> bool;
+
+    /// Returns the ID and the spent serial number of a transaction in the memory pool (other than the given one)
+    /// that spends one of the given serial numbers, if any.
+    fn find_spend(
+        &self,
+        transaction_id: &N::TransactionID,
+        serial_numbers: &IndexSet<Field<N>>,
+    ) -> Result<Option<(N::TransactionID, Field<N>)>>;
+}
+
+impl<N: Network> MemoryPool<N> for Consensus<N> {
+    fn contains_transaction(&self, transaction_id: &N::TransactionID) -> bool {
+        self.contains_unconfirmed_transaction(transaction_id)
+    }
+
+    fn find_spend(
+        &self,
+        transaction_id: &N::TransactionID,
+        serial_numbers: &IndexSet<Field<N>>,
+    ) -> Result<Option<(N::TransactionID, Field<N>)>> {
+        self.find_unconfirmed_spend(transaction_id, serial_numbers)
+    }
+}
+
+/// The outcome of a check of a transaction.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct TransactionCheck {
+    /// The name of the check.
+    pub name: &'static str,
+    /// If `true`, the transaction passed the check.
+    pub passed: bool,
+    /// The reason the transaction failed the check, if it failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The verdict of the checks of a transaction, as performed before it is added to the memory pool.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(bound = "")]
+#[schemars(rename = "TransactionVerdict")]
+pub struct TransactionVerdict<N: Network> {
+    /// The transaction ID.
+    #[schemars(with = "schema::TransactionId")]
+    pub transaction_id: N::TransactionID,
+    /// If `true`, the transaction passed every check.
+    pub is_valid: bool,
+    /// The outcome of each check.
+    pub checks: Vec<TransactionCheck>,
+}
+
+impl<N: Network> TransactionVerdict<N> {
+    /// Checks the given transaction against the ledger and the memory pool (if any).
+    /// Note: This function verifies the proofs of the transaction, and must be called in a blocking task.
+    pub fn new<C: ConsensusStorage<N>>(
+        ledger: &Ledger<N, C>,
+        memory_pool: Option<&dyn MemoryPool<N>>,
+        transaction: &Transaction<N>,
+    ) -> Self {
+        let checks = vec![
+            TransactionCheck::new("well_formed", check_well_formed(transaction)),
+            TransactionCheck::new("unique", check_unique(ledger, memory_pool, transaction)),
+            TransactionCheck::new("double_spend", check_double_spend(ledger, memory_pool, transaction)),
+            TransactionCheck::new("fee", check_fee(ledger, transaction)),
+            TransactionCheck::new("verification", ledger.check_transaction_basic(transaction, None)),
+        ];
+        let is_valid = checks.iter().all(|check| check.passed);
+        Self { transaction_id: transaction.id(), is_valid, checks }
+    }
+
+    /// Returns an error for the first failed check, if the transaction is invalid.
+    /// A transaction that already exists is a conflict, while any other failure is unprocessable.
+    pub fn ensure_is_valid(&self) -> Result<(), RestError> {
+        match self.checks.iter().find(|check| !check.passed) {
+            Some(check) => {
+                let message = format!(
+                    "Transaction '{}' failed the '{}' check - {}",
+                    self.transaction_id,
+                    check.name,
+                    check.error.as_deref().unwrap_or_default()
+                );
+                match check.name {
+                    "unique" => Err(RestError::Conflict(message)),
+                    _ => Err(RestError::UnprocessableEntity(message)),
+                }
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl TransactionCheck {
+    /// Initializes the outcome of a check from its result.
+    fn new(name: &'static str, result: Result<()>) -> Self {
+        match result {
+            Ok(()) => Self { name, passed: true, error: None },
+            Err(error) => Self { name, passed: false, error: Some(error.to_string()) },
+        }
+    }
+}
+
+/// Ensures the transaction is of a type that can be broadcast.
+fn check_well_formed<N: Network>(transaction: &Transaction<N>) -> Result<()> {
+    ensure!(!transaction.is_fee(), "A fee transaction cannot be broadcast on its own");
+    Ok(())
+}
+
+/// Ensures the transaction does not exist in the ledger or the memory pool.
+fn check_unique<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    memory_pool: Option<&dyn MemoryPool<N>>,
+    transaction: &Transaction<N>,
+) -> Result<()> {
+    let transaction_id = transaction.id();
+    ensure!(!ledger.contains_transaction_id(&transaction_id)?, "The transaction exists in the ledger");
+    if let Some(memory_pool) = memory_pool {
+        ensure!(!memory_pool.contains_transaction(&transaction_id), "The transaction exists in the memory pool");
+    }
+    Ok(())
+}
+
+/// Ensures the records spent by the transaction are not spent in the ledger, or by a transaction in the memory pool.
+fn check_double_spend<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    memory_pool: Option<&dyn MemoryPool<N>>,
+    transaction: &Transaction<N>,
+) -> Result<()> {
+    let serial_numbers = transaction.serial_numbers().copied().collect::<IndexSet<_>>();
+    for serial_number in &serial_numbers {
+        ensure!(!ledger.contains_serial_number(serial_number)?, "The record '{serial_number}' is spent in the ledger");
+    }
+    // Note: The transaction itself is skipped, as it is reported by the uniqueness check.
+    if let Some(memory_pool) = memory_pool {
+        if let Some((transaction_id, serial_number)) = memory_pool.find_spend(&transaction.id(), &serial_numbers)? {
+            bail!("The record '{serial_number}' is spent by transaction '{transaction_id}' in the memory pool");
+        }
+    }
+    Ok(())
+}
+
+/// Ensures the fee of the transaction covers the cost of its deployment or execution.
+fn check_fee<N: Network, C: ConsensusStorage<N>>(ledger: &Ledger<N, C>, transaction: &Transaction<N>) -> Result<()> {
+    let minimum_cost = match transaction {
+        Transaction::Deploy(_, _, deployment, _) => deployment_cost(deployment)?.0,
+        Transaction::Execute(_, execution, _) => execution_cost(ledger.vm(), execution)?.0,
+        Transaction::Fee(..) => bail!("A fee transaction does not pay for a deployment or execution"),
+    };
+    let fee = *transaction.fee_amount()?;
+    ensure!(
+        fee >= minimum_cost,
+        "The fee of {fee} microcredits is below the minimum cost of {minimum_cost} microcredits"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::ledger_test_helpers::{sample_fee_public_transaction, sample_genesis_block},
+        prelude::{
+            store::{helpers::memory::ConsensusMemory, ConsensusStore},
+            PrivateKey,
+            Value,
+            ViewKey,
+            VM,
+        },
+        utilities::TestRng,
+    };
+
+    use std::str::FromStr;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+    type CurrentLedger = Ledger<CurrentNetwork, ConsensusMemory<CurrentNetwork>>;
+
+    /// A memory pool of the given transactions.
+    struct SampleMemoryPool(Vec<Transaction<CurrentNetwork>>);
+
+    impl MemoryPool<CurrentNetwork> for SampleMemoryPool {
+        fn contains_transaction(&self, transaction_id: &<CurrentNetwork as Network>::TransactionID) -> bool {
+            self.0.iter().any(|transaction| transaction.id() == *transaction_id)
+        }
+
+        fn find_spend(
+            &self,
+            transaction_id: &<CurrentNetwork as Network>::TransactionID,
+            serial_numbers: &IndexSet<Field<CurrentNetwork>>,
+        ) -> Result<Option<(<CurrentNetwork as Network>::TransactionID, Field<CurrentNetwork>)>> {
+            Ok(self.0.iter().filter(|transaction| transaction.id() != *transaction_id).find_map(|transaction| {
+                let serial_number = transaction.serial_numbers().find(|sn| serial_numbers.contains(*sn))?;
+                Some((transaction.id(), *serial_number))
+            }))
+        }
+    }
+
+    /// Returns a ledger, and two transactions that spend the same records of its genesis block.
+    fn sample_ledger_and_transactions(
+        rng: &mut TestRng,
+    ) -> (CurrentLedger, Transaction<CurrentNetwork>, Transaction<CurrentNetwork>) {
+        // Initialize the ledger.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let view_key = ViewKey::try_from(&private_key).unwrap();
+        let vm =
+            VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None).unwrap()).unwrap();
+        let genesis = vm.genesis_beacon(&private_key, rng).unwrap();
+        let ledger = CurrentLedger::load(genesis.clone(), None).unwrap();
+
+        // Decrypt the records of the genesis block.
+        let mut records = genesis
+            .records()
+            .filter(|(_, record)| record.is_owner(&view_key))
+            .map(|(_, record)| record.decrypt(&view_key).unwrap());
+        let (record, fee_record) = (records.next().unwrap(), records.next().unwrap());
+
+        // Transfer the same record twice, for different amounts.
+        let address = view_key.to_address();
+        let mut transfer = |amount: &str| {
+            let inputs = [
+                Value::Record(record.clone()),
+                Value::from_str(&address.to_string()).unwrap(),
+                Value::from_str(amount).unwrap(),
+            ];
+            let locator = ("credits.aleo", "transfer_private");
+            ledger.vm().execute(&private_key, locator, inputs.iter(), Some(fee_record.clone()), 0, None, rng).unwrap()
+        };
+        let (transaction, double_spend) = (transfer("1u64"), transfer("2u64"));
+        (ledger, transaction, double_spend)
+    }
+
+    #[test]
+    fn test_checks() {
+        let rng = &mut TestRng::default();
+        let (ledger, transaction, double_spend) = sample_ledger_and_transactions(rng);
+        let genesis_transaction =
+            ledger.get_block(0).unwrap().transactions().iter().next().unwrap().transaction().clone();
+        let fee_transaction = sample_fee_public_transaction(rng);
+
+        // Ensure a fee transaction is not well-formed.
+        assert!(check_well_formed(&transaction).is_ok());
+        assert!(check_well_formed(&fee_transaction).is_err());
+
+        // Ensure a transaction is not unique if it exists in the ledger or the memory pool.
+        let memory_pool = SampleMemoryPool(vec![transaction.clone()]);
+        assert!(check_unique(&ledger, None, &transaction).is_ok());
+        assert!(check_unique(&ledger, Some(&memory_pool), &double_spend).is_ok());
+        assert!(check_unique(&ledger, None, &genesis_transaction).is_err());
+        assert!(check_unique(&ledger, Some(&memory_pool), &transaction).is_err());
+
+        // Ensure a transaction is a double spend if another transaction in the memory pool spends its records.
+        assert!(check_double_spend(&ledger, None, &double_spend).is_ok());
+        assert!(check_double_spend(&ledger, Some(&memory_pool), &transaction).is_ok());
+        assert!(check_double_spend(&ledger, Some(&memory_pool), &double_spend).is_err());
+
+        // Ensure a fee transaction does not pay for a deployment or execution.
+        assert!(check_fee(&ledger, &transaction).is_ok());
+        assert!(check_fee(&ledger, &fee_transaction).is_err());
+
+        // Ensure the verdict reports each failed check.
+        let failed_checks = |memory_pool: Option<&dyn MemoryPool<CurrentNetwork>>, transaction| {
+            let verdict = TransactionVerdict::new(&ledger, memory_pool, transaction);
+            assert_eq!(verdict.is_valid, verdict.checks.iter().all(|check| check.passed));
+            verdict.checks.into_iter().filter(|check| !check.passed).map(|check| check.name).collect::<Vec<_>>()
+        };
+        assert!(failed_checks(None, &transaction).is_empty());
+        assert_eq!(failed_checks(Some(&memory_pool), &double_spend), ["double_spend"]);
+        let failed = failed_checks(None, &genesis_transaction);
+        assert!(failed.contains(&"unique") && failed.contains(&"verification"));
+    }
+
+    #[test]
+    fn test_ensure_is_valid() {
+        let rng = &mut TestRng::default();
+        let transaction_id = sample_genesis_block(rng).transactions().iter().next().unwrap().transaction().id();
+        let verdict = |results: Vec<(&'static str, Result<()>)>| {
+            let checks =
+                results.into_iter().map(|(name, result)| TransactionCheck::new(name, result)).collect::<Vec<_>>();
+            let is_valid = checks.iter().all(|check| check.passed);
+            TransactionVerdict::<CurrentNetwork> { transaction_id, is_valid, checks }
+        };
+
+        // Ensure a valid transaction passes.
+        assert!(verdict(vec![("unique", Ok(())), ("fee", Ok(()))]).ensure_is_valid().is_ok());
+
+        // Ensure a transaction that is not unique is a conflict.
+        let result = verdict(vec![("unique", Err(anyhow::anyhow!("exists"))), ("fee", Ok(()))]).ensure_is_valid();
+        assert!(matches!(result, Err(RestError::Conflict(message)) if message.contains("'unique' check - exists")));
+
+        // Ensure any other failure is unprocessable.
+        let result = verdict(vec![("unique", Ok(())), ("fee", Err(anyhow::anyhow!("too low")))]).ensure_is_valid();
+        assert!(matches!(result, Err(RestError::UnprocessableEntity(message)) if message.contains("'fee' check")));
+
+        // Ensure the first failed check determines the error.
+        let results = vec![("well_formed", Err(anyhow::anyhow!("fee"))), ("unique", Err(anyhow::anyhow!("exists")))];
+        assert!(matches!(verdict(results).ensure_is_valid(), Err(RestError::UnprocessableEntity(_))));
+    }
+}
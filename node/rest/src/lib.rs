@@ -21,12 +21,14 @@ mod helpers;
 pub use helpers::*;
 
 mod routes;
+use routes::{AddressIndexPage, BlockRange, BroadcastQuery, ExportQuery, HeightQuery, MappingEntry, RoundRange};
 
 use snarkos_node_consensus::Consensus;
 use snarkos_node_router::{
-    messages::{Message, UnconfirmedTransaction},
+    messages::{Message, UnconfirmedSolution, UnconfirmedTransaction},
     Routing,
 };
+use snarkos_node_sync::BlockSync;
 use snarkvm::{
     console::{program::ProgramID, types::Field},
     ledger::narwhal::Data,
@@ -35,23 +37,55 @@ use snarkvm::{
 
 use anyhow::Result;
 use axum::{
-    extract::{ConnectInfo, DefaultBodyLimit, Path, Query, State},
-    http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+    extract::{ConnectInfo, DefaultBodyLimit, State},
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE, LINK},
+        HeaderName,
+        Method,
+        Request,
+        StatusCode,
+    },
     middleware,
     middleware::Next,
-    response::Response,
-    routing::{get, post},
-    Json,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Response,
+    },
+    routing::get,
 };
 use axum_extra::response::ErasedJson;
 use parking_lot::Mutex;
-use std::{net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, path::Path, sync::Arc, time::Duration};
 use tokio::task::JoinHandle;
 use tower_http::{
     cors::{Any, CorsLayer},
     trace::TraceLayer,
 };
 
+/// The deprecated aliases of the endpoints, and the paths of their successors.
+/// These routes will be removed before mainnet.
+const DEPRECATED_ALIASES: [(&str, &str); 5] = [
+    ("/latest/height", "/block/height/latest"),
+    ("/latest/hash", "/block/hash/latest"),
+    ("/latest/block", "/block/latest"),
+    ("/latest/stateRoot", "/stateRoot/latest"),
+    ("/latest/committee", "/committee/latest"),
+];
+
+/// The interval at which the address index is updated with the new blocks of the ledger.
+const ADDRESS_INDEX_INTERVAL_IN_SECS: u64 = 2; // 2 seconds
+
+/// The configuration of the optional features of the REST server, which are disabled by default.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RestConfig {
+    /// If `true`, the ledger is indexed by address, to serve the account endpoints.
+    pub address_index: bool,
+    /// If `true`, the mapping values of each new block are archived, to serve the past mapping values.
+    pub archive: bool,
+    /// The quotas of the route groups, if the requests are rate limited.
+    pub rate_limits: Option<RateLimits>,
+}
+
 /// A REST API server for the ledger.
 #[derive(Clone)]
 pub struct Rest<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
@@ -59,6 +93,12 @@ pub struct Rest<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
     consensus: Option<Consensus<N>>,
     /// The ledger.
     ledger: Ledger<N, C>,
+    /// The block sync module.
+    sync: BlockSync<N>,
+    /// The address index, if enabled.
+    address_index: Option<AddressIndex<N>>,
+    /// The archive of the mapping values, if enabled.
+    mapping_archive: Option<MappingArchive<N, C>>,
     /// The node (routing).
     routing: Arc<R>,
     /// The server handles.
@@ -67,19 +107,58 @@ pub struct Rest<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
 
 impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     /// Initializes a new instance of the server.
+    /// The address index and the archive of the mapping values, if enabled, are stored in the given directory.
     pub fn start(
         rest_ip: SocketAddr,
         consensus: Option<Consensus<N>>,
         ledger: Ledger<N, C>,
+        sync: BlockSync<N>,
         routing: Arc<R>,
+        config: RestConfig,
+        storage_dir: &Path,
     ) -> Result<Self> {
+        // Open the address index, if enabled.
+        let address_index = match config.address_index {
+            true => Some(AddressIndex::open(&storage_dir.join("address-index"))?),
+            false => None,
+        };
+        // Open the archive of the mapping values, if enabled.
+        let mapping_archive = match config.archive {
+            true => Some(MappingArchive::open(&storage_dir.join("archive"), ledger.clone())?),
+            false => None,
+        };
         // Initialize the server.
-        let mut server = Self { consensus, ledger, routing, handles: Default::default() };
+        let mut server =
+            Self { consensus, ledger, sync, address_index, mapping_archive, routing, handles: Default::default() };
+        // Spawn the address indexer.
+        server.spawn_address_indexer();
         // Spawn the server.
-        server.spawn_server(rest_ip);
+        server.spawn_server(rest_ip, config.rate_limits);
         // Return the server.
         Ok(server)
     }
+
+    /// Spawns a task to build the address index from the ledger, and to keep it up to date as the ledger advances.
+    fn spawn_address_indexer(&self) {
+        let Some(address_index) = self.address_index.clone() else {
+            return;
+        };
+        let ledger = self.ledger.clone();
+        self.handles.lock().push(tokio::spawn(async move {
+            loop {
+                let (address_index, ledger) = (address_index.clone(), ledger.clone());
+                match tokio::task::spawn_blocking(move || address_index.catch_up(&ledger)).await {
+                    // If the index is behind the ledger, continue to index the blocks.
+                    Ok(Ok(false)) => continue,
+                    // If the index is caught up with the ledger, wait for the next blocks.
+                    Ok(Ok(true)) => (),
+                    Ok(Err(error)) => warn!("Failed to update the address index - {error}"),
+                    Err(error) => error!("Failed to update the address index - {error}"),
+                }
+                tokio::time::sleep(Duration::from_secs(ADDRESS_INDEX_INTERVAL_IN_SECS)).await;
+            }
+        }));
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
@@ -88,6 +167,13 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         &self.ledger
     }
 
+    /// Returns the archive of the mapping values, if enabled.
+    /// Note: The archive records the mapping values as blocks are added to the ledger, once it is added
+    /// as an observer of the ledger service.
+    pub const fn mapping_archive(&self) -> Option<&MappingArchive<N, C>> {
+        self.mapping_archive.as_ref()
+    }
+
     /// Returns the handles.
     pub const fn handles(&self) -> &Arc<Mutex<Vec<JoinHandle<()>>>> {
         &self.handles
@@ -95,74 +181,409 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
 }
 
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
-    fn spawn_server(&mut self, rest_ip: SocketAddr) {
-        let cors = CorsLayer::new()
-            .allow_origin(Any)
-            .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
-            .allow_headers([CONTENT_TYPE]);
-
-        let router = {
-            axum::Router::new()
-
-            // All the endpoints before the call to `route_layer` are protected with JWT auth.
-            .route("/testnet3/node/address", get(Self::get_node_address))
-            .route_layer(middleware::from_fn(auth_middleware))
-
-            // ----------------- DEPRECATED ROUTES -----------------
-            // The following `GET ../latest/..` routes will be removed before mainnet.
-            // Please refer to the recommended routes for each endpoint:
-
-            // Deprecated: use `/testnet3/block/height/latest` instead.
-            .route("/testnet3/latest/height", get(Self::latest_height))
-            // Deprecated: use `/testnet3/block/hash/latest` instead.
-            .route("/testnet3/latest/hash", get(Self::latest_hash))
-            // Deprecated: use `/testnet3/latest/block/height` instead.
-            .route("/testnet3/latest/block", get(Self::latest_block))
-            // Deprecated: use `/testnet3/stateRoot/latest` instead.
-            .route("/testnet3/latest/stateRoot", get(Self::latest_state_root))
-            // Deprecated: use `/testnet3/committee/latest` instead.
-            .route("/testnet3/latest/committee", get(Self::latest_committee))
-            // ------------------------------------------------------
-
+    /// Returns the endpoints of the REST API, relative to the prefix of the API version and network.
+    fn endpoints() -> Vec<Endpoint<Self>> {
+        vec![
+            // The endpoints of the node, which are protected with JWT auth.
+            Endpoint::get(
+                "/node/address",
+                "get_node_address",
+                "Returns the address of the node",
+                Self::get_node_address,
+            )
+            .with_response::<schema::Address>()
+            .protected(),
+            Endpoint::post(
+                "/node/logFilter",
+                "set_log_filter",
+                "Updates the log filter directives of the node",
+                Self::set_log_filter,
+            )
+            .with_request_body::<String>("text/plain")
+            .with_response::<String>()
+            .protected(),
+            Endpoint::post(
+                "/node/otlp",
+                "set_otlp",
+                "Enables or disables the OTLP export of the node traces",
+                Self::set_otlp,
+            )
+            .with_request_body::<String>("text/plain")
+            .with_response::<Option<String>>()
+            .protected(),
+            Endpoint::get(
+                "/bft/dag",
+                "get_bft_dag",
+                "Returns the rounds of the DAG in the given range",
+                Self::get_bft_dag,
+            )
+            .with_query::<RoundRange>()
+            .with_response::<schema::Dag>()
+            .protected(),
+            Endpoint::get(
+                "/bft/dag/events",
+                "get_bft_dag_events",
+                "Streams the events of the DAG",
+                Self::get_bft_dag_events,
+            )
+            .with_response_body::<schema::DagEvent>("text/event-stream")
+            .protected(),
+            Endpoint::post(
+                "/bft/proposing/pause",
+                "pause_bft_proposing",
+                "Pauses the batch proposals of the node",
+                Self::pause_bft_proposing,
+            )
+            .with_response::<bool>()
+            .protected(),
+            Endpoint::post(
+                "/bft/proposing/resume",
+                "resume_bft_proposing",
+                "Resumes the batch proposals of the node",
+                Self::resume_bft_proposing,
+            )
+            .with_response::<bool>()
+            .protected(),
+            // GET ../address/..
+            Endpoint::get(
+                "/address/:address/balance",
+                "get_address_balance",
+                "Returns the public balance of the address",
+                Self::get_address_balance,
+            )
+            .with_response::<u64>(),
+            Endpoint::get(
+                "/address/:address/transitions",
+                "get_address_transitions",
+                "Returns the public transitions of credits.aleo that involve the address",
+                Self::get_address_transitions,
+            )
+            .with_query::<PageQuery>()
+            .with_response::<AddressIndexPage<AddressTransition<N>>>(),
+            Endpoint::get(
+                "/address/:address/deployments",
+                "get_address_deployments",
+                "Returns the programs deployed by the address",
+                Self::get_address_deployments,
+            )
+            .with_query::<PageQuery>()
+            .with_response::<AddressIndexPage<AddressDeployment<N>>>(),
+            Endpoint::get(
+                "/address/:address/fees",
+                "get_address_fees",
+                "Returns the public fees paid by the address",
+                Self::get_address_fees,
+            )
+            .with_query::<PageQuery>()
+            .with_response::<AddressIndexPage<AddressFee<N>>>(),
             // GET ../block/..
-            .route("/testnet3/block/height/latest", get(Self::get_block_height_latest))
-            .route("/testnet3/block/hash/latest", get(Self::get_block_hash_latest))
-            .route("/testnet3/block/latest", get(Self::get_block_latest))
-            .route("/testnet3/block/:height_or_hash", get(Self::get_block))
+            Endpoint::get(
+                "/block/height/latest",
+                "get_block_height_latest",
+                "Returns the latest block height",
+                Self::get_block_height_latest,
+            )
+            .with_response::<u32>(),
+            Endpoint::get(
+                "/block/hash/latest",
+                "get_block_hash_latest",
+                "Returns the latest block hash",
+                Self::get_block_hash_latest,
+            )
+            .with_response::<schema::BlockHash>(),
+            Endpoint::get("/block/latest", "get_block_latest", "Returns the latest block", Self::get_block_latest)
+                .with_response::<schema::Block>(),
+            Endpoint::get(
+                "/block/:height_or_hash",
+                "get_block",
+                "Returns the block for the given height or hash",
+                Self::get_block,
+            )
+            .with_response::<schema::Block>()
+            .cached(),
             // The path param here is actually only the height, but the name must match the route
             // above, otherwise there'll be a conflict at runtime.
-            .route("/testnet3/block/:height_or_hash/transactions", get(Self::get_block_transactions))
-
+            Endpoint::get(
+                "/block/:height_or_hash/transactions",
+                "get_block_transactions",
+                "Returns the transactions in the block at the given height",
+                Self::get_block_transactions,
+            )
+            .with_response::<schema::Transactions>()
+            .cached(),
             // GET and POST ../transaction/..
-            .route("/testnet3/transaction/:id", get(Self::get_transaction))
-            .route("/testnet3/transaction/broadcast", post(Self::transaction_broadcast))
-
+            Endpoint::get(
+                "/transaction/:id",
+                "get_transaction",
+                "Returns the transaction for the given ID",
+                Self::get_transaction,
+            )
+            .with_response::<schema::Transaction>()
+            .cached(),
+            Endpoint::post(
+                "/transaction/broadcast",
+                "transaction_broadcast",
+                "Broadcasts the given transaction",
+                Self::transaction_broadcast,
+            )
+            .with_query::<BroadcastQuery>()
+            .with_request_body::<schema::Transaction>("application/json")
+            .with_response::<schema::TransactionId>(),
+            Endpoint::post(
+                "/transaction/validate",
+                "transaction_validate",
+                "Returns the verdict of the checks of the given transaction",
+                Self::transaction_validate,
+            )
+            .with_request_body::<schema::Transaction>("application/json")
+            .with_response::<TransactionVerdict<N>>(),
+            // GET and POST ../puzzle/.. and ../solution/..
+            Endpoint::get(
+                "/puzzle/latest",
+                "get_puzzle_latest",
+                "Returns the latest state of the coinbase puzzle",
+                Self::get_puzzle_latest,
+            )
+            .with_response::<PuzzleState<N>>(),
+            Endpoint::post(
+                "/solution/broadcast",
+                "solution_broadcast",
+                "Broadcasts the given prover solution",
+                Self::solution_broadcast,
+            )
+            .with_request_body::<schema::ProverSolution>("application/json")
+            .with_response::<schema::SolutionId>(),
             // GET ../find/..
-            .route("/testnet3/find/blockHash/:tx_id", get(Self::find_block_hash))
-            .route("/testnet3/find/transactionID/deployment/:program_id", get(Self::find_transaction_id_from_program_id))
-            .route("/testnet3/find/transactionID/:transition_id", get(Self::find_transaction_id_from_transition_id))
-            .route("/testnet3/find/transitionID/:input_or_output_id", get(Self::find_transition_id))
-
+            Endpoint::get(
+                "/find/blockHash/:tx_id",
+                "find_block_hash",
+                "Returns the hash of the block containing the given transaction",
+                Self::find_block_hash,
+            )
+            .with_response::<schema::BlockHash>(),
+            Endpoint::get(
+                "/find/transactionID/deployment/:program_id",
+                "find_transaction_id_from_program_id",
+                "Returns the ID of the transaction that deployed the given program",
+                Self::find_transaction_id_from_program_id,
+            )
+            .with_response::<schema::TransactionId>(),
+            Endpoint::get(
+                "/find/transactionID/:transition_id",
+                "find_transaction_id_from_transition_id",
+                "Returns the ID of the transaction containing the given transition",
+                Self::find_transaction_id_from_transition_id,
+            )
+            .with_response::<schema::TransactionId>(),
+            Endpoint::get(
+                "/find/transitionID/:input_or_output_id",
+                "find_transition_id",
+                "Returns the ID of the transition containing the given input or output",
+                Self::find_transition_id,
+            )
+            .with_response::<schema::TransitionId>(),
             // GET ../peers/..
-            .route("/testnet3/peers/count", get(Self::get_peers_count))
-            .route("/testnet3/peers/all", get(Self::get_peers_all))
-            .route("/testnet3/peers/all/metrics", get(Self::get_peers_all_metrics))
-
+            Endpoint::get(
+                "/peers/count",
+                "get_peers_count",
+                "Returns the number of connected peers",
+                Self::get_peers_count,
+            )
+            .with_response::<usize>(),
+            Endpoint::get("/peers/all", "get_peers_all", "Returns the connected peers", Self::get_peers_all)
+                .with_response::<Vec<String>>(),
+            Endpoint::get(
+                "/peers/all/metrics",
+                "get_peers_all_metrics",
+                "Returns the metrics of the connected peers",
+                Self::get_peers_all_metrics,
+            )
+            .with_response::<Vec<schema::PeerMetrics>>(),
             // GET ../program/..
-            .route("/testnet3/program/:id", get(Self::get_program))
-            .route("/testnet3/program/:id/mappings", get(Self::get_mapping_names))
-            .route("/testnet3/program/:id/mapping/:name/:key", get(Self::get_mapping_value))
-
+            Endpoint::get("/program/:id", "get_program", "Returns the program for the given ID", Self::get_program)
+                .with_response::<schema::Program>(),
+            Endpoint::get(
+                "/program/:id/mappings",
+                "get_mapping_names",
+                "Returns the mapping names of the program",
+                Self::get_mapping_names,
+            )
+            .with_response::<Vec<schema::Identifier>>(),
+            Endpoint::get(
+                "/program/:id/mapping/:name",
+                "get_mapping_entries",
+                "Returns the entries of the mapping",
+                Self::get_mapping_entries,
+            )
+            .with_query::<PageQuery>()
+            .with_response::<Page<MappingEntry<N>>>(),
+            Endpoint::get(
+                "/program/:id/mapping/:name/:key",
+                "get_mapping_value",
+                "Returns the value of the given key in the mapping, at the latest or given height",
+                Self::get_mapping_value,
+            )
+            .with_query::<HeightQuery>()
+            .with_response::<Option<schema::Value>>(),
+            Endpoint::get("/programs", "get_programs", "Returns the deployed programs", Self::get_programs)
+                .with_query::<ProgramFilter<N>>()
+                .with_query::<PageQuery>()
+                .with_response::<Page<DeployedProgram<N>>>(),
+            // GET ../sync/..
+            Endpoint::get(
+                "/sync/progress",
+                "get_sync_progress",
+                "Returns the progress of the block sync",
+                Self::get_sync_progress,
+            )
+            .with_response::<schema::SyncProgress>(),
+            Endpoint::get(
+                "/sync/forks",
+                "get_sync_forks",
+                "Returns the forks between the ledger and the peers",
+                Self::get_sync_forks,
+            )
+            .with_response::<Vec<schema::Fork>>(),
+            Endpoint::get(
+                "/sync/locators",
+                "get_sync_locators",
+                "Returns the block locators of the ledger",
+                Self::get_sync_locators,
+            )
+            .with_response::<schema::BlockLocators>(),
             // GET misc endpoints.
-            .route("/testnet3/blocks", get(Self::get_blocks))
-            .route("/testnet3/height/:hash", get(Self::get_height))
-            .route("/testnet3/memoryPool/transmissions", get(Self::get_memory_pool_transmissions))
-            .route("/testnet3/memoryPool/solutions", get(Self::get_memory_pool_solutions))
-            .route("/testnet3/memoryPool/transactions", get(Self::get_memory_pool_transactions))
-            .route("/testnet3/statePath/:commitment", get(Self::get_state_path_for_commitment))
-            .route("/testnet3/stateRoot/latest", get(Self::get_state_root_latest))
-            .route("/testnet3/committee/latest", get(Self::get_committee_latest))
+            Endpoint::get("/blocks", "get_blocks", "Returns the blocks in the given height range", Self::get_blocks)
+                .with_query::<BlockRange>()
+                .with_response::<Vec<schema::Block>>()
+                .cached(),
+            Endpoint::get(
+                "/blocks/export",
+                "get_blocks_export",
+                "Streams the blocks in the given height range",
+                Self::get_blocks_export,
+            )
+            .with_query::<BlockRange>()
+            .with_query::<ExportQuery>()
+            .with_response_body::<schema::BlockStream>("application/octet-stream"),
+            Endpoint::get(
+                "/height/:hash",
+                "get_height",
+                "Returns the height of the block for the given hash",
+                Self::get_height,
+            )
+            .with_response::<u32>()
+            .cached(),
+            Endpoint::get(
+                "/fee/estimate",
+                "get_fee_estimate",
+                "Returns the suggested priority fees per byte, from the recent blocks and the memory pool",
+                Self::get_fee_estimate,
+            )
+            .with_response::<FeeEstimate>(),
+            Endpoint::get(
+                "/memoryPool/transmissions",
+                "get_memory_pool_transmissions",
+                "Returns the unconfirmed transmissions in the memory pool",
+                Self::get_memory_pool_transmissions,
+            )
+            .with_response::<schema::Transmissions>(),
+            Endpoint::get(
+                "/memoryPool/solutions",
+                "get_memory_pool_solutions",
+                "Returns the unconfirmed solutions in the memory pool",
+                Self::get_memory_pool_solutions,
+            )
+            .with_response::<schema::Solutions>(),
+            Endpoint::get(
+                "/memoryPool/transactions",
+                "get_memory_pool_transactions",
+                "Returns the unconfirmed transactions in the memory pool",
+                Self::get_memory_pool_transactions,
+            )
+            .with_response::<schema::UnconfirmedTransactions>(),
+            Endpoint::get(
+                "/statePath/:commitment",
+                "get_state_path_for_commitment",
+                "Returns the state path for the given commitment",
+                Self::get_state_path_for_commitment,
+            )
+            .with_response::<schema::StatePath>(),
+            Endpoint::get(
+                "/stateRoot/latest",
+                "get_state_root_latest",
+                "Returns the latest state root",
+                Self::get_state_root_latest,
+            )
+            .with_response::<schema::Field>(),
+            Endpoint::get(
+                "/committee/latest",
+                "get_committee_latest",
+                "Returns the latest committee, or the committee at the given height",
+                Self::get_committee_latest,
+            )
+            .with_query::<HeightQuery>()
+            .with_response::<schema::Committee>(),
+        ]
+    }
+
+    fn spawn_server(&mut self, rest_ip: SocketAddr, rate_limits: Option<RateLimits>) {
+        let cors = CorsLayer::new()
+            .allow_origin(Any)
+            .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+            .allow_headers([CONTENT_TYPE, AUTHORIZATION])
+            .expose_headers([
+                LINK,
+                HeaderName::from_static("deprecation"),
+                HeaderName::from_static("sunset"),
+                HeaderName::from_static(BLOCK_COUNT_HEADER),
+            ]);
+
+        // The name of the network in the paths (e.g. `testnet3`), and the prefix of the versioned routes.
+        let network = network_path(N::NAME);
+        let prefix = format!("/{API_VERSION}/{network}");
+
+        // Initialize the rate limiter, if enabled, and the cache of the immutable responses.
+        let rate_limiter = rate_limits.map(RateLimiter::new);
+        let response_cache = ResponseCache::default();
+
+        let router = {
+            let mut endpoints = Self::endpoints();
+            for endpoint in &mut endpoints {
+                // Cache the immutable responses.
+                if endpoint.is_cached {
+                    let cache_layer = middleware::from_fn_with_state(response_cache.clone(), cache_middleware);
+                    endpoint.handler = endpoint.handler.clone().route_layer(cache_layer);
+                }
+                // Limit the rate of the requests to the route group of the endpoint, if enabled.
+                // Note: The rate limit applies before the cache, so the cached responses count towards the quota.
+                if let Some(rate_limiter) = &rate_limiter {
+                    let state = (rate_limiter.clone(), RouteGroup::of(endpoint.path));
+                    let rate_limit_layer = middleware::from_fn_with_state(state, rate_limit_middleware);
+                    endpoint.handler = endpoint.handler.clone().route_layer(rate_limit_layer);
+                }
+            }
+
+            // Serve the OpenAPI document of the endpoints.
+            let document = Arc::new(openapi_document(&prefix, &endpoints));
+            let mut router = axum::Router::new()
+                .route(&format!("{prefix}/openapi.json"), get(move || async move { ErasedJson::pretty(&*document) }));
+
+            // Serve each endpoint under the API version, and (deprecated) without the API version.
+            for endpoint in &endpoints {
+                router = router
+                    .route(&format!("{prefix}{}", endpoint.path), endpoint.handler.clone())
+                    .route(&format!("/{network}{}", endpoint.path), deprecated(endpoint.handler.clone(), None));
+            }
+            // Serve the deprecated aliases of the endpoints.
+            for (path, successor) in DEPRECATED_ALIASES {
+                if let Some(endpoint) = endpoints.iter().find(|endpoint| endpoint.path == successor) {
+                    let successor = Some(format!("{prefix}{successor}"));
+                    router =
+                        router.route(&format!("/{network}{path}"), deprecated(endpoint.handler.clone(), successor));
+                }
+            }
 
+            router
             // Pass in `Rest` to make things convenient.
             .with_state(self.clone())
             // Enable tower-http tracing.
@@ -13,14 +13,23 @@
 // limitations under the License.
 
 use super::*;
-use snarkvm::prelude::{block::Transaction, Identifier, Plaintext};
+use snarkvm::prelude::{block::Transaction, coinbase::ProverSolution, Address, Identifier, Literal, Plaintext, Value};
 
+use std::str::FromStr;
+
+use axum::{body::StreamBody, response::IntoResponse};
 use indexmap::IndexMap;
 use rayon::prelude::*;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    Stream,
+    StreamExt,
+};
 
 /// The `get_blocks` query object.
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, JsonSchema)]
 pub(crate) struct BlockRange {
     /// The starting block height (inclusive).
     start: u32,
@@ -28,60 +37,152 @@ pub(crate) struct BlockRange {
     end: u32,
 }
 
-impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
-    // ----------------- DEPRECATED FUNCTIONS -----------------
-    // The functions below are associated with deprecated routes.
-    // Please use the recommended alternatives when implementing new features or refactoring.
+/// The `get_blocks_export` query object.
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub(crate) struct ExportQuery {
+    /// The encoding of the blocks.
+    #[serde(default)]
+    format: ExportFormat,
+}
 
-    // Deprecated: Use `get_block_height_latest` instead.
-    // GET /testnet3/latest/height
-    pub(crate) async fn latest_height(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_height())
-    }
+/// A page of the entries of the address index.
+#[derive(Serialize, JsonSchema)]
+pub(crate) struct AddressIndexPage<T: Serialize> {
+    /// The height of the latest indexed block, if any.
+    indexed_height: Option<u32>,
+    /// The page of entries.
+    #[serde(flatten)]
+    page: Page<T>,
+}
 
-    // Deprecated: Use `get_block_hash_latest` instead.
-    // GET /testnet3/latest/hash
-    pub(crate) async fn latest_hash(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_hash())
+/// An entry of a mapping.
+#[derive(Serialize, JsonSchema)]
+#[serde(bound = "")]
+#[schemars(rename = "MappingEntry")]
+pub(crate) struct MappingEntry<N: Network> {
+    /// The key.
+    #[schemars(with = "schema::Plaintext")]
+    key: Plaintext<N>,
+    /// The value.
+    #[schemars(with = "schema::Value")]
+    value: Value<N>,
+}
+
+/// The query object of the endpoints that serve the state at a past height.
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub(crate) struct HeightQuery {
+    /// The block height, or the latest height if not specified.
+    height: Option<u32>,
+}
+
+/// The `transaction_broadcast` query object.
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub(crate) struct BroadcastQuery {
+    /// If `true`, the transaction is checked before it is broadcast, and rejected if it is invalid.
+    #[serde(default)]
+    validate: bool,
+}
+
+/// The `get_bft_dag` query object.
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub(crate) struct RoundRange {
+    /// The starting round (inclusive), if specified.
+    start: Option<u64>,
+    /// The ending round (exclusive), if specified.
+    end: Option<u64>,
+}
+
+impl RoundRange {
+    /// Returns the range of rounds `[start_round, end_round)` to return, given the current round,
+    /// and the maximum number of rounds that are retained in storage.
+    fn resolve(&self, current_round: u64, max_gc_rounds: u64) -> Result<(u64, u64), RestError> {
+        // By default, return the rounds up to (and including) the current round.
+        let end_round = self.end.unwrap_or_else(|| current_round.saturating_add(1));
+        // By default, return as many rounds as are retained in storage.
+        let start_round = self.start.unwrap_or_else(|| end_round.saturating_sub(max_gc_rounds));
+
+        // Ensure the end round is greater than the start round.
+        if start_round > end_round {
+            return Err(RestError::BadRequest("Invalid round range".to_string()));
+        }
+
+        // Ensure the round range is bounded.
+        if end_round - start_round > max_gc_rounds {
+            return Err(RestError::BadRequest(format!(
+                "Cannot request more than {max_gc_rounds} rounds per call (requested {})",
+                end_round - start_round
+            )));
+        }
+        Ok((start_round, end_round))
     }
+}
 
-    // Deprecated: Use `get_block_latest` instead.
-    // GET /testnet3/latest/block
-    pub(crate) async fn latest_block(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_block())
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /v1/{network}/address/{address}/balance
+    pub(crate) async fn get_address_balance(
+        State(rest): State<Self>,
+        Path(address): Path<Address<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        let (credits, account) = (ProgramID::from_str("credits.aleo")?, Identifier::from_str("account")?);
+        let key = Plaintext::from(Literal::Address(address));
+        // Note: An address without a public balance has a balance of zero.
+        match rest.ledger.vm().finalize_store().get_value_confirmed(credits, account, &key)? {
+            Some(Value::Plaintext(Plaintext::Literal(Literal::U64(amount), _))) => Ok(ErasedJson::pretty(*amount)),
+            Some(..) => Err(RestError::InternalServerError(format!("Invalid public balance for {address}"))),
+            None => Ok(ErasedJson::pretty(0u64)),
+        }
     }
 
-    // Deprecated: Use `get_state_root_latest` instead.
-    // GET /testnet3/latest/stateRoot
-    pub(crate) async fn latest_state_root(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_state_root())
+    // GET /v1/{network}/address/{address}/transitions?cursor={cursor}&limit={limit}
+    pub(crate) async fn get_address_transitions(
+        State(rest): State<Self>,
+        Path(address): Path<Address<N>>,
+        Query(query): Query<PageQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        let address_index = rest.address_index()?;
+        let page = Page::from_cursors(address_index.transitions(&address, query.parse_cursor()?)?, &query)?;
+        Ok(ErasedJson::pretty(AddressIndexPage { indexed_height: address_index.indexed_height(), page }))
     }
 
-    // Deprecated: Use `get_committee_latest` instead.
-    // GET /testnet3/latest/committee
-    pub(crate) async fn latest_committee(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.latest_committee()?))
+    // GET /v1/{network}/address/{address}/deployments?cursor={cursor}&limit={limit}
+    pub(crate) async fn get_address_deployments(
+        State(rest): State<Self>,
+        Path(address): Path<Address<N>>,
+        Query(query): Query<PageQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        let address_index = rest.address_index()?;
+        let page = Page::from_cursors(address_index.deployments(&address, query.parse_cursor()?)?, &query)?;
+        Ok(ErasedJson::pretty(AddressIndexPage { indexed_height: address_index.indexed_height(), page }))
     }
 
-    // ---------------------------------------------------------
+    // GET /v1/{network}/address/{address}/fees?cursor={cursor}&limit={limit}
+    pub(crate) async fn get_address_fees(
+        State(rest): State<Self>,
+        Path(address): Path<Address<N>>,
+        Query(query): Query<PageQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        let address_index = rest.address_index()?;
+        let page = Page::from_cursors(address_index.fees(&address, query.parse_cursor()?)?, &query)?;
+        Ok(ErasedJson::pretty(AddressIndexPage { indexed_height: address_index.indexed_height(), page }))
+    }
 
-    // GET /testnet3/block/height/latest
+    // GET /v1/{network}/block/height/latest
     pub(crate) async fn get_block_height_latest(State(rest): State<Self>) -> ErasedJson {
         ErasedJson::pretty(rest.ledger.latest_height())
     }
 
-    // GET /testnet3/block/hash/latest
+    // GET /v1/{network}/block/hash/latest
     pub(crate) async fn get_block_hash_latest(State(rest): State<Self>) -> ErasedJson {
         ErasedJson::pretty(rest.ledger.latest_hash())
     }
 
-    // GET /testnet3/block/latest
+    // GET /v1/{network}/block/latest
     pub(crate) async fn get_block_latest(State(rest): State<Self>) -> ErasedJson {
         ErasedJson::pretty(rest.ledger.latest_block())
     }
 
-    // GET /testnet3/block/{height}
-    // GET /testnet3/block/{blockHash}
+    // GET /v1/{network}/block/{height}
+    // GET /v1/{network}/block/{blockHash}
     pub(crate) async fn get_block(
         State(rest): State<Self>,
         Path(height_or_hash): Path<String>,
@@ -89,19 +190,26 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         // Manually parse the height or the height or the hash, axum doesn't support different types
         // for the same path param.
         let block = if let Ok(height) = height_or_hash.parse::<u32>() {
+            // Ensure the block exists.
+            if height > rest.ledger.latest_height() {
+                return Err(RestError::NotFound(format!("Block {height} does not exist")));
+            }
             rest.ledger.get_block(height)?
         } else {
-            let hash = height_or_hash
-                .parse::<N::BlockHash>()
-                .map_err(|_| RestError("invalid input, it is neither a block height nor a block hash".to_string()))?;
-
+            let hash = height_or_hash.parse::<N::BlockHash>().map_err(|_| {
+                RestError::BadRequest("invalid input, it is neither a block height nor a block hash".to_string())
+            })?;
+            // Ensure the block exists.
+            if !rest.ledger.contains_block_hash(&hash)? {
+                return Err(RestError::NotFound(format!("Block '{hash}' does not exist")));
+            }
             rest.ledger.get_block_by_hash(&hash)?
         };
 
         Ok(ErasedJson::pretty(block))
     }
 
-    // GET /testnet3/blocks?start={start_height}&end={end_height}
+    // GET /v1/{network}/blocks?start={start_height}&end={end_height}
     pub(crate) async fn get_blocks(
         State(rest): State<Self>,
         Query(block_range): Query<BlockRange>,
@@ -113,17 +221,23 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
 
         // Ensure the end height is greater than the start height.
         if start_height > end_height {
-            return Err(RestError("Invalid block range".to_string()));
+            return Err(RestError::BadRequest("Invalid block range".to_string()));
         }
 
         // Ensure the block range is bounded.
         if end_height - start_height > MAX_BLOCK_RANGE {
-            return Err(RestError(format!(
+            return Err(RestError::BadRequest(format!(
                 "Cannot request more than {MAX_BLOCK_RANGE} blocks per call (requested {})",
                 end_height - start_height
             )));
         }
 
+        // Ensure the blocks exist.
+        let latest_height = rest.ledger.latest_height();
+        if end_height > latest_height.saturating_add(1) {
+            return Err(RestError::NotFound(format!("Blocks above {latest_height} do not exist")));
+        }
+
         let blocks = cfg_into_iter!((start_height..end_height))
             .map(|height| rest.ledger.get_block(height))
             .collect::<Result<Vec<_>, _>>()?;
@@ -131,159 +245,464 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         Ok(ErasedJson::pretty(blocks))
     }
 
-    // GET /testnet3/height/{blockHash}
+    // GET /v1/{network}/blocks/export?start={start_height}&end={end_height}&format={format}
+    pub(crate) async fn get_blocks_export(
+        State(rest): State<Self>,
+        Query(block_range): Query<BlockRange>,
+        Query(query): Query<ExportQuery>,
+    ) -> Result<Response, RestError> {
+        let start_height = block_range.start;
+        let end_height = block_range.end;
+
+        // Ensure the end height is greater than the start height.
+        if start_height > end_height {
+            return Err(RestError::BadRequest("Invalid block range".to_string()));
+        }
+
+        // Ensure the block range is bounded.
+        if end_height - start_height > MAX_EXPORT_BLOCKS {
+            return Err(RestError::BadRequest(format!(
+                "Cannot export more than {MAX_EXPORT_BLOCKS} blocks per call (requested {})",
+                end_height - start_height
+            )));
+        }
+
+        // Ensure the blocks exist.
+        let latest_height = rest.ledger.latest_height();
+        if end_height > latest_height.saturating_add(1) {
+            return Err(RestError::NotFound(format!("Blocks above {latest_height} do not exist")));
+        }
+
+        // Note: The body is streamed without a length, which is sent with the chunked transfer encoding.
+        let stream = export_blocks(rest.ledger.clone(), start_height..end_height, query.format);
+        let block_count = [(HeaderName::from_static(BLOCK_COUNT_HEADER), (end_height - start_height).to_string())];
+        Ok(([(CONTENT_TYPE, query.format.content_type())], block_count, StreamBody::new(stream)).into_response())
+    }
+
+    // GET /v1/{network}/height/{blockHash}
     pub(crate) async fn get_height(
         State(rest): State<Self>,
         Path(hash): Path<N::BlockHash>,
     ) -> Result<ErasedJson, RestError> {
+        // Ensure the block exists.
+        if !rest.ledger.contains_block_hash(&hash)? {
+            return Err(RestError::NotFound(format!("Block '{hash}' does not exist")));
+        }
         Ok(ErasedJson::pretty(rest.ledger.get_height(&hash)?))
     }
 
-    // GET /testnet3/block/{height}/transactions
+    // GET /v1/{network}/block/{height}/transactions
     pub(crate) async fn get_block_transactions(
         State(rest): State<Self>,
         Path(height): Path<u32>,
     ) -> Result<ErasedJson, RestError> {
+        // Ensure the block exists.
+        if height > rest.ledger.latest_height() {
+            return Err(RestError::NotFound(format!("Block {height} does not exist")));
+        }
         Ok(ErasedJson::pretty(rest.ledger.get_transactions(height)?))
     }
 
-    // GET /testnet3/transaction/{transactionID}
+    // GET /v1/{network}/transaction/{transactionID}
     pub(crate) async fn get_transaction(
         State(rest): State<Self>,
         Path(tx_id): Path<N::TransactionID>,
     ) -> Result<ErasedJson, RestError> {
+        // Ensure the transaction exists.
+        if !rest.ledger.contains_transaction_id(&tx_id)? {
+            return Err(RestError::NotFound(format!("Transaction '{tx_id}' does not exist")));
+        }
         Ok(ErasedJson::pretty(rest.ledger.get_transaction(tx_id)?))
     }
 
-    // GET /testnet3/memoryPool/transmissions
+    // GET /v1/{network}/fee/estimate
+    pub(crate) async fn get_fee_estimate(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        // Note: The estimate deserializes the recent blocks, which is why it runs in a blocking task.
+        let estimate = tokio::task::spawn_blocking(move || FeeEstimate::new(&rest.ledger, rest.consensus.as_ref()))
+            .await
+            .map_err(|error| RestError::InternalServerError(error.to_string()))??;
+        Ok(ErasedJson::pretty(estimate))
+    }
+
+    // GET /v1/{network}/memoryPool/transmissions
     pub(crate) async fn get_memory_pool_transmissions(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
         match rest.consensus {
             Some(consensus) => {
                 Ok(ErasedJson::pretty(consensus.unconfirmed_transmissions().collect::<IndexMap<_, _>>()))
             }
-            None => Err(RestError("Route isn't available for this node type".to_string())),
+            None => Err(RestError::unavailable_for_node_type()),
         }
     }
 
-    // GET /testnet3/memoryPool/solutions
+    // GET /v1/{network}/memoryPool/solutions
     pub(crate) async fn get_memory_pool_solutions(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
         match rest.consensus {
             Some(consensus) => Ok(ErasedJson::pretty(consensus.unconfirmed_solutions().collect::<IndexMap<_, _>>())),
-            None => Err(RestError("Route isn't available for this node type".to_string())),
+            None => Err(RestError::unavailable_for_node_type()),
         }
     }
 
-    // GET /testnet3/memoryPool/transactions
+    // GET /v1/{network}/memoryPool/transactions
     pub(crate) async fn get_memory_pool_transactions(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
         match rest.consensus {
             Some(consensus) => Ok(ErasedJson::pretty(consensus.unconfirmed_transactions().collect::<IndexMap<_, _>>())),
-            None => Err(RestError("Route isn't available for this node type".to_string())),
+            None => Err(RestError::unavailable_for_node_type()),
         }
     }
 
-    // GET /testnet3/program/{programID}
+    // GET /v1/{network}/program/{programID}
     pub(crate) async fn get_program(
         State(rest): State<Self>,
         Path(id): Path<ProgramID<N>>,
     ) -> Result<ErasedJson, RestError> {
+        // Ensure the program exists.
+        if !rest.ledger.vm().contains_program(&id) {
+            return Err(RestError::NotFound(format!("Program '{id}' does not exist")));
+        }
         Ok(ErasedJson::pretty(rest.ledger.get_program(id)?))
     }
 
-    // GET /testnet3/program/{programID}/mappings
+    // GET /v1/{network}/programs?import={programID}&prefix={prefix}&cursor={cursor}&limit={limit}
+    pub(crate) async fn get_programs(
+        State(rest): State<Self>,
+        Query(filter): Query<ProgramFilter<N>>,
+        Query(query): Query<PageQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        let after = query.parse_cursor::<ProgramID<N>>()?;
+        let ledger = rest.ledger.clone();
+        let page = tokio::task::spawn_blocking(move || {
+            Page::from_cursors(DeployedProgram::list(&ledger, &filter, after)?, &query)
+        })
+        .await
+        .map_err(|error| RestError::InternalServerError(error.to_string()))??;
+        Ok(ErasedJson::pretty(page))
+    }
+
+    // GET /v1/{network}/program/{programID}/mappings
     pub(crate) async fn get_mapping_names(
         State(rest): State<Self>,
         Path(id): Path<ProgramID<N>>,
     ) -> Result<ErasedJson, RestError> {
+        // Ensure the program exists.
+        if !rest.ledger.vm().contains_program(&id) {
+            return Err(RestError::NotFound(format!("Program '{id}' does not exist")));
+        }
         Ok(ErasedJson::pretty(rest.ledger.vm().finalize_store().get_mapping_names_confirmed(&id)?))
     }
 
-    // GET /testnet3/program/{programID}/mapping/{mappingName}/{mappingKey}
+    // GET /v1/{network}/program/{programID}/mapping/{mappingName}?cursor={cursor}&limit={limit}
+    pub(crate) async fn get_mapping_entries(
+        State(rest): State<Self>,
+        Path((id, name)): Path<(ProgramID<N>, Identifier<N>)>,
+        Query(query): Query<PageQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        // Ensure the mapping exists.
+        if !rest.ledger.vm().finalize_store().contains_mapping_confirmed(&id, &name)? {
+            return Err(RestError::NotFound(format!("Mapping '{id}/{name}' does not exist")));
+        }
+        // Read the entries of the mapping, after the key of the cursor.
+        // Note: The finalize store only reads a mapping as a whole, so the page is selected from the entries,
+        // and each entry is paired with its key, which is the cursor to resume after it.
+        let after = query.parse_cursor::<Plaintext<N>>()?;
+        let ledger = rest.ledger.clone();
+        let page = tokio::task::spawn_blocking(move || {
+            let finalize_store = ledger.vm().finalize_store();
+            // Ensure the cursor is a key of the mapping, before the mapping is read.
+            // Note: A key that was removed since the previous page is rejected, instead of ending the listing early.
+            let unknown_cursor = |after: &Plaintext<N>| {
+                RestError::BadRequest(format!("Cursor '{after}' is not a key of the mapping '{id}/{name}'"))
+            };
+            if let Some(after) = &after {
+                if finalize_store.get_value_confirmed(id, name, after)?.is_none() {
+                    return Err(unknown_cursor(after));
+                }
+            }
+            let mut entries = finalize_store.get_mapping_confirmed(id, name)?;
+            // Skip the entries up to (and including) the key of the cursor.
+            if let Some(after) = &after {
+                match entries.iter().position(|(key, _)| key == after) {
+                    Some(index) => entries = entries.split_off(index + 1),
+                    None => return Err(unknown_cursor(after)),
+                }
+            }
+            let entries = entries.into_iter().map(|(key, value)| Ok((key.clone(), MappingEntry { key, value })));
+            Page::from_cursors(entries, &query)
+        })
+        .await
+        .map_err(|error| RestError::InternalServerError(error.to_string()))??;
+        Ok(ErasedJson::pretty(page))
+    }
+
+    // GET /v1/{network}/program/{programID}/mapping/{mappingName}/{mappingKey}?height={height}
     pub(crate) async fn get_mapping_value(
         State(rest): State<Self>,
         Path((id, name, key)): Path<(ProgramID<N>, Identifier<N>, Plaintext<N>)>,
+        Query(query): Query<HeightQuery>,
     ) -> Result<ErasedJson, RestError> {
+        // Ensure the mapping exists.
+        if !rest.ledger.vm().finalize_store().contains_mapping_confirmed(&id, &name)? {
+            return Err(RestError::NotFound(format!("Mapping '{id}/{name}' does not exist")));
+        }
+        // Note: The finalize store only holds the mapping values at the latest height.
+        if let Some(height) = query.height.filter(|height| *height != rest.ledger.latest_height()) {
+            if height > rest.ledger.latest_height() {
+                return Err(RestError::NotFound(format!("Block {height} does not exist")));
+            }
+            return match &rest.mapping_archive {
+                Some(mapping_archive) => Ok(ErasedJson::pretty(mapping_archive.get_value(id, name, &key, height)?)),
+                None => Err(RestError::Pruned(format!(
+                    "The state at block {height} was pruned, as this node does not archive the mapping values ('--archive')"
+                ))),
+            };
+        }
+        // Note: A key that is not in the mapping returns `null`.
         Ok(ErasedJson::pretty(rest.ledger.vm().finalize_store().get_value_confirmed(id, name, &key)?))
     }
 
-    // GET /testnet3/statePath/{commitment}
+    // GET /v1/{network}/statePath/{commitment}
     pub(crate) async fn get_state_path_for_commitment(
         State(rest): State<Self>,
         Path(commitment): Path<Field<N>>,
     ) -> Result<ErasedJson, RestError> {
+        // Ensure the commitment exists.
+        if !rest.ledger.contains_commitment(&commitment)? {
+            return Err(RestError::NotFound(format!("Commitment '{commitment}' does not exist")));
+        }
         Ok(ErasedJson::pretty(rest.ledger.get_state_path_for_commitment(&commitment)?))
     }
 
-    // GET /testnet3/stateRoot/latest
+    // GET /v1/{network}/stateRoot/latest
     pub(crate) async fn get_state_root_latest(State(rest): State<Self>) -> ErasedJson {
         ErasedJson::pretty(rest.ledger.latest_state_root())
     }
 
-    // GET /testnet3/committee/latest
-    pub(crate) async fn get_committee_latest(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.latest_committee()?))
+    // GET /v1/{network}/committee/latest?height={height}
+    pub(crate) async fn get_committee_latest(
+        State(rest): State<Self>,
+        Query(query): Query<HeightQuery>,
+    ) -> Result<ErasedJson, RestError> {
+        let Some(height) = query.height else {
+            return Ok(ErasedJson::pretty(rest.ledger.latest_committee()?));
+        };
+        if height > rest.ledger.latest_height() {
+            return Err(RestError::NotFound(format!("Block {height} does not exist")));
+        }
+        // Note: The ledger retains the committee of every round, so this does not require the archive.
+        let round = rest.ledger.get_header(height)?.round();
+        match rest.ledger.get_committee_for_round(round)? {
+            Some(committee) => Ok(ErasedJson::pretty(committee)),
+            None => Err(RestError::NotFound(format!("The committee for block {height} does not exist"))),
+        }
     }
 
-    // GET /testnet3/peers/count
+    // GET /v1/{network}/peers/count
     pub(crate) async fn get_peers_count(State(rest): State<Self>) -> ErasedJson {
         ErasedJson::pretty(rest.routing.router().number_of_connected_peers())
     }
 
-    // GET /testnet3/peers/all
+    // GET /v1/{network}/peers/all
     pub(crate) async fn get_peers_all(State(rest): State<Self>) -> ErasedJson {
         ErasedJson::pretty(rest.routing.router().connected_peers())
     }
 
-    // GET /testnet3/peers/all/metrics
+    // GET /v1/{network}/peers/all/metrics
     pub(crate) async fn get_peers_all_metrics(State(rest): State<Self>) -> ErasedJson {
         ErasedJson::pretty(rest.routing.router().connected_metrics())
     }
 
-    // GET /testnet3/node/address
+    // GET /v1/{network}/sync/progress
+    pub(crate) async fn get_sync_progress(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.sync.progress())
+    }
+
+    // GET /v1/{network}/sync/forks
+    pub(crate) async fn get_sync_forks(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.sync.forks())
+    }
+
+    // GET /v1/{network}/sync/locators
+    pub(crate) async fn get_sync_locators(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.sync.get_block_locators()?))
+    }
+
+    // GET /v1/{network}/node/address
     pub(crate) async fn get_node_address(State(rest): State<Self>) -> ErasedJson {
         ErasedJson::pretty(rest.routing.router().address())
     }
 
-    // GET /testnet3/find/blockHash/{transactionID}
+    // POST /v1/{network}/node/logFilter
+    pub(crate) async fn set_log_filter(directives: String) -> Result<ErasedJson, RestError> {
+        // Update the log filter directives, which take precedence over the verbosity of the node.
+        let log_filter = update_log_filter(&directives).map_err(|error| RestError::BadRequest(error.to_string()))?;
+        info!("Updated the log filter to '{log_filter}'");
+        Ok(ErasedJson::pretty(log_filter))
+    }
+
+    // POST /v1/{network}/node/otlp
+    pub(crate) async fn set_otlp(endpoint: String) -> Result<ErasedJson, RestError> {
+        // Enable the export to the given endpoint, or disable the export if the endpoint is empty.
+        // Note: The previous exporter is flushed on a separate thread, so this does not block.
+        let endpoint =
+            update_otlp_endpoint(Some(&endpoint)).map_err(|error| RestError::BadRequest(error.to_string()))?;
+        match &endpoint {
+            Some(endpoint) => info!("Enabled the OTLP export to '{endpoint}'"),
+            None => info!("Disabled the OTLP export"),
+        }
+        Ok(ErasedJson::pretty(endpoint))
+    }
+
+    // GET /v1/{network}/bft/dag
+    // GET /v1/{network}/bft/dag?start={start_round}&end={end_round}
+    pub(crate) async fn get_bft_dag(
+        State(rest): State<Self>,
+        Query(round_range): Query<RoundRange>,
+    ) -> Result<ErasedJson, RestError> {
+        let Some(consensus) = rest.consensus else {
+            return Err(RestError::unavailable_for_node_type());
+        };
+
+        let bft = consensus.bft();
+        let (start_round, end_round) =
+            round_range.resolve(bft.primary().current_round(), bft.storage().max_gc_rounds())?;
+
+        Ok(ErasedJson::pretty(serde_json::json!({
+            "current_round": bft.primary().current_round(),
+            "last_committed_round": bft.last_committed_round(),
+            "leader": bft.leader(),
+            "rounds": bft.dag_rounds(start_round, end_round),
+        })))
+    }
+
+    // GET /v1/{network}/bft/dag/events
+    pub(crate) async fn get_bft_dag_events(
+        State(rest): State<Self>,
+    ) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, RestError> {
+        let Some(consensus) = rest.consensus else {
+            return Err(RestError::unavailable_for_node_type());
+        };
+
+        // Subscribe to the DAG events, and forward them as server-sent events.
+        let events = BroadcastStream::new(consensus.bft().subscribe_dag_events()).map(|event| match event {
+            Ok(event) => Event::default().event("dag").json_data(event),
+            // Notify the subscriber that it missed events, so it can refetch the DAG.
+            Err(BroadcastStreamRecvError::Lagged(num_missed)) => {
+                Ok(Event::default().event("lagged").data(num_missed.to_string()))
+            }
+        });
+
+        Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+    }
+
+    // POST /v1/{network}/bft/proposing/pause
+    pub(crate) async fn pause_bft_proposing(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        match rest.consensus {
+            Some(consensus) => {
+                // Stop proposing new batches, while continuing to sign the batches of other validators.
+                consensus.bft().primary().pause_proposing();
+                Ok(ErasedJson::pretty(consensus.bft().primary().is_proposing_paused()))
+            }
+            None => Err(RestError::unavailable_for_node_type()),
+        }
+    }
+
+    // POST /v1/{network}/bft/proposing/resume
+    pub(crate) async fn resume_bft_proposing(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        match rest.consensus {
+            Some(consensus) => {
+                consensus.bft().primary().resume_proposing();
+                Ok(ErasedJson::pretty(consensus.bft().primary().is_proposing_paused()))
+            }
+            None => Err(RestError::unavailable_for_node_type()),
+        }
+    }
+
+    // GET /v1/{network}/find/blockHash/{transactionID}
     pub(crate) async fn find_block_hash(
         State(rest): State<Self>,
         Path(tx_id): Path<N::TransactionID>,
     ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.find_block_hash(&tx_id)?))
+        match rest.ledger.find_block_hash(&tx_id)? {
+            Some(block_hash) => Ok(ErasedJson::pretty(block_hash)),
+            None => Err(RestError::NotFound(format!("Transaction '{tx_id}' does not exist"))),
+        }
     }
 
-    // GET /testnet3/find/transactionID/deployment/{programID}
+    // GET /v1/{network}/find/transactionID/deployment/{programID}
     pub(crate) async fn find_transaction_id_from_program_id(
         State(rest): State<Self>,
         Path(program_id): Path<ProgramID<N>>,
     ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.find_transaction_id_from_program_id(&program_id)?))
+        match rest.ledger.find_transaction_id_from_program_id(&program_id)? {
+            Some(tx_id) => Ok(ErasedJson::pretty(tx_id)),
+            None => Err(RestError::NotFound(format!("Deployment of '{program_id}' does not exist"))),
+        }
     }
 
-    // GET /testnet3/find/transactionID/{transitionID}
+    // GET /v1/{network}/find/transactionID/{transitionID}
     pub(crate) async fn find_transaction_id_from_transition_id(
         State(rest): State<Self>,
         Path(transition_id): Path<N::TransitionID>,
     ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.find_transaction_id_from_transition_id(&transition_id)?))
+        match rest.ledger.find_transaction_id_from_transition_id(&transition_id)? {
+            Some(tx_id) => Ok(ErasedJson::pretty(tx_id)),
+            None => Err(RestError::NotFound(format!("Transition '{transition_id}' does not exist"))),
+        }
     }
 
-    // GET /testnet3/find/transitionID/{inputOrOutputID}
+    // GET /v1/{network}/find/transitionID/{inputOrOutputID}
     pub(crate) async fn find_transition_id(
         State(rest): State<Self>,
         Path(input_or_output_id): Path<Field<N>>,
     ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.find_transition_id(&input_or_output_id)?))
+        // Note: The ledger fails to find the transition ID if the input or output ID does not exist.
+        let transition_id = rest
+            .ledger
+            .find_transition_id(&input_or_output_id)
+            .map_err(|_| RestError::NotFound(format!("Input or output '{input_or_output_id}' does not exist")))?;
+        Ok(ErasedJson::pretty(transition_id))
     }
 
-    // POST /testnet3/transaction/broadcast
+    // POST /v1/{network}/transaction/validate
+    pub(crate) async fn transaction_validate(
+        State(rest): State<Self>,
+        Json(tx): Json<Transaction<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.validate_transaction(tx).await?))
+    }
+
+    // POST /v1/{network}/transaction/broadcast
+    // POST /v1/{network}/transaction/broadcast?validate=true
     pub(crate) async fn transaction_broadcast(
         State(rest): State<Self>,
+        Query(query): Query<BroadcastQuery>,
         Json(tx): Json<Transaction<N>>,
     ) -> Result<ErasedJson, RestError> {
+        // If requested, ensure the transaction is valid before it is broadcast.
+        if query.validate {
+            rest.validate_transaction(tx.clone()).await?.ensure_is_valid()?;
+        }
+        // Ensure the transaction is not a fee transaction.
+        if tx.is_fee() {
+            return Err(RestError::UnprocessableEntity("Cannot broadcast a fee transaction".to_string()));
+        }
+        // Ensure the transaction does not exist in the ledger.
+        if rest.ledger.contains_transaction_id(&tx.id())? {
+            return Err(RestError::Conflict(format!("Transaction '{}' exists in the ledger", tx.id())));
+        }
+
         // If the consensus module is enabled, add the unconfirmed transaction to the memory pool.
         if let Some(consensus) = rest.consensus {
             // Add the unconfirmed transaction to the memory pool.
-            consensus.add_unconfirmed_transaction(tx.clone()).await?;
+            if let Err(error) = consensus.add_unconfirmed_transaction(tx.clone()).await {
+                // A transaction that exists in the ledger or the memory pool is a conflict, and any other failure
+                // (e.g. of the storage) is an internal error.
+                let is_duplicate = rest.ledger.contains_transaction_id(&tx.id())?
+                    || consensus.contains_unconfirmed_transaction(&tx.id());
+                return Err(match is_duplicate {
+                    true => RestError::Conflict(error.to_string()),
+                    false => RestError::InternalServerError(error.to_string()),
+                });
+            }
         }
 
         // Prepare the unconfirmed transaction message.
@@ -298,4 +717,91 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
 
         Ok(ErasedJson::pretty(tx_id))
     }
+
+    // GET /v1/{network}/puzzle/latest
+    pub(crate) async fn get_puzzle_latest(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(PuzzleState::latest(&rest.ledger)?))
+    }
+
+    // POST /v1/{network}/solution/broadcast
+    pub(crate) async fn solution_broadcast(
+        State(rest): State<Self>,
+        Json(solution): Json<ProverSolution<N>>,
+    ) -> Result<ErasedJson, RestError> {
+        let solution_id = solution.commitment();
+        // Ensure the solution is new, and valid for the latest epoch challenge and proof target.
+        let epoch_challenge = rest.ledger.latest_epoch_challenge()?;
+        let proof_target = rest.ledger.latest_header().proof_target();
+        let (ledger, solution_) = (rest.ledger.clone(), solution.clone());
+        tokio::task::spawn_blocking(move || check_solution(&ledger, &epoch_challenge, proof_target, &solution_))
+            .await
+            .map_err(|error| RestError::InternalServerError(error.to_string()))??;
+
+        // If the consensus module is enabled, add the unconfirmed solution to the memory pool.
+        if let Some(consensus) = rest.consensus {
+            if let Err(error) = consensus.add_unconfirmed_solution(solution.clone()).await {
+                // A solution that exists in the ledger or the memory pool is a conflict, and any other failure
+                // (e.g. of the storage) is an internal error.
+                let is_duplicate = rest.ledger.contains_puzzle_commitment(&solution_id)?
+                    || consensus.contains_unconfirmed_solution(&solution_id);
+                return Err(match is_duplicate {
+                    true => RestError::Conflict(error.to_string()),
+                    false => RestError::InternalServerError(error.to_string()),
+                });
+            }
+        }
+
+        // Prepare the unconfirmed solution message.
+        let message =
+            Message::UnconfirmedSolution(UnconfirmedSolution { solution_id, solution: Data::Object(solution) });
+
+        // Broadcast the solution.
+        rest.routing.propagate(message, &[]);
+
+        Ok(ErasedJson::pretty(solution_id))
+    }
+
+    /// Returns the address index, if it is enabled.
+    fn address_index(&self) -> Result<&AddressIndex<N>, RestError> {
+        self.address_index.as_ref().ok_or_else(|| {
+            RestError::ServiceUnavailable(
+                "The address index is not enabled on this node ('--address-index')".to_string(),
+            )
+        })
+    }
+
+    /// Returns the verdict of the checks of the given transaction, against the ledger and the memory pool.
+    async fn validate_transaction(&self, tx: Transaction<N>) -> Result<TransactionVerdict<N>, RestError> {
+        let ledger = self.ledger.clone();
+        let consensus = self.consensus.clone();
+        // Note: The checks verify the proofs of the transaction, which is why they run in a blocking task.
+        tokio::task::spawn_blocking(move || {
+            let memory_pool = consensus.as_ref().map(|consensus| consensus as &dyn MemoryPool<N>);
+            TransactionVerdict::new(&ledger, memory_pool, &tx)
+        })
+        .await
+        .map_err(|error| RestError::InternalServerError(error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_range() {
+        let range = |start, end| RoundRange { start, end };
+
+        // Ensure the range defaults to the rounds that are retained in storage, up to the current round.
+        assert_eq!(range(None, None).resolve(100, 50).unwrap(), (51, 101));
+        assert_eq!(range(None, None).resolve(10, 50).unwrap(), (0, 11));
+        assert_eq!(range(Some(20), None).resolve(60, 50).unwrap(), (20, 61));
+        assert_eq!(range(None, Some(30)).resolve(100, 50).unwrap(), (0, 30));
+        assert_eq!(range(Some(5), Some(5)).resolve(100, 50).unwrap(), (5, 5));
+
+        // Ensure the invalid and unbounded ranges are rejected.
+        assert!(matches!(range(Some(6), Some(5)).resolve(100, 50), Err(RestError::BadRequest(_))));
+        assert!(matches!(range(Some(0), Some(51)).resolve(100, 50), Err(RestError::BadRequest(_))));
+        assert!(matches!(range(Some(0), None).resolve(100, 50), Err(RestError::BadRequest(_))));
+    }
 }
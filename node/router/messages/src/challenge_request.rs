@@ -25,6 +25,12 @@ pub struct ChallengeRequest<N: Network> {
     pub node_type: NodeType,
     pub address: Address<N>,
     pub nonce: u64,
+    /// The minimum protocol version the node remains compatible with.
+    pub min_version: u32,
+    /// The optional protocol features supported by the node.
+    pub capabilities: Capabilities,
+    /// The address the node advertises for inbound connections.
+    pub listener_address: ListenerAddress,
 }
 
 impl<N: Network> MessageTrait for ChallengeRequest<N> {
@@ -42,6 +48,9 @@ impl<N: Network> ToBytes for ChallengeRequest<N> {
         self.node_type.write_le(&mut writer)?;
         self.address.write_le(&mut writer)?;
         self.nonce.write_le(&mut writer)?;
+        self.min_version.write_le(&mut writer)?;
+        self.capabilities.write_le(&mut writer)?;
+        self.listener_address.write_le(&mut writer)?;
         Ok(())
     }
 }
@@ -53,27 +62,48 @@ impl<N: Network> FromBytes for ChallengeRequest<N> {
         let node_type = NodeType::read_le(&mut reader)?;
         let address = Address::<N>::read_le(&mut reader)?;
         let nonce = u64::read_le(&mut reader)?;
-
-        Ok(Self { version, listener_port, node_type, address, nonce })
+        // Note: Older nodes do not send the version range and capabilities, in which case
+        // the node only supports its own version, and no optional features.
+        let min_version = read_optional(&mut reader, u32::read_le)?.unwrap_or(version);
+        let capabilities = read_optional(&mut reader, Capabilities::read_le)?.unwrap_or_default();
+        // Note: Older nodes do not send the listener address, in which case they listen on the listener port.
+        let listener_address = read_optional(&mut reader, ListenerAddress::read_le)?.unwrap_or_default();
+
+        Ok(Self { version, listener_port, node_type, address, nonce, min_version, capabilities, listener_address })
     }
 }
 
 impl<N: Network> ChallengeRequest<N> {
     pub fn new(listener_port: u16, node_type: NodeType, address: Address<N>, nonce: u64) -> Self {
-        Self { version: Message::<N>::VERSION, listener_port, node_type, address, nonce }
+        Self {
+            version: Message::<N>::VERSION,
+            listener_port,
+            node_type,
+            address,
+            nonce,
+            min_version: Message::<N>::MIN_VERSION,
+            capabilities: Capabilities::SUPPORTED,
+            listener_address: ListenerAddress::Port,
+        }
+    }
+
+    /// Sets the address the node advertises for inbound connections.
+    pub fn with_listener_address(mut self, listener_address: ListenerAddress) -> Self {
+        self.listener_address = listener_address;
+        self
     }
 }
 
 #[cfg(test)]
 pub mod prop_tests {
-    use crate::{ChallengeRequest, NodeType};
+    use crate::{Capabilities, ChallengeRequest, ListenerAddress, NodeType};
     use snarkvm::{
         console::prelude::{FromBytes, ToBytes},
         prelude::{Address, TestRng, Uniform},
     };
 
     use bytes::{Buf, BufMut, BytesMut};
-    use proptest::prelude::{any, BoxedStrategy, Strategy};
+    use proptest::prelude::{any, prop_oneof, BoxedStrategy, Just, Strategy};
     use test_strategy::proptest;
 
     type CurrentNetwork = snarkvm::prelude::Testnet3;
@@ -93,15 +123,40 @@ pub mod prop_tests {
             .boxed()
     }
 
+    pub fn any_listener_address() -> BoxedStrategy<ListenerAddress> {
+        prop_oneof![
+            Just(ListenerAddress::Port),
+            ("[a-z2-7]{56}\\.onion", any::<u16>()).prop_map(|(host, port)| ListenerAddress::Host(host, port)),
+            Just(ListenerAddress::Unreachable),
+        ]
+        .boxed()
+    }
+
     pub fn any_challenge_request() -> BoxedStrategy<ChallengeRequest<CurrentNetwork>> {
-        (any_valid_address(), any::<u64>(), any::<u32>(), any::<u16>(), any_node_type())
-            .prop_map(|(address, nonce, version, listener_port, node_type)| ChallengeRequest {
-                address,
-                nonce,
-                version,
-                listener_port,
-                node_type,
-            })
+        (
+            any_valid_address(),
+            any::<u64>(),
+            any::<u32>(),
+            any::<u16>(),
+            any_node_type(),
+            any::<u32>(),
+            any::<u64>(),
+            any_listener_address(),
+        )
+            .prop_map(
+                |(address, nonce, version, listener_port, node_type, min_version, capabilities, listener_address)| {
+                    ChallengeRequest {
+                        address,
+                        nonce,
+                        version,
+                        listener_port,
+                        node_type,
+                        min_version,
+                        capabilities: Capabilities::from_bits(capabilities),
+                        listener_address,
+                    }
+                },
+            )
             .boxed()
     }
 
@@ -114,4 +169,16 @@ pub mod prop_tests {
             ChallengeRequest::read_le(buf.into_inner().reader()).unwrap();
         assert_eq!(original, deserialized);
     }
+
+    #[proptest]
+    fn challenge_request_legacy(#[strategy(any_challenge_request())] original: ChallengeRequest<CurrentNetwork>) {
+        // Serialize the request without the version range, capabilities, and listener address, as an older node would.
+        let mut bytes = original.clone().with_listener_address(ListenerAddress::Port).to_bytes_le().unwrap();
+        bytes.truncate(bytes.len() - 13);
+
+        let deserialized = ChallengeRequest::<CurrentNetwork>::read_le(&bytes[..]).unwrap();
+        assert_eq!(deserialized.min_version, original.version);
+        assert_eq!(deserialized.capabilities, Capabilities::empty());
+        assert_eq!(deserialized.listener_address, ListenerAddress::Port);
+    }
 }
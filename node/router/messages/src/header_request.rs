@@ -0,0 +1,73 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use snarkvm::prelude::{FromBytes, ToBytes};
+
+use std::borrow::Cow;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HeaderRequest {
+    /// The starting block height (inclusive).
+    pub start_height: u32,
+    /// The ending block height (exclusive).
+    pub end_height: u32,
+}
+
+impl MessageTrait for HeaderRequest {
+    /// Returns the message name.
+    #[inline]
+    fn name(&self) -> Cow<'static, str> {
+        format!("HeaderRequest {}..{}", self.start_height, self.end_height).into()
+    }
+}
+
+impl ToBytes for HeaderRequest {
+    fn write_le<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        self.start_height.write_le(&mut writer)?;
+        self.end_height.write_le(&mut writer)?;
+        Ok(())
+    }
+}
+
+impl FromBytes for HeaderRequest {
+    fn read_le<R: io::Read>(mut reader: R) -> io::Result<Self> {
+        let start_height = u32::read_le(&mut reader)?;
+        let end_height = u32::read_le(&mut reader)?;
+        Ok(Self { start_height, end_height })
+    }
+}
+
+#[cfg(test)]
+pub mod prop_tests {
+    use crate::HeaderRequest;
+    use snarkvm::utilities::{FromBytes, ToBytes};
+
+    use bytes::{Buf, BufMut, BytesMut};
+    use proptest::prelude::{any, BoxedStrategy, Strategy};
+    use test_strategy::proptest;
+
+    pub fn any_header_request() -> BoxedStrategy<HeaderRequest> {
+        any::<(u32, u32)>().prop_map(|(start_height, end_height)| HeaderRequest { start_height, end_height }).boxed()
+    }
+
+    #[proptest]
+    fn header_request_roundtrip(#[strategy(any_header_request())] header_request: HeaderRequest) {
+        let mut bytes = BytesMut::default().writer();
+        header_request.write_le(&mut bytes).unwrap();
+        let decoded = HeaderRequest::read_le(&mut bytes.into_inner().reader()).unwrap();
+        assert_eq![decoded, header_request];
+    }
+}
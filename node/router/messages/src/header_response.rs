@@ -0,0 +1,171 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use snarkos_node_sync_locators::SyncHeader;
+use snarkvm::{
+    ledger::narwhal::Data,
+    prelude::{FromBytes, ToBytes},
+};
+
+use anyhow::{bail, ensure, Result};
+use std::borrow::Cow;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeaderResponse<N: Network> {
+    /// The original header request.
+    pub request: HeaderRequest,
+    /// The headers.
+    pub headers: Data<DataHeaders<N>>,
+}
+
+impl<N: Network> MessageTrait for HeaderResponse<N> {
+    /// Returns the message name.
+    #[inline]
+    fn name(&self) -> Cow<'static, str> {
+        format!("HeaderResponse {}..{}", self.request.start_height, self.request.end_height).into()
+    }
+}
+
+impl<N: Network> ToBytes for HeaderResponse<N> {
+    fn write_le<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        self.request.write_le(&mut writer)?;
+        self.headers.write_le(writer)
+    }
+}
+
+impl<N: Network> FromBytes for HeaderResponse<N> {
+    fn read_le<R: io::Read>(mut reader: R) -> io::Result<Self> {
+        let request = HeaderRequest::read_le(&mut reader)?;
+        let headers = Data::read_le(reader)?;
+        Ok(Self { request, headers })
+    }
+}
+
+/// A wrapper for a list of block headers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataHeaders<N: Network>(pub Vec<SyncHeader<N>>);
+
+impl<N: Network> DataHeaders<N> {
+    /// The maximum number of headers that can be sent in a single message.
+    pub const MAXIMUM_NUMBER_OF_HEADERS: u32 = 100;
+
+    /// Ensures that the headers are well-formed in a header response.
+    /// Note: The response may end before the requested end height, if the peer does not have the remaining blocks.
+    pub fn ensure_response_is_well_formed(
+        &self,
+        peer_ip: SocketAddr,
+        start_height: u32,
+        end_height: u32,
+    ) -> Result<()> {
+        // Ensure the headers are not empty.
+        ensure!(!self.0.is_empty(), "Peer '{peer_ip}' sent an empty header response ({start_height}..{end_height})");
+        // Check that the headers are sequentially ordered, from the start height.
+        for (height, header) in (start_height..end_height).zip(self.0.iter()) {
+            if header.height() != height {
+                bail!("Peer '{peer_ip}' sent an invalid header response (headers do not match the header request)")
+            }
+        }
+        // Check that the headers do not exceed the header request.
+        if self.0.len() > (start_height..end_height).len() {
+            bail!("Peer '{peer_ip}' sent an invalid header response (headers exceed the header request)")
+        }
+        Ok(())
+    }
+}
+
+impl<N: Network> std::ops::Deref for DataHeaders<N> {
+    type Target = Vec<SyncHeader<N>>;
+
+    /// Returns the list of headers.
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<N: Network> ToBytes for DataHeaders<N> {
+    /// Writes the headers to the given writer.
+    fn write_le<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        // Ensure that the number of headers is within the allowed range.
+        if self.0.len() > Self::MAXIMUM_NUMBER_OF_HEADERS as usize {
+            return Err(error("Header response exceeds maximum number of headers"));
+        }
+        // Write the number of headers.
+        (self.0.len() as u32).write_le(&mut writer)?;
+        // Write the headers.
+        self.0.iter().try_for_each(|header| header.write_le(&mut writer))
+    }
+}
+
+impl<N: Network> FromBytes for DataHeaders<N> {
+    /// Reads the headers from the given reader.
+    fn read_le<R: io::Read>(mut reader: R) -> io::Result<Self> {
+        // Read the number of headers.
+        let num_headers = u32::read_le(&mut reader)?;
+        // Ensure that the number of headers is within the allowed range.
+        if num_headers > Self::MAXIMUM_NUMBER_OF_HEADERS {
+            return Err(error("Header response exceeds maximum number of headers"));
+        }
+        // Read the headers.
+        let headers = (0..num_headers).map(|_| SyncHeader::read_le(&mut reader)).collect::<io::Result<Vec<_>>>()?;
+        Ok(Self(headers))
+    }
+}
+
+#[cfg(test)]
+pub mod prop_tests {
+    use crate::{
+        block_response::prop_tests::any_block,
+        header_request::prop_tests::any_header_request,
+        DataHeaders,
+        HeaderResponse,
+    };
+    use snarkos_node_sync_locators::SyncHeader;
+    use snarkvm::{
+        prelude::narwhal::Data,
+        utilities::{FromBytes, ToBytes},
+    };
+
+    use bytes::{Buf, BufMut, BytesMut};
+    use proptest::{
+        collection::vec,
+        prelude::{BoxedStrategy, Strategy},
+    };
+    use test_strategy::proptest;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    pub fn any_data_headers() -> BoxedStrategy<DataHeaders<CurrentNetwork>> {
+        vec(any_block(), 0..=2).prop_map(|blocks| DataHeaders(blocks.iter().map(SyncHeader::from).collect())).boxed()
+    }
+
+    pub fn any_header_response() -> BoxedStrategy<HeaderResponse<CurrentNetwork>> {
+        (any_header_request(), any_data_headers())
+            .prop_map(|(request, data_headers)| HeaderResponse { request, headers: Data::Object(data_headers) })
+            .boxed()
+    }
+
+    #[proptest]
+    fn header_response_roundtrip(#[strategy(any_header_response())] header_response: HeaderResponse<CurrentNetwork>) {
+        let mut bytes = BytesMut::default().writer();
+        header_response.write_le(&mut bytes).unwrap();
+        let decoded = HeaderResponse::<CurrentNetwork>::read_le(&mut bytes.into_inner().reader()).unwrap();
+        assert_eq!(header_response.request, decoded.request);
+        assert_eq!(
+            header_response.headers.deserialize_blocking().unwrap(),
+            decoded.headers.deserialize_blocking().unwrap(),
+        );
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{FromBytes, ToBytes};
+
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// The set of optional protocol features advertised by a node during the handshake.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub struct Capabilities(u64);
+
+impl Capabilities {
+    /// The node supports compressed message payloads.
+    pub const COMPRESSION: Self = Self(1 << 0);
+    /// The node supports header-first block sync.
+    pub const HEADER_SYNC: Self = Self(1 << 2);
+    /// The node supports the noise-encrypted transport.
+    pub const NOISE: Self = Self(1 << 1);
+    /// The capabilities supported by this node.
+    pub const SUPPORTED: Self = Self::HEADER_SYNC;
+    /// The node supports transaction status queries.
+    pub const TRANSACTION_STATUS: Self = Self(1 << 3);
+
+    /// Returns the empty set of capabilities.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns the capabilities from the given bits, retaining any unknown bits.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the bits of the capabilities.
+    pub const fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if the set of capabilities is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if all of the given capabilities are contained in this set.
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the capabilities contained in both sets.
+    pub const fn intersection(&self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Returns the capabilities contained in either set.
+    pub const fn union(&self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl core::fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let names = [
+            (Self::COMPRESSION, "compression"),
+            (Self::NOISE, "noise"),
+            (Self::HEADER_SYNC, "header-sync"),
+            (Self::TRANSACTION_STATUS, "transaction-status"),
+        ];
+        let names = names.iter().filter(|(flag, _)| self.contains(*flag)).map(|(_, name)| *name).collect::<Vec<_>>();
+        match names.is_empty() {
+            true => write!(f, "none"),
+            false => write!(f, "{}", names.join(", ")),
+        }
+    }
+}
+
+impl ToBytes for Capabilities {
+    fn write_le<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        self.0.write_le(writer)
+    }
+}
+
+impl FromBytes for Capabilities {
+    fn read_le<R: io::Read>(reader: R) -> io::Result<Self> {
+        Ok(Self(u64::read_le(reader)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities() {
+        let capabilities = Capabilities::COMPRESSION.union(Capabilities::HEADER_SYNC);
+        assert!(capabilities.contains(Capabilities::COMPRESSION));
+        assert!(capabilities.contains(Capabilities::HEADER_SYNC));
+        assert!(!capabilities.contains(Capabilities::NOISE));
+        assert!(capabilities.contains(Capabilities::empty()));
+
+        // Ensure the negotiated capabilities are those supported by both sides.
+        let negotiated = capabilities.intersection(Capabilities::HEADER_SYNC.union(Capabilities::NOISE));
+        assert_eq!(negotiated, Capabilities::HEADER_SYNC);
+        assert_eq!(negotiated.to_string(), "header-sync");
+        assert_eq!(Capabilities::empty().to_string(), "none");
+
+        // Ensure unknown bits are retained across serialization.
+        let unknown = Capabilities::from_bits(1 << 63 | 1);
+        assert_eq!(Capabilities::read_le(&unknown.to_bytes_le().unwrap()[..]).unwrap(), unknown);
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{error, FromBytes, ToBytes};
+
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// The address a node advertises for inbound connections during the handshake.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub enum ListenerAddress {
+    /// The node listens on the advertised listener port, at the IP address it connects from.
+    #[default]
+    Port,
+    /// The node listens at the given host name (e.g. an onion address) and port.
+    Host(String, u16),
+    /// The node does not accept inbound connections.
+    Unreachable,
+}
+
+impl ListenerAddress {
+    /// The maximum length of a host name.
+    pub const MAXIMUM_HOST_LENGTH: usize = 255;
+
+    /// Returns `true` if the node can be reached at the IP address it connects from.
+    pub const fn is_port(&self) -> bool {
+        matches!(self, Self::Port)
+    }
+
+    /// Returns `true` if the node can be reached at a host name.
+    pub const fn is_host(&self) -> bool {
+        matches!(self, Self::Host(..))
+    }
+
+    /// Returns `true` if the node does not accept inbound connections.
+    pub const fn is_unreachable(&self) -> bool {
+        matches!(self, Self::Unreachable)
+    }
+
+    /// Returns `true` if the given host name is well-formed.
+    fn is_valid_host(host: &str) -> bool {
+        !host.is_empty()
+            && host.len() <= Self::MAXIMUM_HOST_LENGTH
+            && host.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'.' || byte == b'-')
+    }
+}
+
+impl core::str::FromStr for ListenerAddress {
+    type Err = io::Error;
+
+    /// Parses either `port`, `none`, or a `host:port` pair.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "port" => return Ok(Self::Port),
+            "none" => return Ok(Self::Unreachable),
+            _ => (),
+        }
+        let Some((host, port)) = s.rsplit_once(':') else {
+            return Err(error(format!("Invalid listener address '{s}' (expected 'port', 'none', or 'host:port')")));
+        };
+        let port = port.parse().map_err(|_| error(format!("Invalid port in the listener address '{s}'")))?;
+        match Self::is_valid_host(host) {
+            true => Ok(Self::Host(host.to_string(), port)),
+            false => Err(error(format!("Invalid host in the listener address '{s}'"))),
+        }
+    }
+}
+
+impl core::fmt::Display for ListenerAddress {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Port => write!(f, "port"),
+            Self::Host(host, port) => write!(f, "{host}:{port}"),
+            Self::Unreachable => write!(f, "none"),
+        }
+    }
+}
+
+impl ToBytes for ListenerAddress {
+    fn write_le<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        match self {
+            Self::Port => 0u8.write_le(&mut writer),
+            Self::Host(host, port) => {
+                if !Self::is_valid_host(host) {
+                    return Err(error("Invalid host in the listener address"));
+                }
+                1u8.write_le(&mut writer)?;
+                (host.len() as u8).write_le(&mut writer)?;
+                writer.write_all(host.as_bytes())?;
+                port.write_le(&mut writer)
+            }
+            Self::Unreachable => 2u8.write_le(&mut writer),
+        }
+    }
+}
+
+impl FromBytes for ListenerAddress {
+    fn read_le<R: io::Read>(mut reader: R) -> io::Result<Self> {
+        match u8::read_le(&mut reader)? {
+            0 => Ok(Self::Port),
+            1 => {
+                let mut host = vec![0u8; u8::read_le(&mut reader)? as usize];
+                reader.read_exact(&mut host)?;
+                let host = String::from_utf8(host).map_err(|_| error("Invalid host in the listener address"))?;
+                if !Self::is_valid_host(&host) {
+                    return Err(error("Invalid host in the listener address"));
+                }
+                Ok(Self::Host(host, u16::read_le(&mut reader)?))
+            }
+            2 => Ok(Self::Unreachable),
+            _ => Err(error("Invalid listener address")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listener_address() {
+        let onion = "expyuzz4wqqyqhjn.onion:4133".parse::<ListenerAddress>().unwrap();
+        assert_eq!(onion, ListenerAddress::Host("expyuzz4wqqyqhjn.onion".to_string(), 4133));
+        assert_eq!(onion.to_string(), "expyuzz4wqqyqhjn.onion:4133");
+        assert_eq!("port".parse::<ListenerAddress>().unwrap(), ListenerAddress::Port);
+        assert_eq!("none".parse::<ListenerAddress>().unwrap(), ListenerAddress::Unreachable);
+
+        // Ensure malformed addresses are rejected.
+        assert!("expyuzz4wqqyqhjn.onion".parse::<ListenerAddress>().is_err());
+        assert!(":4133".parse::<ListenerAddress>().is_err());
+        assert!("host name:4133".parse::<ListenerAddress>().is_err());
+
+        // Ensure the addresses are preserved across serialization.
+        for address in [ListenerAddress::Port, onion, ListenerAddress::Unreachable] {
+            assert_eq!(ListenerAddress::read_le(&address.to_bytes_le().unwrap()[..]).unwrap(), address);
+        }
+    }
+}
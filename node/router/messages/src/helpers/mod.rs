@@ -12,11 +12,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod capabilities;
+pub use capabilities::Capabilities;
+
 mod codec;
 pub use codec::MessageCodec;
 
 mod disconnect;
 pub use disconnect::DisconnectReason;
 
+mod listener_address;
+pub use listener_address::ListenerAddress;
+
 mod node_type;
 pub use node_type::*;
+
+use std::io;
+
+/// Reads a trailing field, returning `None` if the reader is already exhausted.
+pub(crate) fn read_optional<R: io::Read, T>(
+    reader: &mut R,
+    read: impl FnOnce(&mut R) -> io::Result<T>,
+) -> io::Result<Option<T>> {
+    match read(reader) {
+        Ok(value) => Ok(Some(value)),
+        Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(error) => Err(error),
+    }
+}
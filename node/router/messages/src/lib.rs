@@ -35,6 +35,12 @@ pub use challenge_response::ChallengeResponse;
 mod disconnect;
 pub use disconnect::Disconnect;
 
+mod header_request;
+pub use header_request::HeaderRequest;
+
+mod header_response;
+pub use header_response::{DataHeaders, HeaderResponse};
+
 mod peer_request;
 pub use peer_request::PeerRequest;
 
@@ -93,6 +99,8 @@ pub enum Message<N: Network> {
     ChallengeRequest(ChallengeRequest<N>),
     ChallengeResponse(ChallengeResponse<N>),
     Disconnect(Disconnect),
+    HeaderRequest(HeaderRequest),
+    HeaderResponse(HeaderResponse<N>),
     PeerRequest(PeerRequest),
     PeerResponse(PeerResponse),
     Ping(Ping<N>),
@@ -110,6 +118,8 @@ impl<N: Network> From<DisconnectReason> for Message<N> {
 }
 
 impl<N: Network> Message<N> {
+    /// The minimum version of the network protocol that this node remains compatible with.
+    pub const MIN_VERSION: u32 = 11;
     /// The version of the network protocol; it can be incremented in order to force users to update.
     pub const VERSION: u32 = 11;
 
@@ -130,6 +140,8 @@ impl<N: Network> Message<N> {
             Self::PuzzleResponse(message) => message.name(),
             Self::UnconfirmedSolution(message) => message.name(),
             Self::UnconfirmedTransaction(message) => message.name(),
+            Self::HeaderRequest(message) => message.name(),
+            Self::HeaderResponse(message) => message.name(),
         }
     }
 
@@ -150,6 +162,29 @@ impl<N: Network> Message<N> {
             Self::PuzzleResponse(..) => 10,
             Self::UnconfirmedSolution(..) => 11,
             Self::UnconfirmedTransaction(..) => 12,
+            Self::HeaderRequest(..) => 13,
+            Self::HeaderResponse(..) => 14,
+        }
+    }
+
+    /// Returns the capabilities the peer must support in order to receive the message.
+    #[inline]
+    pub fn required_capabilities(&self) -> Capabilities {
+        match self {
+            Self::BlockRequest(..)
+            | Self::BlockResponse(..)
+            | Self::ChallengeRequest(..)
+            | Self::ChallengeResponse(..)
+            | Self::Disconnect(..)
+            | Self::PeerRequest(..)
+            | Self::PeerResponse(..)
+            | Self::Ping(..)
+            | Self::Pong(..)
+            | Self::PuzzleRequest(..)
+            | Self::PuzzleResponse(..)
+            | Self::UnconfirmedSolution(..)
+            | Self::UnconfirmedTransaction(..) => Capabilities::empty(),
+            Self::HeaderRequest(..) | Self::HeaderResponse(..) => Capabilities::HEADER_SYNC,
         }
     }
 }
@@ -172,6 +207,8 @@ impl<N: Network> ToBytes for Message<N> {
             Self::PuzzleResponse(message) => message.write_le(writer),
             Self::UnconfirmedSolution(message) => message.write_le(writer),
             Self::UnconfirmedTransaction(message) => message.write_le(writer),
+            Self::HeaderRequest(message) => message.write_le(writer),
+            Self::HeaderResponse(message) => message.write_le(writer),
         }
     }
 }
@@ -198,7 +235,9 @@ impl<N: Network> FromBytes for Message<N> {
             10 => Self::PuzzleResponse(PuzzleResponse::read_le(reader)?),
             11 => Self::UnconfirmedSolution(UnconfirmedSolution::read_le(reader)?),
             12 => Self::UnconfirmedTransaction(UnconfirmedTransaction::read_le(reader)?),
-            13.. => return Err(error("Unknown message ID {id}")),
+            13 => Self::HeaderRequest(HeaderRequest::read_le(reader)?),
+            14 => Self::HeaderResponse(HeaderResponse::read_le(reader)?),
+            15.. => return Err(error("Unknown message ID {id}")),
         };
 
         Ok(message)
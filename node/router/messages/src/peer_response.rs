@@ -21,6 +21,8 @@ use std::borrow::Cow;
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PeerResponse {
     pub peers: Vec<SocketAddr>,
+    /// The peers that can only be reached at a host name (e.g. an onion address).
+    pub hosts: Vec<ListenerAddress>,
 }
 
 impl MessageTrait for PeerResponse {
@@ -38,6 +40,10 @@ impl ToBytes for PeerResponse {
         for peer in self.peers.iter().take(u8::MAX as usize) {
             peer.write_le(&mut writer)?;
         }
+        (self.hosts.len().min(u8::MAX as usize) as u8).write_le(&mut writer)?;
+        for host in self.hosts.iter().take(u8::MAX as usize) {
+            host.write_le(&mut writer)?;
+        }
         Ok(())
     }
 }
@@ -49,14 +55,23 @@ impl FromBytes for PeerResponse {
         for _ in 0..count {
             peers.push(SocketAddr::read_le(&mut reader)?);
         }
+        // Note: Older nodes do not send the host names of the peers.
+        let hosts = match read_optional(&mut reader, u8::read_le)? {
+            Some(count) => (0..count).map(|_| ListenerAddress::read_le(&mut reader)).collect::<io::Result<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+        // Ensure only host names are shared.
+        if !hosts.iter().all(ListenerAddress::is_host) {
+            return Err(error("Invalid host in the peer response"));
+        }
 
-        Ok(Self { peers })
+        Ok(Self { peers, hosts })
     }
 }
 
 #[cfg(test)]
 pub mod prop_tests {
-    use crate::PeerResponse;
+    use crate::{ListenerAddress, PeerResponse};
     use snarkvm::utilities::{FromBytes, ToBytes};
 
     use bytes::{Buf, BufMut, BytesMut};
@@ -75,8 +90,14 @@ pub mod prop_tests {
         vec(any_valid_socket_addr(), 0..50).prop_map(|v| v).boxed()
     }
 
+    pub fn any_hosts() -> BoxedStrategy<Vec<ListenerAddress>> {
+        let any_host =
+            ("[a-z2-7]{56}\\.onion", any::<u16>()).prop_map(|(host, port)| ListenerAddress::Host(host, port));
+        vec(any_host, 0..50).boxed()
+    }
+
     pub fn any_peer_response() -> BoxedStrategy<PeerResponse> {
-        any_vec().prop_map(|peers| PeerResponse { peers }).boxed()
+        (any_vec(), any_hosts()).prop_map(|(peers, hosts)| PeerResponse { peers, hosts }).boxed()
     }
 
     #[proptest]
@@ -86,4 +107,18 @@ pub mod prop_tests {
         let decoded = PeerResponse::read_le(&mut bytes.into_inner().reader()).unwrap();
         assert_eq!(decoded, peer_response);
     }
+
+    #[proptest]
+    fn peer_response_without_hosts(#[strategy(any_vec())] peers: Vec<SocketAddr>) {
+        // Ensure the peer responses of older nodes, which don't share the host names, are accepted.
+        let mut bytes = PeerResponse { peers: peers.clone(), hosts: vec![] }.to_bytes_le().unwrap();
+        bytes.pop();
+        assert_eq!(PeerResponse::read_le(&bytes[..]).unwrap(), PeerResponse { peers, hosts: vec![] });
+    }
+
+    #[test]
+    fn peer_response_rejects_non_hosts() {
+        let peer_response = PeerResponse { peers: vec![], hosts: vec![ListenerAddress::Port] };
+        assert!(PeerResponse::read_le(&peer_response.to_bytes_le().unwrap()[..]).is_err());
+    }
 }
@@ -17,7 +17,7 @@ use crate::{
     Peer,
     Router,
 };
-use snarkos_node_tcp::{ConnectionSide, Tcp, P2P};
+use snarkos_node_tcp::{challenge_message, ConnectionSide, ConnectionStream, Tcp, P2P};
 use snarkvm::{
     ledger::narwhal::Data,
     prelude::{block::Header, error, Address, Network},
@@ -27,7 +27,6 @@ use anyhow::{bail, Result};
 use futures::SinkExt;
 use rand::{rngs::OsRng, Rng};
 use std::{io, net::SocketAddr};
-use tokio::net::TcpStream;
 use tokio_stream::StreamExt;
 use tokio_util::codec::Framed;
 
@@ -71,7 +70,7 @@ macro_rules! expect_message {
 
 /// Send the given message to the peer.
 async fn send<N: Network>(
-    framed: &mut Framed<&mut TcpStream, MessageCodec<N>>,
+    framed: &mut Framed<&mut ConnectionStream, MessageCodec<N>>,
     peer_addr: SocketAddr,
     message: Message<N>,
 ) -> io::Result<()> {
@@ -84,10 +83,11 @@ impl<N: Network> Router<N> {
     pub async fn handshake<'a>(
         &'a self,
         peer_addr: SocketAddr,
-        stream: &'a mut TcpStream,
+        stream: &'a mut ConnectionStream,
         peer_side: ConnectionSide,
         genesis_header: Header<N>,
-    ) -> io::Result<(SocketAddr, Framed<&mut TcpStream, MessageCodec<N>>)> {
+        channel_binding: Option<[u8; 32]>,
+    ) -> io::Result<(SocketAddr, Framed<&mut ConnectionStream, MessageCodec<N>>)> {
         // If this is an inbound connection, we log it, but don't know the listening address yet.
         // Otherwise, we can immediately register the listening address.
         let mut peer_ip = if peer_side == ConnectionSide::Initiator {
@@ -100,9 +100,9 @@ impl<N: Network> Router<N> {
 
         // Perform the handshake; we pass on a mutable reference to peer_ip in case the process is broken at any point in time.
         let handshake_result = if peer_side == ConnectionSide::Responder {
-            self.handshake_inner_initiator(peer_addr, &mut peer_ip, stream, genesis_header).await
+            self.handshake_inner_initiator(peer_addr, &mut peer_ip, stream, genesis_header, channel_binding).await
         } else {
-            self.handshake_inner_responder(peer_addr, &mut peer_ip, stream, genesis_header).await
+            self.handshake_inner_responder(peer_addr, &mut peer_ip, stream, genesis_header, channel_binding).await
         };
 
         // Remove the address from the collection of connecting peers (if the handshake got to the point where it's known).
@@ -123,9 +123,10 @@ impl<N: Network> Router<N> {
         &'a self,
         peer_addr: SocketAddr,
         peer_ip: &mut Option<SocketAddr>,
-        stream: &'a mut TcpStream,
+        stream: &'a mut ConnectionStream,
         genesis_header: Header<N>,
-    ) -> io::Result<(SocketAddr, Framed<&mut TcpStream, MessageCodec<N>>)> {
+        channel_binding: Option<[u8; 32]>,
+    ) -> io::Result<(SocketAddr, Framed<&mut ConnectionStream, MessageCodec<N>>)> {
         // This value is immediately guaranteed to be present, so it can be unwrapped.
         let peer_ip = peer_ip.unwrap();
         // Construct the stream.
@@ -139,7 +140,8 @@ impl<N: Network> Router<N> {
         // Sample a random nonce.
         let our_nonce = rng.gen();
         // Send a challenge request to the peer.
-        let our_request = ChallengeRequest::new(self.local_ip().port(), self.node_type, self.address(), our_nonce);
+        let our_request = ChallengeRequest::new(self.local_ip().port(), self.node_type, self.address(), our_nonce)
+            .with_listener_address(self.listener_address().clone());
         send(&mut framed, peer_addr, Message::ChallengeRequest(our_request)).await?;
 
         /* Step 2: Receive the peer's challenge response followed by the challenge request. */
@@ -151,7 +153,13 @@ impl<N: Network> Router<N> {
 
         // Verify the challenge response. If a disconnect reason was returned, send the disconnect message and abort.
         if let Some(reason) = self
-            .verify_challenge_response(peer_addr, peer_request.address, peer_response, genesis_header, our_nonce)
+            .verify_challenge_response(
+                peer_addr,
+                peer_request.address,
+                peer_response,
+                genesis_header,
+                challenge_message(our_nonce, channel_binding),
+            )
             .await
         {
             send(&mut framed, peer_addr, reason.into()).await?;
@@ -164,8 +172,9 @@ impl<N: Network> Router<N> {
         }
         /* Step 3: Send the challenge response. */
 
-        // Sign the counterparty nonce.
-        let Ok(our_signature) = self.account.sign_bytes(&peer_request.nonce.to_le_bytes(), rng) else {
+        // Sign the counterparty nonce, bound to the session of the connection (if any).
+        let message = challenge_message(peer_request.nonce, channel_binding);
+        let Ok(our_signature) = self.account.sign_bytes(&message, rng) else {
             return Err(error(format!("Failed to sign the challenge request nonce from '{peer_addr}'")));
         };
         // Send the challenge response.
@@ -183,9 +192,10 @@ impl<N: Network> Router<N> {
         &'a self,
         peer_addr: SocketAddr,
         peer_ip: &mut Option<SocketAddr>,
-        stream: &'a mut TcpStream,
+        stream: &'a mut ConnectionStream,
         genesis_header: Header<N>,
-    ) -> io::Result<(SocketAddr, Framed<&mut TcpStream, MessageCodec<N>>)> {
+        channel_binding: Option<[u8; 32]>,
+    ) -> io::Result<(SocketAddr, Framed<&mut ConnectionStream, MessageCodec<N>>)> {
         // Construct the stream.
         let mut framed = Framed::new(stream, MessageCodec::<N>::handshake());
 
@@ -194,8 +204,12 @@ impl<N: Network> Router<N> {
         // Listen for the challenge request message.
         let peer_request = expect_message!(Message::ChallengeRequest, framed, peer_addr);
 
-        // Obtain the peer's listening address.
-        *peer_ip = Some(SocketAddr::new(peer_addr.ip(), peer_request.listener_port));
+        // Obtain the peer's listening address. If the peer can't be reached at its IP address,
+        // its (ambiguous) peer address is used to identify it instead.
+        *peer_ip = match peer_request.listener_address.is_port() {
+            true => Some(SocketAddr::new(peer_addr.ip(), peer_request.listener_port)),
+            false => Some(peer_addr),
+        };
         let peer_ip = peer_ip.unwrap();
 
         // Knowing the peer's listening address, ensure it is allowed to connect.
@@ -212,8 +226,9 @@ impl<N: Network> Router<N> {
         // Initialize an RNG.
         let rng = &mut OsRng;
 
-        // Sign the counterparty nonce.
-        let Ok(our_signature) = self.account.sign_bytes(&peer_request.nonce.to_le_bytes(), rng) else {
+        // Sign the counterparty nonce, bound to the session of the connection (if any).
+        let message = challenge_message(peer_request.nonce, channel_binding);
+        let Ok(our_signature) = self.account.sign_bytes(&message, rng) else {
             return Err(error(format!("Failed to sign the challenge request nonce from '{peer_addr}'")));
         };
         // Send the challenge response.
@@ -223,7 +238,8 @@ impl<N: Network> Router<N> {
         // Sample a random nonce.
         let our_nonce = rng.gen();
         // Send the challenge request.
-        let our_request = ChallengeRequest::new(self.local_ip().port(), self.node_type, self.address(), our_nonce);
+        let our_request = ChallengeRequest::new(self.local_ip().port(), self.node_type, self.address(), our_nonce)
+            .with_listener_address(self.listener_address().clone());
         send(&mut framed, peer_addr, Message::ChallengeRequest(our_request)).await?;
 
         /* Step 3: Receive the challenge response. */
@@ -232,7 +248,13 @@ impl<N: Network> Router<N> {
         let peer_response = expect_message!(Message::ChallengeResponse, framed, peer_addr);
         // Verify the challenge response. If a disconnect reason was returned, send the disconnect message and abort.
         if let Some(reason) = self
-            .verify_challenge_response(peer_addr, peer_request.address, peer_response, genesis_header, our_nonce)
+            .verify_challenge_response(
+                peer_addr,
+                peer_request.address,
+                peer_response,
+                genesis_header,
+                challenge_message(our_nonce, channel_binding),
+            )
             .await
         {
             send(&mut framed, peer_addr, reason.into()).await?;
@@ -283,13 +305,33 @@ impl<N: Network> Router<N> {
         message: &ChallengeRequest<N>,
     ) -> Option<DisconnectReason> {
         // Retrieve the components of the challenge request.
-        let &ChallengeRequest { version, listener_port: _, node_type: _, address: _, nonce: _ } = message;
-
+        let &ChallengeRequest {
+            version,
+            listener_port: _,
+            node_type: _,
+            address: _,
+            nonce: _,
+            min_version,
+            capabilities,
+            listener_address: _,
+        } = message;
+
+        // Ensure the version range of the peer is well-formed.
+        if min_version > version {
+            warn!("Dropping '{peer_addr}' on an invalid version range ({min_version}..={version})");
+            return Some(DisconnectReason::ProtocolViolation);
+        }
         // Ensure the message protocol version is not outdated.
-        if version < Message::<N>::VERSION {
+        if version < Message::<N>::MIN_VERSION {
             warn!("Dropping '{peer_addr}' on version {version} (outdated)");
             return Some(DisconnectReason::OutdatedClientVersion);
         }
+        // Ensure the peer remains compatible with our message protocol version.
+        if min_version > Message::<N>::VERSION {
+            warn!("Dropping '{peer_addr}' on minimum version {min_version} (this node is outdated)");
+            return Some(DisconnectReason::OutdatedClientVersion);
+        }
+        trace!("Peer '{peer_addr}' supports versions {min_version}..={version} with capabilities ({capabilities})");
         None
     }
 
@@ -300,7 +342,7 @@ impl<N: Network> Router<N> {
         peer_address: Address<N>,
         response: ChallengeResponse<N>,
         expected_genesis_header: Header<N>,
-        expected_nonce: u64,
+        expected_message: Vec<u8>,
     ) -> Option<DisconnectReason> {
         // Retrieve the components of the challenge response.
         let ChallengeResponse { genesis_header, signature } = response;
@@ -316,7 +358,7 @@ impl<N: Network> Router<N> {
             return Some(DisconnectReason::InvalidChallengeResponse);
         };
         // Verify the signature.
-        if !signature.verify_bytes(&peer_address, &expected_nonce.to_le_bytes()) {
+        if !signature.verify_bytes(&peer_address, &expected_message) {
             warn!("Handshake with '{peer_addr}' failed (invalid signature)");
             return Some(DisconnectReason::InvalidChallengeResponse);
         }
@@ -20,7 +20,11 @@ use crate::{
 use snarkvm::prelude::Network;
 
 use colored::Colorize;
-use rand::{prelude::IteratorRandom, rngs::OsRng};
+use rand::{
+    prelude::{IteratorRandom, SliceRandom},
+    rngs::OsRng,
+};
+use std::cmp::Reverse;
 
 /// A helper function to compute the maximum of two numbers.
 /// See Rust issue 92391: https://github.com/rust-lang/rust/issues/92391.
@@ -149,14 +153,17 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
             let rng = &mut OsRng;
 
             // TODO (howardwu): As a validator, prioritize disconnecting from clients and provers.
-            //  Remove RNG, pick the `n` oldest nodes.
-            // Determine the peers to disconnect from.
-            let peer_ips_to_disconnect = self
+            // Determine the peers to disconnect from, starting with the ones with the highest latency;
+            // the peers without a measured latency are picked at random, after the measured ones.
+            let mut peer_ips = self
                 .router()
                 .connected_peers()
                 .into_iter()
                 .filter(|peer_ip| !trusted.contains(peer_ip) && !bootstrap.contains(peer_ip))
-                .choose_multiple(rng, num_surplus);
+                .collect::<Vec<_>>();
+            peer_ips.shuffle(rng);
+            peer_ips.sort_by_cached_key(|peer_ip| Reverse(self.router().latency(peer_ip)));
+            let peer_ips_to_disconnect = peer_ips.into_iter().take(num_surplus);
 
             // Proceed to send disconnect requests to these peers.
             for peer_ip in peer_ips_to_disconnect {
@@ -181,9 +188,17 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
             let rng = &mut OsRng;
 
             // Attempt to connect to more peers.
-            for peer_ip in self.router().candidate_peers().into_iter().choose_multiple(rng, num_deficient) {
+            let candidate_peers = self.router().candidate_peers().into_iter().choose_multiple(rng, num_deficient);
+            let num_remaining = num_deficient - candidate_peers.len();
+            for peer_ip in candidate_peers {
                 self.router().connect(peer_ip);
             }
+            // Attempt to connect to more peers at their host names (e.g. onion addresses), if they can be resolved.
+            let candidate_hosts = self.router().candidate_hosts().into_iter();
+            let candidate_hosts = candidate_hosts.filter(|host| self.router().can_resolve_host(host));
+            for host in candidate_hosts.choose_multiple(rng, num_remaining) {
+                self.router().connect_host(host);
+            }
             // Request more peers from the connected peers.
             for peer_ip in self.router().connected_peers().into_iter().choose_multiple(rng, 3) {
                 self.send(peer_ip, Message::PeerRequest(PeerRequest));
@@ -232,9 +247,9 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
         // Ensure that the trusted nodes are connected.
         for peer_ip in self.router().trusted_peers() {
             // If the peer is not connected, attempt to connect to it.
-            if !self.router().is_connected(peer_ip) {
+            if !self.router().is_connected(&peer_ip) {
                 // Attempt to connect to the trusted peer.
-                self.router().connect(*peer_ip);
+                self.router().connect(peer_ip);
             }
         }
     }
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::messages::BlockRequest;
+use crate::messages::{BlockRequest, HeaderRequest};
 use snarkvm::prelude::{coinbase::PuzzleCommitment, Network};
 
 use core::hash::Hash;
@@ -47,6 +47,8 @@ pub struct Cache<N: Network> {
     seen_inbound_transactions: RwLock<LinkedHashMap<TransactionKey<N>, OffsetDateTime>>,
     /// The map of peer IPs to their block requests.
     seen_outbound_block_requests: RwLock<IndexMap<SocketAddr, IndexSet<BlockRequest>>>,
+    /// The map of peer IPs to their header requests.
+    seen_outbound_header_requests: RwLock<IndexMap<SocketAddr, IndexSet<HeaderRequest>>>,
     /// The map of peer IPs to the number of puzzle requests.
     seen_outbound_puzzle_requests: RwLock<IndexMap<SocketAddr, u16>>,
     /// The map of solution commitments to their last seen timestamp.
@@ -72,6 +74,7 @@ impl<N: Network> Cache<N> {
             seen_inbound_solutions: RwLock::new(LinkedHashMap::with_capacity(MAX_CACHE_SIZE)),
             seen_inbound_transactions: RwLock::new(LinkedHashMap::with_capacity(MAX_CACHE_SIZE)),
             seen_outbound_block_requests: Default::default(),
+            seen_outbound_header_requests: Default::default(),
             seen_outbound_puzzle_requests: Default::default(),
             seen_outbound_solutions: RwLock::new(LinkedHashMap::with_capacity(MAX_CACHE_SIZE)),
             seen_outbound_transactions: RwLock::new(LinkedHashMap::with_capacity(MAX_CACHE_SIZE)),
@@ -134,6 +137,20 @@ impl<N: Network> Cache<N> {
         if let Some(requests) = map_write.get_mut(&peer_ip) { requests.remove(request) } else { false }
     }
 
+    /// Inserts the header request for the given peer IP, returning the number of recent requests.
+    pub fn insert_outbound_header_request(&self, peer_ip: SocketAddr, request: HeaderRequest) -> usize {
+        let mut map_write = self.seen_outbound_header_requests.write();
+        let requests = map_write.entry(peer_ip).or_default();
+        requests.insert(request);
+        requests.len()
+    }
+
+    /// Removes the header request for the given peer IP, returning `true` if the request was present.
+    pub fn remove_outbound_header_request(&self, peer_ip: SocketAddr, request: &HeaderRequest) -> bool {
+        let mut map_write = self.seen_outbound_header_requests.write();
+        if let Some(requests) = map_write.get_mut(&peer_ip) { requests.remove(request) } else { false }
+    }
+
     /// Returns `true` if the cache contains a puzzle request from the given peer.
     pub fn contains_outbound_puzzle_request(&self, peer_ip: &SocketAddr) -> bool {
         self.seen_outbound_puzzle_requests.read().get(peer_ip).map(|r| *r > 0).unwrap_or(false)
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::messages::{ChallengeRequest, NodeType};
+use crate::messages::{Capabilities, ChallengeRequest, ListenerAddress, Message, NodeType};
 use snarkvm::prelude::{Address, Network};
 
 use std::{net::SocketAddr, time::Instant};
@@ -28,6 +28,12 @@ pub struct Peer<N: Network> {
     node_type: NodeType,
     /// The message version of the peer.
     version: u32,
+    /// The message version negotiated with the peer.
+    negotiated_version: u32,
+    /// The capabilities supported by both the peer and this node.
+    capabilities: Capabilities,
+    /// The address the peer advertised for inbound connections.
+    listener_address: ListenerAddress,
     /// The timestamp of the first message received from the peer.
     first_seen: Instant,
     /// The timestamp of the last message received from this peer.
@@ -42,6 +48,9 @@ impl<N: Network> Peer<N> {
             address: challenge_request.address,
             node_type: challenge_request.node_type,
             version: challenge_request.version,
+            negotiated_version: challenge_request.version.min(Message::<N>::VERSION),
+            capabilities: challenge_request.capabilities.intersection(Capabilities::SUPPORTED),
+            listener_address: challenge_request.listener_address.clone(),
             first_seen: Instant::now(),
             last_seen: Instant::now(),
         }
@@ -82,6 +91,31 @@ impl<N: Network> Peer<N> {
         self.version
     }
 
+    /// Returns the message version negotiated with the peer.
+    pub const fn negotiated_version(&self) -> u32 {
+        self.negotiated_version
+    }
+
+    /// Returns the capabilities supported by both the peer and this node.
+    pub const fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+
+    /// Returns `true` if the peer supports all of the given capabilities.
+    pub const fn supports(&self, capabilities: Capabilities) -> bool {
+        self.capabilities.contains(capabilities)
+    }
+
+    /// Returns the address the peer advertised for inbound connections.
+    pub const fn listener_address(&self) -> &ListenerAddress {
+        &self.listener_address
+    }
+
+    /// Returns `true` if the peer can be reached at its IP address.
+    pub const fn is_reachable(&self) -> bool {
+        self.listener_address.is_port()
+    }
+
     /// Returns the first seen timestamp of the peer.
     pub fn first_seen(&self) -> Instant {
         self.first_seen
@@ -102,6 +136,7 @@ impl<N: Network> Peer<N> {
     /// Updates the version.
     pub fn set_version(&mut self, version: u32) {
         self.version = version;
+        self.negotiated_version = version.min(Message::<N>::VERSION);
     }
 
     /// Updates the last seen timestamp of the peer.
@@ -17,6 +17,11 @@ use crate::{
         BlockRequest,
         BlockResponse,
         DataBlocks,
+        DataHeaders,
+        DisconnectReason,
+        HeaderRequest,
+        HeaderResponse,
+        ListenerAddress,
         Message,
         PeerResponse,
         Ping,
@@ -27,6 +32,7 @@ use crate::{
     Outbound,
     Peer,
 };
+use snarkos_node_sync_locators::SyncHeader;
 use snarkos_node_tcp::{is_bogon_address, protocols::Reading};
 use snarkvm::prelude::{
     block::{Block, Header, Transaction},
@@ -89,17 +95,37 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
                 if !self.router().cache.remove_outbound_block_request(peer_ip, &request) {
                     bail!("Peer '{peer_ip}' is not following the protocol (unexpected block response)")
                 }
-                // Perform the deferred non-blocking deserialization of the blocks.
-                let blocks = blocks.deserialize().await.map_err(|error| anyhow!("[BlockResponse] {error}"))?;
-                // Ensure the block response is well-formed.
-                blocks.ensure_response_is_well_formed(peer_ip, request.start_height, request.end_height)?;
 
-                // Process the block response.
+                // Process the block response in a separate task, so the subsequent block responses
+                // from this peer are deserialized in parallel.
+                // Note: The number of concurrent tasks is bounded, so the reads from the peer are paused
+                // (by the backpressure of the message queue) while the block responses are processed.
+                let permit = self.router().block_response_permits.clone().acquire_owned().await?;
                 let node = self.clone();
-                match spawn_blocking(move || node.block_response(peer_ip, blocks.0)).await? {
-                    true => Ok(()),
-                    false => bail!("Peer '{peer_ip}' sent an invalid block response"),
-                }
+                tokio::spawn(async move {
+                    let _permit = permit;
+                    let result = async {
+                        // Perform the deferred non-blocking deserialization of the blocks.
+                        let blocks = blocks.deserialize().await.map_err(|error| anyhow!("[BlockResponse] {error}"))?;
+                        // Ensure the block response is well-formed.
+                        blocks.ensure_response_is_well_formed(peer_ip, request.start_height, request.end_height)?;
+
+                        // Process the block response.
+                        let node_ = node.clone();
+                        match spawn_blocking(move || node_.block_response(peer_ip, blocks.0)).await? {
+                            true => Ok::<_, anyhow::Error>(()),
+                            false => bail!("Peer '{peer_ip}' sent an invalid block response"),
+                        }
+                    }
+                    .await;
+                    // Disconnect if the peer violated the protocol.
+                    if let Err(error) = result {
+                        warn!("Disconnecting from '{peer_ip}' - {error}");
+                        Outbound::send(&node, peer_ip, Message::Disconnect(DisconnectReason::ProtocolViolation.into()));
+                        node.router().disconnect(peer_ip);
+                    }
+                });
+                Ok(())
             }
             Message::ChallengeRequest(..) | Message::ChallengeResponse(..) => {
                 // Disconnect as the peer is not following the protocol.
@@ -108,17 +134,54 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
             Message::Disconnect(message) => {
                 bail!("{:?}", message.reason)
             }
+            Message::HeaderRequest(message) => {
+                let HeaderRequest { start_height, end_height } = &message;
+
+                // Ensure the header request is well-formed.
+                if start_height >= end_height {
+                    bail!("Header request from '{peer_ip}' has an invalid range ({start_height}..{end_height})")
+                }
+                // Ensure that the header request is within the allowed bounds.
+                if end_height - start_height > DataHeaders::<N>::MAXIMUM_NUMBER_OF_HEADERS {
+                    bail!("Header request from '{peer_ip}' has an excessive range ({start_height}..{end_height})")
+                }
+
+                let node = self.clone();
+                match spawn_blocking(move || node.header_request(peer_ip, message)).await? {
+                    true => Ok(()),
+                    false => bail!("Peer '{peer_ip}' sent an invalid header request"),
+                }
+            }
+            Message::HeaderResponse(message) => {
+                let HeaderResponse { request, headers } = message;
+
+                // Remove the header request, checking if this node previously sent a header request to this peer.
+                if !self.router().cache.remove_outbound_header_request(peer_ip, &request) {
+                    bail!("Peer '{peer_ip}' is not following the protocol (unexpected header response)")
+                }
+                // Perform the deferred non-blocking deserialization of the headers.
+                let headers = headers.deserialize().await.map_err(|error| anyhow!("[HeaderResponse] {error}"))?;
+                // Ensure the header response is well-formed.
+                headers.ensure_response_is_well_formed(peer_ip, request.start_height, request.end_height)?;
+
+                // Process the header response.
+                let node = self.clone();
+                match spawn_blocking(move || node.header_response(peer_ip, headers.0)).await? {
+                    true => Ok(()),
+                    false => bail!("Peer '{peer_ip}' sent an invalid header response"),
+                }
+            }
             Message::PeerRequest(..) => match self.peer_request(peer_ip) {
                 true => Ok(()),
                 false => bail!("Peer '{peer_ip}' sent an invalid peer request"),
             },
-            Message::PeerResponse(message) => match self.peer_response(peer_ip, &message.peers) {
+            Message::PeerResponse(message) => match self.peer_response(peer_ip, &message.peers, &message.hosts) {
                 true => Ok(()),
                 false => bail!("Peer '{peer_ip}' sent an invalid peer response"),
             },
             Message::Ping(message) => {
                 // Ensure the message protocol version is not outdated.
-                if message.version < Message::<N>::VERSION {
+                if message.version < Message::<N>::MIN_VERSION {
                     bail!("Dropping '{peer_ip}' on message version {} (outdated)", message.version);
                 }
 
@@ -152,10 +215,18 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
                     false => bail!("Peer '{peer_ip}' sent an invalid ping"),
                 }
             }
-            Message::Pong(message) => match self.pong(peer_ip, message) {
-                true => Ok(()),
-                false => bail!("Peer '{peer_ip}' sent an invalid pong"),
-            },
+            Message::Pong(message) => {
+                // Measure the round-trip time to the peer.
+                if let Some(rtt) = self.router().known_peers().register_pong(peer_addr) {
+                    trace!("Measured a round-trip time of {}ms to '{peer_ip}'", rtt.as_millis());
+                }
+
+                // Process the pong message.
+                match self.pong(peer_ip, message) {
+                    true => Ok(()),
+                    false => bail!("Peer '{peer_ip}' sent an invalid pong"),
+                }
+            }
             Message::PuzzleRequest(..) => {
                 // Insert the puzzle request for the peer, and fetch the recent frequency.
                 let frequency = self.router().cache.insert_inbound_puzzle_request(peer_ip);
@@ -246,23 +317,33 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
     /// Handles a `BlockResponse` message.
     fn block_response(&self, peer_ip: SocketAddr, _blocks: Vec<Block<N>>) -> bool;
 
+    /// Handles a `HeaderRequest` message.
+    fn header_request(&self, peer_ip: SocketAddr, _message: HeaderRequest) -> bool;
+
+    /// Handles a `HeaderResponse` message.
+    fn header_response(&self, peer_ip: SocketAddr, _headers: Vec<SyncHeader<N>>) -> bool;
+
     /// Handles a `PeerRequest` message.
     fn peer_request(&self, peer_ip: SocketAddr) -> bool {
-        // Retrieve the connected peers.
-        let peers = self.router().connected_peers();
+        // Retrieve the connected peers that can be reached at their IP address.
+        let peers = self.router().reachable_peers();
         // Filter out bogon addresses.
         let peers = peers.into_iter().filter(|addr| !is_bogon_address(addr.ip())).collect();
+        // Retrieve the connected peers that can only be reached at a host name.
+        let hosts = self.router().connected_hosts();
         // Send a `PeerResponse` message to the peer.
-        self.send(peer_ip, Message::PeerResponse(PeerResponse { peers }));
+        self.send(peer_ip, Message::PeerResponse(PeerResponse { peers, hosts }));
         true
     }
 
     /// Handles a `PeerResponse` message.
-    fn peer_response(&self, _peer_ip: SocketAddr, peers: &[SocketAddr]) -> bool {
+    fn peer_response(&self, _peer_ip: SocketAddr, peers: &[SocketAddr], hosts: &[ListenerAddress]) -> bool {
         // Filter out bogon addresses.
         let peers = peers.iter().copied().filter(|addr| !is_bogon_address(addr.ip())).collect::<Vec<_>>();
         // Adds the given peer IPs to the list of candidate peers.
         self.router().insert_candidate_peers(&peers);
+        // Adds the given peer host names to the list of candidate hosts.
+        self.router().insert_candidate_hosts(hosts);
         true
     }
 
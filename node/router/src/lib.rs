@@ -39,16 +39,24 @@ pub use outbound::*;
 mod routing;
 pub use routing::*;
 
-use crate::messages::NodeType;
+use crate::messages::{ListenerAddress, NodeType};
 use snarkos_account::Account;
-use snarkos_node_tcp::{Config, Tcp};
+use snarkos_node_tcp::{BandwidthLimits, Config, Tcp, Transport};
 use snarkvm::prelude::{Address, Network, PrivateKey, ViewKey};
 
 use anyhow::{bail, Result};
 use indexmap::{IndexMap, IndexSet};
 use parking_lot::{Mutex, RwLock};
-use std::{collections::HashSet, future::Future, net::SocketAddr, ops::Deref, str::FromStr, sync::Arc, time::Instant};
-use tokio::task::JoinHandle;
+use std::{
+    collections::HashSet,
+    future::Future,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    ops::Deref,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{sync::Semaphore, task::JoinHandle};
 
 #[derive(Clone)]
 pub struct Router<N: Network>(Arc<InnerRouter<N>>);
@@ -66,6 +74,8 @@ pub struct InnerRouter<N: Network> {
     tcp: Tcp,
     /// The node type.
     node_type: NodeType,
+    /// The address advertised for inbound connections.
+    listener_address: ListenerAddress,
     /// The account of the node.
     account: Account<N>,
     /// The cache.
@@ -73,7 +83,7 @@ pub struct InnerRouter<N: Network> {
     /// The resolver.
     resolver: Resolver,
     /// The set of trusted peers.
-    trusted_peers: IndexSet<SocketAddr>,
+    trusted_peers: RwLock<IndexSet<SocketAddr>>,
     /// The map of connected peer IPs to their peer handlers.
     connected_peers: RwLock<IndexMap<SocketAddr, Peer<N>>>,
     /// The set of handshaking peers. While `Tcp` already recognizes the connecting IP addresses
@@ -83,8 +93,14 @@ pub struct InnerRouter<N: Network> {
     connecting_peers: Mutex<HashSet<SocketAddr>>,
     /// The set of candidate peer IPs.
     candidate_peers: RwLock<IndexSet<SocketAddr>>,
+    /// The set of candidate peers that can only be reached at a host name (e.g. an onion address).
+    candidate_hosts: RwLock<IndexSet<ListenerAddress>>,
+    /// The set of candidate host names that are being connected to.
+    connecting_hosts: Mutex<HashSet<ListenerAddress>>,
     /// The set of restricted peer IPs.
     restricted_peers: RwLock<IndexMap<SocketAddr, Instant>>,
+    /// The permits to process block responses, which bound the number of block responses processed concurrently.
+    block_response_permits: Arc<Semaphore>,
     /// The spawned handles.
     handles: Mutex<Vec<JoinHandle<()>>>,
     /// The boolean flag for the development mode.
@@ -94,37 +110,57 @@ pub struct InnerRouter<N: Network> {
 impl<N: Network> Router<N> {
     /// The maximum number of candidate peers permitted to be stored in the node.
     const MAXIMUM_CANDIDATE_PEERS: usize = 10_000;
+    // 2.5 minutes
+    /// The maximum number of block responses that are processed concurrently.
+    const MAXIMUM_CONCURRENT_BLOCK_RESPONSES: usize = 8;
     /// The maximum number of connection failures permitted by an inbound connecting peer.
     const MAXIMUM_CONNECTION_FAILURES: usize = 5;
     /// The duration in seconds after which a connected peer is considered inactive or
     /// disconnected if no message has been received in the meantime.
-    const RADIO_SILENCE_IN_SECS: u64 = 150; // 2.5 minutes
+    const RADIO_SILENCE_IN_SECS: u64 = 150;
 }
 
 impl<N: Network> Router<N> {
     /// Initializes a new `Router` instance.
     pub async fn new(
         node_ip: SocketAddr,
+        transport: Transport,
+        bandwidth_limits: BandwidthLimits,
+        socks5_proxy: Option<SocketAddr>,
+        listener_address: ListenerAddress,
         node_type: NodeType,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         max_peers: u16,
         is_dev: bool,
     ) -> Result<Self> {
+        // Prepare the TCP configuration.
+        let mut config = Config::new(node_ip, max_peers)
+            .with_transport(transport)
+            .with_bandwidth_limits(bandwidth_limits)
+            .with_socks5_proxy(socks5_proxy);
+        // If the node does not accept inbound connections, disable the listener.
+        if listener_address.is_unreachable() {
+            config.listener_ip = None;
+        }
         // Initialize the TCP stack.
-        let tcp = Tcp::new(Config::new(node_ip, max_peers));
+        let tcp = Tcp::new(config);
         // Initialize the router.
         Ok(Self(Arc::new(InnerRouter {
             tcp,
             node_type,
+            listener_address,
             account,
             cache: Default::default(),
             resolver: Default::default(),
-            trusted_peers: trusted_peers.iter().copied().collect(),
+            trusted_peers: RwLock::new(trusted_peers.iter().copied().collect()),
             connected_peers: Default::default(),
             connecting_peers: Default::default(),
             candidate_peers: Default::default(),
+            candidate_hosts: Default::default(),
+            connecting_hosts: Default::default(),
             restricted_peers: Default::default(),
+            block_response_permits: Arc::new(Semaphore::new(Self::MAXIMUM_CONCURRENT_BLOCK_RESPONSES)),
             handles: Default::default(),
             is_dev,
         })))
@@ -159,6 +195,71 @@ impl<N: Network> Router<N> {
         }))
     }
 
+    /// Attempts to connect to the given peer host name (e.g. an onion address), through the SOCKS5 proxy (if any).
+    pub fn connect_host(&self, listener_address: ListenerAddress) -> Option<JoinHandle<bool>> {
+        let ListenerAddress::Host(host, port) = listener_address.clone() else {
+            return None;
+        };
+        // Return early if the attempt is against the protocol rules.
+        if let Err(forbidden_message) = self.check_host_connection_attempt(&listener_address) {
+            warn!("{forbidden_message}");
+            return None;
+        }
+
+        let router = self.clone();
+        Some(tokio::spawn(async move {
+            // Attempt to connect to the candidate host.
+            let result = router.tcp.connect_host(&host, port).await;
+            router.connecting_hosts.lock().remove(&listener_address);
+            match result {
+                // Remove the host from the candidate hosts.
+                Ok(_) => {
+                    router.candidate_hosts.write().remove(&listener_address);
+                    true
+                }
+                // If the connection was not allowed, log the error.
+                Err(error) => {
+                    warn!("Unable to connect to '{listener_address}' - {error}");
+                    false
+                }
+            }
+        }))
+    }
+
+    /// Ensure we are allowed to connect to the given peer host name.
+    fn check_host_connection_attempt(&self, listener_address: &ListenerAddress) -> Result<()> {
+        // Ensure the host is not this node.
+        if listener_address == self.listener_address() {
+            bail!("Dropping connection attempt to '{listener_address}' (attempted to self-connect)")
+        }
+        // Ensure the host can be resolved.
+        if !self.can_resolve_host(listener_address) {
+            bail!("Dropping connection attempt to '{listener_address}' (the host requires a SOCKS5 proxy)")
+        }
+        // Ensure the node does not surpass the maximum number of peer connections.
+        if self.number_of_connected_peers() >= self.max_connected_peers() {
+            bail!("Dropping connection attempt to '{listener_address}' (maximum peers reached)")
+        }
+        // Ensure the node is not already connected to this host.
+        if self.is_connected_host(listener_address) {
+            bail!("Dropping connection attempt to '{listener_address}' (already connected)")
+        }
+        // Ensure the node is not already connecting to this host.
+        if !self.connecting_hosts.lock().insert(listener_address.clone()) {
+            bail!("Dropping connection attempt to '{listener_address}' (already connecting)")
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the given peer host name can be resolved; the onion addresses
+    /// can only be resolved by the SOCKS5 proxy (e.g. Tor).
+    pub fn can_resolve_host(&self, listener_address: &ListenerAddress) -> bool {
+        match listener_address {
+            ListenerAddress::Host(host, _) => self.tcp.config().socks5_proxy.is_some() || !host.ends_with(".onion"),
+            _ => false,
+        }
+    }
+
     /// Ensure we are allowed to connect to the given peer.
     fn check_connection_attempt(&self, peer_ip: SocketAddr) -> Result<()> {
         // Ensure the peer IP is not this node.
@@ -204,9 +305,17 @@ impl<N: Network> Router<N> {
         })
     }
 
-    /// Returns the IP address of this node.
+    /// Returns the IP address of this node, or the unspecified address if the listener is disabled.
     pub fn local_ip(&self) -> SocketAddr {
-        self.tcp.listening_addr().expect("The TCP listener is not enabled")
+        match self.listener_address.is_unreachable() {
+            true => SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0),
+            false => self.tcp.listening_addr().expect("The TCP listener is not enabled"),
+        }
+    }
+
+    /// Returns the address this node advertises for inbound connections.
+    pub fn listener_address(&self) -> &ListenerAddress {
+        &self.listener_address
     }
 
     /// Returns `true` if the given IP is this node.
@@ -250,11 +359,21 @@ impl<N: Network> Router<N> {
         self.resolver.get_ambiguous(peer_ip)
     }
 
+    /// Returns the smoothed round-trip time to the given peer IP, if it has been measured.
+    pub fn latency(&self, peer_ip: &SocketAddr) -> Option<Duration> {
+        self.resolve_to_ambiguous(peer_ip).and_then(|peer_addr| self.known_peers().get(peer_addr)?.rtt())
+    }
+
     /// Returns `true` if the node is connected to the given peer IP.
     pub fn is_connected(&self, ip: &SocketAddr) -> bool {
         self.connected_peers.read().contains_key(ip)
     }
 
+    /// Returns `true` if the node is connected to a peer listening at the given host name.
+    pub fn is_connected_host(&self, listener_address: &ListenerAddress) -> bool {
+        self.connected_peers.read().values().any(|peer| peer.listener_address() == listener_address)
+    }
+
     /// Returns `true` if the given peer IP is a connected validator.
     pub fn is_connected_validator(&self, peer_ip: &SocketAddr) -> bool {
         self.connected_peers.read().get(peer_ip).map_or(false, |peer| peer.is_validator())
@@ -314,6 +433,11 @@ impl<N: Network> Router<N> {
         self.candidate_peers.read().len()
     }
 
+    /// Returns the number of candidate hosts.
+    pub fn number_of_candidate_hosts(&self) -> usize {
+        self.candidate_hosts.read().len()
+    }
+
     /// Returns the number of restricted peers.
     pub fn number_of_restricted_peers(&self) -> usize {
         self.restricted_peers.read().len()
@@ -334,6 +458,21 @@ impl<N: Network> Router<N> {
         self.connected_peers.read().keys().copied().collect()
     }
 
+    /// Returns the list of connected peers that can be reached at their IP address.
+    pub fn reachable_peers(&self) -> Vec<SocketAddr> {
+        self.connected_peers.read().iter().filter(|(_, peer)| peer.is_reachable()).map(|(ip, _)| *ip).collect()
+    }
+
+    /// Returns the host names of the connected peers that can only be reached at a host name.
+    pub fn connected_hosts(&self) -> Vec<ListenerAddress> {
+        self.connected_peers
+            .read()
+            .values()
+            .filter(|peer| peer.listener_address().is_host())
+            .map(|peer| peer.listener_address().clone())
+            .collect()
+    }
+
     /// Returns the list of connected validators.
     pub fn connected_validators(&self) -> Vec<SocketAddr> {
         self.connected_peers.read().iter().filter(|(_, peer)| peer.is_validator()).map(|(ip, _)| *ip).collect()
@@ -354,14 +493,19 @@ impl<N: Network> Router<N> {
         self.candidate_peers.read().clone()
     }
 
+    /// Returns the list of candidate hosts.
+    pub fn candidate_hosts(&self) -> IndexSet<ListenerAddress> {
+        self.candidate_hosts.read().clone()
+    }
+
     /// Returns the list of restricted peers.
     pub fn restricted_peers(&self) -> Vec<SocketAddr> {
         self.restricted_peers.read().keys().copied().collect()
     }
 
     /// Returns the list of trusted peers.
-    pub fn trusted_peers(&self) -> &IndexSet<SocketAddr> {
-        &self.trusted_peers
+    pub fn trusted_peers(&self) -> IndexSet<SocketAddr> {
+        self.trusted_peers.read().clone()
     }
 
     /// Returns the list of bootstrap peers.
@@ -378,9 +522,13 @@ impl<N: Network> Router<N> {
         }
     }
 
-    /// Returns the list of metrics for the connected peers.
-    pub fn connected_metrics(&self) -> Vec<(SocketAddr, NodeType)> {
-        self.connected_peers.read().iter().map(|(ip, peer)| (*ip, peer.node_type())).collect()
+    /// Returns the list of metrics for the connected peers, including their smoothed round-trip time in milliseconds.
+    pub fn connected_metrics(&self) -> Vec<(SocketAddr, NodeType, Option<u64>)> {
+        self.connected_peers
+            .read()
+            .iter()
+            .map(|(ip, peer)| (*ip, peer.node_type(), self.latency(ip).map(|rtt| rtt.as_millis() as u64)))
+            .collect()
     }
 
     /// Inserts the given peer into the connected peers.
@@ -416,6 +564,27 @@ impl<N: Network> Router<N> {
         self.candidate_peers.write().extend(eligible_peers);
     }
 
+    /// Inserts the given peer host names to the set of candidate hosts.
+    ///
+    /// This method skips adding any given hosts if the combined size exceeds the threshold,
+    /// as the peer providing this list could be subverting the protocol.
+    pub fn insert_candidate_hosts(&self, hosts: &[ListenerAddress]) {
+        // Compute the maximum number of candidate hosts.
+        let max_candidate_hosts = Self::MAXIMUM_CANDIDATE_PEERS.saturating_sub(self.number_of_candidate_hosts());
+        // Ensure the combined number of hosts does not surpass the threshold.
+        let eligible_hosts = hosts
+            .iter()
+            .filter(|host| {
+                // Ensure the host is a host name, is not itself, and is not already connected.
+                host.is_host() && *host != self.listener_address() && !self.is_connected_host(host)
+            })
+            .take(max_candidate_hosts)
+            .cloned();
+
+        // Proceed to insert the eligible candidate hosts.
+        self.candidate_hosts.write().extend(eligible_hosts);
+    }
+
     /// Inserts the given peer into the restricted peers.
     pub fn insert_restricted_peer(&self, peer_ip: SocketAddr) {
         // Remove this peer from the candidate peers, if it exists.
@@ -448,9 +617,17 @@ impl<N: Network> Router<N> {
         // Removes the bidirectional map between the listener address and (ambiguous) peer address.
         self.resolver.remove_peer(&peer_ip);
         // Remove this peer from the connected peers, if it exists.
-        self.connected_peers.write().remove(&peer_ip);
-        // Add the peer to the candidate peers.
-        self.candidate_peers.write().insert(peer_ip);
+        let peer = self.connected_peers.write().remove(&peer_ip);
+        // Add the peer to the candidate peers, or to the candidate hosts if it can only be reached at a host name.
+        match peer.as_ref().map(Peer::listener_address) {
+            Some(ListenerAddress::Port) | None => {
+                self.candidate_peers.write().insert(peer_ip);
+            }
+            Some(listener_address @ ListenerAddress::Host(..)) => {
+                self.candidate_hosts.write().insert(listener_address.clone());
+            }
+            Some(ListenerAddress::Unreachable) => (),
+        }
     }
 
     #[cfg(feature = "test")]
@@ -458,6 +635,12 @@ impl<N: Network> Router<N> {
         self.candidate_peers.write().clear();
     }
 
+    /// Replaces the set of trusted peers with the given peers.
+    /// Note: Peers that are no longer trusted remain connected, and are subject to the usual peer refresh.
+    pub fn set_trusted_peers(&self, peers: &[SocketAddr]) {
+        *self.trusted_peers.write() = peers.iter().copied().collect();
+    }
+
     /// Removes the given address from the candidate peers, if it exists.
     pub fn remove_candidate_peer(&self, peer_ip: SocketAddr) {
         self.candidate_peers.write().remove(&peer_ip);
@@ -55,10 +55,18 @@ pub trait Outbound<N: Network>: Writing<Message = Message<N>> {
         if let Message::BlockRequest(request) = message {
             self.router().cache.insert_outbound_block_request(peer_ip, request);
         }
+        // If the message type is a header request, add it to the cache.
+        if let Message::HeaderRequest(request) = message {
+            self.router().cache.insert_outbound_header_request(peer_ip, request);
+        }
         // If the message type is a puzzle request, increment the cache.
         if matches!(message, Message::PuzzleRequest(_)) {
             self.router().cache.increment_outbound_puzzle_requests(peer_ip);
         }
+        // If the message type is a ping, start measuring the round-trip time.
+        if matches!(message, Message::Ping(_)) {
+            self.router().known_peers().register_ping(peer_addr);
+        }
         // Retrieve the message name.
         let name = message.name();
         // Send the message to the peer.
@@ -136,6 +144,16 @@ pub trait Outbound<N: Network>: Writing<Message = Message<N>> {
             warn!("Attempted to send to a non-connected peer {peer_ip}");
             return false;
         }
+        // Ensure the peer supports the capabilities required by the message.
+        let required_capabilities = message.required_capabilities();
+        if !required_capabilities.is_empty() {
+            let supported =
+                self.router().get_connected_peer(&peer_ip).map_or(false, |peer| peer.supports(required_capabilities));
+            if !supported {
+                trace!("Skipping '{}' to '{peer_ip}' (missing capabilities: {required_capabilities})", message.name());
+                return false;
+            }
+        }
         // Determine whether to send the message.
         match message {
             Message::UnconfirmedSolution(message) => {
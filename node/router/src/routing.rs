@@ -44,6 +44,10 @@ pub trait Routing<N: Network>:
 
     // Start listening for inbound connections.
     async fn enable_listener(&self) {
+        // Skip the listener if the node does not accept inbound connections.
+        if self.router().listener_address().is_unreachable() {
+            return;
+        }
         self.tcp().enable_listener().await.expect("Failed to enable the TCP listener");
     }
 
@@ -23,7 +23,11 @@ use std::{
 };
 
 use snarkos_account::Account;
-use snarkos_node_router::{messages::NodeType, Router};
+use snarkos_node_router::{
+    messages::{ListenerAddress, NodeType},
+    Router,
+};
+use snarkos_node_tcp::Transport;
 use snarkvm::prelude::{block::Block, FromBytes, Network, Testnet3 as CurrentNetwork};
 
 /// A helper macro to print the TCP listening address, along with the connected and connecting peers.
@@ -73,6 +77,10 @@ pub fn initialize_logger(level: u8) {
 pub async fn client(listening_port: u16, max_peers: u16) -> TestRouter<CurrentNetwork> {
     Router::new(
         SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listening_port),
+        Transport::Tcp,
+        Default::default(),
+        None,
+        ListenerAddress::Port,
         NodeType::Client,
         sample_account(),
         &[],
@@ -89,6 +97,10 @@ pub async fn client(listening_port: u16, max_peers: u16) -> TestRouter<CurrentNe
 pub async fn prover(listening_port: u16, max_peers: u16) -> TestRouter<CurrentNetwork> {
     Router::new(
         SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listening_port),
+        Transport::Tcp,
+        Default::default(),
+        None,
+        ListenerAddress::Port,
         NodeType::Prover,
         sample_account(),
         &[],
@@ -105,6 +117,10 @@ pub async fn prover(listening_port: u16, max_peers: u16) -> TestRouter<CurrentNe
 pub async fn validator(listening_port: u16, max_peers: u16) -> TestRouter<CurrentNetwork> {
     Router::new(
         SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listening_port),
+        Transport::Tcp,
+        Default::default(),
+        None,
+        ListenerAddress::Port,
         NodeType::Validator,
         sample_account(),
         &[],
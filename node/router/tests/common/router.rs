@@ -17,6 +17,7 @@ use snarkos_node_router::{
     messages::{
         BlockRequest,
         DisconnectReason,
+        HeaderRequest,
         Message,
         MessageCodec,
         Ping,
@@ -30,6 +31,7 @@ use snarkos_node_router::{
     Router,
     Routing,
 };
+use snarkos_node_sync_locators::SyncHeader;
 use snarkos_node_tcp::{
     protocols::{Disconnect, Handshake, OnConnect, Reading, Writing},
     Connection,
@@ -78,9 +80,10 @@ impl<N: Network> Handshake for TestRouter<N> {
         // Perform the handshake.
         let peer_addr = connection.addr();
         let conn_side = connection.side();
+        let channel_binding = connection.channel_binding();
         let stream = self.borrow_stream(&mut connection);
         let genesis_header = *sample_genesis_block().header();
-        self.router().handshake(peer_addr, stream, conn_side, genesis_header).await?;
+        self.router().handshake(peer_addr, stream, conn_side, genesis_header, channel_binding).await?;
 
         Ok(connection)
     }
@@ -163,6 +166,16 @@ impl<N: Network> Inbound<N> for TestRouter<N> {
         true
     }
 
+    /// Handles a `HeaderRequest` message.
+    fn header_request(&self, _peer_ip: SocketAddr, _message: HeaderRequest) -> bool {
+        true
+    }
+
+    /// Handles a `HeaderResponse` message.
+    fn header_response(&self, _peer_ip: SocketAddr, _headers: Vec<SyncHeader<N>>) -> bool {
+        true
+    }
+
     /// Handles an `Ping` message.
     fn ping(&self, _peer_ip: SocketAddr, _message: Ping<N>) -> bool {
         true
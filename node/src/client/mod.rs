@@ -17,18 +17,21 @@ mod router;
 use crate::traits::NodeInterface;
 use snarkos_account::Account;
 use snarkos_node_bft::ledger_service::CoreLedgerService;
-use snarkos_node_rest::Rest;
+use snarkos_node_rest::{Rest, RestConfig};
 use snarkos_node_router::{
-    messages::{Message, NodeType, UnconfirmedSolution},
+    messages::{ListenerAddress, Message, NodeType, UnconfirmedSolution},
     Heartbeat,
     Inbound,
     Outbound,
     Router,
     Routing,
 };
-use snarkos_node_sync::{BlockSync, BlockSyncMode};
+use snarkos_node_sync::{locators::TrustedCheckpoints, BlockSync, BlockSyncMode, RestSyncService};
 use snarkos_node_tcp::{
-    protocols::{Disconnect, Handshake, OnConnect, Reading, Writing},
+    protocols::{Disconnect, Handshake, OnConnect, Priority, Reading, Writing},
+    BandwidthLimits,
+    Budget,
+    Transport,
     P2P,
 };
 use snarkvm::{
@@ -61,6 +64,8 @@ pub struct Client<N: Network, C: ConsensusStorage<N>> {
     rest: Option<Rest<N, C, Self>>,
     /// The sync module.
     sync: Arc<BlockSync<N>>,
+    /// The service to sync blocks from the REST servers of other nodes, if any.
+    rest_sync: Option<RestSyncService<N>>,
     /// The genesis block.
     genesis: Block<N>,
     /// The coinbase puzzle.
@@ -75,11 +80,18 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
     /// Initializes a new client node.
     pub async fn new(
         node_ip: SocketAddr,
+        node_transport: Transport,
+        bandwidth_limits: BandwidthLimits,
+        node_socks5_proxy: Option<SocketAddr>,
+        node_listener_address: ListenerAddress,
         rest_ip: Option<SocketAddr>,
+        rest_config: RestConfig,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
         cdn: Option<String>,
+        checkpoints: TrustedCheckpoints<N>,
+        sync_endpoints: &[String],
         dev: Option<u16>,
     ) -> Result<Self> {
         // Initialize the signal handler.
@@ -101,11 +113,20 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
         // Initialize the ledger service.
         let ledger_service = Arc::new(CoreLedgerService::<N, C>::new(ledger.clone()));
         // Initialize the sync module.
-        let sync = BlockSync::new(BlockSyncMode::Router, ledger_service.clone());
+        let sync = BlockSync::new(BlockSyncMode::Router, ledger_service.clone()).with_trusted_checkpoints(checkpoints);
+        // Initialize the service to sync from the REST servers, if any.
+        let rest_sync = match sync_endpoints.is_empty() {
+            true => None,
+            false => Some(RestSyncService::new(sync.clone(), sync_endpoints).await?),
+        };
 
         // Initialize the node router.
         let router = Router::new(
             node_ip,
+            node_transport,
+            bandwidth_limits,
+            node_socks5_proxy,
+            node_listener_address,
             NodeType::Client,
             account,
             trusted_peers,
@@ -121,6 +142,7 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
             router,
             rest: None,
             sync: Arc::new(sync),
+            rest_sync,
             genesis,
             coinbase_puzzle,
             handles: Default::default(),
@@ -129,7 +151,19 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest = Some(Rest::start(rest_ip, None, ledger.clone(), Arc::new(node.clone()))?);
+            node.rest = Some(Rest::start(
+                rest_ip,
+                None,
+                ledger.clone(),
+                (*node.sync).clone(),
+                Arc::new(node.clone()),
+                rest_config,
+                &crate::rest_storage_dir(N::ID, dev),
+            )?);
+        }
+        // Record the mapping values of each new block in the archive, if enabled.
+        if let Some(mapping_archive) = node.rest.as_ref().and_then(|rest| rest.mapping_archive()) {
+            ledger_service.add_observer(Arc::new(mapping_archive.clone()));
         }
         // Initialize the routing.
         node.initialize_routing().await;
@@ -173,6 +207,22 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
                 node.sync.try_block_sync(&node).await;
             }
         }));
+
+        // Start the loop updating the block locators of the REST servers.
+        if let Some(rest_sync) = self.rest_sync.clone() {
+            let node = self.clone();
+            self.handles.lock().push(tokio::spawn(async move {
+                loop {
+                    // If the Ctrl-C handler registered the signal, stop the node.
+                    if node.shutdown.load(std::sync::atomic::Ordering::Relaxed) {
+                        break;
+                    }
+                    // Update the block locators of the REST servers.
+                    rest_sync.update_locators().await;
+                    tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+                }
+            }));
+        }
     }
 
     /// Spawns a task with the given future; it should only be used for long-running tasks.
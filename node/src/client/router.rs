@@ -18,7 +18,10 @@ use snarkos_node_router::{
         BlockRequest,
         BlockResponse,
         DataBlocks,
+        DataHeaders,
         DisconnectReason,
+        HeaderRequest,
+        HeaderResponse,
         MessageCodec,
         Ping,
         Pong,
@@ -27,6 +30,7 @@ use snarkos_node_router::{
     },
     Routing,
 };
+use snarkos_node_sync::locators::SyncHeader;
 use snarkos_node_tcp::{Connection, ConnectionSide, Tcp};
 use snarkvm::{
     ledger::narwhal::Data,
@@ -50,9 +54,10 @@ impl<N: Network, C: ConsensusStorage<N>> Handshake for Client<N, C> {
         // Perform the handshake.
         let peer_addr = connection.addr();
         let conn_side = connection.side();
+        let channel_binding = connection.channel_binding();
         let stream = self.borrow_stream(&mut connection);
         let genesis_header = *self.genesis.header();
-        self.router.handshake(peer_addr, stream, conn_side, genesis_header).await?;
+        self.router.handshake(peer_addr, stream, conn_side, genesis_header, channel_binding).await?;
 
         Ok(connection)
     }
@@ -100,6 +105,14 @@ impl<N: Network, C: ConsensusStorage<N>> Writing for Client<N, C> {
     fn codec(&self, _addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
         Default::default()
     }
+
+    /// Returns the priority of the message; block and header responses are bulk traffic, which is sent on a separate stream if supported.
+    fn priority(&self, message: &Self::Message) -> Priority {
+        match message {
+            Message::BlockResponse(..) | Message::HeaderResponse(..) => Priority::Low,
+            _ => Priority::Normal,
+        }
+    }
 }
 
 #[async_trait]
@@ -113,6 +126,14 @@ impl<N: Network, C: ConsensusStorage<N>> Reading for Client<N, C> {
         Default::default()
     }
 
+    /// Returns the bandwidth budget of the message; block and header responses are sync traffic.
+    fn budget(&self, message: &Self::Message) -> Budget {
+        match message {
+            Message::BlockResponse(..) | Message::HeaderResponse(..) => Budget::Sync,
+            _ => Budget::Consensus,
+        }
+    }
+
     /// Processes a message received from the network.
     async fn process_message(&self, peer_addr: SocketAddr, message: Self::Message) -> io::Result<()> {
         // Process the message. Disconnect if the peer violated the protocol.
@@ -139,6 +160,12 @@ impl<N: Network, C: ConsensusStorage<N>> CommunicationService for Client<N, C> {
         Message::BlockRequest(BlockRequest { start_height, end_height })
     }
 
+    /// Prepares a header request to be sent.
+    fn prepare_header_request(start_height: u32, end_height: u32) -> Option<Self::Message> {
+        debug_assert!(start_height < end_height, "Invalid header request format");
+        Some(Message::HeaderRequest(HeaderRequest { start_height, end_height }))
+    }
+
     /// Sends the given message to specified peer.
     ///
     /// This function returns as soon as the message is queued to be sent,
@@ -149,6 +176,15 @@ impl<N: Network, C: ConsensusStorage<N>> CommunicationService for Client<N, C> {
         peer_ip: SocketAddr,
         message: Self::Message,
     ) -> Option<tokio::sync::oneshot::Receiver<io::Result<()>>> {
+        // If the peer is a REST server, fetch the requested blocks from it.
+        if let Some(rest_sync) = self.rest_sync.as_ref().filter(|rest_sync| rest_sync.contains(&peer_ip)) {
+            return match message {
+                Message::BlockRequest(BlockRequest { start_height, end_height }) => {
+                    rest_sync.send(peer_ip, start_height..end_height).await
+                }
+                _ => None,
+            };
+        }
         Outbound::send(self, peer_ip, message)
     }
 }
@@ -196,6 +232,41 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Client<N, C> {
         }
     }
 
+    /// Handles a `HeaderRequest` message.
+    fn header_request(&self, peer_ip: SocketAddr, message: HeaderRequest) -> bool {
+        let HeaderRequest { start_height, end_height } = &message;
+
+        // Retrieve the blocks within the requested range, up to the latest block.
+        let end_height = (*end_height).min(self.ledger.latest_height() + 1);
+        if *start_height >= end_height {
+            warn!("Peer '{peer_ip}' requested headers above the latest block ({start_height})");
+            return false;
+        }
+        let headers = match self.ledger.get_blocks(*start_height..end_height) {
+            Ok(blocks) => Data::Object(DataHeaders(blocks.iter().map(SyncHeader::from).collect())),
+            Err(error) => {
+                error!("Failed to retrieve headers {start_height} to {end_height} from the ledger - {error}");
+                return false;
+            }
+        };
+        // Send the `HeaderResponse` message to the peer.
+        Outbound::send(self, peer_ip, Message::HeaderResponse(HeaderResponse { request: message, headers }));
+        true
+    }
+
+    /// Handles a `HeaderResponse` message.
+    fn header_response(&self, peer_ip: SocketAddr, headers: Vec<SyncHeader<N>>) -> bool {
+        // Verify the headers, and insert them into the sync pool.
+        if let Err(error) = self.sync.process_header_response(peer_ip, headers) {
+            warn!("{error}");
+            return false;
+        }
+        // Request the next headers.
+        let node = self.clone();
+        tokio::spawn(async move { node.sync.try_header_sync(&node).await });
+        true
+    }
+
     /// Processes the block locators and sends back a `Pong` message.
     fn ping(&self, peer_ip: SocketAddr, message: Ping<N>) -> bool {
         // Check if the sync module is in router mode.
@@ -217,6 +288,14 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Client<N, C> {
 
     /// Sleeps for a period and then sends a `Ping` message to the peer.
     fn pong(&self, peer_ip: SocketAddr, _message: Pong) -> bool {
+        // Check if the sync module is in router mode.
+        if self.sync.mode().is_router() {
+            // If the round-trip time to the peer was measured, then update the peer in the sync pool.
+            if let Some(rtt) = self.router().latency(&peer_ip) {
+                self.sync.update_peer_latency(peer_ip, rtt);
+            }
+        }
+
         // Spawn an asynchronous task for the `Ping` request.
         let self_ = self.clone();
         tokio::spawn(async move {
@@ -45,6 +45,12 @@ pub use node::*;
 mod traits;
 pub use traits::*;
 
+/// Returns the directory of the storage of the REST server.
+/// Note: The directory is kept in the ledger directory, so that it is removed along with the ledger.
+pub(crate) fn rest_storage_dir(network: u16, dev: Option<u16>) -> std::path::PathBuf {
+    aleo_std::aleo_ledger_dir(network, dev).join("rest")
+}
+
 /// A helper to log instructions to recover.
 pub fn log_clean_error(dev: Option<u16>) {
     match dev {
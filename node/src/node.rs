@@ -14,7 +14,13 @@
 
 use crate::{traits::NodeInterface, Client, Prover, Validator};
 use snarkos_account::Account;
-use snarkos_node_router::messages::NodeType;
+use snarkos_node_rest::RestConfig;
+use snarkos_node_router::{
+    messages::{ListenerAddress, NodeType},
+    Routing,
+};
+use snarkos_node_sync::locators::TrustedCheckpoints;
+use snarkos_node_tcp::{BandwidthLimits, Transport};
 use snarkvm::prelude::{
     block::Block,
     store::helpers::{memory::ConsensusMemory, rocksdb::ConsensusDB},
@@ -36,12 +42,28 @@ pub enum Node<N: Network> {
     Client(Arc<Client<N, ConsensusDB<N>>>),
 }
 
+impl<N: Network> Clone for Node<N> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Validator(node) => Self::Validator(node.clone()),
+            Self::Prover(node) => Self::Prover(node.clone()),
+            Self::Client(node) => Self::Client(node.clone()),
+        }
+    }
+}
+
 impl<N: Network> Node<N> {
     /// Initializes a new validator node.
     pub async fn new_validator(
         node_ip: SocketAddr,
+        node_transport: Transport,
+        bandwidth_limits: BandwidthLimits,
+        node_socks5_proxy: Option<SocketAddr>,
+        node_listener_address: ListenerAddress,
         rest_ip: Option<SocketAddr>,
+        rest_config: RestConfig,
         bft_ip: Option<SocketAddr>,
+        bft_transport: Transport,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         trusted_validators: &[SocketAddr],
@@ -50,33 +72,91 @@ impl<N: Network> Node<N> {
         dev: Option<u16>,
     ) -> Result<Self> {
         Ok(Self::Validator(Arc::new(
-            Validator::new(node_ip, rest_ip, bft_ip, account, trusted_peers, trusted_validators, genesis, cdn, dev)
-                .await?,
+            Validator::new(
+                node_ip,
+                node_transport,
+                bandwidth_limits,
+                node_socks5_proxy,
+                node_listener_address,
+                rest_ip,
+                rest_config,
+                bft_ip,
+                bft_transport,
+                account,
+                trusted_peers,
+                trusted_validators,
+                genesis,
+                cdn,
+                dev,
+            )
+            .await?,
         )))
     }
 
     /// Initializes a new prover node.
     pub async fn new_prover(
         node_ip: SocketAddr,
+        node_transport: Transport,
+        bandwidth_limits: BandwidthLimits,
+        node_socks5_proxy: Option<SocketAddr>,
+        node_listener_address: ListenerAddress,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
         dev: Option<u16>,
     ) -> Result<Self> {
-        Ok(Self::Prover(Arc::new(Prover::new(node_ip, account, trusted_peers, genesis, dev).await?)))
+        Ok(Self::Prover(Arc::new(
+            Prover::new(
+                node_ip,
+                node_transport,
+                bandwidth_limits,
+                node_socks5_proxy,
+                node_listener_address,
+                account,
+                trusted_peers,
+                genesis,
+                dev,
+            )
+            .await?,
+        )))
     }
 
     /// Initializes a new client node.
     pub async fn new_client(
         node_ip: SocketAddr,
+        node_transport: Transport,
+        bandwidth_limits: BandwidthLimits,
+        node_socks5_proxy: Option<SocketAddr>,
+        node_listener_address: ListenerAddress,
         rest_ip: Option<SocketAddr>,
+        rest_config: RestConfig,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
         cdn: Option<String>,
+        checkpoints: TrustedCheckpoints<N>,
+        sync_endpoints: &[String],
         dev: Option<u16>,
     ) -> Result<Self> {
-        Ok(Self::Client(Arc::new(Client::new(node_ip, rest_ip, account, trusted_peers, genesis, cdn, dev).await?)))
+        Ok(Self::Client(Arc::new(
+            Client::new(
+                node_ip,
+                node_transport,
+                bandwidth_limits,
+                node_socks5_proxy,
+                node_listener_address,
+                rest_ip,
+                rest_config,
+                account,
+                trusted_peers,
+                genesis,
+                cdn,
+                checkpoints,
+                sync_endpoints,
+                dev,
+            )
+            .await?,
+        )))
     }
 
     /// Returns the node type.
@@ -123,4 +203,38 @@ impl<N: Network> Node<N> {
             Self::Client(node) => node.is_dev(),
         }
     }
+
+    /// Returns the latest block height of the node.
+    pub fn latest_height(&self) -> u32 {
+        match self {
+            Self::Validator(node) => node.ledger().latest_height(),
+            Self::Prover(node) => node.latest_block_height(),
+            Self::Client(node) => node.ledger().latest_height(),
+        }
+    }
+
+    /// Returns the current BFT round of the node, if the node is a validator.
+    pub fn current_round(&self) -> Option<u64> {
+        match self {
+            Self::Validator(node) => Some(node.current_round()),
+            Self::Prover(_) | Self::Client(_) => None,
+        }
+    }
+
+    /// Replaces the trusted peers of the node.
+    pub fn set_trusted_peers(&self, peers: &[SocketAddr]) {
+        match self {
+            Self::Validator(node) => node.router().set_trusted_peers(peers),
+            Self::Prover(node) => node.router().set_trusted_peers(peers),
+            Self::Client(node) => node.router().set_trusted_peers(peers),
+        }
+    }
+
+    /// Replaces the trusted validators of the node.
+    /// Note: This is a no-op for non-validator nodes.
+    pub fn set_trusted_validators(&self, validators: &[SocketAddr]) {
+        if let Self::Validator(node) = self {
+            node.set_trusted_validators(validators);
+        }
+    }
 }
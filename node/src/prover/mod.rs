@@ -18,7 +18,7 @@ use crate::traits::NodeInterface;
 use snarkos_account::Account;
 use snarkos_node_bft::ledger_service::ProverLedgerService;
 use snarkos_node_router::{
-    messages::{Message, NodeType, UnconfirmedSolution},
+    messages::{ListenerAddress, Message, NodeType, UnconfirmedSolution},
     Heartbeat,
     Inbound,
     Outbound,
@@ -28,6 +28,8 @@ use snarkos_node_router::{
 use snarkos_node_sync::{BlockSync, BlockSyncMode};
 use snarkos_node_tcp::{
     protocols::{Disconnect, Handshake, OnConnect, Reading, Writing},
+    BandwidthLimits,
+    Transport,
     P2P,
 };
 use snarkvm::{
@@ -85,6 +87,10 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
     /// Initializes a new prover node.
     pub async fn new(
         node_ip: SocketAddr,
+        node_transport: Transport,
+        bandwidth_limits: BandwidthLimits,
+        node_socks5_proxy: Option<SocketAddr>,
+        node_listener_address: ListenerAddress,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
@@ -101,6 +107,10 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
         // Initialize the node router.
         let router = Router::new(
             node_ip,
+            node_transport,
+            bandwidth_limits,
+            node_socks5_proxy,
+            node_listener_address,
             NodeType::Prover,
             account,
             trusted_peers,
@@ -161,6 +171,11 @@ impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Prover<N, C> {
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
+    /// Returns the height of the latest block header, or `0` if no block header has been received yet.
+    pub fn latest_block_height(&self) -> u32 {
+        self.latest_block_header.read().as_ref().map_or(0, |header| header.height())
+    }
+
     /// Initialize a new instance of the coinbase puzzle.
     async fn initialize_coinbase_puzzle(&self) {
         for _ in 0..self.max_puzzle_instances {
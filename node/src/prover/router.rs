@@ -17,6 +17,7 @@ use super::*;
 use snarkos_node_router::messages::{
     BlockRequest,
     DisconnectReason,
+    HeaderRequest,
     Message,
     MessageCodec,
     Ping,
@@ -24,6 +25,7 @@ use snarkos_node_router::messages::{
     PuzzleRequest,
     UnconfirmedTransaction,
 };
+use snarkos_node_sync::locators::SyncHeader;
 use snarkos_node_tcp::{Connection, ConnectionSide, Tcp};
 use snarkvm::prelude::{block::Transaction, Network};
 
@@ -43,9 +45,10 @@ impl<N: Network, C: ConsensusStorage<N>> Handshake for Prover<N, C> {
         // Perform the handshake.
         let peer_addr = connection.addr();
         let conn_side = connection.side();
+        let channel_binding = connection.channel_binding();
         let stream = self.borrow_stream(&mut connection);
         let genesis_header = *self.genesis.header();
-        self.router.handshake(peer_addr, stream, conn_side, genesis_header).await?;
+        self.router.handshake(peer_addr, stream, conn_side, genesis_header, channel_binding).await?;
 
         Ok(connection)
     }
@@ -151,6 +154,18 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Prover<N, C> {
         false
     }
 
+    /// Handles a `HeaderRequest` message.
+    fn header_request(&self, peer_ip: SocketAddr, _message: HeaderRequest) -> bool {
+        debug!("Disconnecting '{peer_ip}' for the following reason - {:?}", DisconnectReason::ProtocolViolation);
+        false
+    }
+
+    /// Handles a `HeaderResponse` message.
+    fn header_response(&self, peer_ip: SocketAddr, _headers: Vec<SyncHeader<N>>) -> bool {
+        debug!("Disconnecting '{peer_ip}' for the following reason - {:?}", DisconnectReason::ProtocolViolation);
+        false
+    }
+
     /// Processes the block locators and sends back a `Pong` message.
     fn ping(&self, peer_ip: SocketAddr, message: Ping<N>) -> bool {
         // Check if the sync module is in router mode.
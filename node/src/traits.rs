@@ -46,7 +46,7 @@ pub trait NodeInterface<N: Network>: Routing<N> {
     }
 
     /// Handles OS signals for the node to intercept and perform a clean shutdown.
-    /// Note: Only Ctrl-C is supported; it should work on both Unix-family systems and Windows.
+    /// Note: Ctrl-C is supported on both Unix-family systems and Windows; SIGTERM is supported on Unix-family systems.
     fn handle_signals() -> Arc<OnceCell<Self>> {
         // In order for the signal handler to be started as early as possible, a reference to the node needs
         // to be passed to it at a later time.
@@ -54,14 +54,14 @@ pub trait NodeInterface<N: Network>: Routing<N> {
 
         let node_clone = node.clone();
         tokio::task::spawn(async move {
-            match tokio::signal::ctrl_c().await {
+            match shutdown_signal().await {
                 Ok(()) => {
                     if let Some(node) = node_clone.get() {
                         node.shut_down().await;
                     }
                     std::process::exit(0);
                 }
-                Err(error) => error!("tokio::signal::ctrl_c encountered an error: {}", error),
+                Err(error) => error!("Failed to listen for the shutdown signals: {}", error),
             }
         });
 
@@ -71,3 +71,24 @@ pub trait NodeInterface<N: Network>: Routing<N> {
     /// Shuts down the node.
     async fn shut_down(&self);
 }
+
+/// Resolves once the process receives Ctrl-C, or SIGTERM on Unix-family systems.
+async fn shutdown_signal() -> std::io::Result<()> {
+    #[cfg(target_family = "unix")]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut sigterm = signal(SignalKind::terminate())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result,
+            _ = sigterm.recv() => {
+                info!("Received SIGTERM");
+                Ok(())
+            }
+        }
+    }
+    #[cfg(not(target_family = "unix"))]
+    {
+        tokio::signal::ctrl_c().await
+    }
+}
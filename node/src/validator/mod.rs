@@ -18,9 +18,9 @@ use crate::traits::NodeInterface;
 use snarkos_account::Account;
 use snarkos_node_bft::{helpers::init_primary_channels, ledger_service::CoreLedgerService};
 use snarkos_node_consensus::Consensus;
-use snarkos_node_rest::Rest;
+use snarkos_node_rest::{Rest, RestConfig};
 use snarkos_node_router::{
-    messages::{NodeType, PuzzleResponse, UnconfirmedSolution, UnconfirmedTransaction},
+    messages::{ListenerAddress, NodeType, PuzzleResponse, UnconfirmedSolution, UnconfirmedTransaction},
     Heartbeat,
     Inbound,
     Outbound,
@@ -29,7 +29,10 @@ use snarkos_node_router::{
 };
 use snarkos_node_sync::{BlockSync, BlockSyncMode};
 use snarkos_node_tcp::{
-    protocols::{Disconnect, Handshake, OnConnect, Reading, Writing},
+    protocols::{Disconnect, Handshake, OnConnect, Priority, Reading, Writing},
+    BandwidthLimits,
+    Budget,
+    Transport,
     P2P,
 };
 use snarkvm::prelude::{
@@ -73,8 +76,14 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
     /// Initializes a new validator node.
     pub async fn new(
         node_ip: SocketAddr,
+        node_transport: Transport,
+        bandwidth_limits: BandwidthLimits,
+        node_socks5_proxy: Option<SocketAddr>,
+        node_listener_address: ListenerAddress,
         rest_ip: Option<SocketAddr>,
+        rest_config: RestConfig,
         bft_ip: Option<SocketAddr>,
+        bft_transport: Transport,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         trusted_validators: &[SocketAddr],
@@ -104,7 +113,15 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         let sync = BlockSync::new(BlockSyncMode::Gateway, ledger_service.clone());
 
         // Initialize the consensus.
-        let mut consensus = Consensus::new(account.clone(), ledger_service, bft_ip, trusted_validators, dev)?;
+        let mut consensus = Consensus::new(
+            account.clone(),
+            ledger_service.clone(),
+            bft_ip,
+            bft_transport,
+            bandwidth_limits,
+            trusted_validators,
+            dev,
+        )?;
         // Initialize the primary channels.
         let (primary_sender, primary_receiver) = init_primary_channels::<N>();
         // Start the consensus.
@@ -113,6 +130,10 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         // Initialize the node router.
         let router = Router::new(
             node_ip,
+            node_transport,
+            bandwidth_limits,
+            node_socks5_proxy,
+            node_listener_address,
             NodeType::Validator,
             account,
             trusted_peers,
@@ -136,7 +157,21 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest = Some(Rest::start(rest_ip, Some(consensus), ledger.clone(), Arc::new(node.clone()))?);
+            // Note: The block sync of the BFT is exposed, as it tracks the peers' block locators.
+            let sync = consensus.bft().primary().sync().block_sync().clone();
+            node.rest = Some(Rest::start(
+                rest_ip,
+                Some(consensus),
+                ledger.clone(),
+                sync,
+                Arc::new(node.clone()),
+                rest_config,
+                &crate::rest_storage_dir(N::ID, dev),
+            )?);
+        }
+        // Record the mapping values of each new block in the archive, if enabled.
+        if let Some(mapping_archive) = node.rest.as_ref().and_then(|rest| rest.mapping_archive()) {
+            ledger_service.add_observer(Arc::new(mapping_archive.clone()));
         }
         // Initialize the routing.
         node.initialize_routing().await;
@@ -157,6 +192,16 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
     pub fn rest(&self) -> &Option<Rest<N, C, Self>> {
         &self.rest
     }
+
+    /// Returns the current round of the BFT.
+    pub fn current_round(&self) -> u64 {
+        self.consensus.bft().primary().current_round()
+    }
+
+    /// Replaces the trusted validators of the BFT.
+    pub fn set_trusted_validators(&self, validators: &[SocketAddr]) {
+        self.consensus.bft().primary().gateway().set_trusted_validators(validators);
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
@@ -478,8 +523,14 @@ mod tests {
 
         let validator = Validator::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::new(
             node,
+            Transport::Tcp,
+            Default::default(),
+            None,
+            ListenerAddress::Port,
             Some(rest),
+            RestConfig::default(),
             None,
+            Transport::Tcp,
             account,
             &[],
             &[],
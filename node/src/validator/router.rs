@@ -17,13 +17,17 @@ use snarkos_node_router::messages::{
     BlockRequest,
     BlockResponse,
     DataBlocks,
+    DataHeaders,
     DisconnectReason,
+    HeaderRequest,
+    HeaderResponse,
     Message,
     MessageCodec,
     Ping,
     Pong,
     UnconfirmedTransaction,
 };
+use snarkos_node_sync::locators::SyncHeader;
 use snarkos_node_tcp::{Connection, ConnectionSide, Tcp};
 use snarkvm::{
     ledger::narwhal::Data,
@@ -46,9 +50,10 @@ impl<N: Network, C: ConsensusStorage<N>> Handshake for Validator<N, C> {
         // Perform the handshake.
         let peer_addr = connection.addr();
         let conn_side = connection.side();
+        let channel_binding = connection.channel_binding();
         let stream = self.borrow_stream(&mut connection);
         let genesis_header = self.ledger.get_header(0).map_err(|e| error(format!("{e}")))?;
-        self.router.handshake(peer_addr, stream, conn_side, genesis_header).await?;
+        self.router.handshake(peer_addr, stream, conn_side, genesis_header, channel_binding).await?;
 
         Ok(connection)
     }
@@ -96,6 +101,14 @@ impl<N: Network, C: ConsensusStorage<N>> Writing for Validator<N, C> {
     fn codec(&self, _addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
         Default::default()
     }
+
+    /// Returns the priority of the message; block and header responses are bulk traffic, which is sent on a separate stream if supported.
+    fn priority(&self, message: &Self::Message) -> Priority {
+        match message {
+            Message::BlockResponse(..) | Message::HeaderResponse(..) => Priority::Low,
+            _ => Priority::Normal,
+        }
+    }
 }
 
 #[async_trait]
@@ -109,6 +122,14 @@ impl<N: Network, C: ConsensusStorage<N>> Reading for Validator<N, C> {
         Default::default()
     }
 
+    /// Returns the bandwidth budget of the message; block and header responses are sync traffic.
+    fn budget(&self, message: &Self::Message) -> Budget {
+        match message {
+            Message::BlockResponse(..) | Message::HeaderResponse(..) => Budget::Sync,
+            _ => Budget::Consensus,
+        }
+    }
+
     /// Processes a message received from the network.
     async fn process_message(&self, peer_addr: SocketAddr, message: Self::Message) -> io::Result<()> {
         // Process the message. Disconnect if the peer violated the protocol.
@@ -170,6 +191,35 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Validator<N, C> {
         }
     }
 
+    /// Retrieves the headers within the header request range, and returns the header response to the peer.
+    fn header_request(&self, peer_ip: SocketAddr, message: HeaderRequest) -> bool {
+        let HeaderRequest { start_height, end_height } = &message;
+
+        // Retrieve the blocks within the requested range, up to the latest block.
+        let end_height = (*end_height).min(self.ledger.latest_height() + 1);
+        if *start_height >= end_height {
+            warn!("Peer '{peer_ip}' requested headers above the latest block ({start_height})");
+            return false;
+        }
+        let headers = match self.ledger.get_blocks(*start_height..end_height) {
+            Ok(blocks) => Data::Object(DataHeaders(blocks.iter().map(SyncHeader::from).collect())),
+            Err(error) => {
+                error!("Failed to retrieve headers {start_height} to {end_height} from the ledger - {error}");
+                return false;
+            }
+        };
+        // Send the `HeaderResponse` message to the peer.
+        Outbound::send(self, peer_ip, Message::HeaderResponse(HeaderResponse { request: message, headers }));
+        true
+    }
+
+    /// Handles a `HeaderResponse` message.
+    fn header_response(&self, peer_ip: SocketAddr, _headers: Vec<SyncHeader<N>>) -> bool {
+        // Note: The validator syncs through the BFT, and does not request headers from the router.
+        debug!("Disconnecting '{peer_ip}' for the following reason - {:?}", DisconnectReason::ProtocolViolation);
+        false
+    }
+
     /// Processes the block locators and sends back a `Pong` message.
     fn ping(&self, peer_ip: SocketAddr, message: Ping<N>) -> bool {
         // Check if the sync module is in router mode.
@@ -191,6 +241,14 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Validator<N, C> {
 
     /// Sleeps for a period and then sends a `Ping` message to the peer.
     fn pong(&self, peer_ip: SocketAddr, _message: Pong) -> bool {
+        // Check if the sync module is in router mode.
+        if self.sync.mode().is_router() {
+            // If the round-trip time to the peer was measured, then update the peer in the sync pool.
+            if let Some(rtt) = self.router().latency(&peer_ip) {
+                self.sync.update_peer_latency(peer_ip, rtt);
+            }
+        }
+
         // Spawn an asynchronous task for the `Ping` request.
         let self_ = self.clone();
         tokio::spawn(async move {
@@ -28,6 +28,11 @@ pub trait CommunicationService: Send + Sync {
     /// Prepares a block request to be sent.
     fn prepare_block_request(start: u32, end: u32) -> Self::Message;
 
+    /// Prepares a header request to be sent, if the service supports header-first sync.
+    fn prepare_header_request(_start: u32, _end: u32) -> Option<Self::Message> {
+        None
+    }
+
     /// Sends the given message to specified peer.
     ///
     /// This function returns as soon as the message is queued to be sent,
@@ -0,0 +1,159 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::BlockLocators;
+use snarkvm::prelude::{Address, CryptoRng, Field, Network, PrivateKey, Rng, Signature};
+
+use anyhow::{bail, ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// A block hash at a given height, signed by a trusted party.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct TrustedCheckpoint<N: Network> {
+    /// The block height.
+    pub height: u32,
+    /// The block hash.
+    pub hash: N::BlockHash,
+    /// The signature of the trusted party, over the block height and hash.
+    pub signature: Signature<N>,
+}
+
+impl<N: Network> TrustedCheckpoint<N> {
+    /// Signs the given block height and hash with the given private key.
+    pub fn sign<R: Rng + CryptoRng>(
+        private_key: &PrivateKey<N>,
+        height: u32,
+        hash: N::BlockHash,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let signature = Signature::sign(private_key, &Self::message(height, hash), rng)?;
+        Ok(Self { height, hash, signature })
+    }
+
+    /// Returns `true` if the checkpoint is signed by the given address.
+    pub fn is_signed_by(&self, signer: &Address<N>) -> bool {
+        self.signature.verify(signer, &Self::message(self.height, self.hash))
+    }
+
+    /// Returns the message that is signed for the given block height and hash.
+    fn message(height: u32, hash: N::BlockHash) -> [Field<N>; 2] {
+        [Field::from_u32(height), *hash]
+    }
+}
+
+/// The set of trusted checkpoints, which every block locator, header, and block must be consistent with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrustedCheckpoints<N: Network> {
+    /// The map of block height to the trusted block hash.
+    checkpoints: Arc<BTreeMap<u32, N::BlockHash>>,
+}
+
+impl<N: Network> Default for TrustedCheckpoints<N> {
+    fn default() -> Self {
+        Self { checkpoints: Default::default() }
+    }
+}
+
+impl<N: Network> TrustedCheckpoints<N> {
+    /// Initializes the trusted checkpoints, ensuring each is signed by one of the given trusted signers.
+    pub fn new(checkpoints: Vec<TrustedCheckpoint<N>>, signers: &[Address<N>]) -> Result<Self> {
+        ensure!(checkpoints.is_empty() || !signers.is_empty(), "The trusted checkpoints require a trusted signer");
+
+        let mut map = BTreeMap::new();
+        for checkpoint in checkpoints {
+            let height = checkpoint.height;
+            // Ensure the checkpoint is signed by a trusted signer.
+            if !signers.iter().any(|signer| checkpoint.is_signed_by(signer)) {
+                bail!("The checkpoint at block {height} is not signed by a trusted signer");
+            }
+            // Ensure the checkpoint does not conflict with another checkpoint.
+            if let Some(hash) = map.insert(height, checkpoint.hash) {
+                ensure!(hash == checkpoint.hash, "There are conflicting checkpoints at block {height}");
+            }
+        }
+        Ok(Self { checkpoints: Arc::new(map) })
+    }
+
+    /// Returns `true` if there are no trusted checkpoints.
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+
+    /// Returns the trusted block hash at the given height, if there is a checkpoint.
+    pub fn get(&self, height: u32) -> Option<N::BlockHash> {
+        self.checkpoints.get(&height).copied()
+    }
+
+    /// Returns the height of the latest trusted checkpoint, if any.
+    pub fn latest_height(&self) -> Option<u32> {
+        self.checkpoints.keys().next_back().copied()
+    }
+
+    /// Ensures the given block hash is consistent with the checkpoint at the given height, if there is one.
+    pub fn check_hash(&self, height: u32, hash: N::BlockHash) -> Result<()> {
+        match self.get(height) {
+            Some(trusted_hash) if trusted_hash != hash => {
+                bail!("Block {height} ('{hash}') conflicts with the trusted checkpoint ('{trusted_hash}')")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Ensures the given block locators are consistent with the checkpoints.
+    pub fn check_locators(&self, locators: &BlockLocators<N>) -> Result<()> {
+        for (height, trusted_hash) in self.checkpoints.iter() {
+            if let Some(hash) = locators.get_hash(*height) {
+                ensure!(hash == *trusted_hash, "Block locator {height} conflicts with the trusted checkpoint");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{sample_block_locators, sample_block_locators_with_fork};
+    use snarkvm::utilities::TestRng;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_trusted_checkpoints() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let signer = Address::try_from(&private_key).unwrap();
+        let hash = |height: u32| Field::<CurrentNetwork>::from_u32(height).into();
+
+        // Ensure the checkpoints must be signed by a trusted signer.
+        let checkpoint = TrustedCheckpoint::sign(&private_key, 15, hash(15), rng).unwrap();
+        assert!(checkpoint.is_signed_by(&signer));
+        let other_signer = Address::try_from(&PrivateKey::new(rng).unwrap()).unwrap();
+        assert!(TrustedCheckpoints::new(vec![checkpoint.clone()], &[other_signer]).is_err());
+        assert!(TrustedCheckpoints::new(vec![checkpoint.clone()], &[]).is_err());
+
+        let checkpoints = TrustedCheckpoints::new(vec![checkpoint], &[other_signer, signer]).unwrap();
+        assert_eq!(checkpoints.latest_height(), Some(15));
+        assert!(checkpoints.check_hash(15, hash(15)).is_ok());
+        assert!(checkpoints.check_hash(15, hash(16)).is_err());
+        assert!(checkpoints.check_hash(16, hash(16)).is_ok());
+
+        // Ensure the block locators must be consistent with the checkpoints.
+        assert!(checkpoints.check_locators(&sample_block_locators(20)).is_ok());
+        assert!(checkpoints.check_locators(&sample_block_locators_with_fork(20, 16)).is_ok());
+        assert!(checkpoints.check_locators(&sample_block_locators_with_fork(20, 15)).is_err());
+    }
+}
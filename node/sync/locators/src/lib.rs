@@ -19,3 +19,9 @@ extern crate tracing;
 
 mod block_locators;
 pub use block_locators::*;
+
+mod checkpoints;
+pub use checkpoints::*;
+
+mod sync_header;
+pub use sync_header::*;
@@ -0,0 +1,213 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    ledger::{
+        authority::Authority,
+        block::{Block, Header},
+        committee::Committee,
+    },
+    prelude::{FromBytes, IoResult, Network, Read, ToBits, ToBytes, Write},
+};
+
+use anyhow::{bail, ensure, Result};
+use indexmap::IndexSet;
+
+/// The block header and authority of a block, used to sync and verify the chain of headers
+/// before the (full) blocks are requested.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyncHeader<N: Network> {
+    /// The hash of the block.
+    pub hash: N::BlockHash,
+    /// The hash of the previous block.
+    pub previous_hash: N::BlockHash,
+    /// The header of the block.
+    pub header: Header<N>,
+    /// The authority of the block.
+    pub authority: Authority<N>,
+}
+
+impl<N: Network> From<&Block<N>> for SyncHeader<N> {
+    /// Returns the sync header of the given block.
+    fn from(block: &Block<N>) -> Self {
+        Self {
+            hash: block.hash(),
+            previous_hash: block.previous_hash(),
+            header: *block.header(),
+            authority: block.authority().clone(),
+        }
+    }
+}
+
+impl<N: Network> SyncHeader<N> {
+    /// Returns the height of the block.
+    pub fn height(&self) -> u32 {
+        self.header.height()
+    }
+
+    /// Checks that the block hash is derived from the previous block hash and the header.
+    pub fn check_hash(&self) -> Result<()> {
+        let preimage = [self.previous_hash.to_bits_le(), self.header.to_root()?.to_bits_le()].concat();
+        ensure!(*self.hash == N::hash_bhp1024(&preimage)?, "The hash of header {} is incorrect", self.height());
+        Ok(())
+    }
+
+    /// Checks that the authority certifies the header. A beacon authority is only valid for the genesis block,
+    /// and must be signed by a member of the given committee. For a quorum authority, this checks that the subdag
+    /// is the one committed to by the header, and that the leader certificate reached the quorum threshold of the
+    /// given committee.
+    /// Note: The signatures of the certificates are verified when they are deserialized.
+    pub fn check_authority(&self, committee: &Committee<N>) -> Result<()> {
+        let height = self.height();
+
+        match &self.authority {
+            Authority::Beacon(signature) => {
+                // Ensure only the genesis block has a beacon authority, as the later blocks are certified by a quorum.
+                ensure!(
+                    height == 0,
+                    "Header {height} has a beacon authority, which is only valid for the genesis block"
+                );
+                // Ensure the beacon is in the committee, and signed the block hash.
+                let signer = signature.to_address();
+                ensure!(
+                    committee.is_committee_member(signer),
+                    "The beacon {signer} of header {height} is not in the committee"
+                );
+                ensure!(signature.verify(&signer, &[*self.hash]), "Invalid beacon for header {height}");
+            }
+            Authority::Quorum(subdag) => {
+                // Ensure the subdag is the one committed to by the header (and thereby by the block hash).
+                ensure!(
+                    self.header.subdag_root() == subdag.to_subdag_root()?,
+                    "The subdag of header {height} does not match the subdag root"
+                );
+                // Ensure the leader certificate is for the round of the block.
+                let leader_certificate = subdag.leader_certificate();
+                ensure!(
+                    leader_certificate.round() == self.header.round(),
+                    "The leader certificate of header {height} is for the wrong round"
+                );
+                // Ensure the author and signers of the leader certificate reached the quorum threshold.
+                let mut signers = IndexSet::new();
+                signers.insert(leader_certificate.author());
+                for signature in leader_certificate.signatures() {
+                    let signer = signature.to_address();
+                    if !committee.is_committee_member(signer) {
+                        bail!("Signer {signer} of header {height} is not in the committee");
+                    }
+                    signers.insert(signer);
+                }
+                ensure!(
+                    committee.is_quorum_threshold_reached(&signers),
+                    "The leader certificate of header {height} did not reach the quorum threshold"
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<N: Network> ToBytes for SyncHeader<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.hash.write_le(&mut writer)?;
+        self.previous_hash.write_le(&mut writer)?;
+        self.header.write_le(&mut writer)?;
+        self.authority.write_le(&mut writer)
+    }
+}
+
+impl<N: Network> FromBytes for SyncHeader<N> {
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let hash = N::BlockHash::read_le(&mut reader)?;
+        let previous_hash = N::BlockHash::read_le(&mut reader)?;
+        let header = Header::read_le(&mut reader)?;
+        let authority = Authority::read_le(&mut reader)?;
+        Ok(Self { hash, previous_hash, header, authority })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::{ledger_test_helpers::sample_genesis_block, Ledger},
+        prelude::{
+            store::{helpers::memory::ConsensusMemory, ConsensusStore},
+            PrivateKey,
+            VM,
+        },
+        utilities::TestRng,
+    };
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+    type CurrentLedger = Ledger<CurrentNetwork, ConsensusMemory<CurrentNetwork>>;
+
+    /// Returns a ledger with a beacon genesis block, and the private key of the beacon.
+    fn sample_ledger(rng: &mut TestRng) -> (CurrentLedger, PrivateKey<CurrentNetwork>) {
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let vm =
+            VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None).unwrap()).unwrap();
+        let genesis = vm.genesis_beacon(&private_key, rng).unwrap();
+        (CurrentLedger::load(genesis, None).unwrap(), private_key)
+    }
+
+    #[test]
+    fn test_sync_header() {
+        let rng = &mut TestRng::default();
+        let (ledger, _) = sample_ledger(rng);
+        let block = ledger.get_block(0).unwrap();
+
+        // Ensure the header of a block is valid, and is preserved across serialization.
+        let header = SyncHeader::<CurrentNetwork>::from(&block);
+        assert_eq!(header.height(), 0);
+        assert_eq!(SyncHeader::read_le(&header.to_bytes_le().unwrap()[..]).unwrap(), header);
+        header.check_hash().unwrap();
+
+        // Ensure a header with the wrong hash is rejected.
+        let mut invalid_header = header.clone();
+        invalid_header.hash = Default::default();
+        assert!(invalid_header.check_hash().is_err());
+
+        // Ensure the authority certifies the header, and the authority of another block is rejected.
+        let committee = ledger.latest_committee().unwrap();
+        header.check_authority(&committee).unwrap();
+        let mut invalid_header = header.clone();
+        invalid_header.authority = sample_genesis_block(rng).authority().clone();
+        assert!(invalid_header.check_authority(&committee).is_err());
+
+        // Ensure the beacon is rejected if it is not in the committee.
+        let other_committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        assert!(header.check_authority(&other_committee).is_err());
+    }
+
+    #[test]
+    fn test_forged_beacon_header() {
+        let rng = &mut TestRng::default();
+        let (ledger, private_key) = sample_ledger(rng);
+        let committee = ledger.latest_committee().unwrap();
+
+        // Ensure a header above genesis with a beacon authority is rejected, even if the beacon is in the committee.
+        let block = ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![], rng).unwrap();
+        let header = SyncHeader::<CurrentNetwork>::from(&block);
+        assert_eq!(header.height(), 1);
+        header.check_hash().unwrap();
+        assert!(header.check_authority(&committee).is_err());
+
+        // Ensure a genesis header re-signed by a key outside the committee is rejected.
+        let mut forged_header = SyncHeader::<CurrentNetwork>::from(&ledger.get_block(0).unwrap());
+        let forger = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        forged_header.authority = Authority::new_beacon(&forger, *forged_header.hash, rng).unwrap();
+        assert!(forged_header.check_authority(&committee).is_err());
+    }
+}
@@ -13,19 +13,23 @@
 // limitations under the License.
 
 use crate::{
-    helpers::{PeerPair, SyncRequest},
+    helpers::{Fork, ForkMonitor, PeerPair, RateMeter, SyncProgress, SyncRequest},
     locators::BlockLocators,
 };
 use snarkos_node_bft_ledger_service::LedgerService;
 use snarkos_node_sync_communication_service::CommunicationService;
-use snarkos_node_sync_locators::{CHECKPOINT_INTERVAL, NUM_RECENT_BLOCKS};
+use snarkos_node_sync_locators::{SyncHeader, TrustedCheckpoints, CHECKPOINT_INTERVAL, NUM_RECENT_BLOCKS};
 use snarkvm::prelude::{block::Block, Network};
 
-use anyhow::{bail, ensure, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use indexmap::{IndexMap, IndexSet};
 use itertools::Itertools;
-use parking_lot::RwLock;
-use rand::{prelude::IteratorRandom, CryptoRng, Rng};
+use parking_lot::{Mutex, RwLock};
+use rand::{
+    prelude::{IteratorRandom, SliceRandom},
+    CryptoRng,
+    Rng,
+};
 use std::{
     collections::BTreeMap,
     net::{IpAddr, Ipv4Addr, SocketAddr},
@@ -33,20 +37,41 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 pub const REDUNDANCY_FACTOR: usize = 3;
 const EXTRA_REDUNDANCY_FACTOR: usize = REDUNDANCY_FACTOR * 2;
 const NUM_SYNC_CANDIDATE_PEERS: usize = REDUNDANCY_FACTOR * 5;
+/// The number of peers that each block with a verified header is requested from.
+/// Note: The first response that matches the verified header completes the request.
+const HEADER_REDUNDANCY_FACTOR: usize = 2;
 
 const BLOCK_REQUEST_TIMEOUT_IN_SECS: u64 = 15; // 15 seconds
-const MAX_BLOCK_REQUESTS: usize = 50; // 50 requests
+const HEADER_REQUEST_TIMEOUT_IN_SECS: u64 = 15; // 15 seconds
+/// The maximum number of headers requested at a time.
+/// Note: This must not exceed the maximum number of headers in a header response.
+const MAX_HEADERS_PER_REQUEST: u32 = 100; // 100 headers
+/// The minimum number of blocks requested ahead of the canonical ledger.
+const MIN_BLOCK_REQUESTS: usize = 50; // 50 requests
+/// The maximum number of blocks requested ahead of the canonical ledger.
+const MAX_BLOCK_REQUESTS: usize = 1_000; // 1000 requests
+/// The number of seconds of the sync peers' combined throughput that the block requests are sized to cover.
+/// Note: This must remain below the block request timeout, so that the requests can be fulfilled in time.
+const REQUEST_WINDOW_IN_SECS: u64 = 10; // 10 seconds
+/// The interval at which the sync progress is reported.
+const PROGRESS_REPORT_INTERVAL_IN_SECS: u64 = 30; // 30 seconds
 const MAX_BLOCK_REQUEST_TIMEOUTS: usize = 5; // 5 timeouts
+/// The latency assumed for the peers whose round-trip time has not been measured yet.
+const UNKNOWN_PEER_LATENCY_IN_MS: u64 = 500; // 500 milliseconds
 
 /// The maximum number of blocks tolerated before the primary is considered behind its peers.
 pub const MAX_BLOCKS_BEHIND: u32 = 2; // blocks
 
+/// A pending header request, as the peer IP, the start height (inclusive), the end height (exclusive),
+/// and the timestamp of the request.
+type HeaderRequest = (SocketAddr, u32, u32, Instant);
+
 /// This is a dummy IP address that is used to represent the local node.
 /// Note: This here does not need to be a real IP address, but it must be unique/distinct from all other connections.
 const DUMMY_SELF_IP: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 0);
@@ -86,6 +111,13 @@ pub struct BlockSync<N: Network> {
     /// This map is a linearly-increasing map of block heights to block hashes,
     /// updated solely from the ledger and candidate blocks (not from peers' block locators, to ensure there are no forks).
     canon: Arc<dyn LedgerService<N>>,
+    /// The trusted checkpoints, which the peers' block locators, headers, and blocks must be consistent with.
+    checkpoints: TrustedCheckpoints<N>,
+    /// The map of block height to block hash, for the verified headers above the canonical ledger.
+    /// The blocks up to the latest header are requested against these hashes.
+    headers: Arc<RwLock<BTreeMap<u32, N::BlockHash>>>,
+    /// The pending header request, if any.
+    header_request: Arc<Mutex<Option<HeaderRequest>>>,
     /// The map of peer IP to their block locators.
     /// The block locators are consistent with the canonical map and every other peer's block locators.
     locators: Arc<RwLock<IndexMap<SocketAddr, BlockLocators<N>>>>,
@@ -101,9 +133,26 @@ pub struct BlockSync<N: Network> {
     /// The map of block height to the timestamp of the last time the block was requested.
     /// This map is used to determine which requests to remove if they have been pending for too long.
     request_timestamps: Arc<RwLock<BTreeMap<u32, Instant>>>,
+    /// The map of block height to the peer IPs whose responses are no longer awaited, as another peer
+    /// delivered the block for the verified header first, and the timestamp of the delivery.
+    redundant_requests: Arc<RwLock<BTreeMap<u32, (IndexSet<SocketAddr>, Instant)>>>,
     /// The map of (timed out) peer IPs to their request timestamps.
     /// This map is used to determine which peers to remove if they have timed out too many times.
     request_timeouts: Arc<RwLock<IndexMap<SocketAddr, Vec<Instant>>>>,
+    /// The map of peer IP to their smoothed round-trip time.
+    /// This map is used to prefer the lower-latency peers when requesting blocks.
+    latencies: Arc<RwLock<IndexMap<SocketAddr, Duration>>>,
+    /// The map of peer IP to the rate at which they deliver the requested blocks.
+    /// This map is used to size the block requests, and to prefer the faster peers when requesting blocks.
+    throughputs: Arc<RwLock<IndexMap<SocketAddr, RateMeter>>>,
+    /// The rate at which the canonical ledger advances with the sync blocks.
+    sync_rate: Arc<Mutex<RateMeter>>,
+    /// The monitor of the forks between the canonical ledger and the peers.
+    fork_monitor: ForkMonitor<N>,
+    /// The timestamp of the last sync progress report.
+    last_progress_report: Arc<Mutex<Option<Instant>>>,
+    /// The lock ensuring that only one caller advances the canonical ledger with the sync blocks at a time.
+    advance_lock: Arc<Mutex<()>>,
     /// The boolean indicator of whether the node is synced up to the latest block (within the given tolerance).
     is_block_synced: Arc<AtomicBool>,
 }
@@ -114,16 +163,32 @@ impl<N: Network> BlockSync<N> {
         Self {
             mode,
             canon: ledger,
+            checkpoints: Default::default(),
+            headers: Default::default(),
+            header_request: Default::default(),
             locators: Default::default(),
             common_ancestors: Default::default(),
             requests: Default::default(),
             responses: Default::default(),
             request_timestamps: Default::default(),
+            redundant_requests: Default::default(),
             request_timeouts: Default::default(),
+            latencies: Default::default(),
+            throughputs: Default::default(),
+            sync_rate: Default::default(),
+            fork_monitor: Default::default(),
+            last_progress_report: Default::default(),
+            advance_lock: Default::default(),
             is_block_synced: Default::default(),
         }
     }
 
+    /// Sets the trusted checkpoints, which the peers' block locators, headers, and blocks must be consistent with.
+    pub fn with_trusted_checkpoints(mut self, checkpoints: TrustedCheckpoints<N>) -> Self {
+        self.checkpoints = checkpoints;
+        self
+    }
+
     /// Returns the block sync mode.
     #[inline]
     pub const fn mode(&self) -> BlockSyncMode {
@@ -210,10 +275,15 @@ impl<N: Network> BlockSync<N> {
     /// Performs one iteration of the block sync.
     #[inline]
     pub async fn try_block_sync<C: CommunicationService>(&self, communication: &C) {
+        // Request the next headers, if the communication service supports header-first sync.
+        self.try_header_sync(communication).await;
+
         // Prepare the block requests, if any.
         // In the process, we update the state of `is_block_synced` for the sync module.
         let block_requests = self.prepare_block_requests();
         trace!("Prepared {} block requests", block_requests.len());
+        // Report the sync progress, if it is due.
+        self.report_progress();
 
         // Process the block requests.
         'outer: for (height, (hash, previous_hash, sync_ips)) in block_requests {
@@ -245,6 +315,115 @@ impl<N: Network> BlockSync<N> {
         }
     }
 
+    /// Requests the next headers above the latest verified header, if no header request is pending.
+    /// The headers are requested from the peers ahead of the latest header, in order of latency,
+    /// skipping the peers that do not support header-first sync.
+    pub async fn try_header_sync<C: CommunicationService>(&self, communication: &C) {
+        // Ensure there is no pending header request, or remove it if it has timed out.
+        {
+            let mut header_request = self.header_request.lock();
+            if let Some((peer_ip, start_height, end_height, timestamp)) = *header_request {
+                if timestamp.elapsed().as_secs() <= HEADER_REQUEST_TIMEOUT_IN_SECS {
+                    return;
+                }
+                debug!("The header request {start_height}..{end_height} to '{peer_ip}' timed out");
+                *header_request = None;
+            }
+        }
+
+        // Compute the start height for the header request.
+        let start_height = self.latest_header_height() + 1;
+        // Retrieve the peers ahead of the latest header, from the lowest to the highest latency.
+        let candidates = self
+            .locators
+            .read()
+            .iter()
+            .map(|(peer_ip, locators)| (*peer_ip, locators.latest_locator_height()))
+            .filter(|(_, height)| *height >= start_height)
+            .sorted_by_key(|(peer_ip, _)| self.get_peer_latency(peer_ip))
+            .collect_vec();
+
+        for (peer_ip, peer_height) in candidates {
+            // Compute the end height for the header request.
+            let end_height = (peer_height + 1).min(start_height + MAX_HEADERS_PER_REQUEST);
+            // Construct the message, if the communication service supports header-first sync.
+            let Some(message) = C::prepare_header_request(start_height, end_height) else {
+                return;
+            };
+            // Send the message to the peer.
+            *self.header_request.lock() = Some((peer_ip, start_height, end_height, Instant::now()));
+            if communication.send(peer_ip, message).await.is_some() {
+                trace!("Requested headers {start_height}..{end_height} from '{peer_ip}'");
+                return;
+            }
+            // If the peer does not support header-first sync, try the next peer.
+            *self.header_request.lock() = None;
+        }
+    }
+
+    /// Processes the header response from the given peer IP.
+    /// The headers must extend the chain of verified headers (or the canonical ledger), and each header must
+    /// have a valid hash, an authority that reached the quorum threshold, and be consistent with the checkpoints.
+    /// Note: The committee is retrieved from the canonical ledger, falling back to the current committee for
+    /// the rounds the ledger has not reached yet.
+    pub fn process_header_response(&self, peer_ip: SocketAddr, headers: Vec<SyncHeader<N>>) -> Result<()> {
+        // Ensure the sync pool requested the headers from the given peer, and remove the request.
+        let start_height = {
+            let mut header_request = self.header_request.lock();
+            match *header_request {
+                Some((request_ip, start_height, ..)) if request_ip == peer_ip => {
+                    *header_request = None;
+                    start_height
+                }
+                _ => bail!("The sync pool did not request headers from '{peer_ip}'"),
+            }
+        };
+
+        // Retrieve the hash of the block preceding the headers.
+        let mut previous_hash = match self.headers.read().get(&(start_height - 1)) {
+            Some(hash) => *hash,
+            None => self.canon.get_block_hash(start_height - 1)?,
+        };
+        // Verify the headers, in order.
+        for (height, header) in (start_height..).zip(headers.iter()) {
+            // Ensure the header extends the chain of headers.
+            ensure!(header.height() == height, "Header {height} from '{peer_ip}' is out of order");
+            ensure!(
+                header.previous_hash == previous_hash,
+                "Header {height} from '{peer_ip}' does not extend the chain"
+            );
+            // Ensure the header is valid.
+            let committee = self
+                .canon
+                .get_previous_committee_for_round(header.header.round())
+                .or_else(|_| self.canon.current_committee())?;
+            header
+                .check_hash()
+                .and_then(|_| header.check_authority(&committee))
+                .map_err(|error| anyhow!("Header {height} from '{peer_ip}' is invalid - {error}"))?;
+            // Ensure the header is consistent with the checkpoints.
+            self.checkpoints.check_hash(height, header.hash)?;
+            previous_hash = header.hash;
+        }
+
+        // Insert the headers, and remove the headers that are already canon.
+        let canon_height = self.canon.latest_block_height();
+        let mut headers_map = self.headers.write();
+        for header in headers {
+            if let Some(existing_hash) = headers_map.insert(header.height(), header.hash) {
+                ensure!(existing_hash == header.hash, "Header {} from '{peer_ip}' conflicts", header.height());
+            }
+        }
+        headers_map.retain(|height, _| *height > canon_height);
+        Ok(())
+    }
+
+    /// Returns the height of the latest verified header, or the latest canon height if it is greater.
+    pub fn latest_header_height(&self) -> u32 {
+        let canon_height = self.canon.latest_block_height();
+        self.headers.read().keys().next_back().map_or(canon_height, |height| (*height).max(canon_height))
+    }
+
     /// Processes the block response from the given peer IP.
     #[inline]
     pub fn process_block_response(&self, peer_ip: SocketAddr, blocks: Vec<Block<N>>) -> Result<()> {
@@ -261,7 +440,12 @@ impl<N: Network> BlockSync<N> {
     #[inline]
     pub fn process_next_block(&self, next_height: u32) -> Option<Block<N>> {
         // Try to advance the ledger with a block from the sync pool.
-        self.remove_block_response(next_height)
+        let block = self.remove_block_response(next_height);
+        // Update the sync rate.
+        if block.is_some() {
+            self.sync_rate.lock().record(1);
+        }
+        block
     }
 
     /// Attempts to advance with blocks from the sync pool.
@@ -269,30 +453,133 @@ impl<N: Network> BlockSync<N> {
     pub fn advance_with_sync_blocks(&self, peer_ip: SocketAddr, blocks: Vec<Block<N>>) -> Result<()> {
         // Process the block response from the given peer IP.
         self.process_block_response(peer_ip, blocks)?;
-
-        // Retrieve the latest block height.
-        let mut current_height = self.canon.latest_block_height();
         // Try to advance the ledger with the sync pool.
-        while let Some(block) = self.remove_block_response(current_height + 1) {
-            // Ensure the block height matches.
-            if block.height() != current_height + 1 {
-                warn!("Block height mismatch: expected {}, found {}", current_height + 1, block.height());
-                break;
+        self.try_advancing_with_sync_blocks();
+        Ok(())
+    }
+
+    /// Advances the ledger with the blocks from the sync pool, in order.
+    ///
+    /// While a block is being applied, the proofs of the following block are checked on a separate thread,
+    /// so a block with an invalid proof is discarded (and requested again) before it reaches the head of the ledger.
+    /// Note: Only the checks that are independent of the state of the ledger run ahead, as the ledger does not
+    /// include the block yet. The following block stays in the sync pool, and is fully checked (with
+    /// `check_next_block`) once it is the next block.
+    ///
+    /// If the ledger is already being advanced by another caller, this returns immediately, as that caller
+    /// proceeds with the newly-staged blocks. This way, the block responses from the other peers are
+    /// deserialized, checked, and staged while the previous block is being applied.
+    fn try_advancing_with_sync_blocks(&self) {
+        loop {
+            // Ensure only one caller advances the ledger at a time.
+            let Some(_lock) = self.advance_lock.try_lock() else {
+                return;
+            };
+
+            // Retrieve the latest block height.
+            let mut current_height = self.canon.latest_block_height();
+            // Retrieve the next block from the sync pool.
+            let mut next_block = self.remove_block_response(current_height + 1);
+            // Try to advance the ledger with the sync pool.
+            while let Some(block) = next_block.take() {
+                // Ensure the block height matches.
+                if block.height() != current_height + 1 {
+                    warn!("Block height mismatch: expected {}, found {}", current_height + 1, block.height());
+                    break;
+                }
+                // Check the next block.
+                if let Err(error) = self.canon.check_next_block(&block) {
+                    warn!("The next block ({}) is invalid - {error}", block.height());
+                    break;
+                }
+                // Attempt to advance to the next block, while checking the proofs of the following block.
+                let result = std::thread::scope(|scope| {
+                    scope.spawn(|| self.precheck_block_response(current_height + 2));
+                    self.canon.advance_to_next_block(&block)
+                });
+                if let Err(error) = result {
+                    warn!("{error}");
+                    break;
+                }
+                // Update the sync rate.
+                self.sync_rate.lock().record(1);
+                // Increment the latest height.
+                current_height += 1;
+                // Proceed with the following block, which remained in the sync pool unless its proofs are invalid.
+                next_block = self.remove_block_response(current_height + 1);
             }
-            // Check the next block.
-            if let Err(error) = self.canon.check_next_block(&block) {
-                warn!("The next block ({}) is invalid - {error}", block.height());
-                break;
+
+            // Release the lock, and ensure no block was staged after the last check.
+            drop(_lock);
+            if !self.is_block_response_ready(current_height + 1) {
+                return;
             }
-            // Attempt to advance to the next block.
-            if let Err(error) = self.canon.advance_to_next_block(&block) {
-                warn!("{error}");
-                break;
+        }
+    }
+
+    /// Checks the proofs of the block response for the given height, if the request is complete,
+    /// and removes the block response from the sync pool if its proofs are invalid.
+    fn precheck_block_response(&self, height: u32) {
+        // Retrieve the block response, leaving it in the sync pool.
+        if !self.is_block_response_ready(height) {
+            return;
+        }
+        let Some(block) = self.responses.read().get(&height).cloned() else {
+            return;
+        };
+        // Note: The proofs are independent of the state of the ledger, so an invalid proof invalidates the block.
+        if let Err(error) = self.canon.check_block_proofs(&block) {
+            warn!("The upcoming block ({height}) is invalid - {error}");
+            self.remove_block_response(height);
+        }
+    }
+
+    /// Returns the current progress of the block sync.
+    pub fn progress(&self) -> SyncProgress {
+        // Retrieve the greatest block height among the peers.
+        let greatest_peer_height =
+            self.locators.read().values().map(|locators| locators.latest_locator_height()).max().unwrap_or(0);
+        // Retrieve the latest canon height.
+        let canon_height = self.canon.latest_block_height();
+        // Retrieve the sync rate.
+        let blocks_per_second = self.sync_rate.lock().rate().unwrap_or(0.0);
+
+        SyncProgress::new(canon_height, greatest_peer_height.max(canon_height), blocks_per_second)
+    }
+
+    /// Logs the sync progress, if the node is syncing and the last report is older than the report interval.
+    fn report_progress(&self) {
+        // Ensure the report is due.
+        {
+            let mut last_progress_report = self.last_progress_report.lock();
+            if let Some(timestamp) = *last_progress_report {
+                if timestamp.elapsed().as_secs() < PROGRESS_REPORT_INTERVAL_IN_SECS {
+                    return;
+                }
             }
-            // Increment the latest height.
-            current_height += 1;
+            *last_progress_report = Some(Instant::now());
+        }
+
+        // Ensure the node is syncing.
+        let progress = self.progress();
+        if self.is_block_synced() || progress.remaining() == 0 {
+            return;
+        }
+        match progress.eta_in_secs {
+            Some(eta) => info!(
+                "Syncing blocks - {}/{} ({:.1} blocks/s, {} remaining, ETA {eta}s)",
+                progress.canon_height,
+                progress.target_height,
+                progress.blocks_per_second,
+                progress.remaining()
+            ),
+            None => info!(
+                "Syncing blocks - {}/{} ({} remaining)",
+                progress.canon_height,
+                progress.target_height,
+                progress.remaining()
+            ),
         }
-        Ok(())
     }
 }
 
@@ -312,6 +599,11 @@ impl<N: Network> BlockSync<N> {
         }
     }
 
+    /// Updates the smoothed round-trip time for the given peer IP.
+    pub fn update_peer_latency(&self, peer_ip: SocketAddr, rtt: Duration) {
+        self.latencies.write().insert(peer_ip, rtt);
+    }
+
     /// Updates the block locators and common ancestors for the given peer IP.
     /// This function checks that the given block locators are well-formed, however it does **not** check
     /// that the block locators are consistent the peer's previous block locators or other peers' block locators.
@@ -323,21 +615,32 @@ impl<N: Network> BlockSync<N> {
 
         // Ensure the given block locators are well-formed.
         locators.ensure_is_valid()?;
+        // Ensure the given block locators are consistent with the trusted checkpoints.
+        self.checkpoints.check_locators(&locators)?;
         // Update the locators entry for the given peer IP.
         self.locators.write().insert(peer_ip, locators.clone());
 
         // Compute the common ancestor with this node.
         let mut ancestor = 0;
+        let mut divergence = None;
         for (height, hash) in locators.clone().into_iter() {
             if let Ok(canon_hash) = self.canon.get_block_hash(height) {
                 match canon_hash == hash {
                     true => ancestor = height,
-                    false => break, // fork
+                    false => {
+                        divergence = Some((ancestor, height, canon_hash));
+                        break; // fork
+                    }
                 }
             }
         }
         // Update the common ancestor entry for this node.
         self.common_ancestors.write().insert(PeerPair(DUMMY_SELF_IP, peer_ip), ancestor);
+        // Update the forks with the peer's branch, and raise an alert if the peer revealed a new fork.
+        if let Some(fork) = self.fork_monitor.update_peer(peer_ip, &locators, divergence) {
+            self.alert_fork(&fork);
+        }
+        self.update_fork_metrics();
 
         // Compute the common ancestor with every other peer.
         let mut common_ancestors = self.common_ancestors.write();
@@ -372,14 +675,102 @@ impl<N: Network> BlockSync<N> {
         self.remove_block_requests_to_peer(peer_ip);
         // Remove the timeouts for the peer.
         self.request_timeouts.write().remove(peer_ip);
+        // Remove the latency of the peer.
+        self.latencies.write().remove(peer_ip);
+        // Remove the throughput of the peer.
+        self.throughputs.write().remove(peer_ip);
+        // Remove the peer from the forks.
+        self.fork_monitor.remove_peer(peer_ip);
+        self.update_fork_metrics();
+        // Remove the pending header request to the peer.
+        let mut header_request = self.header_request.lock();
+        if matches!(*header_request, Some((request_ip, ..)) if request_ip == *peer_ip) {
+            *header_request = None;
+        }
+    }
+}
+
+impl<N: Network> BlockSync<N> {
+    /// Returns the active forks between the canonical ledger and the peers.
+    pub fn forks(&self) -> Vec<Fork<N>> {
+        self.fork_monitor.forks()
+    }
+
+    /// Logs an alert for the given (newly-detected) fork.
+    fn alert_fork(&self, fork: &Fork<N>) {
+        error!("!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!");
+        error!("FORK DETECTED - peers diverge from the canonical ledger at block {}", fork.divergence_height);
+        error!("  Common ancestor: block {}", fork.common_ancestor);
+        for (hash, peers) in &fork.branches {
+            let label = if *hash == fork.canon_hash { "canonical" } else { "diverging" };
+            error!("  Branch {hash} ({label}): {}", peers.iter().join(", "));
+        }
+        error!("  If this node is on the wrong branch, see 'snarkos rollback --help'");
+        error!("!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!");
+    }
+
+    /// Updates the fork metrics.
+    fn update_fork_metrics(&self) {
+        #[cfg(feature = "metrics")]
+        {
+            let forks = self.fork_monitor.forks();
+            let num_diverging_peers: usize = forks.iter().map(|fork| fork.diverging_peers().len()).sum();
+            metrics::gauge!(metrics::sync::FORKS, forks.len() as f64);
+            metrics::gauge!(metrics::sync::DIVERGING_PEERS, num_diverging_peers as f64);
+        }
     }
 }
 
 impl<N: Network> BlockSync<N> {
+    /// Returns the smoothed round-trip time of the given peer IP, or a default latency if it has not been measured.
+    fn get_peer_latency(&self, peer_ip: &SocketAddr) -> Duration {
+        self.latencies.read().get(peer_ip).copied().unwrap_or(Duration::from_millis(UNKNOWN_PEER_LATENCY_IN_MS))
+    }
+
+    /// Returns the rate at which the given peer IP delivers blocks, in blocks per second. The rate is at least
+    /// the one estimated from the round-trip time (as if a single block were requested at a time), so that
+    /// the peers that have not been measured yet, or have recently been idle, are still requested from.
+    fn get_peer_throughput(&self, peer_ip: &SocketAddr) -> f64 {
+        let estimated_rate = 1.0 / self.get_peer_latency(peer_ip).as_secs_f64().max(f64::EPSILON);
+        let measured_rate = self.throughputs.write().get_mut(peer_ip).and_then(|meter| meter.rate()).unwrap_or(0.0);
+        measured_rate.max(estimated_rate)
+    }
+
+    /// Returns the number of blocks to request ahead of the canonical ledger, based on the measured throughput
+    /// of the given sync peers, so that the requests keep the peers busy without exceeding the request timeout.
+    fn get_request_window(&self, sync_peers: &IndexMap<SocketAddr, BlockLocators<N>>) -> usize {
+        // Sum the measured throughput of the sync peers.
+        let throughput: f64 = {
+            let mut throughputs = self.throughputs.write();
+            sync_peers.keys().filter_map(|peer_ip| throughputs.get_mut(peer_ip).and_then(|meter| meter.rate())).sum()
+        };
+        // Size the window to cover the combined throughput over the window duration.
+        let window = (throughput * REQUEST_WINDOW_IN_SECS as f64) as usize;
+        window.clamp(MIN_BLOCK_REQUESTS, MAX_BLOCK_REQUESTS)
+    }
+
+    /// Returns the block hash and previous block hash for the given height, if the header is verified.
+    fn get_header_hashes(&self, height: u32) -> Option<(N::BlockHash, Option<N::BlockHash>)> {
+        let headers = self.headers.read();
+        let hash = *headers.get(&height)?;
+        let previous_hash = match headers.get(&(height - 1)) {
+            Some(previous_hash) => Some(*previous_hash),
+            None => self.canon.get_block_hash(height - 1).ok(),
+        };
+        Some((hash, previous_hash))
+    }
+
     /// Returns a list of block requests, if the node needs to sync.
     fn prepare_block_requests(&self) -> Vec<(u32, SyncRequest<N>)> {
         // Remove timed out block requests.
         self.remove_timed_out_block_requests();
+        // Remove the headers that are already canon.
+        let canon_height = self.canon.latest_block_height();
+        self.headers.write().retain(|height, _| *height > canon_height);
+        // Remove the redundant requests whose responses are no longer expected.
+        self.redundant_requests
+            .write()
+            .retain(|_, (_, timestamp)| timestamp.elapsed().as_secs() <= BLOCK_REQUEST_TIMEOUT_IN_SECS);
         // Prepare the block requests.
         if let Some((sync_peers, min_common_ancestor)) = self.find_sync_peers_inner() {
             // Retrieve the highest block height.
@@ -431,6 +822,19 @@ impl<N: Network> BlockSync<N> {
         // Retrieve the block height.
         let height = block.height();
 
+        // Ignore the response, if another peer already delivered the block for the verified header.
+        {
+            let mut redundant_requests = self.redundant_requests.write();
+            if let Some((peer_ips, _)) = redundant_requests.get_mut(&height) {
+                if peer_ips.remove(&peer_ip) {
+                    if peer_ips.is_empty() {
+                        redundant_requests.remove(&height);
+                    }
+                    return Ok(());
+                }
+            }
+        }
+
         // Ensure the block (response) from the peer is well-formed. On failure, remove all block requests to the peer.
         if let Err(error) = self.check_block_response(&peer_ip, &block) {
             // Remove all block requests to the peer.
@@ -441,7 +845,13 @@ impl<N: Network> BlockSync<N> {
         // Remove the peer IP from the request entry.
         if let Some((_, _, sync_ips)) = self.requests.write().get_mut(&height) {
             sync_ips.remove(&peer_ip);
+            // If the block matches the verified header, complete the request without awaiting the other peers.
+            if !sync_ips.is_empty() && self.headers.read().contains_key(&height) {
+                self.redundant_requests.write().insert(height, (std::mem::take(sync_ips), Instant::now()));
+            }
         }
+        // Update the throughput of the peer.
+        self.throughputs.write().entry(peer_ip).or_default().record(1);
 
         // Acquire the write lock on the responses map.
         let mut responses = self.responses.write();
@@ -504,7 +914,8 @@ impl<N: Network> BlockSync<N> {
             if !sync_ips.contains(peer_ip) {
                 bail!("The sync pool did not request block {height} from '{peer_ip}'")
             }
-            Ok(())
+            // Ensure the candidate block is consistent with the checkpoints.
+            self.checkpoints.check_hash(height, block.hash())
         } else {
             bail!("The sync pool did not request block {height}")
         }
@@ -520,6 +931,13 @@ impl<N: Network> BlockSync<N> {
         self.request_timestamps.write().remove(&height);
     }
 
+    /// Returns `true` if the block response for the given height is ready to be removed, as the request is complete.
+    fn is_block_response_ready(&self, height: u32) -> bool {
+        let is_request_complete =
+            self.requests.read().get(&height).map(|(_, _, peer_ips)| peer_ips.is_empty()).unwrap_or(false);
+        is_request_complete && self.responses.read().contains_key(&height)
+    }
+
     /// Removes and returns the block response for the given height, if the request is complete.
     fn remove_block_response(&self, height: u32) -> Option<Block<N>> {
         // Acquire the requests write lock.
@@ -649,13 +1067,18 @@ impl<N: Network> BlockSync<N> {
             .collect::<IndexMap<_, _>>();
 
         // Pick a set of peers above the latest canon height, and include their locators.
+        // The peers with the same height are ordered from the lowest to the highest latency.
         let candidate_locators: IndexMap<_, _> = self
             .locators
             .read()
             .iter()
             .filter(|(_, locators)| locators.latest_locator_height() > latest_canon_height)
             .filter(|(ip, _)| timeouts.get(*ip).map(|count| *count < MAX_BLOCK_REQUEST_TIMEOUTS).unwrap_or(true))
-            .sorted_by(|(_, a), (_, b)| b.latest_locator_height().cmp(&a.latest_locator_height()))
+            .sorted_by(|(a_ip, a), (b_ip, b)| {
+                b.latest_locator_height()
+                    .cmp(&a.latest_locator_height())
+                    .then_with(|| self.get_peer_latency(a_ip).cmp(&self.get_peer_latency(b_ip)))
+            })
             .take(NUM_SYNC_CANDIDATE_PEERS)
             .map(|(peer_ip, locators)| (*peer_ip, locators.clone()))
             .collect();
@@ -715,6 +1138,9 @@ impl<N: Network> BlockSync<N> {
             return None;
         }
 
+        // Order the sync peers from the lowest to the highest latency.
+        sync_peers.sort_by(|a_ip, _, b_ip, _| self.get_peer_latency(a_ip).cmp(&self.get_peer_latency(b_ip)));
+
         Some((sync_peers, min_common_ancestor))
     }
 
@@ -735,10 +1161,18 @@ impl<N: Network> BlockSync<N> {
 
         // Compute the start height for the block request.
         let start_height = latest_canon_height + 1;
-        // Compute the end height for the block request.
-        let end_height = (min_common_ancestor + 1).min(start_height + MAX_BLOCK_REQUESTS as u32);
+        // Compute the end height for the block request, up to the latest verified header if it is above the
+        // minimum common ancestor, and based on the throughput of the sync peers.
+        let max_height = min_common_ancestor.max(self.latest_header_height());
+        let end_height = (max_height + 1).min(start_height + self.get_request_window(&sync_peers) as u32);
 
         let mut requests = Vec::with_capacity((start_height..end_height).len());
+        // Track whether a redundant request has already been constructed.
+        // Note: Only the first block that is not confirmed by the locators is requested redundantly. Once it is
+        // confirmed, the blocks that follow are chained to it, and each is verified by the ledger before it is applied.
+        let mut is_confirmation_requested = false;
+        // Retrieve the throughput of each sync peer.
+        let throughputs = sync_peers.keys().map(|peer_ip| (*peer_ip, self.get_peer_throughput(peer_ip))).collect_vec();
 
         for height in start_height..end_height {
             // Ensure the current height is not canonized or already requested.
@@ -746,8 +1180,22 @@ impl<N: Network> BlockSync<N> {
                 continue;
             }
 
-            // Construct the block request.
-            let (hash, previous_hash, num_sync_ips, is_honest) = construct_request(height, &sync_peers);
+            // Construct the block request. If the header is verified, the block is requested from multiple peers,
+            // and the first response that matches the header completes the request.
+            let (hash, previous_hash, num_sync_ips, is_honest) = match self.get_header_hashes(height) {
+                Some((hash, previous_hash)) => (Some(hash), previous_hash, HEADER_REDUNDANCY_FACTOR, true),
+                None => {
+                    let (hash, previous_hash, mut num_sync_ips, is_honest) = construct_request(height, &sync_peers);
+                    // Request the blocks after the first confirmation from a single peer.
+                    if is_honest && num_sync_ips > 1 {
+                        match is_confirmation_requested {
+                            true => num_sync_ips = 1,
+                            false => is_confirmation_requested = true,
+                        }
+                    }
+                    (hash, previous_hash, num_sync_ips, is_honest)
+                }
+            };
 
             // Handle the dishonest case.
             if !is_honest {
@@ -759,8 +1207,22 @@ impl<N: Network> BlockSync<N> {
                 }
             }
 
-            // Pick the sync peers.
-            let sync_ips = sync_peers.keys().copied().choose_multiple(rng, num_sync_ips);
+            // Retrieve the sync peers that have the block.
+            let candidates = throughputs
+                .iter()
+                .filter(|(peer_ip, _)| sync_peers[peer_ip].latest_locator_height() >= height)
+                .copied()
+                .collect_vec();
+            // If no sync peer has the block, then return early.
+            if candidates.is_empty() {
+                break;
+            }
+
+            // Pick the sync peers, preferring the ones with a higher throughput.
+            let sync_ips = candidates
+                .choose_multiple_weighted(rng, num_sync_ips, |(_, throughput)| *throughput)
+                .map(|sync_ips| sync_ips.map(|(peer_ip, _)| *peer_ip).collect::<Vec<_>>())
+                .unwrap_or_else(|_| candidates.iter().map(|(peer_ip, _)| *peer_ip).choose_multiple(rng, num_sync_ips));
 
             // Append the request.
             requests.push((height, (hash, previous_hash, sync_ips.into_iter().collect())));
@@ -851,7 +1313,8 @@ mod tests {
         NUM_RECENT_BLOCKS,
     };
     use snarkos_node_bft_ledger_service::MockLedgerService;
-    use snarkvm::prelude::{Field, TestRng};
+    use snarkos_node_sync_locators::TrustedCheckpoint;
+    use snarkvm::prelude::{Address, Field, PrivateKey, TestRng};
 
     use indexmap::indexset;
     use snarkvm::ledger::committee::Committee;
@@ -912,7 +1375,7 @@ mod tests {
         }
 
         // Otherwise, there should be requests.
-        let expected_num_requests = core::cmp::min(min_common_ancestor as usize, MAX_BLOCK_REQUESTS);
+        let expected_num_requests = core::cmp::min(min_common_ancestor as usize, MIN_BLOCK_REQUESTS);
         assert_eq!(requests.len(), expected_num_requests);
 
         for (idx, (height, (hash, previous_hash, sync_ips))) in requests.into_iter().enumerate() {
@@ -920,7 +1383,8 @@ mod tests {
             assert_eq!(hash, Some((Field::<CurrentNetwork>::from_u32(height)).into()));
             assert_eq!(previous_hash, Some((Field::<CurrentNetwork>::from_u32(height - 1)).into()));
 
-            if num_peers_within_recent_range_of_canon >= REDUNDANCY_FACTOR {
+            // Only the first request is redundant, if the hashes are not confirmed by enough peers.
+            if num_peers_within_recent_range_of_canon >= REDUNDANCY_FACTOR || idx > 0 {
                 assert_eq!(sync_ips.len(), 1);
             } else {
                 assert_eq!(sync_ips.len(), num_peers_within_recent_range_of_canon);
@@ -1198,6 +1662,181 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_find_sync_peers_prefers_lower_latency() {
+        let sync = sample_sync_at_height(0);
+
+        // Add peers at the same height, with decreasing latencies.
+        for peer_id in 1..=3 {
+            sync.update_peer_locators(sample_peer_ip(peer_id), sample_block_locators(10)).unwrap();
+            sync.update_peer_latency(sample_peer_ip(peer_id), Duration::from_millis(400 / peer_id as u64));
+        }
+
+        // Ensure the sync peers are ordered from the lowest to the highest latency.
+        let (sync_peers, _) = sync.find_sync_peers().unwrap();
+        assert_eq!(sync_peers.keys().copied().collect::<Vec<_>>(), vec![
+            sample_peer_ip(3),
+            sample_peer_ip(2),
+            sample_peer_ip(1)
+        ]);
+
+        // Ensure the latency is removed along with the peer.
+        sync.remove_peer(&sample_peer_ip(3));
+        assert_eq!(sync.get_peer_latency(&sample_peer_ip(3)), Duration::from_millis(UNKNOWN_PEER_LATENCY_IN_MS));
+    }
+
+    #[test]
+    fn test_request_window_adapts_to_throughput() {
+        let sync = sample_sync_at_height(0);
+
+        // Add peers far ahead of this node.
+        for peer_id in 1..=3 {
+            sync.update_peer_locators(sample_peer_ip(peer_id), sample_block_locators(20_000)).unwrap();
+        }
+        let (sync_peers, _) = sync.find_sync_peers_inner().unwrap();
+
+        // Without a measured throughput, the minimum number of blocks is requested.
+        assert_eq!(sync.get_request_window(&sync_peers), MIN_BLOCK_REQUESTS);
+
+        // Simulate a measured throughput of 20 blocks per second for each peer, over the last interval.
+        let start = Instant::now() - Duration::from_secs(1);
+        for peer_id in 1..=3 {
+            let mut meter = RateMeter::new(start);
+            meter.record_at(20, start);
+            sync.throughputs.write().insert(sample_peer_ip(peer_id), meter);
+        }
+        let window = sync.get_request_window(&sync_peers);
+        assert!(window > MIN_BLOCK_REQUESTS && window <= 3 * 20 * REQUEST_WINDOW_IN_SECS as usize, "{window}");
+
+        // Ensure the window is capped.
+        for peer_id in 1..=3 {
+            let mut meter = RateMeter::new(start);
+            meter.record_at(1_000, start);
+            sync.throughputs.write().insert(sample_peer_ip(peer_id), meter);
+        }
+        assert_eq!(sync.get_request_window(&sync_peers), MAX_BLOCK_REQUESTS);
+
+        // Ensure the throughput is removed along with the peer.
+        sync.remove_peer(&sample_peer_ip(1));
+        assert!(!sync.throughputs.read().contains_key(&sample_peer_ip(1)));
+    }
+
+    #[test]
+    fn test_progress() {
+        let sync = sample_sync_at_height(5);
+        sync.update_peer_locators(sample_peer_ip(1), sample_block_locators(100)).unwrap();
+
+        let progress = sync.progress();
+        assert_eq!(progress.canon_height, 5);
+        assert_eq!(progress.target_height, 100);
+        assert_eq!(progress.remaining(), 95);
+        // The ledger has not advanced yet.
+        assert_eq!(progress.eta_in_secs, None);
+    }
+
+    #[test]
+    fn test_forks() {
+        let sync = sample_sync_at_height(20);
+
+        // A peer on the canonical chain does not reveal a fork.
+        sync.update_peer_locators(sample_peer_ip(1), sample_block_locators(20)).unwrap();
+        assert!(sync.forks().is_empty());
+
+        // A peer on a different chain reveals a fork.
+        sync.update_peer_locators(sample_peer_ip(2), sample_block_locators_with_fork(20, 15)).unwrap();
+        let forks = sync.forks();
+        assert_eq!(forks.len(), 1);
+        assert_eq!(forks[0].common_ancestor, 14);
+        assert_eq!(forks[0].divergence_height, 15);
+        assert_eq!(forks[0].diverging_peers(), IndexSet::from([sample_peer_ip(2)]));
+
+        // The fork is resolved once the diverging peer is removed.
+        sync.remove_peer(&sample_peer_ip(2));
+        assert!(sync.forks().is_empty());
+    }
+
+    #[test]
+    fn test_prepare_block_requests_with_headers() {
+        let sync = sample_sync_at_height(0);
+
+        // Add a peer at height 20, and two peers at height 10.
+        sync.update_peer_locators(sample_peer_ip(1), sample_block_locators(20)).unwrap();
+        sync.update_peer_locators(sample_peer_ip(2), sample_block_locators(10)).unwrap();
+        sync.update_peer_locators(sample_peer_ip(3), sample_block_locators(10)).unwrap();
+
+        // Without headers, the blocks are requested up to the common ancestor.
+        assert_eq!(sync.prepare_block_requests().len(), 10);
+
+        // With the verified headers, the blocks are requested up to the latest header, each from multiple peers.
+        sync.headers.write().extend((1..=20).map(|height| (height, Field::<CurrentNetwork>::from_u32(height).into())));
+        assert_eq!(sync.latest_header_height(), 20);
+        let requests = sync.prepare_block_requests();
+        assert_eq!(requests.len(), 20);
+        for (height, (hash, previous_hash, sync_ips)) in requests {
+            assert_eq!(hash, Some(Field::<CurrentNetwork>::from_u32(height).into()));
+            assert_eq!(previous_hash, Some(Field::<CurrentNetwork>::from_u32(height - 1).into()));
+            // The blocks above the common ancestor are requested from the peer that has them.
+            match height > 10 {
+                true => assert_eq!(sync_ips, IndexSet::from([sample_peer_ip(1)])),
+                false => assert_eq!(sync_ips.len(), HEADER_REDUNDANCY_FACTOR),
+            }
+        }
+    }
+
+    #[test]
+    fn test_insert_block_response_with_header() {
+        let rng = &mut TestRng::default();
+        let sync = sample_sync_at_height(0);
+        let block = snarkvm::ledger::ledger_test_helpers::sample_genesis_block(rng);
+        let height = block.height();
+
+        // Request the block for the verified header from two peers.
+        sync.headers.write().insert(height, block.hash());
+        let sync_ips = IndexSet::from([sample_peer_ip(1), sample_peer_ip(2)]);
+        sync.requests.write().insert(height, (Some(block.hash()), None, sync_ips));
+
+        // Ensure the first response completes the request.
+        sync.insert_block_response(sample_peer_ip(1), block.clone()).unwrap();
+        assert!(sync.is_block_response_ready(height));
+        // Ensure the response from the other peer is ignored, but only once.
+        sync.insert_block_response(sample_peer_ip(2), block.clone()).unwrap();
+        assert!(sync.redundant_requests.read().is_empty());
+        assert!(sync.insert_block_response(sample_peer_ip(2), block).is_err());
+    }
+
+    #[test]
+    fn test_process_header_response_requires_request() {
+        let sync = sample_sync_at_height(0);
+        sync.update_peer_locators(sample_peer_ip(1), sample_block_locators(20)).unwrap();
+
+        // Ensure headers that were not requested are rejected.
+        assert!(sync.process_header_response(sample_peer_ip(1), vec![]).is_err());
+        // Ensure headers from a peer other than the requested one are rejected.
+        *sync.header_request.lock() = Some((sample_peer_ip(2), 1, 21, Instant::now()));
+        assert!(sync.process_header_response(sample_peer_ip(1), vec![]).is_err());
+        assert!(sync.header_request.lock().is_some());
+
+        // Ensure the pending header request is removed with the peer.
+        sync.remove_peer(&sample_peer_ip(2));
+        assert!(sync.header_request.lock().is_none());
+    }
+
+    #[test]
+    fn test_trusted_checkpoints() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let signer = Address::try_from(&private_key).unwrap();
+        let checkpoint = TrustedCheckpoint::sign(&private_key, 15, Field::from_u32(15).into(), rng).unwrap();
+        let checkpoints = TrustedCheckpoints::new(vec![checkpoint], &[signer]).unwrap();
+        let sync = sample_sync_at_height(0).with_trusted_checkpoints(checkpoints);
+
+        // Ensure the block locators must be consistent with the checkpoints.
+        sync.update_peer_locators(sample_peer_ip(1), sample_block_locators(20)).unwrap();
+        sync.update_peer_locators(sample_peer_ip(2), sample_block_locators_with_fork(20, 16)).unwrap();
+        assert!(sync.update_peer_locators(sample_peer_ip(3), sample_block_locators_with_fork(20, 15)).is_err());
+        assert_eq!(sync.get_peer_height(&sample_peer_ip(3)), None);
+    }
+
     #[test]
     fn test_remove_peer() {
         let sync = sample_sync_at_height(0);
@@ -0,0 +1,198 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::locators::BlockLocators;
+use snarkvm::prelude::Network;
+
+use indexmap::{IndexMap, IndexSet};
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    net::SocketAddr,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A divergence between the canonical ledger and the chain of one or more peers.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(bound = "")]
+pub struct Fork<N: Network> {
+    /// The height of the latest block that the canonical ledger and the diverging peers agree on.
+    pub common_ancestor: u32,
+    /// The lowest observed height at which the canonical ledger and the diverging peers disagree.
+    pub divergence_height: u32,
+    /// The canonical block hash at the divergence height.
+    pub canon_hash: N::BlockHash,
+    /// The map of block hash at the divergence height to the peers on that branch.
+    pub branches: IndexMap<N::BlockHash, IndexSet<SocketAddr>>,
+    /// The UNIX timestamp (in seconds) of when the fork was first detected.
+    pub detected_at: u64,
+}
+
+impl<N: Network> Fork<N> {
+    /// Returns the peers on the canonical branch.
+    pub fn canon_peers(&self) -> IndexSet<SocketAddr> {
+        self.branches.get(&self.canon_hash).cloned().unwrap_or_default()
+    }
+
+    /// Returns the peers that are not on the canonical branch.
+    pub fn diverging_peers(&self) -> IndexSet<SocketAddr> {
+        self.branches
+            .iter()
+            .filter(|(hash, _)| **hash != self.canon_hash)
+            .flat_map(|(_, peers)| peers)
+            .copied()
+            .collect()
+    }
+}
+
+/// A monitor of the forks between the canonical ledger and the peers' block locators.
+#[derive(Clone, Debug)]
+pub struct ForkMonitor<N: Network> {
+    /// The map of divergence height to the fork.
+    forks: Arc<RwLock<BTreeMap<u32, Fork<N>>>>,
+}
+
+impl<N: Network> Default for ForkMonitor<N> {
+    fn default() -> Self {
+        Self { forks: Default::default() }
+    }
+}
+
+impl<N: Network> ForkMonitor<N> {
+    /// Returns the active forks, in order of divergence height.
+    pub fn forks(&self) -> Vec<Fork<N>> {
+        self.forks.read().values().cloned().collect()
+    }
+
+    /// Returns the number of active forks.
+    pub fn num_forks(&self) -> usize {
+        self.forks.read().len()
+    }
+
+    /// Updates the branches of the forks with the given block locators of the peer, given the divergence
+    /// (i.e. the common ancestor, divergence height, and canonical hash) of the peer, if any.
+    /// Returns the fork, if this peer revealed a new fork.
+    pub fn update_peer(
+        &self,
+        peer_ip: SocketAddr,
+        locators: &BlockLocators<N>,
+        divergence: Option<(u32, u32, N::BlockHash)>,
+    ) -> Option<Fork<N>> {
+        let mut forks = self.forks.write();
+        let mut is_new = false;
+
+        // If the peer diverges from the canonical ledger, record the fork.
+        if let Some((common_ancestor, divergence_height, canon_hash)) = divergence {
+            forks.entry(divergence_height).or_insert_with(|| {
+                is_new = true;
+                Fork {
+                    common_ancestor,
+                    divergence_height,
+                    canon_hash,
+                    branches: Default::default(),
+                    detected_at: now(),
+                }
+            });
+        }
+
+        // Place the peer on the branch of every fork that its locators cover.
+        for fork in forks.values_mut() {
+            fork.branches.values_mut().for_each(|peers| {
+                peers.remove(&peer_ip);
+            });
+            if let Some(hash) = locators.get_hash(fork.divergence_height) {
+                fork.branches.entry(hash).or_default().insert(peer_ip);
+            }
+        }
+        // Drop the forks that no longer have any diverging peers.
+        Self::prune(&mut forks);
+
+        match is_new {
+            true => divergence.and_then(|(_, divergence_height, _)| forks.get(&divergence_height).cloned()),
+            false => None,
+        }
+    }
+
+    /// Removes the given peer from the branches of every fork.
+    pub fn remove_peer(&self, peer_ip: &SocketAddr) {
+        let mut forks = self.forks.write();
+        for fork in forks.values_mut() {
+            fork.branches.values_mut().for_each(|peers| {
+                peers.remove(peer_ip);
+            });
+        }
+        Self::prune(&mut forks);
+    }
+
+    /// Removes the empty branches, and the forks without any diverging peers.
+    fn prune(forks: &mut BTreeMap<u32, Fork<N>>) {
+        forks.retain(|_, fork| {
+            fork.branches.retain(|_, peers| !peers.is_empty());
+            !fork.diverging_peers().is_empty()
+        });
+    }
+}
+
+/// Returns the current UNIX timestamp, in seconds.
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locators::test_helpers::{sample_block_locators, sample_block_locators_with_fork};
+
+    use std::net::{IpAddr, Ipv4Addr};
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    fn sample_peer_ip(id: u16) -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), id)
+    }
+
+    #[test]
+    fn test_fork_monitor() {
+        let monitor = ForkMonitor::<CurrentNetwork>::default();
+        let canon_locators = sample_block_locators(20);
+        let fork_locators = sample_block_locators_with_fork(20, 11);
+        let canon_hash = canon_locators.get_hash(11).unwrap();
+        let fork_hash = fork_locators.get_hash(11).unwrap();
+        assert_ne!(canon_hash, fork_hash);
+
+        // A peer on the canonical chain does not reveal a fork.
+        assert!(monitor.update_peer(sample_peer_ip(1), &canon_locators, None).is_none());
+        assert_eq!(monitor.num_forks(), 0);
+
+        // A peer on a different chain reveals a fork, once.
+        let fork = monitor.update_peer(sample_peer_ip(2), &fork_locators, Some((10, 11, canon_hash))).unwrap();
+        assert_eq!(fork.divergence_height, 11);
+        assert!(monitor.update_peer(sample_peer_ip(3), &fork_locators, Some((10, 11, canon_hash))).is_none());
+
+        // The canonical peers are placed on the canonical branch, once their locators are updated.
+        monitor.update_peer(sample_peer_ip(1), &canon_locators, None);
+        let fork = &monitor.forks()[0];
+        assert_eq!(fork.canon_peers(), IndexSet::from([sample_peer_ip(1)]));
+        assert_eq!(fork.diverging_peers(), IndexSet::from([sample_peer_ip(2), sample_peer_ip(3)]));
+        assert_eq!(fork.branches[&fork_hash].len(), 2);
+
+        // The fork is resolved once no diverging peers remain.
+        monitor.remove_peer(&sample_peer_ip(2));
+        assert_eq!(monitor.num_forks(), 1);
+        monitor.update_peer(sample_peer_ip(3), &canon_locators, None);
+        assert_eq!(monitor.num_forks(), 0);
+    }
+}
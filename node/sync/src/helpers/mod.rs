@@ -12,6 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod fork_monitor;
+pub use fork_monitor::{Fork, ForkMonitor};
+
+mod progress;
+pub(crate) use progress::RateMeter;
+pub use progress::SyncProgress;
+
 use snarkvm::prelude::Network;
 
 use core::hash::Hash;
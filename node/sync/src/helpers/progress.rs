@@ -0,0 +1,147 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// The interval over which each rate sample is measured.
+const RATE_INTERVAL: Duration = Duration::from_secs(1);
+/// The weight of a new sample in the smoothed rate.
+const RATE_SAMPLE_WEIGHT: f64 = 0.25;
+
+/// The progress of the block sync.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SyncProgress {
+    /// The latest block height of the ledger.
+    pub canon_height: u32,
+    /// The greatest block height among the peers.
+    pub target_height: u32,
+    /// The smoothed number of blocks the ledger advances by per second.
+    pub blocks_per_second: f64,
+    /// The estimated number of seconds until the ledger reaches the target height, if it is advancing.
+    pub eta_in_secs: Option<u64>,
+}
+
+impl SyncProgress {
+    /// Initializes the sync progress from the given heights and rate.
+    pub fn new(canon_height: u32, target_height: u32, blocks_per_second: f64) -> Self {
+        let remaining = target_height.saturating_sub(canon_height);
+        let eta_in_secs = match blocks_per_second > 0.0 {
+            true => Some((remaining as f64 / blocks_per_second).ceil() as u64),
+            false => None,
+        };
+        Self { canon_height, target_height, blocks_per_second, eta_in_secs }
+    }
+
+    /// Returns the number of blocks remaining to reach the target height.
+    pub fn remaining(&self) -> u32 {
+        self.target_height.saturating_sub(self.canon_height)
+    }
+}
+
+/// A meter of the (smoothed) rate at which events occur, in events per second.
+#[derive(Clone, Debug)]
+pub(crate) struct RateMeter {
+    /// The number of events in the current interval.
+    count: u64,
+    /// The start of the current interval.
+    start: Instant,
+    /// The smoothed rate, if at least one interval has elapsed.
+    rate: Option<f64>,
+}
+
+impl Default for RateMeter {
+    fn default() -> Self {
+        Self::new(Instant::now())
+    }
+}
+
+impl RateMeter {
+    /// Initializes a new rate meter, with the first interval starting at the given time.
+    pub(crate) fn new(start: Instant) -> Self {
+        Self { count: 0, start, rate: None }
+    }
+
+    /// Records the given number of events.
+    pub(crate) fn record(&mut self, count: u64) {
+        self.record_at(count, Instant::now())
+    }
+
+    /// Returns the smoothed rate, in events per second, if at least one interval has elapsed.
+    pub(crate) fn rate(&mut self) -> Option<f64> {
+        self.rate_at(Instant::now())
+    }
+
+    /// Records the given number of events, at the given time.
+    pub(crate) fn record_at(&mut self, count: u64, now: Instant) {
+        self.update(now);
+        self.count += count;
+    }
+
+    /// Returns the smoothed rate at the given time, if at least one interval has elapsed.
+    pub(crate) fn rate_at(&mut self, now: Instant) -> Option<f64> {
+        self.update(now);
+        self.rate
+    }
+
+    /// Folds the current interval into the smoothed rate, if it has elapsed by the given time.
+    fn update(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.start);
+        if elapsed < RATE_INTERVAL {
+            return;
+        }
+        let sample = self.count as f64 / elapsed.as_secs_f64();
+        self.rate = Some(match self.rate {
+            Some(rate) => rate + RATE_SAMPLE_WEIGHT * (sample - rate),
+            None => sample,
+        });
+        self.count = 0;
+        self.start = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_meter() {
+        let start = Instant::now();
+        let mut meter = RateMeter::new(start);
+        meter.record_at(10, start);
+        // The rate is unknown until the first interval elapses.
+        assert_eq!(meter.rate_at(start + RATE_INTERVAL / 2), None);
+
+        let rate = meter.rate_at(start + RATE_INTERVAL).unwrap();
+        assert_eq!(rate, 10.0);
+
+        // An idle interval lowers the smoothed rate.
+        let idle_rate = meter.rate_at(start + 2 * RATE_INTERVAL).unwrap();
+        assert_eq!(idle_rate, rate * (1.0 - RATE_SAMPLE_WEIGHT));
+
+        // A busier interval raises the smoothed rate.
+        meter.record_at(40, start + 2 * RATE_INTERVAL);
+        assert!(meter.rate_at(start + 3 * RATE_INTERVAL).unwrap() > idle_rate);
+    }
+
+    #[test]
+    fn test_sync_progress() {
+        let progress = SyncProgress::new(100, 1100, 40.0);
+        assert_eq!(progress.remaining(), 1000);
+        assert_eq!(progress.eta_in_secs, Some(25));
+
+        // A stalled sync has no estimate.
+        assert_eq!(SyncProgress::new(100, 1100, 0.0).eta_in_secs, None);
+    }
+}
@@ -14,6 +14,8 @@
 
 #![forbid(unsafe_code)]
 
+#[macro_use]
+extern crate async_trait;
 #[macro_use]
 extern crate tracing;
 
@@ -25,3 +27,6 @@ pub use block_sync::*;
 
 mod helpers;
 pub use helpers::*;
+
+mod rest_service;
+pub use rest_service::*;
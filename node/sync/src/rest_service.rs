@@ -0,0 +1,287 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{locators::BlockLocators, BlockSync};
+use snarkos_node_sync_communication_service::CommunicationService;
+use snarkvm::prelude::{block::Block, DeserializeOwned, Network};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use indexmap::IndexMap;
+use reqwest::{Client, Url};
+use std::{
+    io,
+    net::SocketAddr,
+    ops::Range,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{sync::oneshot, task::JoinSet};
+
+/// The version of the REST API that the blocks are fetched with.
+const API_VERSION: &str = "v1";
+/// The maximum number of blocks the REST server returns per call.
+const MAX_BLOCKS_PER_CALL: u32 = 50;
+/// The timeout of each call to a REST server.
+const REQUEST_TIMEOUT_IN_SECS: u64 = 15; // 15 seconds
+
+/// A communication service that fetches blocks from the REST servers of (archive) nodes,
+/// so that the block sync can request blocks from them alongside its P2P peers.
+///
+/// Each REST server is represented in the block sync by the socket address of its endpoint.
+/// The block locators of the REST servers are polled with [`RestSyncService::update_locators`],
+/// and their block responses are processed by the block sync as the ones from the P2P peers.
+#[derive(Clone)]
+pub struct RestSyncService<N: Network> {
+    /// The block sync module.
+    sync: BlockSync<N>,
+    /// The HTTP client.
+    client: Client,
+    /// The map of the socket address to the base URL of each REST server.
+    endpoints: Arc<IndexMap<SocketAddr, Url>>,
+}
+
+impl<N: Network> RestSyncService<N> {
+    /// Initializes the service for the given base URLs of the REST servers (e.g. `http://1.2.3.4:3033`).
+    pub async fn new(sync: BlockSync<N>, base_urls: &[String]) -> Result<Self> {
+        let mut endpoints = IndexMap::with_capacity(base_urls.len());
+        for base_url in base_urls {
+            // Parse the base URL.
+            let url = Url::parse(base_url).map_err(|error| anyhow!("Invalid sync endpoint '{base_url}' - {error}"))?;
+            let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+                bail!("Invalid sync endpoint '{base_url}' - missing the host")
+            };
+            // Resolve the socket address of the endpoint.
+            let Some(addr) = tokio::net::lookup_host((host, port)).await?.next() else {
+                bail!("Failed to resolve the sync endpoint '{base_url}'")
+            };
+            endpoints.insert(addr, url);
+        }
+        // Initialize the HTTP client.
+        let client = Client::builder().timeout(Duration::from_secs(REQUEST_TIMEOUT_IN_SECS)).build()?;
+        Ok(Self { sync, client, endpoints: Arc::new(endpoints) })
+    }
+
+    /// Returns `true` if the given socket address is one of the REST servers.
+    pub fn contains(&self, peer_ip: &SocketAddr) -> bool {
+        self.endpoints.contains_key(peer_ip)
+    }
+
+    /// Returns the socket addresses of the REST servers.
+    pub fn peers(&self) -> Vec<SocketAddr> {
+        self.endpoints.keys().copied().collect()
+    }
+
+    /// Fetches the block locators of the REST servers concurrently, and updates them in the block sync.
+    /// The REST servers that cannot be reached, or that return invalid locators, are removed from the block sync.
+    pub async fn update_locators(&self) {
+        let mut tasks = JoinSet::new();
+        for peer_ip in self.endpoints.keys().copied() {
+            let self_ = self.clone();
+            tasks.spawn(async move { self_.update_peer_locators(peer_ip).await });
+        }
+        while tasks.join_next().await.is_some() {}
+    }
+
+    /// Fetches the block locators of the given REST server, and updates them in the block sync.
+    async fn update_peer_locators(&self, peer_ip: SocketAddr) {
+        let timer = Instant::now();
+        let result = self.get::<BlockLocators<N>>(peer_ip, &Self::route("sync/locators")).await.and_then(|locators| {
+            // Update the latency of the REST server.
+            self.sync.update_peer_latency(peer_ip, timer.elapsed());
+            self.sync.update_peer_locators(peer_ip, locators)
+        });
+        if let Err(error) = result {
+            warn!("Failed to update the block locators of sync endpoint '{peer_ip}' - {error}");
+            self.sync.remove_peer(&peer_ip);
+        }
+    }
+
+    /// Fetches the blocks in the given range from the given REST server, and processes them in the block sync.
+    async fn fetch_blocks(&self, peer_ip: SocketAddr, heights: Range<u32>) -> Result<()> {
+        let path = Self::route(&format!("blocks?start={}&end={}", heights.start, heights.end));
+        let blocks = self.get::<Vec<Block<N>>>(peer_ip, &path).await?;
+        // Ensure the block response is well-formed.
+        ensure!(blocks.len() == heights.len(), "Sync endpoint '{peer_ip}' sent the wrong number of blocks");
+        for (height, block) in heights.zip(blocks.iter()) {
+            ensure!(block.height() == height, "Sync endpoint '{peer_ip}' sent an unrequested block");
+        }
+        // Process the block response.
+        let sync = self.sync.clone();
+        tokio::task::spawn_blocking(move || sync.advance_with_sync_blocks(peer_ip, blocks)).await?
+    }
+
+    /// Returns the given path of a REST route, under the API version and the network (e.g. `v1/testnet3/{path}`).
+    fn route(path: &str) -> String {
+        let network = N::NAME.split_whitespace().last().unwrap_or(N::NAME).to_lowercase();
+        format!("{API_VERSION}/{network}/{path}")
+    }
+
+    /// Fetches and deserializes the JSON object at the given path of the given REST server.
+    async fn get<T: 'static + DeserializeOwned + Send>(&self, peer_ip: SocketAddr, path: &str) -> Result<T> {
+        let Some(base_url) = self.endpoints.get(&peer_ip) else { bail!("Unknown sync endpoint '{peer_ip}'") };
+        let url = base_url.join(path)?;
+        let response = self.client.get(url).send().await?.error_for_status()?;
+        let bytes = response.bytes().await?;
+        // Deserialize the object.
+        tokio::task::spawn_blocking(move || serde_json::from_slice::<T>(&bytes)).await?.map_err(Into::into)
+    }
+}
+
+#[async_trait]
+impl<N: Network> CommunicationService for RestSyncService<N> {
+    /// The message type.
+    type Message = Range<u32>;
+
+    /// Prepares a block request to be sent.
+    fn prepare_block_request(start_height: u32, end_height: u32) -> Self::Message {
+        debug_assert!(start_height < end_height, "Invalid block request format");
+        start_height..end_height
+    }
+
+    /// Fetches the requested blocks from the given REST server.
+    ///
+    /// This function returns as soon as the request is dispatched; the returned [`oneshot::Receiver`]
+    /// resolves once the blocks have been fetched and processed by the block sync.
+    /// If the REST server cannot be reached, or returns an invalid response, it is removed from the block sync.
+    async fn send(&self, peer_ip: SocketAddr, heights: Self::Message) -> Option<oneshot::Receiver<io::Result<()>>> {
+        // Ensure the peer is a REST server, and the range is within the limit of the REST server.
+        if !self.contains(&peer_ip) || heights.len() > MAX_BLOCKS_PER_CALL as usize {
+            return None;
+        }
+        let (sender, receiver) = oneshot::channel();
+        let self_ = self.clone();
+        tokio::spawn(async move {
+            let result = self_.fetch_blocks(peer_ip, heights).await;
+            if let Err(error) = &result {
+                warn!("Failed to sync blocks from sync endpoint '{peer_ip}' - {error}");
+                self_.sync.remove_peer(&peer_ip);
+            }
+            let _ = sender.send(result.map_err(|error| io::Error::new(io::ErrorKind::Other, error.to_string())));
+        });
+        Some(receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{locators::test_helpers::sample_block_locators, BlockSyncMode};
+    use snarkos_node_bft_ledger_service::MockLedgerService;
+    use snarkvm::{ledger::ledger_test_helpers::sample_genesis_block, prelude::TestRng};
+
+    use std::io::{BufRead, BufReader, Write};
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    /// Serves the given JSON responses by request path over HTTP, and returns the base URL of the server.
+    fn serve(responses: Vec<(String, String)>) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = stream.unwrap();
+                // Read the request line (e.g. `GET /path HTTP/1.1`), and skip the request headers.
+                let mut reader = BufReader::new(&stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                // Write the response for the requested path.
+                let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+                let (status, body) = match responses.iter().find(|(response_path, _)| response_path == path) {
+                    Some((_, body)) => ("200 OK", body.as_str()),
+                    None => ("404 Not Found", ""),
+                };
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                (&stream).write_all(response.as_bytes()).unwrap();
+            }
+        });
+        base_url
+    }
+
+    #[test]
+    fn test_rest_sync_service() {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let committee = snarkvm::ledger::committee::test_helpers::sample_committee(&mut TestRng::default());
+            let ledger = Arc::new(MockLedgerService::<CurrentNetwork>::new_at_height(committee, 0));
+            let sync = BlockSync::new(BlockSyncMode::Router, ledger);
+
+            // Ensure the endpoints are resolved to their socket addresses.
+            let service = RestSyncService::new(sync.clone(), &["http://127.0.0.1:3033".to_string()]).await.unwrap();
+            let peer_ip: SocketAddr = "127.0.0.1:3033".parse().unwrap();
+            assert!(service.contains(&peer_ip));
+            assert_eq!(service.peers(), vec![peer_ip]);
+            assert!(RestSyncService::new(sync, &["127.0.0.1:3033".to_string()]).await.is_err());
+
+            // Ensure the requests are only sent to the endpoints, within the limit of the REST server.
+            let message = RestSyncService::<CurrentNetwork>::prepare_block_request(1, 11);
+            assert!(service.send("127.0.0.1:4133".parse().unwrap(), message).await.is_none());
+            let message = RestSyncService::<CurrentNetwork>::prepare_block_request(1, 2 + MAX_BLOCKS_PER_CALL);
+            assert!(service.send(peer_ip, message).await.is_none());
+        });
+    }
+
+    #[test]
+    fn test_rest_sync_service_fetches_from_server() {
+        let rng = &mut TestRng::default();
+        let block = sample_genesis_block(rng);
+
+        // Serve the block locators and the blocks under the versioned routes.
+        assert_eq!(RestSyncService::<CurrentNetwork>::route("sync/locators"), "v1/testnet3/sync/locators");
+        let locators = serde_json::to_string(&sample_block_locators(10)).unwrap();
+        let blocks = serde_json::to_string(&vec![block.clone()]).unwrap();
+        let base_url = serve(vec![
+            ("/v1/testnet3/sync/locators".to_string(), locators),
+            ("/v1/testnet3/blocks?start=0&end=1".to_string(), blocks.clone()),
+            ("/v1/testnet3/blocks?start=1&end=2".to_string(), blocks),
+        ]);
+        // Serve an endpoint without the routes.
+        let unavailable_url = serve(vec![]);
+
+        let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+            let ledger = Arc::new(MockLedgerService::<CurrentNetwork>::new_at_height(committee, 0));
+            let sync = BlockSync::new(BlockSyncMode::Router, ledger);
+            let service = RestSyncService::new(sync.clone(), &[base_url, unavailable_url]).await.unwrap();
+            let (peer_ip, unavailable_ip) = (service.peers()[0], service.peers()[1]);
+
+            // Ensure the block locators are fetched and updated in the block sync.
+            service.update_locators().await;
+            assert_eq!(sync.progress().target_height, 10);
+
+            // Ensure the blocks are fetched and deserialized.
+            let path = RestSyncService::<CurrentNetwork>::route("blocks?start=0&end=1");
+            assert_eq!(service.get::<Vec<Block<CurrentNetwork>>>(peer_ip, &path).await.unwrap(), vec![block]);
+            // Ensure a response that does not match the requested range is rejected.
+            let error = service.fetch_blocks(peer_ip, 1..2).await.unwrap_err();
+            assert!(error.to_string().contains("unrequested block"), "{error}");
+            // Ensure an unavailable endpoint is reported.
+            assert!(service.fetch_blocks(unavailable_ip, 0..1).await.is_err());
+
+            // Ensure an endpoint that sends an invalid response is removed from the block sync.
+            let message = RestSyncService::<CurrentNetwork>::prepare_block_request(1, 2);
+            let receiver = service.send(peer_ip, message).await.unwrap();
+            assert!(receiver.await.unwrap().is_err());
+            assert_eq!(sync.progress().target_height, 0);
+            assert!(sync.find_sync_peers().is_none());
+        });
+    }
+}
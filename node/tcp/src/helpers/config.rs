@@ -18,7 +18,10 @@ use std::{
 };
 
 #[cfg(doc)]
-use crate::protocols::{self, Handshake, Reading, Writing};
+use crate::{
+    protocols::{self, Handshake, Reading, Writing},
+    Budget,
+};
 
 /// The Tcp's configuration. See the source of [`Config::default`] for the defaults.
 #[derive(Debug, Clone)]
@@ -52,6 +55,104 @@ pub struct Config {
     pub max_connections: u16,
     /// The maximum time (in milliseconds) allowed to establish a raw (before the [`Handshake`] protocol) TCP connection.
     pub connection_timeout_ms: u16,
+    /// The transport used by the Tcp's listener and outbound connections.
+    pub transport: Transport,
+    /// The bandwidth limits of the Tcp.
+    ///
+    /// note: Tcp needs to implement the [`Reading`] and/or [`Writing`] protocol in order for it to have any effect.
+    pub bandwidth_limits: BandwidthLimits,
+    /// The address of the SOCKS5 proxy all the outbound connections are established through.
+    ///
+    /// note: If set to `None`, the outbound connections are established directly. The proxy is only supported
+    /// by the [`Transport::Tcp`] transport.
+    pub socks5_proxy: Option<SocketAddr>,
+}
+
+/// The bandwidth limits, in bytes per second; the traffic is unlimited by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BandwidthLimits {
+    /// The maximum upload rate across all connections.
+    pub upload: Option<u64>,
+    /// The maximum download rate across all connections.
+    pub download: Option<u64>,
+    /// The maximum upload rate of a single connection.
+    pub peer_upload: Option<u64>,
+    /// The maximum download rate of a single connection.
+    pub peer_download: Option<u64>,
+    /// The percentage (between 1 and 99) of each limit reserved for the [`Budget::Sync`] traffic;
+    /// the rest is reserved for the [`Budget::Consensus`] traffic.
+    pub sync_share: u8,
+}
+
+impl Default for BandwidthLimits {
+    /// Initializes the bandwidth limits with the default values.
+    fn default() -> Self {
+        Self { upload: None, download: None, peer_upload: None, peer_download: None, sync_share: 50 }
+    }
+}
+
+impl core::str::FromStr for BandwidthLimits {
+    type Err = io::Error;
+
+    /// Parses the bandwidth limits from a comma-separated list of limits (e.g. 'upload=10000000,sync-share=30'),
+    /// with the keys 'upload', 'download', 'peer-upload', 'peer-download' (in bytes per second), and 'sync-share';
+    /// the omitted limits retain their default values.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |msg: String| io::Error::new(InvalidInput, msg);
+
+        let mut limits = Self::default();
+        for limit in s.split(',').map(str::trim).filter(|limit| !limit.is_empty()) {
+            let Some((key, value)) = limit.split_once('=') else {
+                return Err(invalid(format!("Invalid bandwidth limit '{limit}' (expected 'key=value')")));
+            };
+            let Ok(value) = value.trim().parse::<u64>() else {
+                return Err(invalid(format!("Invalid value in the bandwidth limit '{limit}'")));
+            };
+            match key.trim().to_ascii_lowercase().as_str() {
+                "upload" => limits.upload = Some(value),
+                "download" => limits.download = Some(value),
+                "peer-upload" => limits.peer_upload = Some(value),
+                "peer-download" => limits.peer_download = Some(value),
+                "sync-share" => match value {
+                    1..=99 => limits.sync_share = value as u8,
+                    _ => return Err(invalid(format!("The sync share must be between 1 and 99, found '{value}'"))),
+                },
+                key => return Err(invalid(format!("Unknown bandwidth limit '{key}'"))),
+            }
+        }
+        Ok(limits)
+    }
+}
+
+/// The transport used to establish connections.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Transport {
+    /// A single, ordered TCP stream per connection.
+    #[default]
+    Tcp,
+    /// A QUIC connection (over UDP, encrypted with TLS 1.3), with separate streams for the primary and bulk traffic.
+    Quic,
+}
+
+impl core::str::FromStr for Transport {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "tcp" => Ok(Self::Tcp),
+            "quic" => Ok(Self::Quic),
+            _ => Err(io::Error::new(InvalidInput, format!("Unknown transport '{s}' (expected 'tcp' or 'quic')"))),
+        }
+    }
+}
+
+impl core::fmt::Display for Transport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Tcp => write!(f, "tcp"),
+            Self::Quic => write!(f, "quic"),
+        }
+    }
 }
 
 impl Config {
@@ -65,6 +166,24 @@ impl Config {
             ..Default::default()
         }
     }
+
+    /// Sets the transport used by the listener and outbound connections.
+    pub fn with_transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Sets the bandwidth limits.
+    pub fn with_bandwidth_limits(mut self, bandwidth_limits: BandwidthLimits) -> Self {
+        self.bandwidth_limits = bandwidth_limits;
+        self
+    }
+
+    /// Sets the SOCKS5 proxy used to establish the outbound connections.
+    pub fn with_socks5_proxy(mut self, socks5_proxy: Option<SocketAddr>) -> Self {
+        self.socks5_proxy = socks5_proxy;
+        self
+    }
 }
 
 impl Default for Config {
@@ -88,6 +207,34 @@ impl Default for Config {
             fatal_io_errors: vec![ConnectionReset, ConnectionAborted, BrokenPipe, InvalidData, UnexpectedEof],
             max_connections: 100,
             connection_timeout_ms: 1_000,
+            transport: Transport::Tcp,
+            bandwidth_limits: Default::default(),
+            socks5_proxy: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bandwidth_limits() {
+        assert_eq!("".parse::<BandwidthLimits>().unwrap(), BandwidthLimits::default());
+
+        let limits = "upload=1000, peer-download=200,sync-share=30".parse::<BandwidthLimits>().unwrap();
+        assert_eq!(limits, BandwidthLimits {
+            upload: Some(1000),
+            download: None,
+            peer_upload: None,
+            peer_download: Some(200),
+            sync_share: 30
+        });
+
+        assert!("upload".parse::<BandwidthLimits>().is_err());
+        assert!("upload=fast".parse::<BandwidthLimits>().is_err());
+        assert!("uplink=1000".parse::<BandwidthLimits>().is_err());
+        assert!("sync-share=0".parse::<BandwidthLimits>().is_err());
+        assert!("sync-share=100".parse::<BandwidthLimits>().is_err());
+    }
+}
@@ -14,16 +14,25 @@
 
 //! Objects associated with connection handling.
 
-use std::{collections::HashMap, net::SocketAddr, ops::Not};
+use std::{
+    collections::HashMap,
+    io,
+    net::SocketAddr,
+    ops::Not,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 use parking_lot::RwLock;
 use tokio::{
-    io::{AsyncRead, AsyncWrite},
+    io::{AsyncRead, AsyncWrite, ReadBuf},
     net::TcpStream,
     sync::oneshot,
     task::JoinHandle,
 };
 
+use crate::{helpers::quic, QuicStream};
+
 #[cfg(doc)]
 use crate::protocols::{Handshake, Reading, Writing};
 
@@ -74,11 +83,19 @@ pub struct Connection {
     /// The connection's side in relation to Tcp.
     side: ConnectionSide,
     /// Available and used only in the [`Handshake`] protocol.
-    pub(crate) stream: Option<TcpStream>,
+    pub(crate) stream: Option<ConnectionStream>,
+    /// The stream dedicated to bulk traffic, if the transport supports multiple streams.
+    pub(crate) bulk_stream: Option<QuicStream>,
+    /// The underlying QUIC connection, if the connection uses the QUIC transport.
+    pub(crate) quic: Option<quinn::Connection>,
     /// Available and used only in the [`Reading`] protocol.
     pub(crate) reader: Option<Box<dyn AR>>,
+    /// Available and used only in the [`Reading`] protocol, if there is a bulk stream.
+    pub(crate) bulk_reader: Option<Box<dyn AR>>,
     /// Available and used only in the [`Writing`] protocol.
     pub(crate) writer: Option<Box<dyn AW>>,
+    /// Available and used only in the [`Writing`] protocol, if there is a bulk stream.
+    pub(crate) bulk_writer: Option<Box<dyn AW>>,
     /// Used to notify the [`Reading`] protocol that the connection is fully ready.
     pub(crate) readiness_notifier: Option<oneshot::Sender<()>>,
     /// Handles to tasks spawned for the connection.
@@ -90,9 +107,35 @@ impl Connection {
     pub(crate) fn new(addr: SocketAddr, stream: TcpStream, side: ConnectionSide) -> Self {
         Self {
             addr,
-            stream: Some(stream),
+            stream: Some(ConnectionStream::Tcp(stream)),
+            bulk_stream: None,
+            quic: None,
             reader: None,
+            bulk_reader: None,
             writer: None,
+            bulk_writer: None,
+            readiness_notifier: None,
+            side,
+            tasks: Default::default(),
+        }
+    }
+
+    /// Creates a [`Connection`] from a QUIC connection and its primary and bulk streams.
+    pub(crate) fn new_quic(
+        addr: SocketAddr,
+        quic: quinn::Connection,
+        (primary, bulk): (QuicStream, QuicStream),
+        side: ConnectionSide,
+    ) -> Self {
+        Self {
+            addr,
+            stream: Some(ConnectionStream::Quic(primary)),
+            bulk_stream: Some(bulk),
+            quic: Some(quic),
+            reader: None,
+            bulk_reader: None,
+            writer: None,
+            bulk_writer: None,
             readiness_notifier: None,
             side,
             tasks: Default::default(),
@@ -109,6 +152,52 @@ impl Connection {
     pub fn side(&self) -> ConnectionSide {
         self.side
     }
+
+    /// Returns the channel binding of the connection, if it uses the QUIC transport
+    /// (see [`challenge_message`](crate::challenge_message)).
+    pub fn channel_binding(&self) -> Option<[u8; 32]> {
+        self.quic.as_ref().and_then(quic::channel_binding)
+    }
+}
+
+/// The full stream of a connection, available during the [`Handshake`] protocol.
+pub enum ConnectionStream {
+    /// A TCP stream.
+    Tcp(TcpStream),
+    /// The primary stream of a QUIC connection.
+    Quic(QuicStream),
+}
+
+impl AsyncRead for ConnectionStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_read(cx, buf),
+            Self::Quic(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for ConnectionStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_write(cx, buf),
+            Self::Quic(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_flush(cx),
+            Self::Quic(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_shutdown(cx),
+            Self::Quic(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
 }
 
 /// Indicates who was the initiator and who was the responder when the connection was established.
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
 
 use parking_lot::RwLock;
 
@@ -57,6 +57,18 @@ impl KnownPeers {
         }
     }
 
+    /// Registers a ping sent to the given address.
+    pub fn register_ping(&self, to: SocketAddr) {
+        if let Some(stats) = self.0.read().get(&to) {
+            stats.register_ping();
+        }
+    }
+
+    /// Registers a pong received from the given address, and returns the updated smoothed round-trip time.
+    pub fn register_pong(&self, from: SocketAddr) -> Option<Duration> {
+        self.0.read().get(&from).and_then(|stats| stats.register_pong())
+    }
+
     /// Registers a failure associated with the given address.
     pub fn register_failure(&self, addr: SocketAddr) {
         if let Some(stats) = self.0.read().get(&addr) {
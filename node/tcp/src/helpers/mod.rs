@@ -13,17 +13,25 @@
 // limitations under the License.
 
 mod config;
-pub use config::Config;
+pub use config::{BandwidthLimits, Config, Transport};
 
 pub mod connections;
-pub use connections::{Connection, ConnectionSide};
+pub use connections::{Connection, ConnectionSide, ConnectionStream};
 
 mod known_peers;
 pub use known_peers::KnownPeers;
 
+pub(crate) mod quic;
+pub use quic::{challenge_message, QuicStream};
+
+pub(crate) mod socks5;
+
 mod stats;
 pub use stats::Stats;
 
+pub(crate) mod throttle;
+pub use throttle::Budget;
+
 use tracing::{debug_span, error_span, info_span, trace_span, warn_span, Span};
 
 /// Creates the Tcp's tracing span based on its name.
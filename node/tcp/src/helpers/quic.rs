@@ -0,0 +1,231 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Objects associated with the QUIC transport.
+
+use std::{
+    io,
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::SystemTime,
+};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+
+/// The ALPN protocol identifier of the QUIC transport.
+const ALPN_PROTOCOL: &[u8] = b"snarkos";
+/// The server name presented during the TLS handshake; it is not verified (see [`SkipServerVerification`]).
+const SERVER_NAME: &str = "snarkos";
+/// The label of the keying material that is exported from the TLS session to bind the handshake to it (RFC 5705).
+const CHANNEL_BINDING_LABEL: &[u8] = b"EXPORTER-snarkos-handshake";
+
+/// The tag written by the initiator at the start of the primary stream.
+const PRIMARY_STREAM_TAG: u8 = 0;
+/// The tag written by the initiator at the start of the bulk stream.
+const BULK_STREAM_TAG: u8 = 1;
+
+/// A bidirectional QUIC stream.
+pub struct QuicStream {
+    /// The sending half of the stream.
+    pub(crate) send: quinn::SendStream,
+    /// The receiving half of the stream.
+    pub(crate) recv: quinn::RecvStream,
+}
+
+impl AsyncRead for QuicStream {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.recv).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for QuicStream {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        AsyncWrite::poll_write(Pin::new(&mut self.send), cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_flush(Pin::new(&mut self.send), cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_shutdown(Pin::new(&mut self.send), cx)
+    }
+}
+
+/// Creates a QUIC endpoint bound to the given address, which accepts inbound connections
+/// if `is_listener` is `true`, and is always able to initiate outbound connections.
+pub(crate) fn create_endpoint(addr: SocketAddr, is_listener: bool) -> io::Result<quinn::Endpoint> {
+    let mut endpoint = match is_listener {
+        true => quinn::Endpoint::server(server_config()?, addr)?,
+        false => quinn::Endpoint::client(addr)?,
+    };
+    endpoint.set_default_client_config(client_config()?);
+    Ok(endpoint)
+}
+
+/// Initiates a QUIC connection with the given address.
+pub(crate) async fn connect(endpoint: &quinn::Endpoint, addr: SocketAddr) -> io::Result<quinn::Connection> {
+    let connecting = endpoint.connect(addr, SERVER_NAME).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    Ok(connecting.await?)
+}
+
+/// Returns the keying material exported from the TLS session of the given connection.
+///
+/// Both sides of a connection derive the same value, whereas a man-in-the-middle, which terminates a separate
+/// TLS session with each side, derives two different values. The handshake signs it along with the nonce.
+pub(crate) fn channel_binding(connection: &quinn::Connection) -> Option<[u8; 32]> {
+    let mut binding = [0u8; 32];
+    connection.export_keying_material(&mut binding, CHANNEL_BINDING_LABEL, &[]).ok()?;
+    Some(binding)
+}
+
+/// Returns the message that is signed during the handshake, for the given nonce:
+/// the nonce, followed by the channel binding of the connection, if any.
+pub fn challenge_message(nonce: u64, channel_binding: Option<[u8; 32]>) -> Vec<u8> {
+    let mut message = nonce.to_le_bytes().to_vec();
+    if let Some(channel_binding) = channel_binding {
+        message.extend_from_slice(&channel_binding);
+    }
+    message
+}
+
+/// Opens the primary and bulk streams of a freshly initiated QUIC connection.
+pub(crate) async fn open_streams(connection: &quinn::Connection) -> io::Result<(QuicStream, QuicStream)> {
+    let primary = open_stream(connection, PRIMARY_STREAM_TAG).await?;
+    let bulk = open_stream(connection, BULK_STREAM_TAG).await?;
+    Ok((primary, bulk))
+}
+
+/// Opens a bidirectional stream with the given tag.
+async fn open_stream(connection: &quinn::Connection, tag: u8) -> io::Result<QuicStream> {
+    let (send, recv) = connection.open_bi().await?;
+    let mut stream = QuicStream { send, recv };
+    // Note: The peer is only notified of a new stream once data is written to it, so each stream starts with its tag.
+    stream.write_u8(tag).await?;
+    Ok(stream)
+}
+
+/// Accepts the primary and bulk streams of a freshly accepted QUIC connection.
+pub(crate) async fn accept_streams(connection: &quinn::Connection) -> io::Result<(QuicStream, QuicStream)> {
+    let (mut primary, mut bulk) = (None, None);
+    while primary.is_none() || bulk.is_none() {
+        let (send, recv) = connection.accept_bi().await?;
+        let mut stream = QuicStream { send, recv };
+        // Sort the stream by its tag, as the streams may be accepted in any order.
+        let slot = match stream.read_u8().await? {
+            PRIMARY_STREAM_TAG => &mut primary,
+            BULK_STREAM_TAG => &mut bulk,
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid QUIC stream tag")),
+        };
+        if slot.replace(stream).is_some() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "duplicate QUIC stream"));
+        }
+    }
+    Ok((primary.unwrap(), bulk.unwrap())) // safe; both streams were just accepted
+}
+
+/// Returns the server configuration, with a freshly generated self-signed certificate.
+fn server_config() -> io::Result<quinn::ServerConfig> {
+    let certificate = rcgen::generate_simple_self_signed(vec![SERVER_NAME.to_string()])
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    let certificate_der = certificate.serialize_der().map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    let private_key = rustls::PrivateKey(certificate.serialize_private_key_der());
+
+    let mut crypto = rustls::ServerConfig::builder()
+        .with_safe_default_cipher_suites()
+        .with_safe_default_kx_groups()
+        .with_protocol_versions(&[&rustls::version::TLS13])
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+        .with_no_client_auth()
+        .with_single_cert(vec![rustls::Certificate(certificate_der)], private_key)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    crypto.alpn_protocols = vec![ALPN_PROTOCOL.to_vec()];
+
+    Ok(quinn::ServerConfig::with_crypto(Arc::new(crypto)))
+}
+
+/// Returns the client configuration.
+fn client_config() -> io::Result<quinn::ClientConfig> {
+    let mut crypto = rustls::ClientConfig::builder()
+        .with_safe_default_cipher_suites()
+        .with_safe_default_kx_groups()
+        .with_protocol_versions(&[&rustls::version::TLS13])
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+        .with_custom_certificate_verifier(Arc::new(SkipServerVerification))
+        .with_no_client_auth();
+    crypto.alpn_protocols = vec![ALPN_PROTOCOL.to_vec()];
+
+    Ok(quinn::ClientConfig::new(Arc::new(crypto)))
+}
+
+/// A certificate verifier that accepts any server certificate.
+///
+/// Nodes do not have certificates signed by a common authority; instead, the identity of a peer is
+/// established by the [`Handshake`](crate::protocols::Handshake) protocol, which runs over the encrypted stream.
+/// The handshake signs the [`channel_binding`] of the TLS session, so the signatures of a peer can't be
+/// relayed into another session, and a man-in-the-middle fails the handshake.
+struct SkipServerVerification;
+
+impl rustls::client::ServerCertVerifier for SkipServerVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<rustls::client::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::ServerCertVerified::assertion())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn test_challenge_message() {
+        assert_eq!(challenge_message(7, None), 7u64.to_le_bytes().to_vec());
+        let message = challenge_message(7, Some([1u8; 32]));
+        assert_eq!(&message[..8], &7u64.to_le_bytes());
+        assert_eq!(&message[8..], &[1u8; 32]);
+    }
+
+    #[tokio::test]
+    async fn test_channel_binding() {
+        let localhost = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let server = create_endpoint(localhost, true).unwrap();
+        let client = create_endpoint(localhost, false).unwrap();
+
+        // Establish a connection between the endpoints.
+        let server_addr = server.local_addr().unwrap();
+        let (client_connection, server_connection) =
+            tokio::join!(connect(&client, server_addr), async { server.accept().await.unwrap().await });
+        let (client_connection, server_connection) = (client_connection.unwrap(), server_connection.unwrap());
+
+        // Ensure both sides derive the same channel binding.
+        let binding = channel_binding(&client_connection).unwrap();
+        assert_eq!(channel_binding(&server_connection), Some(binding));
+
+        // Ensure another connection derives another channel binding.
+        let (other_connection, _) =
+            tokio::join!(connect(&client, server_addr), async { server.accept().await.unwrap().await });
+        assert_ne!(channel_binding(&other_connection.unwrap()), Some(binding));
+    }
+}
@@ -0,0 +1,256 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A minimal SOCKS5 client (RFC 1928), used to establish outbound connections through a proxy.
+
+use std::{
+    io,
+    net::{IpAddr, SocketAddr},
+};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+/// The SOCKS protocol version.
+const VERSION: u8 = 5;
+/// The "no authentication required" method.
+const METHOD_NO_AUTH: u8 = 0;
+/// The `CONNECT` command.
+const COMMAND_CONNECT: u8 = 1;
+/// The address type of an IPv4 address.
+const ATYP_IPV4: u8 = 1;
+/// The address type of a domain name.
+const ATYP_DOMAIN: u8 = 3;
+/// The address type of an IPv6 address.
+const ATYP_IPV6: u8 = 4;
+
+/// Connects to the given target address through the SOCKS5 proxy at the given address.
+pub(crate) async fn connect(proxy: SocketAddr, target: SocketAddr) -> io::Result<TcpStream> {
+    let mut address = Vec::with_capacity(17);
+    match target.ip() {
+        IpAddr::V4(ip) => {
+            address.push(ATYP_IPV4);
+            address.extend_from_slice(&ip.octets());
+        }
+        IpAddr::V6(ip) => {
+            address.push(ATYP_IPV6);
+            address.extend_from_slice(&ip.octets());
+        }
+    }
+    connect_to(proxy, &address, target.port()).await
+}
+
+/// Connects to the given host name (e.g. an onion address) and port through the SOCKS5 proxy at the given address;
+/// the host name is resolved by the proxy.
+pub(crate) async fn connect_domain(proxy: SocketAddr, host: &str, port: u16) -> io::Result<TcpStream> {
+    if host.is_empty() || host.len() > u8::MAX as usize {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid host name '{host}'")));
+    }
+    let mut address = Vec::with_capacity(2 + host.len());
+    address.push(ATYP_DOMAIN);
+    address.push(host.len() as u8);
+    address.extend_from_slice(host.as_bytes());
+    connect_to(proxy, &address, port).await
+}
+
+/// Connects to the given target through the SOCKS5 proxy at the given address; the target address
+/// is given in its encoded form, i.e. its address type followed by the address.
+async fn connect_to(proxy: SocketAddr, address: &[u8], port: u16) -> io::Result<TcpStream> {
+    let mut stream = TcpStream::connect(proxy).await?;
+
+    // Offer the proxy the "no authentication required" method, which is the only one supported.
+    stream.write_all(&[VERSION, 1, METHOD_NO_AUTH]).await?;
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply).await?;
+    if reply != [VERSION, METHOD_NO_AUTH] {
+        return Err(io::Error::new(io::ErrorKind::PermissionDenied, "The SOCKS5 proxy requires authentication"));
+    }
+
+    // Request a connection to the target address.
+    let mut request = vec![VERSION, COMMAND_CONNECT, 0];
+    request.extend_from_slice(address);
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request).await?;
+
+    // Read the reply header.
+    let mut reply = [0u8; 4];
+    stream.read_exact(&mut reply).await?;
+    if reply[0] != VERSION {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "The SOCKS5 proxy sent an invalid reply"));
+    }
+    if reply[1] != 0 {
+        return Err(reply_error(reply[1]));
+    }
+    // Skip the address the proxy bound to, followed by its port.
+    let address_len = match reply[3] {
+        ATYP_IPV4 => 4,
+        ATYP_IPV6 => 16,
+        ATYP_DOMAIN => stream.read_u8().await? as usize,
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "The SOCKS5 proxy sent an invalid address type")),
+    };
+    let mut bound_address = vec![0u8; address_len + 2];
+    stream.read_exact(&mut bound_address).await?;
+
+    Ok(stream)
+}
+
+/// Returns the IO error corresponding to the given (non-zero) SOCKS5 reply code.
+fn reply_error(code: u8) -> io::Error {
+    let (kind, reason) = match code {
+        2 => (io::ErrorKind::PermissionDenied, "connection not allowed by ruleset"),
+        3 => (io::ErrorKind::ConnectionRefused, "network unreachable"),
+        4 => (io::ErrorKind::ConnectionRefused, "host unreachable"),
+        5 => (io::ErrorKind::ConnectionRefused, "connection refused"),
+        6 => (io::ErrorKind::TimedOut, "TTL expired"),
+        7 => (io::ErrorKind::Unsupported, "command not supported"),
+        8 => (io::ErrorKind::Unsupported, "address type not supported"),
+        _ => (io::ErrorKind::Other, "general failure"),
+    };
+    io::Error::new(kind, format!("The SOCKS5 proxy failed to connect - {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Config, Tcp};
+
+    use std::net::Ipv4Addr;
+    use tokio::net::TcpListener;
+
+    /// Spawns a local SOCKS5 proxy stand-in, which relays the connections to their targets.
+    async fn spawn_proxy() -> SocketAddr {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let proxy_ip = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    // Accept the "no authentication required" method.
+                    let mut greeting = [0u8; 3];
+                    stream.read_exact(&mut greeting).await.unwrap();
+                    assert_eq!(greeting, [VERSION, 1, METHOD_NO_AUTH]);
+                    stream.write_all(&[VERSION, METHOD_NO_AUTH]).await.unwrap();
+
+                    // Read the target address, which is either an IPv4 address or a domain name.
+                    let mut request = [0u8; 4];
+                    stream.read_exact(&mut request).await.unwrap();
+                    assert_eq!(request[..3], [VERSION, COMMAND_CONNECT, 0]);
+                    let host = match request[3] {
+                        ATYP_IPV4 => {
+                            let mut ip = [0u8; 4];
+                            stream.read_exact(&mut ip).await.unwrap();
+                            Ipv4Addr::from(ip).to_string()
+                        }
+                        ATYP_DOMAIN => {
+                            let mut host = vec![0u8; stream.read_u8().await.unwrap() as usize];
+                            stream.read_exact(&mut host).await.unwrap();
+                            String::from_utf8(host).unwrap()
+                        }
+                        atyp => panic!("unexpected address type {atyp}"),
+                    };
+                    let port = stream.read_u16().await.unwrap();
+                    // Resolve the domain names on behalf of the client (the test targets only listen on IPv4).
+                    let mut targets = tokio::net::lookup_host((host, port)).await.unwrap();
+                    let target = targets.find(SocketAddr::is_ipv4).unwrap();
+
+                    // Relay the connection, or report that the target refused it.
+                    match TcpStream::connect(target).await {
+                        Ok(mut target) => {
+                            stream.write_all(&[VERSION, 0, 0, ATYP_DOMAIN, 9]).await.unwrap();
+                            stream.write_all(b"localhost").await.unwrap();
+                            stream.write_all(&0u16.to_be_bytes()).await.unwrap();
+                            let _ = tokio::io::copy_bidirectional(&mut stream, &mut target).await;
+                        }
+                        Err(_) => stream.write_all(&[VERSION, 5, 0, ATYP_IPV4, 0, 0, 0, 0, 0, 0]).await.unwrap(),
+                    }
+                });
+            }
+        });
+        proxy_ip
+    }
+
+    #[tokio::test]
+    async fn test_connect() {
+        let proxy_ip = spawn_proxy().await;
+
+        // Ensure the stream is relayed to the target.
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let target_ip = listener.local_addr().unwrap();
+        let mut stream = connect(proxy_ip, target_ip).await.unwrap();
+        let (mut target_stream, _) = listener.accept().await.unwrap();
+        stream.write_all(b"ping").await.unwrap();
+        let mut message = [0u8; 4];
+        target_stream.read_exact(&mut message).await.unwrap();
+        assert_eq!(&message, b"ping");
+
+        // Ensure a refused connection is reported.
+        drop(listener);
+        let error = connect(proxy_ip, target_ip).await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::ConnectionRefused);
+    }
+
+    #[tokio::test]
+    async fn test_connect_domain() {
+        let proxy_ip = spawn_proxy().await;
+
+        // Ensure the stream is relayed to the target, whose domain name is resolved by the proxy.
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let target_port = listener.local_addr().unwrap().port();
+        let mut stream = connect_domain(proxy_ip, "localhost", target_port).await.unwrap();
+        let (mut target_stream, _) = listener.accept().await.unwrap();
+        stream.write_all(b"ping").await.unwrap();
+        let mut message = [0u8; 4];
+        target_stream.read_exact(&mut message).await.unwrap();
+        assert_eq!(&message, b"ping");
+
+        // Ensure an invalid host name is rejected.
+        let error = connect_domain(proxy_ip, "", target_port).await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn test_tcp_connect_through_proxy() {
+        let proxy_ip = spawn_proxy().await;
+
+        // Initialize a node without a listener, which connects through the proxy.
+        let tcp = Tcp::new(Config { listener_ip: None, socks5_proxy: Some(proxy_ip), ..Default::default() });
+
+        // Initialize the peer.
+        let peer = Tcp::new(Config { listener_ip: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)), ..Default::default() });
+        let peer_ip = peer.enable_listener().await.unwrap();
+
+        // Connect to the peer.
+        tcp.connect(peer_ip).await.unwrap();
+        assert!(tcp.is_connected(peer_ip));
+        assert_eq!(tcp.num_connecting(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_tcp_connect_host_through_proxy() {
+        let proxy_ip = spawn_proxy().await;
+
+        // Initialize a node without a listener, which connects through the proxy.
+        let tcp = Tcp::new(Config { listener_ip: None, socks5_proxy: Some(proxy_ip), ..Default::default() });
+
+        // Initialize the peer.
+        let peer = Tcp::new(Config { listener_ip: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)), ..Default::default() });
+        let peer_ip = peer.enable_listener().await.unwrap();
+
+        // Connect to the peer by its host name; the connection is identified by the local address of the stream.
+        let addr = tcp.connect_host("localhost", peer_ip.port()).await.unwrap();
+        assert!(tcp.is_connected(addr));
+        assert_eq!(tcp.num_connecting(), 0);
+    }
+}
@@ -12,7 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+use std::{
+    sync::atomic::{AtomicU64, Ordering::Relaxed},
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+use crate::protocols::Priority;
+
+/// The minimum interval over which the throughput is measured.
+const THROUGHPUT_INTERVAL: Duration = Duration::from_secs(1);
+/// The weight of a new round-trip time sample in the smoothed round-trip time.
+const RTT_SAMPLE_WEIGHT: f64 = 0.125;
 
 /// Contains statistics related to Tcp.
 #[derive(Default)]
@@ -27,6 +39,49 @@ pub struct Stats {
     bytes_received: AtomicU64,
     /// The number of failures.
     failures: AtomicU64,
+    /// The number of outbound messages dropped due to a full queue, per priority.
+    msgs_dropped: [AtomicU64; Priority::ALL.len()],
+    /// Measures the current throughput.
+    throughput: Mutex<Throughput>,
+    /// Measures the round-trip time.
+    latency: Mutex<Latency>,
+}
+
+/// Measures the round-trip time between pings and the corresponding pongs.
+#[derive(Default)]
+struct Latency {
+    /// The time the last unanswered ping was sent.
+    ping_sent: Option<Instant>,
+    /// The smoothed round-trip time.
+    rtt: Option<Duration>,
+}
+
+/// Measures the throughput, in bytes per second, over consecutive intervals.
+#[derive(Default)]
+struct Throughput {
+    /// The start of the current interval.
+    interval_start: Option<Instant>,
+    /// The number of bytes sent in the current interval.
+    bytes_sent: u64,
+    /// The number of bytes received in the current interval.
+    bytes_received: u64,
+    /// The upload and download rates measured over the last full interval.
+    rates: (u64, u64),
+}
+
+impl Throughput {
+    /// Concludes the current interval if it has lasted long enough, updating the measured rates.
+    fn update(&mut self, now: Instant) {
+        let interval_start = *self.interval_start.get_or_insert(now);
+        let elapsed = now.duration_since(interval_start);
+        if elapsed >= THROUGHPUT_INTERVAL {
+            let secs = elapsed.as_secs_f64();
+            self.rates = ((self.bytes_sent as f64 / secs) as u64, (self.bytes_received as f64 / secs) as u64);
+            self.bytes_sent = 0;
+            self.bytes_received = 0;
+            self.interval_start = Some(now);
+        }
+    }
 }
 
 impl Stats {
@@ -51,20 +106,99 @@ impl Stats {
         self.failures.load(Relaxed)
     }
 
+    /// Returns the current upload and download throughput, in bytes per second.
+    pub fn throughput(&self) -> (u64, u64) {
+        let mut throughput = self.throughput.lock();
+        throughput.update(Instant::now());
+        throughput.rates
+    }
+
+    /// Returns the smoothed round-trip time, if it has been measured.
+    pub fn rtt(&self) -> Option<Duration> {
+        self.latency.lock().rtt
+    }
+
+    /// Returns the number of outbound messages of the given priority that were dropped due to a full queue.
+    pub fn dropped(&self, priority: Priority) -> u64 {
+        self.msgs_dropped[priority as usize].load(Relaxed)
+    }
+
     /// Registers a sent message of the provided `size` in bytes.
     pub fn register_sent_message(&self, size: usize) {
         self.msgs_sent.fetch_add(1, Relaxed);
         self.bytes_sent.fetch_add(size as u64, Relaxed);
+
+        let mut throughput = self.throughput.lock();
+        throughput.update(Instant::now());
+        throughput.bytes_sent += size as u64;
     }
 
     /// Registers a received message of the provided `size` in bytes.
     pub fn register_received_message(&self, size: usize) {
         self.msgs_received.fetch_add(1, Relaxed);
         self.bytes_received.fetch_add(size as u64, Relaxed);
+
+        let mut throughput = self.throughput.lock();
+        throughput.update(Instant::now());
+        throughput.bytes_received += size as u64;
+    }
+
+    /// Registers a sent ping, which is measured against the next received pong.
+    pub fn register_ping(&self) {
+        self.latency.lock().ping_sent = Some(Instant::now());
+    }
+
+    /// Registers a received pong, and returns the updated smoothed round-trip time;
+    /// returns `None` if there was no unanswered ping.
+    pub fn register_pong(&self) -> Option<Duration> {
+        let mut latency = self.latency.lock();
+        let sample = latency.ping_sent.take()?.elapsed();
+        // Smooth the round-trip time with an exponentially weighted moving average.
+        let rtt = match latency.rtt {
+            Some(rtt) => rtt.mul_f64(1.0 - RTT_SAMPLE_WEIGHT) + sample.mul_f64(RTT_SAMPLE_WEIGHT),
+            None => sample,
+        };
+        latency.rtt = Some(rtt);
+        Some(rtt)
     }
 
     /// Registers a failure.
     pub fn register_failure(&self) {
         self.failures.fetch_add(1, Relaxed);
     }
+
+    /// Registers an outbound message of the given priority that was dropped.
+    pub fn register_dropped_message(&self, priority: Priority) {
+        self.msgs_dropped[priority as usize].fetch_add(1, Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rtt() {
+        let stats = Stats::default();
+
+        // A pong without a preceding ping is not measured.
+        assert!(stats.register_pong().is_none());
+        assert!(stats.rtt().is_none());
+
+        // The first sample is the initial round-trip time.
+        stats.register_ping();
+        std::thread::sleep(Duration::from_millis(10));
+        let rtt = stats.register_pong().unwrap();
+        assert!(rtt >= Duration::from_millis(10));
+        assert_eq!(stats.rtt(), Some(rtt));
+
+        // The subsequent samples are smoothed.
+        stats.register_ping();
+        let smoothed = stats.register_pong().unwrap();
+        assert!(smoothed < rtt && smoothed >= rtt.mul_f64(1.0 - RTT_SAMPLE_WEIGHT));
+
+        // A ping is only answered once.
+        assert!(stats.register_pong().is_none());
+        assert_eq!(stats.rtt(), Some(smoothed));
+    }
 }
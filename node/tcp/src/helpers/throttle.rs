@@ -0,0 +1,133 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::protocols::Priority;
+
+/// The bandwidth budget a message is accounted against; each budget receives its own share of the
+/// bandwidth limits, so that the sync traffic can't starve the consensus traffic (and vice versa).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Budget {
+    /// The consensus traffic.
+    Consensus,
+    /// The sync (bulk) traffic.
+    Sync,
+}
+
+impl Budget {
+    /// The budgets.
+    pub const ALL: [Self; 2] = [Self::Consensus, Self::Sync];
+}
+
+impl From<Priority> for Budget {
+    /// Returns the budget of an outbound message of the given priority; the low-priority messages are sync traffic.
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::High | Priority::Normal => Self::Consensus,
+            Priority::Low => Self::Sync,
+        }
+    }
+}
+
+/// A token bucket limiting the rate at which bytes can be transferred.
+struct TokenBucket {
+    /// The rate at which the bucket is refilled, in bytes per second.
+    rate: f64,
+    /// The number of available tokens (which is negative if they had been overdrawn), and the time of the last refill.
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    /// Initializes a full token bucket with the given rate, in bytes per second; it allows bursts of up to one second.
+    fn new(rate: u64) -> Self {
+        let rate = rate.max(1) as f64;
+        Self { rate, state: Mutex::new((rate, Instant::now())) }
+    }
+
+    /// Consumes the given number of tokens, and returns the time to wait before the transfer fits within the rate.
+    fn consume(&self, bytes: usize) -> Duration {
+        let mut state = self.state.lock();
+        let (tokens, last_refill) = &mut *state;
+
+        // Refill the bucket, up to its capacity.
+        let now = Instant::now();
+        *tokens = (*tokens + now.duration_since(*last_refill).as_secs_f64() * self.rate).min(self.rate);
+        *last_refill = now;
+
+        // Consume the tokens; the bucket can be overdrawn, so that large messages are not stuck forever.
+        *tokens -= bytes as f64;
+        match *tokens < 0.0 {
+            true => Duration::from_secs_f64(-*tokens / self.rate),
+            false => Duration::ZERO,
+        }
+    }
+}
+
+/// Limits the bandwidth of a single direction (upload or download), with a separate token bucket per [`Budget`].
+#[derive(Default)]
+pub(crate) struct Throttle {
+    /// The token bucket of the consensus traffic, if the bandwidth is limited.
+    consensus: Option<TokenBucket>,
+    /// The token bucket of the sync traffic, if the bandwidth is limited.
+    sync: Option<TokenBucket>,
+}
+
+impl Throttle {
+    /// Initializes a new throttle with the given limit in bytes per second (if any), and the percentage of it
+    /// reserved for the sync traffic; the rest is reserved for the consensus traffic.
+    pub(crate) fn new(limit: Option<u64>, sync_share: u8) -> Self {
+        let Some(limit) = limit else {
+            return Self::default();
+        };
+        // Ensure each budget receives a non-zero share of the limit.
+        let sync_limit = limit * sync_share.clamp(1, 99) as u64 / 100;
+        Self { consensus: Some(TokenBucket::new(limit - sync_limit)), sync: Some(TokenBucket::new(sync_limit)) }
+    }
+
+    /// Accounts for the transfer of the given number of bytes against the given budget, and returns
+    /// the time to wait before the next transfer in order to stay within the limit.
+    pub(crate) fn consume(&self, budget: Budget, bytes: usize) -> Duration {
+        let bucket = match budget {
+            Budget::Consensus => &self.consensus,
+            Budget::Sync => &self.sync,
+        };
+        bucket.as_ref().map_or(Duration::ZERO, |bucket| bucket.consume(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle() {
+        // An unlimited throttle never delays the transfers.
+        let throttle = Throttle::new(None, 50);
+        assert_eq!(throttle.consume(Budget::Consensus, usize::MAX), Duration::ZERO);
+
+        // Each budget receives its share of the limit, allowing bursts of up to one second.
+        let throttle = Throttle::new(Some(1000), 20);
+        assert_eq!(throttle.consume(Budget::Consensus, 800), Duration::ZERO);
+        assert_eq!(throttle.consume(Budget::Sync, 200), Duration::ZERO);
+
+        // Once a budget is exhausted, its transfers are delayed proportionally to their size.
+        let delay = throttle.consume(Budget::Sync, 200);
+        assert!(delay > Duration::from_millis(900) && delay <= Duration::from_secs(1), "{delay:?}");
+        let delay = throttle.consume(Budget::Consensus, 400);
+        assert!(delay > Duration::from_millis(400) && delay <= Duration::from_millis(500), "{delay:?}");
+    }
+}
@@ -16,30 +16,37 @@ use std::{io, net::SocketAddr};
 
 use async_trait::async_trait;
 use bytes::BytesMut;
-use futures_util::StreamExt;
+use futures_util::{stream, StreamExt};
 use tokio::{
     io::AsyncRead,
     sync::{mpsc, oneshot},
+    time::sleep,
 };
 use tokio_util::codec::{Decoder, FramedRead};
 use tracing::*;
 
-#[cfg(doc)]
-use crate::{protocols::Handshake, Config};
 use crate::{
+    helpers::throttle::Throttle,
     protocols::{ProtocolHandler, ReturnableConnection},
+    Budget,
     ConnectionSide,
     Tcp,
     P2P,
 };
+#[cfg(doc)]
+use crate::{protocols::Handshake, Config, Transport};
 
 /// Can be used to specify and enable reading, i.e. receiving inbound messages. If the [`Handshake`]
 /// protocol is enabled too, it goes into force only after the handshake has been concluded.
 ///
 /// Each inbound message is isolated by the user-supplied [`Reading::Codec`], creating a [`Reading::Message`],
-/// which is immediately queued (with a [`Reading::MESSAGE_QUEUE_DEPTH`] limit) to be processed by
-/// [`Reading::process_message`]. The configured fatal IO errors result in an immediate disconnect
-/// (in order to e.g. avoid accidentally reading "borked" messages).
+/// which is immediately queued (with a [`Reading::MESSAGE_QUEUE_DEPTH`] limit per [`Budget`]) to be processed by
+/// [`Reading::process_message`]. If the download bandwidth is limited, the next read from the connection is delayed
+/// until the message fits within the limit of its budget, so that an inbound burst is paced by the reads (and the
+/// backpressure of the transport), instead of overflowing the queues. The configured fatal IO errors result in an
+/// immediate disconnect
+/// (in order to e.g. avoid accidentally reading "borked" messages). If the connection has a separate
+/// bulk stream (see [`Transport::Quic`]), the messages are read from both streams concurrently.
 #[async_trait]
 pub trait Reading: P2P
 where
@@ -94,6 +101,13 @@ where
     /// The `side` param indicates the connection side **from the node's perspective**.
     fn codec(&self, addr: SocketAddr, side: ConnectionSide) -> Self::Codec;
 
+    /// Returns the [`Budget`] the inbound message is accounted against if the download bandwidth is limited.
+    ///
+    /// By default, all messages are accounted against [`Budget::Consensus`].
+    fn budget(&self, _message: &Self::Message) -> Budget {
+        Budget::Consensus
+    }
+
     /// Processes an inbound message. Can be used to update state, send replies etc.
     async fn process_message(&self, source: SocketAddr, message: Self::Message) -> io::Result<()>;
 }
@@ -125,38 +139,65 @@ impl<R: Reading> ReadingInternal for R {
         let (tx_conn_ready, rx_conn_ready) = oneshot::channel();
         conn.readiness_notifier = Some(tx_conn_ready);
 
+        // if the connection has a bulk stream, read from it as well
+        let mut bulk_framed = conn.bulk_reader.take().map(|reader| {
+            let codec = self.codec(addr, !conn.side());
+            self.map_codec(FramedRead::new(reader, codec), addr)
+        });
+
         if Self::INITIAL_BUFFER_SIZE != 0 {
             framed.read_buffer_mut().reserve(Self::INITIAL_BUFFER_SIZE);
+            if let Some(bulk_framed) = bulk_framed.as_mut() {
+                bulk_framed.read_buffer_mut().reserve(Self::INITIAL_BUFFER_SIZE);
+            }
         }
 
-        let (inbound_message_sender, mut inbound_message_receiver) = mpsc::channel(Self::MESSAGE_QUEUE_DEPTH);
-
-        // use a channel to know when the processing task is ready
-        let (tx_processing, rx_processing) = oneshot::channel::<()>();
-
-        // the task for processing parsed messages
-        let self_clone = self.clone();
-        let inbound_processing_task = tokio::spawn(async move {
-            let node = self_clone.tcp();
-            trace!(parent: node.span(), "spawned a task for processing messages from {}", addr);
-            tx_processing.send(()).unwrap(); // safe; the channel was just opened
-
-            while let Some(msg) = inbound_message_receiver.recv().await {
-                if let Err(e) = self_clone.process_message(addr, msg).await {
-                    error!(parent: node.span(), "can't process a message from {}: {}", addr, e);
-                    node.known_peers().register_failure(addr);
+        // merge the inbound messages from all the streams of the connection
+        let mut framed = match bulk_framed {
+            Some(bulk_framed) => stream::select(framed, bulk_framed).boxed(),
+            None => framed.boxed(),
+        };
+
+        // the download bandwidth limit of the connection
+        let limits = self.tcp().config().bandwidth_limits;
+        let throttle = Throttle::new(limits.peer_download, limits.sync_share);
+
+        // the messages of each budget are processed by a dedicated task, so that a slow budget does not block
+        // the processing of the messages of other budgets
+        let mut inbound_message_senders = Vec::with_capacity(Budget::ALL.len());
+        for budget in Budget::ALL {
+            let (inbound_message_sender, mut inbound_message_receiver) =
+                mpsc::channel::<Self::Message>(Self::MESSAGE_QUEUE_DEPTH);
+            inbound_message_senders.push(inbound_message_sender);
+
+            // use a channel to know when the processing task is ready
+            let (tx_processing, rx_processing) = oneshot::channel::<()>();
+
+            // the task for processing parsed messages
+            let self_clone = self.clone();
+            let inbound_processing_task = tokio::spawn(async move {
+                let node = self_clone.tcp();
+                trace!(parent: node.span(), "spawned a task for processing messages from {} ({:?})", addr, budget);
+                tx_processing.send(()).unwrap(); // safe; the channel was just opened
+
+                while let Some(msg) = inbound_message_receiver.recv().await {
+                    if let Err(e) = self_clone.process_message(addr, msg).await {
+                        error!(parent: node.span(), "can't process a message from {}: {}", addr, e);
+                        node.known_peers().register_failure(addr);
+                    }
                 }
-            }
-        });
-        let _ = rx_processing.await;
-        conn.tasks.push(inbound_processing_task);
+            });
+            let _ = rx_processing.await;
+            conn.tasks.push(inbound_processing_task);
+        }
 
         // use a channel to know when the reader task is ready
         let (tx_reader, rx_reader) = oneshot::channel::<()>();
 
         // the task for reading messages from a stream
-        let node = self.tcp().clone();
+        let self_clone = self.clone();
         let reader_task = tokio::spawn(async move {
+            let node = self_clone.tcp();
             trace!(parent: node.span(), "spawned a task for reading messages from {}", addr);
             tx_reader.send(()).unwrap(); // safe; the channel was just opened
 
@@ -166,12 +207,22 @@ impl<R: Reading> ReadingInternal for R {
 
             while let Some(bytes) = framed.next().await {
                 match bytes {
-                    Ok(msg) => {
+                    Ok((msg, len)) => {
+                        let budget = self_clone.budget(&msg);
+
                         // send the message for further processing
-                        if let Err(e) = inbound_message_sender.try_send(msg) {
+                        if let Err(e) = inbound_message_senders[budget as usize].try_send(msg) {
                             error!(parent: node.span(), "can't process a message from {}: {}", addr, e);
                             node.stats().register_failure();
                         }
+
+                        // stay within the global and the connection's download bandwidth limits, by postponing
+                        // the next read from the connection
+                        let delay = node.download_throttle.consume(budget, len).max(throttle.consume(budget, len));
+                        if !delay.is_zero() {
+                            trace!(parent: node.span(), "pausing the reads from {} for {:?}", addr, delay);
+                            sleep(delay).await;
+                        }
                     }
                     Err(e) => {
                         error!(parent: node.span(), "can't read from {}: {}", addr, e);
@@ -203,7 +254,7 @@ impl<R: Reading> ReadingInternal for R {
     }
 }
 
-/// A wrapper [`Decoder`] that also counts the inbound messages.
+/// A wrapper [`Decoder`] that also counts the inbound messages, and returns them alongside their size in bytes.
 struct CountingCodec<D: Decoder> {
     codec: D,
     node: Tcp,
@@ -213,7 +264,7 @@ struct CountingCodec<D: Decoder> {
 
 impl<D: Decoder> Decoder for CountingCodec<D> {
     type Error = D::Error;
-    type Item = D::Item;
+    type Item = (D::Item, usize);
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
         let initial_buf_len = src.len();
@@ -233,6 +284,6 @@ impl<D: Decoder> Decoder for CountingCodec<D> {
             }
         }
 
-        Ok(ret)
+        Ok(ret.map(|message| (message, read_len)))
     }
 }
@@ -12,7 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{any::Any, collections::HashMap, io, net::SocketAddr, sync::Arc};
+use std::{
+    any::Any,
+    collections::HashMap,
+    future::{poll_fn, Future},
+    io,
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::Poll,
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use futures_util::sink::SinkExt;
@@ -20,20 +30,65 @@ use parking_lot::RwLock;
 use tokio::{
     io::AsyncWrite,
     sync::{mpsc, oneshot},
+    task::JoinHandle,
+    time::{sleep, Sleep},
 };
 use tokio_util::codec::{Encoder, FramedWrite};
 use tracing::*;
 
-#[cfg(doc)]
-use crate::{protocols::Handshake, Config, Tcp};
 use crate::{
+    connections::AW,
+    helpers::throttle::Throttle,
     protocols::{Protocol, ProtocolHandler, ReturnableConnection},
+    Budget,
     Connection,
     ConnectionSide,
+    Tcp,
     P2P,
 };
+#[cfg(doc)]
+use crate::{protocols::Handshake, Config, Transport};
+
+/// The senders of the outbound message queues of each connection, indexed by [`Priority`].
+type WritingSenders = Arc<RwLock<HashMap<SocketAddr, Vec<mpsc::Sender<WrappedMessage>>>>>;
+
+/// The priority of an outbound message. Each connection has a queue per priority, and the queues are served
+/// in a weighted round-robin: a higher priority gets a larger share of the connection (see [`Priority::weight`]),
+/// without starving the lower priorities.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Priority {
+    /// Consensus-critical messages.
+    High = 0,
+    /// Regular messages.
+    Normal = 1,
+    /// Bulk traffic; it is sent on a separate stream if the connection has one (see [`Transport::Quic`]),
+    /// and it is accounted against the [`Budget::Sync`] if the upload bandwidth is limited.
+    Low = 2,
+}
+
+impl Priority {
+    /// The priorities, from the highest to the lowest.
+    pub const ALL: [Self; 3] = [Self::High, Self::Normal, Self::Low];
+
+    /// Returns the number of queued messages of this priority that are sent in a row,
+    /// before the queues of the other priorities get their turn.
+    pub const fn weight(&self) -> usize {
+        match self {
+            Self::High => 8,
+            Self::Normal => 4,
+            Self::Low => 1,
+        }
+    }
 
-type WritingSenders = Arc<RwLock<HashMap<SocketAddr, mpsc::Sender<WrappedMessage>>>>;
+    /// Returns the name of the priority.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::High => "high",
+            Self::Normal => "normal",
+            Self::Low => "low",
+        }
+    }
+}
 
 /// Can be used to specify and enable writing, i.e. sending outbound messages. If the [`Handshake`]
 /// protocol is enabled too, it goes into force only after the handshake has been concluded.
@@ -42,7 +97,7 @@ pub trait Writing: P2P
 where
     Self: Clone + Send + Sync + 'static,
 {
-    /// The depth of per-connection queues used to send outbound messages; the greater it is, the more outbound
+    /// The depth of per-connection (and per-[`Priority`]) queues used to send outbound messages; the greater it is, the more outbound
     /// messages the node can enqueue. Setting it to a large value is not recommended, as doing it might
     /// obscure potential issues with your implementation (like slow serialization) or network.
     ///
@@ -92,6 +147,13 @@ where
     /// The `side` param indicates the connection side **from the node's perspective**.
     fn codec(&self, addr: SocketAddr, side: ConnectionSide) -> Self::Codec;
 
+    /// Returns the [`Priority`] of the message, which determines the order in which the queued messages are sent.
+    ///
+    /// By default, all messages have [`Priority::Normal`].
+    fn priority(&self, _message: &Self::Message) -> Priority {
+        Priority::Normal
+    }
+
     /// Sends the provided message to the specified [`SocketAddr`]. Returns as soon as the message is queued to
     /// be sent, without waiting for the actual delivery; instead, the caller is provided with a [`oneshot::Receiver`]
     /// which can be used to determine when and whether the message has been delivered.
@@ -100,19 +162,20 @@ where
     ///
     /// The following errors can be returned:
     /// - [`io::ErrorKind::NotConnected`] if the node is not connected to the provided address
-    /// - [`io::ErrorKind::Other`] if the outbound message queue for this address is full
+    /// - [`io::ErrorKind::Other`] if the outbound message queue of the message's priority for this address is full
     /// - [`io::ErrorKind::Unsupported`] if [`Writing::enable_writing`] hadn't been called yet
     fn unicast(&self, addr: SocketAddr, message: Self::Message) -> io::Result<oneshot::Receiver<io::Result<()>>> {
         // access the protocol handler
         if let Some(handler) = self.tcp().protocols.writing.get() {
             // find the message sender for the given address
-            if let Some(sender) = handler.senders.read().get(&addr).cloned() {
-                let (msg, delivery) = WrappedMessage::new(Box::new(message));
+            let priority = self.priority(&message);
+            if let Some(sender) = handler.senders.read().get(&addr).map(|senders| senders[priority as usize].clone()) {
+                let (msg, delivery) = WrappedMessage::new(Box::new(message), self.tcp(), priority);
                 sender
                     .try_send(msg)
                     .map_err(|e| {
                         error!(parent: self.tcp().span(), "can't send a message to {}: {}", addr, e);
-                        self.tcp().stats().register_failure();
+                        register_dropped_message(self.tcp(), priority);
                         io::ErrorKind::Other.into()
                     })
                     .map(|_| delivery)
@@ -139,11 +202,12 @@ where
         // access the protocol handler
         if let Some(handler) = self.tcp().protocols.writing.get() {
             let senders = handler.senders.read().clone();
-            for (addr, message_sender) in senders {
-                let (msg, _delivery) = WrappedMessage::new(Box::new(message.clone()));
-                let _ = message_sender.try_send(msg).map_err(|e| {
+            let priority = self.priority(&message);
+            for (addr, message_senders) in senders {
+                let (msg, _delivery) = WrappedMessage::new(Box::new(message.clone()), self.tcp(), priority);
+                let _ = message_senders[priority as usize].try_send(msg).map_err(|e| {
                     error!(parent: self.tcp().span(), "can't send a message to {}: {}", addr, e);
-                    self.tcp().stats().register_failure();
+                    register_dropped_message(self.tcp(), priority);
                 });
             }
 
@@ -166,6 +230,16 @@ trait WritingInternal: Writing {
 
     /// Applies the [`Writing`] protocol to a single connection.
     async fn handle_new_connection(&self, (conn, conn_returner): ReturnableConnection, conn_senders: &WritingSenders);
+
+    /// Spawns a task writing the queued outbound messages to the given stream of a connection.
+    async fn spawn_writer_task(
+        &self,
+        addr: SocketAddr,
+        framed: FramedWrite<Box<dyn AW>, Self::Codec>,
+        outbound_messages: PriorityQueues,
+        throttle: Arc<Throttle>,
+        auto_cleanup: Option<SenderCleanup>,
+    ) -> JoinHandle<()>;
 }
 
 #[async_trait]
@@ -190,20 +264,53 @@ impl<W: Writing> WritingInternal for W {
         let addr = conn.addr();
         let codec = self.codec(addr, !conn.side());
         let writer = conn.writer.take().expect("missing connection writer!");
-        let mut framed = FramedWrite::new(writer, codec);
+        let framed = FramedWrite::new(writer, codec);
 
-        let (outbound_message_sender, mut outbound_message_receiver) = mpsc::channel(Self::MESSAGE_QUEUE_DEPTH);
+        // create a queue for each message priority
+        let (outbound_message_senders, outbound_message_receivers): (Vec<_>, Vec<_>) =
+            Priority::ALL.iter().map(|_| mpsc::channel(Self::MESSAGE_QUEUE_DEPTH)).unzip();
+        let mut outbound_messages = PriorityQueues::new(Priority::ALL.into_iter().zip(outbound_message_receivers));
 
-        // register the connection's message sender with the Writing protocol handler
-        conn_senders.write().insert(addr, outbound_message_sender);
+        // register the connection's message senders with the Writing protocol handler
+        conn_senders.write().insert(addr, outbound_message_senders);
 
-        // this will automatically drop the sender upon a disconnect
+        // this will automatically drop the senders upon a disconnect
         let auto_cleanup = SenderCleanup { addr, senders: Arc::clone(conn_senders) };
 
+        // the upload bandwidth limit of the connection, shared by all of its streams
+        let limits = self.tcp().config().bandwidth_limits;
+        let throttle = Arc::new(Throttle::new(limits.peer_upload, limits.sync_share));
+
+        // if the connection has a bulk stream, the low-priority messages are written to it by a dedicated task
+        if let Some(writer) = conn.bulk_writer.take() {
+            let framed = FramedWrite::new(writer, self.codec(addr, !conn.side()));
+            let low_priority_messages = outbound_messages.split_off(Priority::Low);
+            let bulk_writer_task =
+                self.spawn_writer_task(addr, framed, low_priority_messages, Arc::clone(&throttle), None).await;
+            conn.tasks.push(bulk_writer_task);
+        }
+
+        // the task for writing outbound messages
+        let writer_task = self.spawn_writer_task(addr, framed, outbound_messages, throttle, Some(auto_cleanup)).await;
+        conn.tasks.push(writer_task);
+
+        // return the Connection to the Tcp, resuming Tcp::adapt_stream
+        if conn_returner.send(Ok(conn)).is_err() {
+            unreachable!("couldn't return a Connection to the Tcp");
+        }
+    }
+
+    async fn spawn_writer_task(
+        &self,
+        addr: SocketAddr,
+        mut framed: FramedWrite<Box<dyn AW>, Self::Codec>,
+        mut outbound_messages: PriorityQueues,
+        throttle: Arc<Throttle>,
+        auto_cleanup: Option<SenderCleanup>,
+    ) -> JoinHandle<()> {
         // use a channel to know when the writer task is ready
         let (tx_writer, rx_writer) = oneshot::channel();
 
-        // the task for writing outbound messages
         let self_clone = self.clone();
         let writer_task = tokio::spawn(async move {
             let node = self_clone.tcp();
@@ -213,8 +320,9 @@ impl<W: Writing> WritingInternal for W {
             // move the cleanup into the task that gets aborted on disconnect
             let _auto_cleanup = auto_cleanup;
 
-            while let Some(wrapped_msg) = outbound_message_receiver.recv().await {
+            while let Some(wrapped_msg) = outbound_messages.recv().await {
                 let msg = wrapped_msg.msg.downcast().unwrap();
+                let budget = Budget::from(self_clone.priority(&msg));
 
                 match self_clone.write_to_stream(*msg, &mut framed).await {
                     Ok(len) => {
@@ -222,6 +330,13 @@ impl<W: Writing> WritingInternal for W {
                         node.known_peers().register_sent_message(addr, len);
                         node.stats().register_sent_message(len);
                         trace!(parent: node.span(), "sent {}B to {}", len, addr);
+
+                        // stay within the global and the connection's upload bandwidth limits, by pausing
+                        // the queues of the exhausted budget while the other queues are still written
+                        let delay = node.upload_throttle.consume(budget, len).max(throttle.consume(budget, len));
+                        if !delay.is_zero() {
+                            outbound_messages.pause(budget, delay);
+                        }
                     }
                     Err(e) => {
                         node.known_peers().register_failure(addr);
@@ -238,36 +353,175 @@ impl<W: Writing> WritingInternal for W {
             node.disconnect(addr).await;
         });
         let _ = rx_writer.await;
-        conn.tasks.push(writer_task);
+        writer_task
+    }
+}
 
-        // return the Connection to the Tcp, resuming Tcp::adapt_stream
-        if conn_returner.send(Ok(conn)).is_err() {
-            unreachable!("couldn't return a Connection to the Tcp");
+/// An outbound message queue of a connection.
+struct MessageQueue {
+    /// The priority of the queued messages.
+    priority: Priority,
+    /// The receiver of the queued messages.
+    receiver: mpsc::Receiver<WrappedMessage>,
+    /// The timer until which the queue is paused, as its bandwidth budget is exhausted.
+    pause: Option<Pin<Box<Sleep>>>,
+}
+
+/// The outbound message queues of a connection, which are served in a weighted round-robin.
+struct PriorityQueues {
+    /// The queues, from the highest to the lowest priority.
+    queues: Vec<MessageQueue>,
+    /// The index of the queue whose turn it is.
+    current: usize,
+    /// The number of messages that the current queue may still send in its turn.
+    credit: usize,
+}
+
+impl PriorityQueues {
+    /// Initializes the queues from the given receivers, ordered from the highest to the lowest priority.
+    fn new(receivers: impl IntoIterator<Item = (Priority, mpsc::Receiver<WrappedMessage>)>) -> Self {
+        let queues = receivers.into_iter().map(|(priority, receiver)| MessageQueue { priority, receiver, pause: None });
+        let mut queues = Self { queues: queues.collect(), current: 0, credit: 0 };
+        queues.set_turn(0);
+        queues
+    }
+
+    /// Removes the queue of the given priority, and returns it on its own.
+    fn split_off(&mut self, priority: Priority) -> Self {
+        let index = self.queues.iter().position(|queue| queue.priority == priority).expect("missing message queue");
+        let queue = self.queues.remove(index);
+        // Restart the round-robin, as the indices of the queues may have shifted.
+        self.set_turn(0);
+        Self::new([(queue.priority, queue.receiver)])
+    }
+
+    /// Moves the turn to the queue at the given index.
+    fn set_turn(&mut self, index: usize) {
+        self.current = index;
+        self.credit = self.queues.get(index).map_or(0, |queue| queue.priority.weight());
+    }
+
+    /// Pauses the queues of the given bandwidth budget for the given duration; the other queues are unaffected.
+    fn pause(&mut self, budget: Budget, duration: Duration) {
+        for queue in self.queues.iter_mut().filter(|queue| Budget::from(queue.priority) == budget) {
+            queue.pause = Some(Box::pin(sleep(duration)));
         }
     }
+
+    /// Receives the next message: the queue whose turn it is sends up to its [`Priority::weight`] messages in a row,
+    /// after which the turn passes to the next queue; the empty and paused queues are skipped.
+    /// Returns `None` once all queues are closed.
+    async fn recv(&mut self) -> Option<WrappedMessage> {
+        poll_fn(|cx| {
+            let num_queues = self.queues.len();
+            let mut num_closed = 0;
+            // Poll each queue at most once, starting with the one whose turn it is.
+            for offset in 0..num_queues {
+                let index = (self.current + offset) % num_queues;
+                let queue = &mut self.queues[index];
+                // Skip the queue until its pause has elapsed; the timer wakes the task up once it has.
+                if let Some(pause) = queue.pause.as_mut() {
+                    if pause.as_mut().poll(cx).is_pending() {
+                        continue;
+                    }
+                    queue.pause = None;
+                }
+                match queue.receiver.poll_recv(cx) {
+                    Poll::Ready(Some(wrapped_msg)) => {
+                        // If the queue whose turn it was is empty or paused, the turn passes to this queue.
+                        if index != self.current {
+                            self.set_turn(index);
+                        }
+                        self.credit -= 1;
+                        if self.credit == 0 {
+                            self.set_turn((index + 1) % num_queues);
+                        }
+                        return Poll::Ready(Some(wrapped_msg));
+                    }
+                    Poll::Ready(None) => num_closed += 1,
+                    Poll::Pending => (),
+                }
+            }
+            match num_closed == num_queues {
+                true => Poll::Ready(None),
+                false => Poll::Pending,
+            }
+        })
+        .await
+    }
+}
+
+/// Registers an outbound message that was dropped due to a full queue.
+fn register_dropped_message(tcp: &Tcp, priority: Priority) {
+    tcp.stats().register_failure();
+    tcp.stats().register_dropped_message(priority);
+    #[cfg(feature = "metrics")]
+    metrics::increment_counter!(metrics::tcp::DROPPED_MESSAGES, "tcp" => tcp.name().to_string(), "priority" => priority.as_str());
 }
 
 /// Used to queue messages for delivery.
 struct WrappedMessage {
     msg: Box<dyn Any + Send>,
     delivery_notification: oneshot::Sender<io::Result<()>>,
+    /// Counts the message in the queued messages metric, until the message is sent or dropped.
+    #[cfg(feature = "metrics")]
+    _queued: QueuedMessage,
 }
 
 impl WrappedMessage {
-    fn new(msg: Box<dyn Any + Send>) -> (Self, oneshot::Receiver<io::Result<()>>) {
+    fn new(msg: Box<dyn Any + Send>, _tcp: &Tcp, _priority: Priority) -> (Self, oneshot::Receiver<io::Result<()>>) {
         let (tx, rx) = oneshot::channel();
-        let wrapped_msg = Self { msg, delivery_notification: tx };
+        let wrapped_msg = Self {
+            msg,
+            delivery_notification: tx,
+            #[cfg(feature = "metrics")]
+            _queued: QueuedMessage::new(_tcp, _priority),
+        };
 
         (wrapped_msg, rx)
     }
 }
 
+/// Increments the queued messages metric of the given Tcp and priority, and decrements it once dropped.
+#[cfg(feature = "metrics")]
+struct QueuedMessage(Vec<metrics::Label>);
+
+#[cfg(feature = "metrics")]
+impl QueuedMessage {
+    fn new(tcp: &Tcp, priority: Priority) -> Self {
+        let labels = vec![
+            metrics::Label::new("tcp", tcp.name().to_string()),
+            metrics::Label::new("priority", priority.as_str()),
+        ];
+        metrics::increment_gauge!(metrics::tcp::QUEUED_MESSAGES, 1.0, labels.clone());
+        Self(labels)
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl Drop for QueuedMessage {
+    fn drop(&mut self) {
+        metrics::decrement_gauge!(metrics::tcp::QUEUED_MESSAGES, 1.0, std::mem::take(&mut self.0));
+    }
+}
+
 /// The handler object dedicated to the [`Writing`] protocol.
 pub(crate) struct WritingHandler {
     handler: ProtocolHandler<Connection, io::Result<Connection>>,
     senders: WritingSenders,
 }
 
+impl WritingHandler {
+    /// Returns the number of queued outbound messages of the given priority, across all connections.
+    pub(crate) fn queue_depth(&self, priority: Priority) -> usize {
+        let senders = self.senders.read();
+        senders
+            .values()
+            .map(|senders| senders[priority as usize].max_capacity() - senders[priority as usize].capacity())
+            .sum()
+    }
+}
+
 impl Protocol<Connection, io::Result<Connection>> for WritingHandler {
     fn trigger(&self, item: ReturnableConnection) {
         self.handler.trigger(item);
@@ -284,3 +538,80 @@ impl Drop for SenderCleanup {
         self.senders.write().remove(&self.addr);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+
+    /// Receives the priority of the next message.
+    async fn recv(queues: &mut PriorityQueues) -> Priority {
+        *queues.recv().await.unwrap().msg.downcast::<Priority>().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_priority_queues() {
+        let tcp = Tcp::new(Config::default());
+        let (senders, receivers): (Vec<_>, Vec<_>) = Priority::ALL.iter().map(|_| mpsc::channel(64)).unzip();
+        let mut queues = PriorityQueues::new(Priority::ALL.into_iter().zip(receivers));
+
+        // Queue the same number of messages of each priority.
+        for priority in Priority::ALL {
+            for _ in 0..20 {
+                let (msg, _) = WrappedMessage::new(Box::new(priority), &tcp, priority);
+                senders[priority as usize].try_send(msg).unwrap();
+            }
+        }
+
+        // Ensure each priority sends its weight of messages in turn, so the lower priorities are not starved.
+        for _ in 0..2 {
+            for priority in Priority::ALL {
+                for _ in 0..priority.weight() {
+                    assert_eq!(recv(&mut queues).await, priority);
+                }
+            }
+        }
+        // Ensure the empty queues are skipped, once the high-priority queue is drained.
+        for _ in 0..4 {
+            assert_eq!(recv(&mut queues).await, Priority::High);
+        }
+        for _ in 0..Priority::Normal.weight() {
+            assert_eq!(recv(&mut queues).await, Priority::Normal);
+        }
+        assert_eq!(recv(&mut queues).await, Priority::Low);
+        for _ in 0..Priority::Normal.weight() {
+            assert_eq!(recv(&mut queues).await, Priority::Normal);
+        }
+        assert_eq!(recv(&mut queues).await, Priority::Low);
+
+        // Ensure no message is received once all the queues are closed and drained.
+        drop(senders);
+        for _ in 0..(20 - 4 * Priority::Normal.weight()) {
+            assert_eq!(recv(&mut queues).await, Priority::Normal);
+        }
+        for _ in 0..(20 - 4) {
+            assert_eq!(recv(&mut queues).await, Priority::Low);
+        }
+        assert!(queues.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_priority_queues_pause() {
+        let tcp = Tcp::new(Config::default());
+        let (senders, receivers): (Vec<_>, Vec<_>) = Priority::ALL.iter().map(|_| mpsc::channel(8)).unzip();
+        let mut queues = PriorityQueues::new(Priority::ALL.into_iter().zip(receivers));
+
+        // Queue a message of each priority, and pause the sync budget.
+        for priority in Priority::ALL {
+            let (msg, _) = WrappedMessage::new(Box::new(priority), &tcp, priority);
+            senders[priority as usize].try_send(msg).unwrap();
+        }
+        queues.pause(Budget::Sync, Duration::from_millis(200));
+
+        // Ensure the consensus messages are still received, while the sync message waits for the pause to elapse.
+        assert_eq!(recv(&mut queues).await, Priority::High);
+        assert_eq!(recv(&mut queues).await, Priority::Normal);
+        assert!(tokio::time::timeout(Duration::from_millis(50), queues.recv()).await.is_err());
+        assert_eq!(recv(&mut queues).await, Priority::Low);
+    }
+}
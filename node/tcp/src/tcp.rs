@@ -15,8 +15,9 @@
 use std::{
     collections::HashSet,
     fmt,
+    future::Future,
     io,
-    net::{IpAddr, SocketAddr},
+    net::{IpAddr, Ipv4Addr, SocketAddr},
     ops::Deref,
     sync::{
         atomic::{AtomicUsize, Ordering::*},
@@ -29,7 +30,7 @@ use once_cell::sync::OnceCell;
 use parking_lot::Mutex;
 use tokio::{
     io::split,
-    net::{TcpListener, TcpStream},
+    net::{lookup_host, TcpListener, TcpStream},
     sync::oneshot,
     task::JoinHandle,
     time::timeout,
@@ -38,10 +39,12 @@ use tracing::*;
 
 use crate::{
     connections::{Connection, ConnectionSide, Connections},
-    protocols::{Protocol, Protocols},
+    helpers::{quic, socks5, throttle::Throttle},
+    protocols::{Priority, Protocol, Protocols},
     Config,
     KnownPeers,
     Stats,
+    Transport,
 };
 
 // A sequential numeric identifier assigned to `Tcp`s that were not provided with a name.
@@ -67,6 +70,8 @@ pub struct InnerTcp {
     config: Config,
     /// The node's listening address.
     listening_addr: OnceCell<SocketAddr>,
+    /// The node's QUIC endpoint, if the QUIC transport is used.
+    quic_endpoint: OnceCell<quinn::Endpoint>,
     /// Contains objects used by the protocols implemented by the node.
     pub(crate) protocols: Protocols,
     /// A list of connections that have not been finalized yet.
@@ -77,6 +82,10 @@ pub struct InnerTcp {
     known_peers: KnownPeers,
     /// Collects statistics related to the node itself.
     stats: Stats,
+    /// Limits the node's upload bandwidth across all connections.
+    pub(crate) upload_throttle: Throttle,
+    /// Limits the node's download bandwidth across all connections.
+    pub(crate) download_throttle: Throttle,
     /// The node's tasks.
     pub(crate) tasks: Mutex<Vec<JoinHandle<()>>>,
 }
@@ -93,15 +102,23 @@ impl Tcp {
         let span = crate::helpers::create_span(config.name.as_deref().unwrap());
 
         // Initialize the Tcp stack.
+        // Prepare the global bandwidth limits.
+        let limits = config.bandwidth_limits;
+        let upload_throttle = Throttle::new(limits.upload, limits.sync_share);
+        let download_throttle = Throttle::new(limits.download, limits.sync_share);
+
         let tcp = Tcp(Arc::new(InnerTcp {
             span,
             config,
             listening_addr: Default::default(),
+            quic_endpoint: Default::default(),
             protocols: Default::default(),
             connecting: Default::default(),
             connections: Default::default(),
             known_peers: Default::default(),
             stats: Default::default(),
+            upload_throttle,
+            download_throttle,
             tasks: Default::default(),
         }));
 
@@ -171,6 +188,11 @@ impl Tcp {
         &self.stats
     }
 
+    /// Returns the number of outbound messages of the given priority queued across all connections.
+    pub fn queue_depth(&self, priority: Priority) -> usize {
+        self.protocols.writing.get().map_or(0, |handler| handler.queue_depth(priority))
+    }
+
     /// Returns the tracing [`Span`] associated with Tcp.
     #[inline]
     pub fn span(&self) -> &Span {
@@ -196,6 +218,10 @@ impl Tcp {
         for handle in tasks {
             handle.abort();
         }
+        // Close the QUIC endpoint, if any.
+        if let Some(endpoint) = self.quic_endpoint.get() {
+            endpoint.close(0u32.into(), b"shutdown");
+        }
     }
 }
 
@@ -225,23 +251,25 @@ impl Tcp {
             return Err(io::ErrorKind::AlreadyExists.into());
         }
 
-        let stream =
-            match timeout(Duration::from_millis(self.config().connection_timeout_ms.into()), TcpStream::connect(addr))
-                .await
-            {
-                Ok(Ok(stream)) => Ok(stream),
-                Ok(err) => {
-                    self.connecting.lock().remove(&addr);
-                    err
-                }
-                Err(err) => {
-                    self.connecting.lock().remove(&addr);
-                    error!("connection timeout error: {}", err);
-                    Err(io::ErrorKind::TimedOut.into())
-                }
-            }?;
-
-        let ret = self.adapt_stream(stream, addr, ConnectionSide::Initiator).await;
+        let ret = match (self.config().transport, self.config().socks5_proxy) {
+            (Transport::Tcp, None) => {
+                let stream = self.connect_within_timeout(addr, TcpStream::connect(addr)).await?;
+                self.adapt_stream(stream, addr, ConnectionSide::Initiator).await
+            }
+            (Transport::Tcp, Some(proxy)) => {
+                let stream = self.connect_within_timeout(addr, socks5::connect(proxy, addr)).await?;
+                self.adapt_stream(stream, addr, ConnectionSide::Initiator).await
+            }
+            (Transport::Quic, Some(_)) => {
+                self.connecting.lock().remove(&addr);
+                error!(parent: self.span(), "The SOCKS5 proxy is not supported by the QUIC transport");
+                return Err(io::ErrorKind::Unsupported.into());
+            }
+            (Transport::Quic, None) => {
+                let connection = self.connect_within_timeout(addr, self.connect_quic(addr)).await?;
+                self.adapt_connection(connection).await
+            }
+        };
 
         if let Err(ref e) = ret {
             self.connecting.lock().remove(&addr);
@@ -252,6 +280,89 @@ impl Tcp {
         ret
     }
 
+    /// Connects to the provided host name (e.g. an onion address) and port. If a SOCKS5 proxy is configured,
+    /// the host name is resolved by the proxy, and the connection is identified by the local address of the
+    /// stream to the proxy; otherwise, the connection is established to the resolved address of the host.
+    /// Returns the address identifying the connection.
+    pub async fn connect_host(&self, host: &str, port: u16) -> io::Result<SocketAddr> {
+        let proxy = match (self.config().transport, self.config().socks5_proxy) {
+            (_, None) => {
+                let addr = lookup_host((host, port)).await?.next().ok_or(io::ErrorKind::NotFound)?;
+                return self.connect(addr).await.map(|()| addr);
+            }
+            (Transport::Tcp, Some(proxy)) => proxy,
+            (Transport::Quic, Some(_)) => {
+                error!(parent: self.span(), "The SOCKS5 proxy is not supported by the QUIC transport");
+                return Err(io::ErrorKind::Unsupported.into());
+            }
+        };
+
+        if !self.can_add_connection() {
+            error!(parent: self.span(), "Too many connections; refusing to connect to {host}:{port}");
+            return Err(io::ErrorKind::ConnectionRefused.into());
+        }
+
+        let connecting = socks5::connect_domain(proxy, host, port);
+        let stream = match timeout(Duration::from_millis(self.config().connection_timeout_ms.into()), connecting).await
+        {
+            Ok(result) => result?,
+            Err(_) => {
+                error!(parent: self.span(), "Timed out connecting to {host}:{port}");
+                return Err(io::ErrorKind::TimedOut.into());
+            }
+        };
+        let addr = stream.local_addr()?;
+        self.connecting.lock().insert(addr);
+
+        let ret = self.adapt_stream(stream, addr, ConnectionSide::Initiator).await;
+        if let Err(ref e) = ret {
+            self.connecting.lock().remove(&addr);
+            error!(parent: self.span(), "Unable to initiate a connection with {host}:{port}: {e}");
+        }
+
+        ret.map(|()| addr)
+    }
+
+    /// Awaits the given connection attempt within the connection timeout; if the attempt fails,
+    /// the address is no longer considered to be connecting.
+    async fn connect_within_timeout<T>(
+        &self,
+        addr: SocketAddr,
+        connecting: impl Future<Output = io::Result<T>>,
+    ) -> io::Result<T> {
+        match timeout(Duration::from_millis(self.config().connection_timeout_ms.into()), connecting).await {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(err)) => {
+                self.connecting.lock().remove(&addr);
+                Err(err)
+            }
+            Err(err) => {
+                self.connecting.lock().remove(&addr);
+                error!("connection timeout error: {}", err);
+                Err(io::ErrorKind::TimedOut.into())
+            }
+        }
+    }
+
+    /// Initiates a QUIC connection with the provided `SocketAddr`, and opens its streams.
+    async fn connect_quic(&self, addr: SocketAddr) -> io::Result<Connection> {
+        let endpoint = self.quic_endpoint()?;
+        let connection = quic::connect(&endpoint, addr).await?;
+        let streams = quic::open_streams(&connection).await?;
+        debug!(
+            parent: self.span(), "establishing connection with {}; the peer is connected on port {}",
+            addr, endpoint.local_addr()?.port()
+        );
+        Ok(Connection::new_quic(addr, connection, streams, ConnectionSide::Responder))
+    }
+
+    /// Returns the QUIC endpoint; if the listener is not enabled, an endpoint for outbound connections is created.
+    fn quic_endpoint(&self) -> io::Result<quinn::Endpoint> {
+        self.quic_endpoint
+            .get_or_try_init(|| quic::create_endpoint(SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0), false))
+            .cloned()
+    }
+
     /// Disconnects from the provided `SocketAddr`.
     pub async fn disconnect(&self, addr: SocketAddr) -> bool {
         if let Some(handler) = self.protocols.disconnect.get() {
@@ -271,6 +382,10 @@ impl Tcp {
             for task in conn.tasks.iter().rev() {
                 task.abort();
             }
+            // Close the QUIC connection, if any.
+            if let Some(quic) = &conn.quic {
+                quic.close(0u32.into(), b"disconnect");
+            }
 
             // If the (owning) Tcp was not the initiator of the connection, it doesn't know the listening address
             // of the associated peer, so the related stats are unreliable; the next connection initiated by the
@@ -295,8 +410,18 @@ impl Tcp {
         let listener_ip =
             self.config().listener_ip.expect("Tcp::enable_listener was called, but Config::listener_ip is not set");
 
-        // Initialize the TCP listener.
-        let listener = self.create_listener(listener_ip).await?;
+        // Initialize the listener.
+        let listener = match self.config().transport {
+            Transport::Tcp => Listener::Tcp(self.create_listener(listener_ip).await?),
+            Transport::Quic => {
+                let endpoint = self.create_quic_endpoint(listener_ip).await?;
+                if self.quic_endpoint.set(endpoint.clone()).is_err() {
+                    error!(parent: self.span(), "The QUIC listener must be enabled before connecting to peers");
+                    return Err(io::ErrorKind::AlreadyExists.into());
+                }
+                Listener::Quic(endpoint)
+            }
+        };
 
         // Discover the port, if it was unspecified.
         let port = listener.local_addr()?.port();
@@ -313,11 +438,19 @@ impl Tcp {
             trace!(parent: tcp.span(), "Spawned the listening task");
             tx.send(()).unwrap(); // safe; the channel was just opened
 
-            loop {
-                // Await for a new connection.
-                match listener.accept().await {
-                    Ok((stream, addr)) => tcp.handle_connection(stream, addr),
-                    Err(e) => error!(parent: tcp.span(), "Failed to accept a connection: {e}"),
+            match listener {
+                Listener::Tcp(listener) => loop {
+                    // Await for a new connection.
+                    match listener.accept().await {
+                        Ok((stream, addr)) => tcp.handle_connection(stream, addr),
+                        Err(e) => error!(parent: tcp.span(), "Failed to accept a connection: {e}"),
+                    }
+                },
+                Listener::Quic(endpoint) => {
+                    // Await for a new connection, until the endpoint is closed.
+                    while let Some(connecting) = endpoint.accept().await {
+                        tcp.handle_quic_connection(connecting);
+                    }
                 }
             }
         });
@@ -331,11 +464,26 @@ impl Tcp {
     /// Creates an instance of `TcpListener` based on the node's configuration.
     async fn create_listener(&self, listener_ip: IpAddr) -> io::Result<TcpListener> {
         debug!("Creating a TCP listener on {listener_ip}...");
+        self.bind_listener(listener_ip, TcpListener::bind).await
+    }
+
+    /// Creates a QUIC endpoint accepting inbound connections, based on the node's configuration.
+    async fn create_quic_endpoint(&self, listener_ip: IpAddr) -> io::Result<quinn::Endpoint> {
+        debug!("Creating a QUIC listener on {listener_ip}...");
+        self.bind_listener(listener_ip, |addr| async move { quic::create_endpoint(addr, true) }).await
+    }
+
+    /// Binds a listener to the desired listening port, or to a random port, based on the node's configuration.
+    async fn bind_listener<T, F: Future<Output = io::Result<T>>>(
+        &self,
+        listener_ip: IpAddr,
+        bind: impl Fn(SocketAddr) -> F,
+    ) -> io::Result<T> {
         let listener = if let Some(port) = self.config().desired_listening_port {
             // Construct the desired listening IP address.
             let desired_listening_addr = SocketAddr::new(listener_ip, port);
             // If a desired listening port is set, try to bind to it.
-            match TcpListener::bind(desired_listening_addr).await {
+            match bind(desired_listening_addr).await {
                 Ok(listener) => listener,
                 Err(e) => {
                     if self.config().allow_random_port {
@@ -344,7 +492,7 @@ impl Tcp {
                             "Trying any listening port, as the desired port is unavailable: {e}"
                         );
                         let random_available_addr = SocketAddr::new(listener_ip, 0);
-                        TcpListener::bind(random_available_addr).await?
+                        bind(random_available_addr).await?
                     } else {
                         error!(parent: self.span(), "The desired listening port is unavailable: {e}");
                         return Err(e);
@@ -353,7 +501,7 @@ impl Tcp {
             }
         } else if self.config().allow_random_port {
             let random_available_addr = SocketAddr::new(listener_ip, 0);
-            TcpListener::bind(random_available_addr).await?
+            bind(random_available_addr).await?
         } else {
             panic!("As 'listener_ip' is set, either 'desired_listening_port' or 'allow_random_port' must be set");
         };
@@ -382,6 +530,41 @@ impl Tcp {
         });
     }
 
+    /// Handles a new inbound QUIC connection.
+    fn handle_quic_connection(&self, connecting: quinn::Connecting) {
+        let addr = connecting.remote_address();
+        debug!(parent: self.span(), "Received a connection from {addr}");
+
+        // Note: Unlike with TCP, the address of a QUIC peer is the address of its endpoint, which is
+        // also used for its outbound connections, so it must not clash with an existing connection.
+        if !self.can_add_connection()
+            || self.is_self_connect(addr)
+            || self.is_connected(addr)
+            || !self.connecting.lock().insert(addr)
+        {
+            debug!(parent: self.span(), "Rejecting the connection from {addr}");
+            return;
+        }
+
+        let tcp = self.clone();
+        tokio::spawn(async move {
+            let accepting = async {
+                let connection = connecting.await?;
+                let streams = quic::accept_streams(&connection).await?;
+                Ok(Connection::new_quic(addr, connection, streams, ConnectionSide::Initiator))
+            };
+            let result = match tcp.connect_within_timeout(addr, accepting).await {
+                Ok(connection) => tcp.adapt_connection(connection).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                tcp.connecting.lock().remove(&addr);
+                tcp.known_peers().register_failure(addr);
+                error!(parent: tcp.span(), "Failed to connect with {addr}: {e}");
+            }
+        });
+    }
+
     /// Checks if the given IP address is the same as the listening address of this `Tcp`.
     fn is_self_connect(&self, addr: SocketAddr) -> bool {
         // SAFETY: if we're opening connections, this should never fail.
@@ -416,8 +599,6 @@ impl Tcp {
 
     /// Prepares the freshly acquired connection to handle the protocols the Tcp implements.
     async fn adapt_stream(&self, stream: TcpStream, peer_addr: SocketAddr, own_side: ConnectionSide) -> io::Result<()> {
-        self.known_peers.add(peer_addr);
-
         // Register the port seen by the peer.
         if own_side == ConnectionSide::Initiator {
             if let Ok(addr) = stream.local_addr() {
@@ -430,7 +611,13 @@ impl Tcp {
             }
         }
 
-        let connection = Connection::new(peer_addr, stream, !own_side);
+        self.adapt_connection(Connection::new(peer_addr, stream, !own_side)).await
+    }
+
+    /// Prepares the freshly established connection to handle the protocols the Tcp implements.
+    async fn adapt_connection(&self, connection: Connection) -> io::Result<()> {
+        let peer_addr = connection.addr();
+        self.known_peers.add(peer_addr);
 
         // Enact the enabled protocols.
         let mut connection = self.enable_protocols(connection).await?;
@@ -485,6 +672,11 @@ impl Tcp {
             conn.reader = Some(Box::new(reader));
             conn.writer = Some(Box::new(writer));
         }
+        // Split the bulk stream, if any.
+        if let Some(stream) = conn.bulk_stream.take() {
+            conn.bulk_reader = Some(Box::new(stream.recv));
+            conn.bulk_writer = Some(Box::new(stream.send));
+        }
 
         let conn = enable_protocol!(reading, self, conn);
         let conn = enable_protocol!(writing, self, conn);
@@ -493,6 +685,24 @@ impl Tcp {
     }
 }
 
+/// The listener accepting inbound connections.
+enum Listener {
+    /// The TCP listener.
+    Tcp(TcpListener),
+    /// The QUIC endpoint.
+    Quic(quinn::Endpoint),
+}
+
+impl Listener {
+    /// Returns the local address of the listener.
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        match self {
+            Self::Tcp(listener) => listener.local_addr(),
+            Self::Quic(endpoint) => endpoint.local_addr(),
+        }
+    }
+}
+
 impl fmt::Debug for Tcp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "The TCP stack config: {:?}", self.config)
@@ -502,8 +712,173 @@ impl fmt::Debug for Tcp {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        protocols::{Priority, Reading, Writing},
+        BandwidthLimits,
+        P2P,
+    };
+
+    use bytes::{Bytes, BytesMut};
+    use std::{
+        net::{IpAddr, Ipv4Addr},
+        time::Instant,
+    };
+    use tokio::sync::mpsc;
+    use tokio_util::codec::LengthDelimitedCodec;
+
+    /// A node that forwards the received messages into a channel.
+    #[derive(Clone)]
+    struct TestNode(Tcp, mpsc::UnboundedSender<BytesMut>);
+
+    impl P2P for TestNode {
+        fn tcp(&self) -> &Tcp {
+            &self.0
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Reading for TestNode {
+        type Codec = LengthDelimitedCodec;
+        type Message = BytesMut;
+
+        fn codec(&self, _addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
+            Default::default()
+        }
+
+        async fn process_message(&self, _source: SocketAddr, message: Self::Message) -> io::Result<()> {
+            let _ = self.1.send(message);
+            Ok(())
+        }
+    }
+
+    impl Writing for TestNode {
+        type Codec = LengthDelimitedCodec;
+        type Message = Bytes;
+
+        fn codec(&self, _addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
+            Default::default()
+        }
+
+        fn priority(&self, message: &Self::Message) -> Priority {
+            match message.starts_with(b"bulk") {
+                true => Priority::Low,
+                false => Priority::High,
+            }
+        }
+    }
+
+    /// Initializes a node with the given transport, which listens on localhost.
+    async fn test_node(transport: Transport) -> (TestNode, mpsc::UnboundedReceiver<BytesMut>) {
+        let tcp =
+            Tcp::new(Config { listener_ip: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)), transport, ..Default::default() });
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let node = TestNode(tcp, sender);
+        node.enable_reading().await;
+        node.enable_writing().await;
+        node.tcp().enable_listener().await.unwrap();
+        (node, receiver)
+    }
+
+    /// Ensures the messages are delivered on both the primary and the bulk streams of the given transport.
+    async fn check_messaging(transport: Transport) {
+        let (node, _) = test_node(transport).await;
+        let (peer, mut peer_receiver) = test_node(transport).await;
+        let peer_ip = peer.tcp().listening_addr().unwrap();
+
+        // Connect to the peer.
+        node.tcp().connect(peer_ip).await.unwrap();
+        assert!(node.tcp().is_connected(peer_ip));
+
+        // Send a bulk message, followed by a primary message.
+        node.unicast(peer_ip, Bytes::from_static(b"bulk message")).unwrap().await.unwrap().unwrap();
+        node.unicast(peer_ip, Bytes::from_static(b"primary message")).unwrap().await.unwrap().unwrap();
+
+        // Ensure the peer received both messages.
+        let mut received = vec![peer_receiver.recv().await.unwrap(), peer_receiver.recv().await.unwrap()];
+        received.sort();
+        assert_eq!(received, vec![BytesMut::from(&b"bulk message"[..]), BytesMut::from(&b"primary message"[..])]);
+
+        // Disconnect from the peer.
+        assert!(node.tcp().disconnect(peer_ip).await);
+        assert!(!node.tcp().is_connected(peer_ip));
+    }
+
+    #[tokio::test]
+    async fn test_messaging_tcp() {
+        check_messaging(Transport::Tcp).await;
+    }
+
+    #[tokio::test]
+    async fn test_messaging_quic() {
+        check_messaging(Transport::Quic).await;
+    }
+
+    #[tokio::test]
+    async fn test_throttled_reading() {
+        const NUM_MESSAGES: usize = 1500;
+
+        let (node, _) = test_node(Transport::Tcp).await;
+        // Initialize a peer, which downloads 10kB per second of consensus traffic (with bursts of up to 10kB).
+        let tcp = Tcp::new(Config {
+            listener_ip: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            bandwidth_limits: BandwidthLimits { peer_download: Some(20_000), sync_share: 50, ..Default::default() },
+            ..Default::default()
+        });
+        let (sender, mut peer_receiver) = mpsc::unbounded_channel();
+        let peer = TestNode(tcp, sender);
+        peer.enable_reading().await;
+        let peer_ip = peer.tcp().enable_listener().await.unwrap();
+
+        // Send a burst of 30kB, in more messages than the peer can queue.
+        node.tcp().connect(peer_ip).await.unwrap();
+        let start = Instant::now();
+        for _ in 0..NUM_MESSAGES {
+            node.unicast(peer_ip, Bytes::from_static(b"sixteen bytes...")).unwrap().await.unwrap().unwrap();
+        }
+
+        // Ensure the burst is delayed by the download limit, and none of the messages are dropped.
+        for _ in 0..NUM_MESSAGES {
+            peer_receiver.recv().await.unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(1500), "{:?}", start.elapsed());
+        assert_eq!(peer.tcp().stats().failures(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_connect_quic() {
+        let tcp = Tcp::new(Config { transport: Transport::Quic, ..Default::default() });
+        let node_ip = tcp.enable_listener().await.unwrap();
+
+        // Ensure self-connecting is not possible.
+        tcp.connect(node_ip).await.unwrap_err();
+        assert_eq!(tcp.num_connected(), 0);
+        assert_eq!(tcp.num_connecting(), 0);
+
+        // Initialize the peer.
+        let peer = Tcp::new(Config {
+            listener_ip: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            desired_listening_port: Some(0),
+            max_connections: 1,
+            transport: Transport::Quic,
+            ..Default::default()
+        });
+        let peer_ip = peer.enable_listener().await.unwrap();
 
-    use std::net::{IpAddr, Ipv4Addr};
+        // Connect to the peer.
+        tcp.connect(peer_ip).await.unwrap();
+        assert_eq!(tcp.num_connected(), 1);
+        assert_eq!(tcp.num_connecting(), 0);
+        assert!(tcp.is_connected(peer_ip));
+
+        // Ensure connecting to the peer a second time is rejected.
+        tcp.connect(peer_ip).await.unwrap_err();
+        assert_eq!(tcp.num_connected(), 1);
+
+        // Disconnect from the peer.
+        tcp.disconnect(peer_ip).await;
+        assert_eq!(tcp.num_connected(), 0);
+        assert!(!tcp.is_connected(peer_ip));
+    }
 
     #[tokio::test]
     async fn test_new() {
@@ -14,19 +14,30 @@
 
 use crate::common::test_peer::sample_genesis_block;
 use snarkos_account::Account;
-use snarkos_node::{Client, Prover, Validator};
+use snarkos_node::{router::messages::ListenerAddress, tcp::Transport, Client, Prover, Validator};
 use snarkvm::prelude::{store::helpers::memory::ConsensusMemory, Testnet3 as CurrentNetwork};
 
-use std::str::FromStr;
+use std::{net::SocketAddr, str::FromStr};
 
 pub async fn client() -> Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
+    client_with_rest(None).await
+}
+
+pub async fn client_with_rest(rest_ip: Option<SocketAddr>) -> Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
     Client::new(
         "127.0.0.1:0".parse().unwrap(),
+        Transport::Tcp,
+        Default::default(),
         None,
+        ListenerAddress::Port,
+        rest_ip,
+        Default::default(),
         Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
         &[],
         sample_genesis_block(),
         None, // No CDN.
+        Default::default(),
+        &[],
         None,
     )
     .await
@@ -36,6 +47,10 @@ pub async fn client() -> Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>>
 pub async fn prover() -> Prover<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
     Prover::new(
         "127.0.0.1:0".parse().unwrap(),
+        Transport::Tcp,
+        Default::default(),
+        None,
+        ListenerAddress::Port,
         Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
         &[],
         sample_genesis_block(),
@@ -48,8 +63,14 @@ pub async fn prover() -> Prover<CurrentNetwork, ConsensusMemory<CurrentNetwork>>
 pub async fn validator() -> Validator<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
     Validator::new(
         "127.0.0.1:0".parse().unwrap(),
+        Transport::Tcp,
+        Default::default(),
+        None,
+        ListenerAddress::Port,
         None,
+        Default::default(),
         None,
+        Transport::Tcp,
         Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
         &[],
         &[],
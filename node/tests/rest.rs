@@ -0,0 +1,91 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![recursion_limit = "256"]
+
+#[allow(dead_code)]
+mod common;
+use common::{node::*, sample_genesis_block};
+
+use serde_json::Value;
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    time::Duration,
+};
+
+/// Returns an available local address for the REST server.
+fn sample_rest_ip() -> SocketAddr {
+    TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap()
+}
+
+/// Sends a `POST` request with the given JSON body to the REST server, and returns the status code and the body.
+async fn post(rest_ip: SocketAddr, path: &str, body: &str) -> (u16, String) {
+    // Wait for the REST server to start.
+    let stream = loop {
+        match TcpStream::connect(rest_ip) {
+            Ok(stream) => break stream,
+            Err(_) => tokio::time::sleep(Duration::from_millis(100)).await,
+        }
+    };
+    let request = format!(
+        "POST {path} HTTP/1.1\r\nHost: {rest_ip}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{body}",
+        body.len()
+    );
+    // Note: The blocking request runs on its own thread, so that it does not stall the server.
+    let response = tokio::task::spawn_blocking(move || {
+        let mut stream = stream;
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    })
+    .await
+    .unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, body.to_string())
+}
+
+#[tokio::test]
+async fn test_transaction_validate_and_broadcast() {
+    let rest_ip = sample_rest_ip();
+    let _client = client_with_rest(Some(rest_ip)).await;
+
+    // Use a transaction of the genesis block, which exists in the ledger.
+    let genesis = sample_genesis_block();
+    let transaction = genesis.transactions().iter().next().unwrap().transaction().clone();
+    let body = serde_json::to_string(&transaction).unwrap();
+
+    // Ensure the verdict reports the failed checks.
+    let (status, response) = post(rest_ip, "/v1/testnet3/transaction/validate", &body).await;
+    assert_eq!(status, 200);
+    let verdict: Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(verdict["transaction_id"], transaction.id().to_string());
+    assert_eq!(verdict["is_valid"], false);
+    let unique = verdict["checks"].as_array().unwrap().iter().find(|check| check["name"] == "unique").unwrap();
+    assert_eq!(unique["passed"], false);
+
+    // Ensure the broadcast is rejected by the validation, as a conflict.
+    let (status, response) = post(rest_ip, "/v1/testnet3/transaction/broadcast?validate=true", &body).await;
+    assert_eq!(status, 409);
+    assert!(response.contains("failed the 'unique' check"));
+
+    // Ensure the broadcast without validation is rejected as well, as the transaction exists in the ledger.
+    let (status, response) = post(rest_ip, "/v1/testnet3/transaction/broadcast", &body).await;
+    assert_eq!(status, 409);
+    assert!(response.contains("exists in the ledger"));
+}